@@ -0,0 +1,233 @@
+use plexo_sdk::{
+    errors::sdk::SDKError,
+    resources::{
+        assets::{
+            asset::Asset,
+            extensions::{AssetsExtensionOperations, UploadAssetInputBuilder},
+        },
+        changes::change::{ChangeOperation, ChangeResourceType},
+    },
+};
+use poem::{
+    handler,
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_LENGTH, CONTENT_TYPE, X_CONTENT_TYPE_OPTIONS},
+        HeaderMap, StatusCode,
+    },
+    web::{Data, Json, Multipart, Path, Query},
+    Body, Response, Result,
+};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::io::AsyncReadExt;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+use crate::{
+    api::graphql::commons::create_change,
+    auth::commons::{get_token_from_cookie, get_token_from_headers},
+    core::app::Core,
+    errors::app::PlexoAppError,
+};
+
+fn extract_member_id(core: &Core, headers: &HeaderMap) -> Result<Uuid, PlexoAppError> {
+    let token = get_token_from_cookie(headers)
+        .or_else(|| get_token_from_headers(headers))
+        .ok_or(PlexoAppError::MissingAuthorizationToken)?;
+
+    Ok(core.auth.extract_claims(&token)?.member_id())
+}
+
+/// The fields of an upload form: the content in a `file` field, and optional `name` and
+/// `project_id` fields.
+#[derive(Default)]
+struct UploadForm {
+    content: Option<Vec<u8>>,
+    file_name: Option<String>,
+    name: Option<String>,
+    project_id: Option<Uuid>,
+}
+
+async fn read_upload_form(multipart: &mut Multipart, max_size: u64) -> Result<UploadForm, PlexoAppError> {
+    let mut form = UploadForm::default();
+
+    while let Some(field) = multipart.next_field().await? {
+        match field.name() {
+            Some("file") => {
+                form.file_name = field.file_name().map(|file_name| file_name.to_string());
+
+                // Reads one byte past the limit, so a larger file is noticed without buffering it.
+                let mut content = Vec::new();
+                field
+                    .into_async_read()
+                    .take(max_size + 1)
+                    .read_to_end(&mut content)
+                    .await
+                    .map_err(|err| PlexoAppError::InvalidUploadField(err.to_string()))?;
+
+                if content.len() as u64 > max_size {
+                    return Err(SDKError::AssetTooLarge(max_size).into());
+                }
+
+                form.content = Some(content);
+            }
+            Some("name") => form.name = Some(field.text().await?),
+            Some("project_id") => {
+                let value = field.text().await?;
+
+                form.project_id = Some(
+                    value
+                        .trim()
+                        .parse::<Uuid>()
+                        .map_err(|_| PlexoAppError::InvalidUploadField("project_id".to_string()))?,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    Ok(form)
+}
+
+/// Creates an asset from a `multipart/form-data` upload. The name defaults to the file name.
+#[handler]
+pub async fn upload_asset_handler(
+    core: Data<&Core>,
+    headers: &HeaderMap,
+    mut multipart: Multipart,
+) -> Result<Json<Asset>> {
+    let member_id = extract_member_id(&core, headers)?;
+
+    let form = read_upload_form(&mut multipart, core.engine.config.asset_max_size).await?;
+    let content = form.content.ok_or(PlexoAppError::MissingAssetFile)?;
+
+    let mut input = UploadAssetInputBuilder::default()
+        .name(form.name.or(form.file_name).unwrap_or("Untitled".to_string()))
+        .owner_id(member_id);
+
+    if let Some(project_id) = form.project_id {
+        input = input.project_id(project_id);
+    }
+
+    let input = input.build().unwrap();
+    let saved_input = input.clone();
+
+    let asset = core
+        .engine
+        .upload_asset(input, content.into())
+        .await
+        .map_err(PlexoAppError::from)?;
+    let saved_asset = asset.clone();
+
+    let core = core.0.clone();
+
+    tokio::spawn(async move {
+        create_change(
+            &core,
+            member_id,
+            asset.id,
+            ChangeOperation::Insert,
+            ChangeResourceType::Assets,
+            serde_json::to_string(&json!({
+                "input": saved_input,
+                "result": asset,
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    });
+
+    Ok(Json(saved_asset))
+}
+
+/// Uploads a new version of an asset's content, from the `file` field of a `multipart/form-data`
+/// body.
+#[handler]
+pub async fn upload_asset_version_handler(
+    core: Data<&Core>,
+    headers: &HeaderMap,
+    Path(id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<Json<Asset>> {
+    let member_id = extract_member_id(&core, headers)?;
+
+    let form = read_upload_form(&mut multipart, core.engine.config.asset_max_size).await?;
+    let content = form.content.ok_or(PlexoAppError::MissingAssetFile)?;
+
+    let asset = core
+        .engine
+        .upload_asset_version(id, member_id, content.into())
+        .await
+        .map_err(PlexoAppError::from)?;
+    let saved_asset = asset.clone();
+
+    let core = core.0.clone();
+
+    tokio::spawn(async move {
+        create_change(
+            &core,
+            member_id,
+            asset.id,
+            ChangeOperation::Update,
+            ChangeResourceType::Assets,
+            serde_json::to_string(&json!({
+                "input": {
+                    "version": asset.version,
+                },
+                "result": asset,
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    });
+
+    Ok(Json(saved_asset))
+}
+
+#[derive(Deserialize)]
+pub struct DownloadAssetParams {
+    /// Defaults to the current version.
+    pub version: Option<i32>,
+}
+
+#[handler]
+pub async fn download_asset_handler(
+    core: Data<&Core>,
+    headers: &HeaderMap,
+    Path(id): Path<Uuid>,
+    Query(params): Query<DownloadAssetParams>,
+) -> Result<Response> {
+    extract_member_id(&core, headers)?;
+
+    let (version, content) = core
+        .engine
+        .download_asset(id, params.version)
+        .await
+        .map_err(PlexoAppError::from)?;
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, version.mime_type)
+        .header(CONTENT_LENGTH, version.size)
+        // Served as a download, uploaded HTML mustn't run under our origin.
+        .header(
+            CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", file_name(&version.name)),
+        )
+        .header(X_CONTENT_TYPE_OPTIONS, "nosniff")
+        .body(Body::from_bytes_stream(
+            content.map(|chunk| chunk.map_err(|err| std::io::Error::other(err.to_string()))),
+        )))
+}
+
+/// A name that fits in a quoted `Content-Disposition` filename.
+fn file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c.is_ascii_graphic() || c == ' ' {
+            true if c != '"' && c != '\\' => c,
+            _ => '_',
+        })
+        .collect()
+}
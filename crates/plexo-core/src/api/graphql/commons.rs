@@ -1,7 +1,13 @@
-use async_graphql::{Context, Result};
-use plexo_sdk::resources::changes::{
-    change::{Change, ChangeOperation, ChangeResourceType},
-    operations::{ChangeCrudOperations, CreateChangeInputBuilder},
+use async_graphql::{
+    connection::{Connection, Edge, EmptyFields},
+    Context, OutputType, Result, SimpleObject,
+};
+use plexo_sdk::{
+    common::pagination::{Page, PageCursor},
+    resources::changes::{
+        change::{Change, ChangeOperation, ChangeResourceType},
+        operations::{ChangeCrudOperations, CreateChangeInputBuilder},
+    },
 };
 // use tracing::info;
 use uuid::Uuid;
@@ -48,3 +54,31 @@ pub async fn create_change(
 
     Ok(change)
 }
+
+#[derive(SimpleObject)]
+pub struct ConnectionFields {
+    pub total_count: i64,
+}
+
+pub type PlexoConnection<T> = Connection<PageCursor, T, ConnectionFields, EmptyFields>;
+
+pub fn into_connection<S, T>(page: Page<S>) -> PlexoConnection<T>
+where
+    T: From<S> + OutputType,
+{
+    let mut connection = Connection::with_additional_fields(
+        page.has_previous_page,
+        page.has_next_page,
+        ConnectionFields {
+            total_count: page.total_count,
+        },
+    );
+
+    connection.edges.extend(
+        page.edges
+            .into_iter()
+            .map(|(cursor, node)| Edge::new(cursor, node.into())),
+    );
+
+    connection
+}
@@ -1,11 +1,15 @@
 use crate::api::graphql::{
-    commons::{create_change, extract_context},
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
     resources::assets::Asset,
 };
-use async_graphql::{Context, Object, Result, Subscription};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
 
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
 use plexo_sdk::resources::{
-    assets::operations::{AssetCrudOperations, CreateAssetInput, GetAssetsInput, UpdateAssetInput},
+    assets::{
+        extensions::AssetsExtensionOperations,
+        operations::{AssetCrudOperations, CreateAssetInput, GetAssetsInput, GetAssetsWhere, UpdateAssetInput},
+    },
     changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
 };
 use serde_json::json;
@@ -28,6 +32,39 @@ impl AssetsGraphQLQuery {
             .map(|assets| assets.into_iter().map(|asset| asset.into()).collect())
     }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn assets_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetAssetsWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<Asset>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_assets_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
     async fn asset(&self, ctx: &Context<'_>, id: Uuid) -> Result<Asset> {
         let (core, _member_id) = extract_context(ctx)?;
 
@@ -132,6 +169,36 @@ impl AssetsGraphQLMutation {
 
         Ok(saved_asset.into())
     }
+
+    /// Makes the content of an older version current again. The restored content becomes the
+    /// newest version, authored by the caller.
+    async fn restore_asset_version(&self, ctx: &Context<'_>, id: Uuid, version: i32) -> Result<Asset> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let asset = core.engine.restore_asset_version(id, version, member_id).await?;
+        let saved_asset = asset.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                asset.id,
+                ChangeOperation::Update,
+                ChangeResourceType::Assets,
+                serde_json::to_string(&json!({
+                    "input": {
+                        "restore_version": version,
+                    },
+                    "result": asset,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_asset.into())
+    }
 }
 
 #[derive(Default)]
@@ -1,12 +1,13 @@
 use crate::api::graphql::{
-    commons::{create_change, extract_context},
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
     resources::changes::Change,
 };
-use async_graphql::{Context, Object, Result};
+use async_graphql::{connection::query_with, Context, Object, Result};
 
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
 use plexo_sdk::resources::changes::{
     change::{ChangeOperation, ChangeResourceType},
-    operations::{ChangeCrudOperations, CreateChangeInput, GetChangesInput, UpdateChangeInput},
+    operations::{ChangeCrudOperations, CreateChangeInput, GetChangesInput, GetChangesWhere, UpdateChangeInput},
 };
 use serde_json::json;
 use uuid::Uuid;
@@ -26,6 +27,39 @@ impl ChangesGraphQLQuery {
             .map(|changes| changes.into_iter().map(|change| change.into()).collect())
     }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn changes_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetChangesWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<Change>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_changes_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
     async fn change(&self, ctx: &Context<'_>, id: Uuid) -> Result<Change> {
         let (core, _member_id) = extract_context(ctx)?;
 
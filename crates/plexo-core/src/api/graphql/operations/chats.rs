@@ -1,12 +1,13 @@
 use crate::api::graphql::{
-    commons::{create_change, extract_context},
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
     resources::chats::Chat,
 };
-use async_graphql::{Context, Object, Result, Subscription};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
 
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
 use plexo_sdk::resources::{
     changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
-    chats::operations::{ChatCrudOperations, CreateChatInput, GetChatsInput, UpdateChatInput},
+    chats::operations::{ChatCrudOperations, CreateChatInput, GetChatsInput, GetChatsWhere, UpdateChatInput},
 };
 
 use serde_json::json;
@@ -29,6 +30,39 @@ impl ChatsGraphQLQuery {
             .map(|chats| chats.into_iter().map(|chat| chat.into()).collect())
     }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn chats_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetChatsWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<Chat>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_chats_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
     async fn chat(&self, ctx: &Context<'_>, id: Uuid) -> Result<Chat> {
         let (core, _member_id) = extract_context(ctx)?;
 
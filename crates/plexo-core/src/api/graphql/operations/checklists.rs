@@ -0,0 +1,169 @@
+use crate::api::graphql::commons::{create_change, extract_context};
+use async_graphql::{Context, Object, Result, Subscription};
+
+use plexo_sdk::resources::{
+    changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
+    checklists::{
+        checklist::ChecklistItem,
+        operations::{AddChecklistItemInput, ChecklistOperations, ToggleChecklistItemInput},
+    },
+};
+use serde_json::json;
+use tokio::task;
+use tokio_stream::{Stream, StreamExt};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct ChecklistsGraphQLQuery;
+
+#[Object]
+impl ChecklistsGraphQLQuery {
+    async fn checklist_items(&self, ctx: &Context<'_>, task_id: Uuid) -> Result<Vec<ChecklistItem>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_checklist_items(task_id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+    }
+}
+
+#[derive(Default)]
+pub struct ChecklistsGraphQLMutation;
+
+#[Object]
+impl ChecklistsGraphQLMutation {
+    async fn add_checklist_item(&self, ctx: &Context<'_>, input: AddChecklistItemInput) -> Result<ChecklistItem> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        let saved_input = input.clone();
+
+        let item = core.engine.add_checklist_item(input).await?;
+        let saved_item = item.clone();
+
+        task::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                item.id,
+                ChangeOperation::Insert,
+                ChangeResourceType::ChecklistItems,
+                serde_json::to_string(&json!({
+                    "input": saved_input,
+                    "result": item,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_item)
+    }
+
+    async fn reorder_checklist_item(&self, ctx: &Context<'_>, id: Uuid, position: i32) -> Result<ChecklistItem> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let item = core.engine.reorder_checklist_item(id, position).await?;
+        let saved_item = item.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                item.id,
+                ChangeOperation::Update,
+                ChangeResourceType::ChecklistItems,
+                serde_json::to_string(&json!({
+                    "input": { "position": position },
+                    "result": item,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_item)
+    }
+
+    async fn toggle_checklist_item(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        input: Option<ToggleChecklistItemInput>,
+    ) -> Result<ChecklistItem> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input.unwrap_or_default();
+        input.member_id = member_id;
+
+        let saved_input = input.clone();
+
+        let item = core.engine.toggle_checklist_item(id, input).await?;
+        let saved_item = item.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                item.id,
+                ChangeOperation::Update,
+                ChangeResourceType::ChecklistItems,
+                serde_json::to_string(&json!({
+                    "input": saved_input,
+                    "result": item,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_item)
+    }
+
+    async fn delete_checklist_item(&self, ctx: &Context<'_>, id: Uuid) -> Result<ChecklistItem> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let item = core.engine.delete_checklist_item(id).await?;
+        let saved_item = item.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                item.id,
+                ChangeOperation::Delete,
+                ChangeResourceType::ChecklistItems,
+                serde_json::to_string(&json!({
+                    "result": item,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_item)
+    }
+}
+
+#[derive(Default)]
+pub struct ChecklistsGraphQLSubscription;
+
+#[Subscription]
+impl ChecklistsGraphQLSubscription {
+    async fn checklist_items(&self, ctx: &Context<'_>) -> impl Stream<Item = ListenEvent> {
+        let (core, _member_id) = extract_context(ctx).unwrap();
+
+        core.engine
+            .listen(ChangeResourceType::ChecklistItems)
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+    }
+}
@@ -0,0 +1,185 @@
+use crate::api::graphql::{
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
+    resources::comments::Comment,
+};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
+
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
+use plexo_sdk::resources::{
+    changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
+    comments::operations::{
+        CommentCrudOperations, CreateCommentInput, GetCommentsInput, GetCommentsWhere, UpdateCommentInput,
+    },
+};
+use serde_json::json;
+use tokio::task;
+use tokio_stream::{Stream, StreamExt};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct CommentsGraphQLQuery;
+
+#[Object]
+impl CommentsGraphQLQuery {
+    async fn comments(&self, ctx: &Context<'_>, input: Option<GetCommentsInput>) -> Result<Vec<Comment>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_comments(input.unwrap_or_default())
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|comments| comments.into_iter().map(|comment| comment.into()).collect())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn comments_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetCommentsWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<Comment>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_comments_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
+    async fn comment(&self, ctx: &Context<'_>, id: Uuid) -> Result<Comment> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_comment(id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|comment| comment.into())
+    }
+}
+
+#[derive(Default)]
+pub struct CommentsGraphQLMutation;
+
+#[Object]
+impl CommentsGraphQLMutation {
+    async fn create_comment(&self, ctx: &Context<'_>, input: CreateCommentInput) -> Result<Comment> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        let saved_input = input.clone();
+
+        let comment = core.engine.create_comment(input).await?;
+        let saved_comment = comment.clone();
+
+        task::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                comment.id,
+                ChangeOperation::Insert,
+                ChangeResourceType::Comments,
+                serde_json::to_string(&json!({
+                    "input": saved_input,
+                    "result": comment,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_comment.into())
+    }
+
+    async fn update_comment(&self, ctx: &Context<'_>, id: Uuid, input: UpdateCommentInput) -> Result<Comment> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.editor_id = member_id;
+
+        let saved_input = input.clone();
+
+        let comment = core.engine.update_comment(id, input).await?;
+        let saved_comment = comment.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                comment.id,
+                ChangeOperation::Update,
+                ChangeResourceType::Comments,
+                serde_json::to_string(&json!({
+                    "input": saved_input,
+                    "result": comment,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_comment.into())
+    }
+
+    async fn delete_comment(&self, ctx: &Context<'_>, id: Uuid) -> Result<Comment> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let comment = core.engine.delete_comment(id, member_id).await?;
+        let saved_comment = comment.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                comment.id,
+                ChangeOperation::Delete,
+                ChangeResourceType::Comments,
+                serde_json::to_string(&json!({
+                    "result": comment,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_comment.into())
+    }
+}
+
+#[derive(Default)]
+pub struct CommentsGraphQLSubscription;
+
+#[Subscription]
+impl CommentsGraphQLSubscription {
+    async fn comments(&self, ctx: &Context<'_>) -> impl Stream<Item = ListenEvent> {
+        let (core, _member_id) = extract_context(ctx).unwrap();
+
+        core.engine
+            .listen(ChangeResourceType::Comments)
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+    }
+}
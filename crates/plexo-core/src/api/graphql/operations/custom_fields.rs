@@ -0,0 +1,214 @@
+use crate::api::graphql::{
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
+    resources::custom_fields::CustomField,
+};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
+
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
+use plexo_sdk::resources::{
+    changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
+    custom_fields::{
+        custom_field::CustomFieldValue,
+        extensions::{CustomFieldsExtensionOperations, SetCustomFieldValuesInput},
+        operations::{
+            CreateCustomFieldInput, CustomFieldCrudOperations, GetCustomFieldsInput, GetCustomFieldsWhere,
+            UpdateCustomFieldInput,
+        },
+    },
+};
+use serde_json::json;
+use tokio::task;
+use tokio_stream::{Stream, StreamExt};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct CustomFieldsGraphQLQuery;
+
+#[Object]
+impl CustomFieldsGraphQLQuery {
+    async fn custom_fields(&self, ctx: &Context<'_>, input: Option<GetCustomFieldsInput>) -> Result<Vec<CustomField>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_custom_fields(input.unwrap_or_default())
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|custom_fields| {
+                custom_fields
+                    .into_iter()
+                    .map(|custom_field| custom_field.into())
+                    .collect()
+            })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn custom_fields_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetCustomFieldsWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<CustomField>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_custom_fields_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
+    async fn custom_field(&self, ctx: &Context<'_>, id: Uuid) -> Result<CustomField> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_custom_field(id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|custom_field| custom_field.into())
+    }
+}
+
+#[derive(Default)]
+pub struct CustomFieldsGraphQLMutation;
+
+#[Object]
+impl CustomFieldsGraphQLMutation {
+    async fn create_custom_field(&self, ctx: &Context<'_>, input: CreateCustomFieldInput) -> Result<CustomField> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        let saved_input = input.clone();
+
+        let custom_field = core.engine.create_custom_field(input).await?;
+        let saved_custom_field = custom_field.clone();
+
+        let input = saved_input.clone();
+
+        task::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                custom_field.id,
+                ChangeOperation::Insert,
+                ChangeResourceType::CustomFields,
+                serde_json::to_string(&json!({
+                    "input": input,
+                    "result": custom_field,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_custom_field.into())
+    }
+
+    async fn update_custom_field(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        input: UpdateCustomFieldInput,
+    ) -> Result<CustomField> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let saved_input = input.clone();
+
+        let custom_field = core.engine.update_custom_field(id, input).await?;
+        let saved_custom_field = custom_field.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                custom_field.id,
+                ChangeOperation::Update,
+                ChangeResourceType::CustomFields,
+                serde_json::to_string(&json!({
+                    "input": saved_input,
+                    "result": custom_field,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_custom_field.into())
+    }
+
+    async fn delete_custom_field(&self, ctx: &Context<'_>, id: Uuid) -> Result<CustomField> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let custom_field = core.engine.delete_custom_field(id).await?;
+        let saved_custom_field = custom_field.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                custom_field.id,
+                ChangeOperation::Delete,
+                ChangeResourceType::CustomFields,
+                serde_json::to_string(&json!({
+                    "result": custom_field,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_custom_field.into())
+    }
+
+    // The task change is recorded by the SDK, in the same transaction as the values.
+    async fn set_task_custom_field_values(
+        &self,
+        ctx: &Context<'_>,
+        task_id: Uuid,
+        input: SetCustomFieldValuesInput,
+    ) -> Result<Vec<CustomFieldValue>> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        Ok(core.engine.set_task_custom_field_values(task_id, input).await?)
+    }
+}
+
+#[derive(Default)]
+pub struct CustomFieldsGraphQLSubscription;
+
+#[Subscription]
+impl CustomFieldsGraphQLSubscription {
+    async fn custom_fields(&self, ctx: &Context<'_>) -> impl Stream<Item = ListenEvent> {
+        let (core, _member_id) = extract_context(ctx).unwrap();
+
+        core.engine
+            .listen(ChangeResourceType::CustomFields)
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+    }
+}
@@ -0,0 +1,198 @@
+use crate::api::graphql::{
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
+    resources::cycles::Cycle,
+};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
+
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
+use plexo_sdk::resources::{
+    changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
+    cycles::{
+        extensions::{CloseCycleInput, CyclesExtensionOperations},
+        operations::{CreateCycleInput, CycleCrudOperations, GetCyclesInput, GetCyclesWhere, UpdateCycleInput},
+    },
+};
+use serde_json::json;
+use tokio::task;
+use tokio_stream::{Stream, StreamExt};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct CyclesGraphQLQuery;
+
+#[Object]
+impl CyclesGraphQLQuery {
+    async fn cycles(&self, ctx: &Context<'_>, input: Option<GetCyclesInput>) -> Result<Vec<Cycle>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_cycles(input.unwrap_or_default())
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|cycles| cycles.into_iter().map(|cycle| cycle.into()).collect())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn cycles_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetCyclesWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<Cycle>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_cycles_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
+    async fn cycle(&self, ctx: &Context<'_>, id: Uuid) -> Result<Cycle> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_cycle(id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|cycle| cycle.into())
+    }
+}
+
+#[derive(Default)]
+pub struct CyclesGraphQLMutation;
+
+#[Object]
+impl CyclesGraphQLMutation {
+    async fn create_cycle(&self, ctx: &Context<'_>, input: CreateCycleInput) -> Result<Cycle> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        let saved_input = input.clone();
+
+        let cycle = core.engine.create_cycle(input).await?;
+        let saved_cycle = cycle.clone();
+
+        let input = saved_input.clone();
+
+        task::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                cycle.id,
+                ChangeOperation::Insert,
+                ChangeResourceType::Cycles,
+                serde_json::to_string(&json!({
+                    "input": input,
+                    "result": cycle,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_cycle.into())
+    }
+
+    async fn update_cycle(&self, ctx: &Context<'_>, id: Uuid, input: UpdateCycleInput) -> Result<Cycle> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let saved_input = input.clone();
+
+        let cycle = core.engine.update_cycle(id, input).await?;
+        let saved_cycle = cycle.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                cycle.id,
+                ChangeOperation::Update,
+                ChangeResourceType::Cycles,
+                serde_json::to_string(&json!({
+                    "input": saved_input,
+                    "result": cycle,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_cycle.into())
+    }
+
+    async fn delete_cycle(&self, ctx: &Context<'_>, id: Uuid) -> Result<Cycle> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let cycle = core.engine.delete_cycle(id).await?;
+        let saved_cycle = cycle.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                cycle.id,
+                ChangeOperation::Delete,
+                ChangeResourceType::Cycles,
+                serde_json::to_string(&json!({
+                    "result": cycle,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_cycle.into())
+    }
+
+    // The changes of the cycle and its rolled over tasks are recorded by the SDK, in the same
+    // transaction as the rollover.
+    async fn close_cycle(&self, ctx: &Context<'_>, id: Uuid, input: Option<CloseCycleInput>) -> Result<Cycle> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input.unwrap_or_default();
+        input.owner_id = member_id;
+
+        let cycle = core.engine.close_cycle(id, input).await?;
+
+        Ok(cycle.into())
+    }
+}
+
+#[derive(Default)]
+pub struct CyclesGraphQLSubscription;
+
+#[Subscription]
+impl CyclesGraphQLSubscription {
+    async fn cycles(&self, ctx: &Context<'_>) -> impl Stream<Item = ListenEvent> {
+        let (core, _member_id) = extract_context(ctx).unwrap();
+
+        core.engine
+            .listen(ChangeResourceType::Cycles)
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+    }
+}
@@ -0,0 +1,192 @@
+use crate::api::graphql::{
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
+    resources::label_groups::LabelGroup,
+};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
+
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
+use plexo_sdk::resources::{
+    changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
+    label_groups::operations::{
+        CreateLabelGroupInput, GetLabelGroupsInput, GetLabelGroupsWhere, LabelGroupCrudOperations,
+        UpdateLabelGroupInput,
+    },
+};
+use serde_json::json;
+use tokio::task;
+use tokio_stream::{Stream, StreamExt};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct LabelGroupsGraphQLQuery;
+
+#[Object]
+impl LabelGroupsGraphQLQuery {
+    async fn label_groups(&self, ctx: &Context<'_>, input: Option<GetLabelGroupsInput>) -> Result<Vec<LabelGroup>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_label_groups(input.unwrap_or_default())
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|label_groups| label_groups.into_iter().map(|label_group| label_group.into()).collect())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn label_groups_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetLabelGroupsWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<LabelGroup>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_label_groups_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
+    async fn label_group(&self, ctx: &Context<'_>, id: Uuid) -> Result<LabelGroup> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_label_group(id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|label_group| label_group.into())
+    }
+}
+
+#[derive(Default)]
+pub struct LabelGroupsGraphQLMutation;
+
+#[Object]
+impl LabelGroupsGraphQLMutation {
+    async fn create_label_group(&self, ctx: &Context<'_>, input: CreateLabelGroupInput) -> Result<LabelGroup> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        let saved_input = input.clone();
+
+        let label_group = core.engine.create_label_group(input).await?;
+        let saved_label_group = label_group.clone();
+
+        let input = saved_input.clone();
+
+        task::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                label_group.id,
+                ChangeOperation::Insert,
+                ChangeResourceType::LabelGroups,
+                serde_json::to_string(&json!({
+                    "input": input,
+                    "result": label_group,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_label_group.into())
+    }
+
+    async fn update_label_group(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        input: UpdateLabelGroupInput,
+    ) -> Result<LabelGroup> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let saved_input = input.clone();
+
+        let label_group = core.engine.update_label_group(id, input).await?;
+
+        let label_group = label_group.clone();
+        let saved_label_group = label_group.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                label_group.id,
+                ChangeOperation::Update,
+                ChangeResourceType::LabelGroups,
+                serde_json::to_string(&json!({
+                    "input": saved_input,
+                    "result": label_group,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_label_group.into())
+    }
+
+    async fn delete_label_group(&self, ctx: &Context<'_>, id: Uuid) -> Result<LabelGroup> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        let label_group = core.engine.delete_label_group(id).await?;
+        let saved_label_group = label_group.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                label_group.owner_id,
+                label_group.id,
+                ChangeOperation::Delete,
+                ChangeResourceType::LabelGroups,
+                serde_json::to_string(&json!({
+                    "result": label_group,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_label_group.into())
+    }
+}
+
+#[derive(Default)]
+pub struct LabelGroupsGraphQLSubscription;
+
+#[Subscription]
+impl LabelGroupsGraphQLSubscription {
+    async fn label_groups(&self, ctx: &Context<'_>) -> impl Stream<Item = ListenEvent> {
+        let (core, _member_id) = extract_context(ctx).unwrap();
+
+        core.engine
+            .listen(ChangeResourceType::LabelGroups)
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+    }
+}
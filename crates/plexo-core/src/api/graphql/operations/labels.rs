@@ -1,12 +1,16 @@
 use crate::api::graphql::{
-    commons::{create_change, extract_context},
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
     resources::labels::Label,
 };
-use async_graphql::{Context, Object, Result, Subscription};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
 
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
 use plexo_sdk::resources::{
     changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
-    labels::operations::{CreateLabelInput, GetLabelsInput, LabelCrudOperations, UpdateLabelInput},
+    labels::{
+        extensions::LabelsExtensionOperations,
+        operations::{CreateLabelInput, GetLabelsInput, GetLabelsWhere, LabelCrudOperations, UpdateLabelInput},
+    },
 };
 use serde_json::json;
 use tokio::task;
@@ -28,6 +32,39 @@ impl LabelsGraphQLQuery {
             .map(|labels| labels.into_iter().map(|label| label.into()).collect())
     }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn labels_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetLabelsWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<Label>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_labels_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
     async fn label(&self, ctx: &Context<'_>, id: Uuid) -> Result<Label> {
         let (core, _member_id) = extract_context(ctx)?;
 
@@ -132,6 +169,53 @@ impl LabelsGraphQLMutation {
 
         Ok(saved_label.into())
     }
+
+    /// Moves the tasks of the source label to the target label and deletes the source label.
+    async fn merge_labels(&self, ctx: &Context<'_>, source_id: Uuid, target_id: Uuid) -> Result<Label> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let source = core.engine.get_label(source_id).await?;
+        let label = core.engine.merge_labels(source_id, target_id).await?;
+        let saved_label = label.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                source.id,
+                ChangeOperation::Delete,
+                ChangeResourceType::Labels,
+                serde_json::to_string(&json!({
+                    "input": {
+                        "merged_into": label.id,
+                    },
+                    "result": source,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+
+            create_change(
+                &core,
+                member_id,
+                label.id,
+                ChangeOperation::Update,
+                ChangeResourceType::Labels,
+                serde_json::to_string(&json!({
+                    "input": {
+                        "merged_from": source.id,
+                    },
+                    "result": label,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_label.into())
+    }
 }
 
 #[derive(Default)]
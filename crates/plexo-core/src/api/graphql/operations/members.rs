@@ -1,9 +1,15 @@
-use crate::api::graphql::{commons::extract_context, resources::members::Member};
-use async_graphql::{Context, Object, Result, Subscription};
+use crate::api::graphql::{
+    commons::{extract_context, into_connection, PlexoConnection},
+    resources::members::Member,
+};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
 
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
 use plexo_sdk::resources::{
     changes::change::{ChangeResourceType, ListenEvent},
-    members::operations::{CreateMemberInput, GetMembersInput, MemberCrudOperations, UpdateMemberInput},
+    members::operations::{
+        CreateMemberInput, GetMembersInput, GetMembersWhere, MemberCrudOperations, UpdateMemberInput,
+    },
 };
 use tokio_stream::{Stream, StreamExt};
 use uuid::Uuid;
@@ -23,6 +29,39 @@ impl MembersGraphQLQuery {
             .map(|members| members.into_iter().map(|member| member.into()).collect())
     }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn members_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetMembersWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<Member>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_members_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
     async fn member(&self, ctx: &Context<'_>, id: Uuid) -> Result<Member> {
         let (core, _member_id) = extract_context(ctx)?;
 
@@ -1,12 +1,15 @@
 use crate::api::graphql::{
-    commons::{create_change, extract_context},
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
     resources::messages::Message,
 };
-use async_graphql::{Context, Object, Result, Subscription};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
 
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
 use plexo_sdk::resources::{
     changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
-    messages::operations::{CreateMessageInput, GetMessagesInput, MessageCrudOperations, UpdateMessageInput},
+    messages::operations::{
+        CreateMessageInput, GetMessagesInput, GetMessagesWhere, MessageCrudOperations, UpdateMessageInput,
+    },
 };
 
 use serde_json::json;
@@ -29,6 +32,39 @@ impl MessagesGraphQLQuery {
             .map(|messages| messages.into_iter().map(|message| message.into()).collect())
     }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn messages_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetMessagesWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<Message>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_messages_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
     async fn message(&self, ctx: &Context<'_>, id: Uuid) -> Result<Message> {
         let (core, _member_id) = extract_context(ctx)?;
 
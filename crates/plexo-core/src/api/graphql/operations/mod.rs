@@ -2,10 +2,20 @@ pub mod assets;
 pub mod auth;
 pub mod changes;
 pub mod chats;
+pub mod checklists;
+pub mod comments;
+pub mod custom_fields;
+pub mod cycles;
+pub mod label_groups;
 pub mod labels;
 pub mod members;
 pub mod messages;
 pub mod profile;
+pub mod project_templates;
 pub mod projects;
+pub mod search;
+pub mod task_templates;
 pub mod tasks;
 pub mod teams;
+pub mod work_logs;
+pub mod workflows;
@@ -0,0 +1,218 @@
+use crate::api::graphql::{
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
+    resources::{project_templates::ProjectTemplate, projects::ProjectCopySummary},
+};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
+
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
+use plexo_sdk::resources::{
+    changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
+    project_templates::{
+        extensions::{InstantiateProjectTemplateInput, ProjectTemplatesExtensionOperations},
+        operations::{
+            CreateProjectTemplateInput, GetProjectTemplatesInput, GetProjectTemplatesWhere,
+            ProjectTemplateCrudOperations, UpdateProjectTemplateInput,
+        },
+    },
+};
+use serde_json::json;
+use tokio::task;
+use tokio_stream::{Stream, StreamExt};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct ProjectTemplatesGraphQLQuery;
+
+#[Object]
+impl ProjectTemplatesGraphQLQuery {
+    async fn project_templates(
+        &self,
+        ctx: &Context<'_>,
+        input: Option<GetProjectTemplatesInput>,
+    ) -> Result<Vec<ProjectTemplate>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_project_templates(input.unwrap_or_default())
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|templates| templates.into_iter().map(|template| template.into()).collect())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn project_templates_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetProjectTemplatesWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<ProjectTemplate>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_project_templates_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
+    async fn project_template(&self, ctx: &Context<'_>, id: Uuid) -> Result<ProjectTemplate> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_project_template(id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|template| template.into())
+    }
+}
+
+#[derive(Default)]
+pub struct ProjectTemplatesGraphQLMutation;
+
+#[Object]
+impl ProjectTemplatesGraphQLMutation {
+    async fn create_project_template(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateProjectTemplateInput,
+    ) -> Result<ProjectTemplate> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        let saved_input = input.clone();
+
+        let template = core.engine.create_project_template(input).await?;
+        let saved_template = template.clone();
+
+        task::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                template.id,
+                ChangeOperation::Insert,
+                ChangeResourceType::ProjectTemplates,
+                serde_json::to_string(&json!({
+                    "input": saved_input,
+                    "result": template,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_template.into())
+    }
+
+    async fn update_project_template(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        input: UpdateProjectTemplateInput,
+    ) -> Result<ProjectTemplate> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let saved_input = input.clone();
+
+        let template = core.engine.update_project_template(id, input).await?;
+        let saved_template = template.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                template.id,
+                ChangeOperation::Update,
+                ChangeResourceType::ProjectTemplates,
+                serde_json::to_string(&json!({
+                    "input": saved_input,
+                    "result": template,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_template.into())
+    }
+
+    async fn delete_project_template(&self, ctx: &Context<'_>, id: Uuid) -> Result<ProjectTemplate> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let template = core.engine.delete_project_template(id).await?;
+        let saved_template = template.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                template.id,
+                ChangeOperation::Delete,
+                ChangeResourceType::ProjectTemplates,
+                serde_json::to_string(&json!({
+                    "result": template,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_template.into())
+    }
+
+    // The project change is recorded by the SDK, in the same transaction as the copy.
+    async fn instantiate_project_template(
+        &self,
+        ctx: &Context<'_>,
+        template_id: Uuid,
+        input: InstantiateProjectTemplateInput,
+    ) -> Result<ProjectCopySummary> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        Ok(core
+            .engine
+            .instantiate_project_template(template_id, input)
+            .await?
+            .into())
+    }
+}
+
+#[derive(Default)]
+pub struct ProjectTemplatesGraphQLSubscription;
+
+#[Subscription]
+impl ProjectTemplatesGraphQLSubscription {
+    async fn project_templates(&self, ctx: &Context<'_>) -> impl Stream<Item = ListenEvent> {
+        let (core, _member_id) = extract_context(ctx).unwrap();
+
+        core.engine
+            .listen(ChangeResourceType::ProjectTemplates)
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+    }
+}
@@ -1,12 +1,18 @@
 use crate::api::graphql::{
-    commons::{create_change, extract_context},
-    resources::projects::Project,
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
+    resources::projects::{Project, ProjectCopySummary},
 };
-use async_graphql::{Context, Object, Result, Subscription};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
 
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
 use plexo_sdk::resources::{
     changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
-    projects::operations::{CreateProjectInput, GetProjectsInput, ProjectCrudOperations, UpdateProjectInput},
+    projects::{
+        extensions::{CloneProjectInput, ProjectsExtensionOperations},
+        operations::{
+            CreateProjectInput, GetProjectsInput, GetProjectsWhere, ProjectCrudOperations, UpdateProjectInput,
+        },
+    },
 };
 
 use serde_json::json;
@@ -29,6 +35,39 @@ impl ProjectsGraphQLQuery {
             .map(|projects| projects.into_iter().map(|project| project.into()).collect())
     }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn projects_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetProjectsWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<Project>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_projects_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
     async fn project(&self, ctx: &Context<'_>, id: Uuid) -> Result<Project> {
         let (core, _member_id) = extract_context(ctx)?;
 
@@ -150,6 +189,77 @@ impl ProjectsGraphQLMutation {
         //     .map_err(|err| async_graphql::Error::new(err.to_string()))
         //     .map(|project| project.into())
     }
+
+    async fn archive_project(&self, ctx: &Context<'_>, id: Uuid) -> Result<Project> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let project = core.engine.archive_project(id).await?;
+        let saved_project = project.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                project.id,
+                ChangeOperation::Update,
+                ChangeResourceType::Projects,
+                serde_json::to_string(&json!({
+                    "input": {
+                        "archive": true,
+                    },
+                    "result": project,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_project.into())
+    }
+
+    async fn restore_project(&self, ctx: &Context<'_>, id: Uuid) -> Result<Project> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let project = core.engine.restore_project(id).await?;
+        let saved_project = project.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                project.id,
+                ChangeOperation::Update,
+                ChangeResourceType::Projects,
+                serde_json::to_string(&json!({
+                    "input": {
+                        "restore": true,
+                    },
+                    "result": project,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_project.into())
+    }
+
+    // The project change is recorded by the SDK, in the same transaction as the copy.
+    async fn clone_project(
+        &self,
+        ctx: &Context<'_>,
+        source_id: Uuid,
+        input: CloneProjectInput,
+    ) -> Result<ProjectCopySummary> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        Ok(core.engine.clone_project(source_id, input).await?.into())
+    }
 }
 
 #[derive(Default)]
@@ -0,0 +1,32 @@
+use async_graphql::{Context, Object, Result};
+use plexo_sdk::search::operations::{SearchHit, SearchInput, SearchOperations, SearchResourceType};
+use uuid::Uuid;
+
+use crate::api::graphql::commons::extract_context;
+
+#[derive(Default)]
+pub struct SearchGraphQLQuery;
+
+#[Object]
+impl SearchGraphQLQuery {
+    async fn search(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        types: Option<Vec<SearchResourceType>>,
+        project_id: Option<Uuid>,
+        limit: Option<i32>,
+    ) -> Result<Vec<SearchHit>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .search(SearchInput {
+                query,
+                types,
+                project_id,
+                limit: limit.or(Some(20)),
+            })
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+    }
+}
@@ -0,0 +1,252 @@
+use crate::api::graphql::{
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
+    resources::{task_templates::TaskTemplate, tasks::Task},
+};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
+
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
+use plexo_sdk::resources::{
+    changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
+    task_templates::{
+        extensions::{InstantiateTemplateInput, SaveTaskAsTemplateInput, TaskTemplatesExtensionOperations},
+        operations::{
+            CreateTaskTemplateInput, GetTaskTemplatesInput, GetTaskTemplatesWhere, TaskTemplateCrudOperations,
+            UpdateTaskTemplateInput,
+        },
+    },
+};
+use serde_json::json;
+use tokio::task;
+use tokio_stream::{Stream, StreamExt};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct TaskTemplatesGraphQLQuery;
+
+#[Object]
+impl TaskTemplatesGraphQLQuery {
+    async fn task_templates(
+        &self,
+        ctx: &Context<'_>,
+        input: Option<GetTaskTemplatesInput>,
+    ) -> Result<Vec<TaskTemplate>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_task_templates(input.unwrap_or_default())
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|templates| templates.into_iter().map(|template| template.into()).collect())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn task_templates_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetTaskTemplatesWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<TaskTemplate>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_task_templates_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
+    async fn task_template(&self, ctx: &Context<'_>, id: Uuid) -> Result<TaskTemplate> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_task_template(id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|template| template.into())
+    }
+}
+
+#[derive(Default)]
+pub struct TaskTemplatesGraphQLMutation;
+
+#[Object]
+impl TaskTemplatesGraphQLMutation {
+    async fn create_task_template(&self, ctx: &Context<'_>, input: CreateTaskTemplateInput) -> Result<TaskTemplate> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        let saved_input = input.clone();
+
+        let template = core.engine.create_task_template(input).await?;
+        let saved_template = template.clone();
+
+        task::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                template.id,
+                ChangeOperation::Insert,
+                ChangeResourceType::TaskTemplates,
+                serde_json::to_string(&json!({
+                    "input": saved_input,
+                    "result": template,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_template.into())
+    }
+
+    async fn update_task_template(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        input: UpdateTaskTemplateInput,
+    ) -> Result<TaskTemplate> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let saved_input = input.clone();
+
+        let template = core.engine.update_task_template(id, input).await?;
+        let saved_template = template.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                template.id,
+                ChangeOperation::Update,
+                ChangeResourceType::TaskTemplates,
+                serde_json::to_string(&json!({
+                    "input": saved_input,
+                    "result": template,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_template.into())
+    }
+
+    async fn delete_task_template(&self, ctx: &Context<'_>, id: Uuid) -> Result<TaskTemplate> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let template = core.engine.delete_task_template(id).await?;
+        let saved_template = template.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                template.id,
+                ChangeOperation::Delete,
+                ChangeResourceType::TaskTemplates,
+                serde_json::to_string(&json!({
+                    "result": template,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_template.into())
+    }
+
+    // The task change is recorded by the SDK, in the same transaction as the task tree.
+    async fn instantiate_task_template(
+        &self,
+        ctx: &Context<'_>,
+        template_id: Uuid,
+        project_id: Uuid,
+        input: Option<InstantiateTemplateInput>,
+    ) -> Result<Task> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input.unwrap_or_default();
+        input.owner_id = member_id;
+
+        Ok(core
+            .engine
+            .instantiate_template(template_id, project_id, input)
+            .await?
+            .into())
+    }
+
+    async fn save_task_as_template(
+        &self,
+        ctx: &Context<'_>,
+        task_id: Uuid,
+        input: SaveTaskAsTemplateInput,
+    ) -> Result<TaskTemplate> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        let saved_input = input.clone();
+
+        let template = core.engine.save_task_as_template(task_id, input).await?;
+        let saved_template = template.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                template.id,
+                ChangeOperation::Insert,
+                ChangeResourceType::TaskTemplates,
+                serde_json::to_string(&json!({
+                    "task_id": task_id,
+                    "input": saved_input,
+                    "result": template,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_template.into())
+    }
+}
+
+#[derive(Default)]
+pub struct TaskTemplatesGraphQLSubscription;
+
+#[Subscription]
+impl TaskTemplatesGraphQLSubscription {
+    async fn task_templates(&self, ctx: &Context<'_>) -> impl Stream<Item = ListenEvent> {
+        let (core, _member_id) = extract_context(ctx).unwrap();
+
+        core.engine
+            .listen(ChangeResourceType::TaskTemplates)
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+    }
+}
@@ -1,14 +1,20 @@
 use crate::api::graphql::{
-    commons::{create_change, extract_context},
-    resources::tasks::Task,
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
+    resources::tasks::{BoardColumn, Task, TaskTree},
 };
-use async_graphql::{Context, Object, Result, Subscription};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
 
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
 use plexo_sdk::resources::{
     changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
+    recurrences::{
+        operations::{RecurrenceOperations, SetTaskRecurrenceInput, UpdateTaskRecurrenceInput},
+        recurrence::Recurrence,
+    },
     tasks::{
-        extensions::{CreateTasksInput, TasksExtensionOperations},
-        operations::{CreateTaskInput, GetTasksInput, TaskCrudOperations, UpdateTaskInput},
+        extensions::{CreateTasksInput, DeleteTasksInput, MoveTaskInput, TasksExtensionOperations, UpdateTasksInput},
+        operations::{CreateTaskInput, GetTasksInput, GetTasksWhere, TaskCrudOperations, UpdateTaskInput},
+        task::TaskStatus,
     },
 };
 use serde_json::json;
@@ -31,6 +37,39 @@ impl TasksGraphQLQuery {
             .map(|tasks| tasks.into_iter().map(|task| task.into()).collect())
     }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn tasks_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetTasksWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<Task>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_tasks_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
     async fn task(&self, ctx: &Context<'_>, id: Uuid) -> Result<Task> {
         let (core, _member_id) = extract_context(ctx)?;
 
@@ -40,6 +79,37 @@ impl TasksGraphQLQuery {
             .map_err(|err| async_graphql::Error::new(err.to_string()))
             .map(|task| task.into())
     }
+
+    async fn task_tree(&self, ctx: &Context<'_>, id: Uuid, max_depth: Option<i32>) -> Result<TaskTree> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .task_tree(id, max_depth)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|tree| tree.into())
+    }
+
+    async fn task_by_key(&self, ctx: &Context<'_>, key: String) -> Result<Task> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .task_by_key(key)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|task| task.into())
+    }
+
+    /// The tasks of a project grouped by status, each column in board order.
+    async fn board(&self, ctx: &Context<'_>, project_id: Uuid) -> Result<Vec<BoardColumn>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .board(project_id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|columns| columns.into_iter().map(|column| column.into()).collect())
+    }
 }
 
 #[derive(Default)]
@@ -54,12 +124,6 @@ impl TasksGraphQLMutation {
         let mut input = input;
         input.owner_id = member_id;
 
-        if let Some(ref mut subtasks) = input.subtasks {
-            for subtask in subtasks.iter_mut() {
-                subtask.owner_id = input.owner_id;
-            }
-        };
-
         let saved_input = input.clone();
 
         let task = core.engine.create_task(input).await?;
@@ -154,6 +218,153 @@ impl TasksGraphQLMutation {
         Ok(saved_task.into())
     }
 
+    async fn add_task_dependency(&self, ctx: &Context<'_>, task_id: Uuid, blocked_by_id: Uuid) -> Result<Task> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let task = core.engine.add_task_dependency(task_id, blocked_by_id).await?;
+        let saved_task = task.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                task.id,
+                ChangeOperation::Update,
+                ChangeResourceType::Tasks,
+                serde_json::to_string(&json!({
+                    "input": { "add_blocked_by": blocked_by_id },
+                    "result": task,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_task.into())
+    }
+
+    async fn remove_task_dependency(&self, ctx: &Context<'_>, task_id: Uuid, blocked_by_id: Uuid) -> Result<Task> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let task = core.engine.remove_task_dependency(task_id, blocked_by_id).await?;
+        let saved_task = task.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                task.id,
+                ChangeOperation::Update,
+                ChangeResourceType::Tasks,
+                serde_json::to_string(&json!({
+                    "input": { "remove_blocked_by": blocked_by_id },
+                    "result": task,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_task.into())
+    }
+
+    async fn set_task_recurrence(
+        &self,
+        ctx: &Context<'_>,
+        task_id: Uuid,
+        input: SetTaskRecurrenceInput,
+    ) -> Result<Recurrence> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        let saved_input = input.clone();
+
+        let recurrence = core.engine.set_task_recurrence(task_id, input).await?;
+        let saved_recurrence = recurrence.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                task_id,
+                ChangeOperation::Update,
+                ChangeResourceType::Tasks,
+                serde_json::to_string(&json!({
+                    "input": { "set_recurrence": saved_input },
+                    "result": recurrence,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_recurrence)
+    }
+
+    async fn update_task_recurrence(
+        &self,
+        ctx: &Context<'_>,
+        task_id: Uuid,
+        input: UpdateTaskRecurrenceInput,
+    ) -> Result<Recurrence> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let saved_input = input.clone();
+
+        let recurrence = core.engine.update_task_recurrence(task_id, input).await?;
+        let saved_recurrence = recurrence.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                task_id,
+                ChangeOperation::Update,
+                ChangeResourceType::Tasks,
+                serde_json::to_string(&json!({
+                    "input": { "update_recurrence": saved_input },
+                    "result": recurrence,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_recurrence)
+    }
+
+    async fn stop_task_recurrence(&self, ctx: &Context<'_>, task_id: Uuid) -> Result<Recurrence> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let recurrence = core.engine.stop_task_recurrence(task_id).await?;
+        let saved_recurrence = recurrence.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                task_id,
+                ChangeOperation::Update,
+                ChangeResourceType::Tasks,
+                serde_json::to_string(&json!({
+                    "input": { "stop_recurrence": true },
+                    "result": recurrence,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_recurrence)
+    }
+
     async fn delete_task(&self, ctx: &Context<'_>, id: Uuid) -> Result<Task> {
         let (core, _member_id) = extract_context(ctx)?;
 
@@ -178,6 +389,137 @@ impl TasksGraphQLMutation {
 
         Ok(saved_task.into())
     }
+
+    async fn archive_task(&self, ctx: &Context<'_>, id: Uuid) -> Result<Task> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let task = core.engine.archive_task(id).await?;
+        let saved_task = task.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                task.id,
+                ChangeOperation::Update,
+                ChangeResourceType::Tasks,
+                serde_json::to_string(&json!({
+                    "input": {
+                        "archive": true,
+                    },
+                    "result": task,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_task.into())
+    }
+
+    async fn restore_task(&self, ctx: &Context<'_>, id: Uuid) -> Result<Task> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let task = core.engine.restore_task(id).await?;
+        let saved_task = task.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                task.id,
+                ChangeOperation::Update,
+                ChangeResourceType::Tasks,
+                serde_json::to_string(&json!({
+                    "input": {
+                        "restore": true,
+                    },
+                    "result": task,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_task.into())
+    }
+
+    // The move change is recorded by the SDK, in the same transaction as the moved tasks.
+    async fn move_task(&self, ctx: &Context<'_>, id: Uuid, input: MoveTaskInput) -> Result<Task> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        Ok(core.engine.move_task(id, input).await?.into())
+    }
+
+    /// Moves a task right below `before_id` and/or right above `after_id`, optionally into the
+    /// `status` column. When both are given they must be next to each other. Without neighbours
+    /// the task goes to the end of the column.
+    async fn reorder_task(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        before_id: Option<Uuid>,
+        after_id: Option<Uuid>,
+        status: Option<TaskStatus>,
+    ) -> Result<Task> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let task = core.engine.reorder_task(id, before_id, after_id, status).await?;
+        let saved_task = task.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                task.id,
+                ChangeOperation::Update,
+                ChangeResourceType::Tasks,
+                serde_json::to_string(&json!({
+                    "input": {
+                        "reorder": {
+                            "before_id": before_id,
+                            "after_id": after_id,
+                            "status": status,
+                        },
+                    },
+                    "result": task,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_task.into())
+    }
+
+    // The changes of bulk operations are recorded by the SDK, in the same transaction as the tasks.
+    async fn update_tasks(&self, ctx: &Context<'_>, input: UpdateTasksInput) -> Result<Vec<Task>> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        let tasks = core.engine.update_tasks(input).await?;
+
+        Ok(tasks.into_iter().map(|task| task.into()).collect())
+    }
+
+    async fn delete_tasks(&self, ctx: &Context<'_>, input: DeleteTasksInput) -> Result<Vec<Task>> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        let tasks = core.engine.delete_tasks(input).await?;
+
+        Ok(tasks.into_iter().map(|task| task.into()).collect())
+    }
 }
 
 #[derive(Default)]
@@ -1,12 +1,16 @@
 use crate::api::graphql::{
-    commons::{create_change, extract_context},
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
     resources::teams::Team,
 };
-use async_graphql::{Context, Object, Result, Subscription};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
 
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
 use plexo_sdk::resources::{
     changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
-    teams::operations::{CreateTeamInput, GetTeamsInput, TeamCrudOperations, UpdateTeamInput},
+    teams::{
+        extensions::TeamsExtensionOperations,
+        operations::{CreateTeamInput, GetTeamsInput, GetTeamsWhere, TeamCrudOperations, UpdateTeamInput},
+    },
 };
 
 use serde_json::json;
@@ -29,6 +33,39 @@ impl TeamsGraphQLQuery {
             .map(|teams| teams.into_iter().map(|team| team.into()).collect())
     }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn teams_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetTeamsWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<Team>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_teams_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
     async fn team(&self, ctx: &Context<'_>, id: Uuid) -> Result<Team> {
         let (core, _member_id) = extract_context(ctx)?;
 
@@ -156,6 +193,62 @@ impl TeamsGraphQLMutation {
         //     .map_err(|err| async_graphql::Error::new(err.to_string()))
         //     .map(|team| team.into())
     }
+
+    async fn archive_team(&self, ctx: &Context<'_>, id: Uuid) -> Result<Team> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let team = core.engine.archive_team(id).await?;
+        let saved_team = team.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                team.id,
+                ChangeOperation::Update,
+                ChangeResourceType::Teams,
+                serde_json::to_string(&json!({
+                    "input": {
+                        "archive": true,
+                    },
+                    "result": team,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_team.into())
+    }
+
+    async fn restore_team(&self, ctx: &Context<'_>, id: Uuid) -> Result<Team> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let team = core.engine.restore_team(id).await?;
+        let saved_team = team.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                team.id,
+                ChangeOperation::Update,
+                ChangeResourceType::Teams,
+                serde_json::to_string(&json!({
+                    "input": {
+                        "restore": true,
+                    },
+                    "result": team,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_team.into())
+    }
 }
 
 #[derive(Default)]
@@ -0,0 +1,264 @@
+use crate::api::graphql::{
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
+    resources::work_logs::WorkLog,
+};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
+
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
+use plexo_sdk::resources::{
+    changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
+    work_logs::{
+        extensions::{GetWorkLogTotalsInput, StartTimerInput, WorkLogTotal, WorkLogsExtensionOperations},
+        operations::{
+            CreateWorkLogInput, GetWorkLogsInput, GetWorkLogsWhere, UpdateWorkLogInput, WorkLogCrudOperations,
+        },
+    },
+};
+use serde_json::json;
+use tokio::task;
+use tokio_stream::{Stream, StreamExt};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct WorkLogsGraphQLQuery;
+
+#[Object]
+impl WorkLogsGraphQLQuery {
+    async fn work_logs(&self, ctx: &Context<'_>, input: Option<GetWorkLogsInput>) -> Result<Vec<WorkLog>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_work_logs(input.unwrap_or_default())
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|work_logs| work_logs.into_iter().map(|work_log| work_log.into()).collect())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn work_logs_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetWorkLogsWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<WorkLog>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_work_logs_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
+    async fn work_log(&self, ctx: &Context<'_>, id: Uuid) -> Result<WorkLog> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_work_log(id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|work_log| work_log.into())
+    }
+
+    /// The running timer of the current member.
+    async fn running_timer(&self, ctx: &Context<'_>) -> Result<Option<WorkLog>> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        core.engine
+            .running_timer(member_id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|work_log| work_log.map(|work_log| work_log.into()))
+    }
+
+    async fn work_log_totals(&self, ctx: &Context<'_>, input: GetWorkLogTotalsInput) -> Result<Vec<WorkLogTotal>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .work_log_totals(input)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+    }
+}
+
+#[derive(Default)]
+pub struct WorkLogsGraphQLMutation;
+
+#[Object]
+impl WorkLogsGraphQLMutation {
+    async fn create_work_log(&self, ctx: &Context<'_>, input: CreateWorkLogInput) -> Result<WorkLog> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.member_id = member_id;
+
+        let saved_input = input.clone();
+
+        let work_log = core.engine.create_work_log(input).await?;
+        let saved_work_log = work_log.clone();
+
+        let input = saved_input.clone();
+
+        task::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                work_log.id,
+                ChangeOperation::Insert,
+                ChangeResourceType::WorkLogs,
+                serde_json::to_string(&json!({
+                    "input": input,
+                    "result": work_log,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_work_log.into())
+    }
+
+    async fn update_work_log(&self, ctx: &Context<'_>, id: Uuid, input: UpdateWorkLogInput) -> Result<WorkLog> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let saved_input = input.clone();
+
+        let work_log = core.engine.update_work_log(id, input).await?;
+        let saved_work_log = work_log.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                work_log.id,
+                ChangeOperation::Update,
+                ChangeResourceType::WorkLogs,
+                serde_json::to_string(&json!({
+                    "input": saved_input,
+                    "result": work_log,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_work_log.into())
+    }
+
+    async fn delete_work_log(&self, ctx: &Context<'_>, id: Uuid) -> Result<WorkLog> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let work_log = core.engine.delete_work_log(id).await?;
+        let saved_work_log = work_log.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                work_log.id,
+                ChangeOperation::Delete,
+                ChangeResourceType::WorkLogs,
+                serde_json::to_string(&json!({
+                    "result": work_log,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_work_log.into())
+    }
+
+    async fn start_timer(&self, ctx: &Context<'_>, input: StartTimerInput) -> Result<WorkLog> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.member_id = member_id;
+
+        let saved_input = input.clone();
+
+        let work_log = core.engine.start_timer(input).await?;
+        let saved_work_log = work_log.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                work_log.id,
+                ChangeOperation::Insert,
+                ChangeResourceType::WorkLogs,
+                serde_json::to_string(&json!({
+                    "input": saved_input,
+                    "result": work_log,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_work_log.into())
+    }
+
+    async fn stop_timer(&self, ctx: &Context<'_>) -> Result<WorkLog> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let work_log = core.engine.stop_timer(member_id).await?;
+        let saved_work_log = work_log.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                work_log.id,
+                ChangeOperation::Update,
+                ChangeResourceType::WorkLogs,
+                serde_json::to_string(&json!({
+                    "input": { "stop_timer": true },
+                    "result": work_log,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_work_log.into())
+    }
+}
+
+#[derive(Default)]
+pub struct WorkLogsGraphQLSubscription;
+
+#[Subscription]
+impl WorkLogsGraphQLSubscription {
+    async fn work_logs(&self, ctx: &Context<'_>) -> impl Stream<Item = ListenEvent> {
+        let (core, _member_id) = extract_context(ctx).unwrap();
+
+        core.engine
+            .listen(ChangeResourceType::WorkLogs)
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+    }
+}
@@ -0,0 +1,186 @@
+use crate::api::graphql::{
+    commons::{create_change, extract_context, into_connection, PlexoConnection},
+    resources::workflows::Workflow,
+};
+use async_graphql::{connection::query_with, Context, Object, Result, Subscription};
+
+use plexo_sdk::common::{commons::SortOrder, pagination::PageInput};
+use plexo_sdk::resources::{
+    changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
+    workflows::operations::{
+        CreateWorkflowInput, GetWorkflowsInput, GetWorkflowsWhere, UpdateWorkflowInput, WorkflowCrudOperations,
+    },
+};
+use serde_json::json;
+use tokio::task;
+use tokio_stream::{Stream, StreamExt};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct WorkflowsGraphQLQuery;
+
+#[Object]
+impl WorkflowsGraphQLQuery {
+    async fn workflows(&self, ctx: &Context<'_>, input: Option<GetWorkflowsInput>) -> Result<Vec<Workflow>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_workflows(input.unwrap_or_default())
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|workflows| workflows.into_iter().map(|workflow| workflow.into()).collect())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn workflows_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<GetWorkflowsWhere>,
+        sort_by: Option<String>,
+        sort_order: Option<SortOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<PlexoConnection<Workflow>> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        query_with(after, before, first, last, |after, before, first, last| async move {
+            let page = core
+                .engine
+                .get_workflows_page(PageInput {
+                    filter,
+                    sort_by,
+                    sort_order,
+                    first,
+                    after,
+                    last,
+                    before,
+                })
+                .await?;
+
+            Ok::<_, async_graphql::Error>(into_connection(page))
+        })
+        .await
+    }
+
+    async fn workflow(&self, ctx: &Context<'_>, id: Uuid) -> Result<Workflow> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        core.engine
+            .get_workflow(id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+            .map(|workflow| workflow.into())
+    }
+}
+
+#[derive(Default)]
+pub struct WorkflowsGraphQLMutation;
+
+#[Object]
+impl WorkflowsGraphQLMutation {
+    async fn create_workflow(&self, ctx: &Context<'_>, input: CreateWorkflowInput) -> Result<Workflow> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let mut input = input;
+        input.owner_id = member_id;
+
+        let saved_input = input.clone();
+
+        let workflow = core.engine.create_workflow(input).await?;
+        let saved_workflow = workflow.clone();
+
+        let input = saved_input.clone();
+
+        task::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                workflow.id,
+                ChangeOperation::Insert,
+                ChangeResourceType::Workflows,
+                serde_json::to_string(&json!({
+                    "input": input,
+                    "result": workflow,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_workflow.into())
+    }
+
+    async fn update_workflow(&self, ctx: &Context<'_>, id: Uuid, input: UpdateWorkflowInput) -> Result<Workflow> {
+        let (core, member_id) = extract_context(ctx)?;
+
+        let saved_input = input.clone();
+
+        let workflow = core.engine.update_workflow(id, input).await?;
+
+        let workflow = workflow.clone();
+        let saved_workflow = workflow.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                member_id,
+                workflow.id,
+                ChangeOperation::Update,
+                ChangeResourceType::Workflows,
+                serde_json::to_string(&json!({
+                    "input": saved_input,
+                    "result": workflow,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_workflow.into())
+    }
+
+    async fn delete_workflow(&self, ctx: &Context<'_>, id: Uuid) -> Result<Workflow> {
+        let (core, _member_id) = extract_context(ctx)?;
+
+        let workflow = core.engine.delete_workflow(id).await?;
+        let saved_workflow = workflow.clone();
+
+        tokio::spawn(async move {
+            create_change(
+                &core,
+                workflow.owner_id,
+                workflow.id,
+                ChangeOperation::Delete,
+                ChangeResourceType::Workflows,
+                serde_json::to_string(&json!({
+                    "result": workflow,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        });
+
+        Ok(saved_workflow.into())
+    }
+}
+
+#[derive(Default)]
+pub struct WorkflowsGraphQLSubscription;
+
+#[Subscription]
+impl WorkflowsGraphQLSubscription {
+    async fn workflows(&self, ctx: &Context<'_>) -> impl Stream<Item = ListenEvent> {
+        let (core, _member_id) = extract_context(ctx).unwrap();
+
+        core.engine
+            .listen(ChangeResourceType::Workflows)
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+    }
+}
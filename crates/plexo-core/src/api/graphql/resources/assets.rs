@@ -1,9 +1,12 @@
 use async_graphql::{ComplexObject, Context, Result, SimpleObject};
-use plexo_sdk::resources::assets::{asset::Asset as SDKAsset, relations::AssetRelations};
+use plexo_sdk::resources::assets::{
+    asset::{Asset as SDKAsset, AssetVersion},
+    relations::AssetRelations,
+};
 
 use crate::api::graphql::commons::extract_context;
 
-use super::{members::Member, projects::Project};
+use super::{members::Member, projects::Project, tasks::Task};
 
 #[derive(SimpleObject)]
 #[graphql(complex)]
@@ -39,4 +42,21 @@ impl Asset {
             .map_err(|e| e.into())
             .map(|project| project.map(|project| project.into()))
     }
+
+    async fn tasks(&self, ctx: &Context<'_>) -> Result<Vec<Task>> {
+        let (plexo_engine, _asset_id) = extract_context(ctx)?;
+
+        self.asset
+            .tasks(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|tasks| tasks.into_iter().map(|task| task.into()).collect())
+    }
+
+    /// Uploaded versions of the content, newest first.
+    async fn versions(&self, ctx: &Context<'_>) -> Result<Vec<AssetVersion>> {
+        let (plexo_engine, _asset_id) = extract_context(ctx)?;
+
+        self.asset.versions(&plexo_engine.loaders).await.map_err(|e| e.into())
+    }
 }
@@ -0,0 +1,71 @@
+use async_graphql::{ComplexObject, Context, Result, SimpleObject};
+use plexo_sdk::resources::comments::{
+    comment::{Comment as SDKComment, CommentEdit},
+    relations::CommentRelations,
+};
+
+use crate::api::graphql::commons::extract_context;
+
+use super::members::Member;
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Comment {
+    #[graphql(flatten)]
+    comment: SDKComment,
+}
+
+impl From<SDKComment> for Comment {
+    fn from(val: SDKComment) -> Self {
+        Comment { comment: val }
+    }
+}
+
+#[ComplexObject]
+impl Comment {
+    async fn owner(&self, ctx: &Context<'_>) -> Result<Member> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.comment
+            .owner(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|member| member.into())
+    }
+
+    async fn parent(&self, ctx: &Context<'_>) -> Result<Option<Comment>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.comment
+            .parent(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|parent| parent.map(|parent| parent.into()))
+    }
+
+    async fn replies(&self, ctx: &Context<'_>) -> Result<Vec<Comment>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.comment
+            .replies(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|replies| replies.into_iter().map(|reply| reply.into()).collect())
+    }
+
+    async fn edits(&self, ctx: &Context<'_>) -> Result<Vec<CommentEdit>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.comment.edits(&plexo_engine.loaders).await.map_err(|e| e.into())
+    }
+
+    async fn mentions(&self, ctx: &Context<'_>) -> Result<Vec<Member>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.comment
+            .mentions(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|members| members.into_iter().map(|member| member.into()).collect())
+    }
+}
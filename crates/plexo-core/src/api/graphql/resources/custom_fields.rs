@@ -0,0 +1,44 @@
+use async_graphql::{ComplexObject, Context, Result, SimpleObject};
+use plexo_sdk::resources::custom_fields::{
+    custom_field::CustomField as SDKCustomField, relations::CustomFieldRelations,
+};
+
+use crate::api::graphql::commons::extract_context;
+
+use super::{members::Member, projects::Project};
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct CustomField {
+    #[graphql(flatten)]
+    custom_field: SDKCustomField,
+}
+
+impl From<SDKCustomField> for CustomField {
+    fn from(val: SDKCustomField) -> Self {
+        CustomField { custom_field: val }
+    }
+}
+
+#[ComplexObject]
+impl CustomField {
+    async fn owner(&self, ctx: &Context<'_>) -> Result<Member> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.custom_field
+            .owner(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|member| member.into())
+    }
+
+    async fn project(&self, ctx: &Context<'_>) -> Result<Project> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.custom_field
+            .project(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|project| project.into())
+    }
+}
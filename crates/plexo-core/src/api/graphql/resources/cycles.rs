@@ -0,0 +1,76 @@
+use async_graphql::{ComplexObject, Context, Result, SimpleObject};
+use plexo_sdk::resources::cycles::{
+    cycle::Cycle as SDKCycle,
+    extensions::{CycleCounts, CyclesExtensionOperations},
+    relations::CycleRelations,
+};
+
+use crate::api::graphql::commons::extract_context;
+
+use super::{members::Member, projects::Project, tasks::Task, teams::Team};
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Cycle {
+    #[graphql(flatten)]
+    cycle: SDKCycle,
+}
+
+impl From<SDKCycle> for Cycle {
+    fn from(val: SDKCycle) -> Self {
+        Cycle { cycle: val }
+    }
+}
+
+#[ComplexObject]
+impl Cycle {
+    async fn owner(&self, ctx: &Context<'_>) -> Result<Member> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.cycle
+            .owner(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|member| member.into())
+    }
+
+    async fn project(&self, ctx: &Context<'_>) -> Result<Option<Project>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.cycle
+            .project(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|project| project.map(|project| project.into()))
+    }
+
+    async fn team(&self, ctx: &Context<'_>) -> Result<Option<Team>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.cycle
+            .team(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|team| team.map(|team| team.into()))
+    }
+
+    async fn tasks(&self, ctx: &Context<'_>) -> Result<Vec<Task>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.cycle
+            .tasks(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|tasks| tasks.into_iter().map(|task| task.into()).collect())
+    }
+
+    async fn counts(&self, ctx: &Context<'_>) -> Result<CycleCounts> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        plexo_engine
+            .engine
+            .cycle_counts(self.cycle.id)
+            .await
+            .map_err(|e| e.into())
+    }
+}
@@ -0,0 +1,42 @@
+use async_graphql::{ComplexObject, Context, Result, SimpleObject};
+use plexo_sdk::resources::label_groups::{label_group::LabelGroup as SDKLabelGroup, relations::LabelGroupRelations};
+
+use crate::api::graphql::commons::extract_context;
+
+use super::{labels::Label, members::Member};
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct LabelGroup {
+    #[graphql(flatten)]
+    label_group: SDKLabelGroup,
+}
+
+impl From<SDKLabelGroup> for LabelGroup {
+    fn from(val: SDKLabelGroup) -> Self {
+        LabelGroup { label_group: val }
+    }
+}
+
+#[ComplexObject]
+impl LabelGroup {
+    async fn owner(&self, ctx: &Context<'_>) -> Result<Member> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.label_group
+            .owner(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|member| member.into())
+    }
+
+    async fn labels(&self, ctx: &Context<'_>) -> Result<Vec<Label>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.label_group
+            .labels(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|labels| labels.into_iter().map(|label| label.into()).collect())
+    }
+}
@@ -3,7 +3,7 @@ use plexo_sdk::resources::labels::{label::Label as SDKLabel, relations::LabelRel
 
 use crate::api::graphql::commons::extract_context;
 
-use super::{members::Member, tasks::Task};
+use super::{label_groups::LabelGroup, members::Member, projects::Project, tasks::Task, teams::Team};
 
 #[derive(SimpleObject)]
 #[graphql(complex)]
@@ -39,4 +39,34 @@ impl Label {
             .map_err(|e| e.into())
             .map(|tasks| tasks.into_iter().map(|task| task.into()).collect())
     }
+
+    async fn project(&self, ctx: &Context<'_>) -> Result<Option<Project>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.label
+            .project(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|project| project.map(|project| project.into()))
+    }
+
+    async fn team(&self, ctx: &Context<'_>) -> Result<Option<Team>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.label
+            .team(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|team| team.map(|team| team.into()))
+    }
+
+    async fn group(&self, ctx: &Context<'_>) -> Result<Option<LabelGroup>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.label
+            .group(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|group| group.map(|group| group.into()))
+    }
 }
@@ -0,0 +1,44 @@
+use async_graphql::{ComplexObject, Context, Result, SimpleObject};
+use plexo_sdk::resources::project_templates::{
+    project_template::ProjectTemplate as SDKProjectTemplate, relations::ProjectTemplateRelations,
+};
+
+use crate::api::graphql::commons::extract_context;
+
+use super::{members::Member, projects::Project};
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct ProjectTemplate {
+    #[graphql(flatten)]
+    project_template: SDKProjectTemplate,
+}
+
+impl From<SDKProjectTemplate> for ProjectTemplate {
+    fn from(val: SDKProjectTemplate) -> Self {
+        ProjectTemplate { project_template: val }
+    }
+}
+
+#[ComplexObject]
+impl ProjectTemplate {
+    async fn owner(&self, ctx: &Context<'_>) -> Result<Member> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.project_template
+            .owner(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|member| member.into())
+    }
+
+    async fn source_project(&self, ctx: &Context<'_>) -> Result<Option<Project>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.project_template
+            .source_project(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|project| project.map(|project| project.into()))
+    }
+}
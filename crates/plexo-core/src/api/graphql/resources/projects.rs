@@ -1,9 +1,15 @@
 use async_graphql::{ComplexObject, Context, Result, SimpleObject};
-use plexo_sdk::resources::projects::{project::Project as SDKProject, relations::ProjectRelations};
+use plexo_sdk::resources::projects::{
+    extensions::ProjectCopySummary as SDKProjectCopySummary, project::Project as SDKProject,
+    relations::ProjectRelations,
+};
 
 use crate::api::graphql::commons::extract_context;
 
-use super::{assets::Asset, changes::Change, members::Member, tasks::Task, teams::Team};
+use super::{
+    assets::Asset, changes::Change, comments::Comment, custom_fields::CustomField, members::Member, tasks::Task,
+    teams::Team, workflows::Workflow,
+};
 
 #[derive(SimpleObject)]
 #[graphql(complex)]
@@ -18,6 +24,32 @@ impl From<SDKProject> for Project {
     }
 }
 
+#[derive(SimpleObject)]
+pub struct ProjectCopySummary {
+    project: Project,
+
+    members: i32,
+    teams: i32,
+    workflow_statuses: i32,
+    custom_fields: i32,
+    tasks: i32,
+    labels: i32,
+}
+
+impl From<SDKProjectCopySummary> for ProjectCopySummary {
+    fn from(val: SDKProjectCopySummary) -> Self {
+        ProjectCopySummary {
+            project: val.project.into(),
+            members: val.members,
+            teams: val.teams,
+            workflow_statuses: val.workflow_statuses,
+            custom_fields: val.custom_fields,
+            tasks: val.tasks,
+            labels: val.labels,
+        }
+    }
+}
+
 #[ComplexObject]
 impl Project {
     async fn owner(&self, ctx: &Context<'_>) -> Result<Member> {
@@ -80,6 +112,41 @@ impl Project {
             .map(|teams| teams.into_iter().map(|team| team.into()).collect())
     }
 
+    async fn workflow(&self, ctx: &Context<'_>) -> Result<Option<Workflow>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.project
+            .workflow(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|workflow| workflow.map(|w| w.into()))
+    }
+
+    async fn custom_fields(&self, ctx: &Context<'_>) -> Result<Vec<CustomField>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.project
+            .custom_fields(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|custom_fields| {
+                custom_fields
+                    .into_iter()
+                    .map(|custom_field| custom_field.into())
+                    .collect()
+            })
+    }
+
+    async fn comments(&self, ctx: &Context<'_>) -> Result<Vec<Comment>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.project
+            .comments(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|comments| comments.into_iter().map(|comment| comment.into()).collect())
+    }
+
     async fn changes(&self, ctx: &Context<'_>) -> Result<Vec<Change>> {
         let (plexo_engine, _member_id) = extract_context(ctx)?;
 
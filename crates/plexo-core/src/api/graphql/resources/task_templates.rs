@@ -0,0 +1,54 @@
+use async_graphql::{ComplexObject, Context, Result, SimpleObject};
+use plexo_sdk::resources::task_templates::{
+    relations::TaskTemplateRelations,
+    task_template::{TaskTemplate as SDKTaskTemplate, TaskTemplateItem},
+};
+
+use crate::api::graphql::commons::extract_context;
+
+use super::{members::Member, projects::Project};
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct TaskTemplate {
+    #[graphql(flatten)]
+    task_template: SDKTaskTemplate,
+}
+
+impl From<SDKTaskTemplate> for TaskTemplate {
+    fn from(val: SDKTaskTemplate) -> Self {
+        TaskTemplate { task_template: val }
+    }
+}
+
+#[ComplexObject]
+impl TaskTemplate {
+    async fn owner(&self, ctx: &Context<'_>) -> Result<Member> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.task_template
+            .owner(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|member| member.into())
+    }
+
+    async fn project(&self, ctx: &Context<'_>) -> Result<Option<Project>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.task_template
+            .project(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|project| project.map(|project| project.into()))
+    }
+
+    async fn items(&self, ctx: &Context<'_>) -> Result<Vec<TaskTemplateItem>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.task_template
+            .items(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+    }
+}
@@ -1,9 +1,27 @@
 use async_graphql::{ComplexObject, Context, Result, SimpleObject};
-use plexo_sdk::resources::tasks::{relations::TaskRelations, task::Task as SDKTask};
+use chrono::{DateTime, Utc};
+use plexo_sdk::resources::{
+    checklists::{
+        checklist::{ChecklistItem, ChecklistProgress},
+        operations::ChecklistOperations,
+    },
+    custom_fields::custom_field::CustomFieldValue,
+    recurrences::recurrence::Recurrence,
+    tasks::{
+        extensions::{TaskBoardColumn as SDKTaskBoardColumn, TaskTree as SDKTaskTree},
+        relations::TaskRelations,
+        task::{Task as SDKTask, TaskStatus},
+    },
+    work_logs::extensions::{TaskTimeSummary, WorkLogsExtensionOperations},
+    workflows::workflow::WorkflowStatus,
+};
 
 use crate::api::graphql::commons::extract_context;
 
-use super::{changes::Change, labels::Label, members::Member, projects::Project};
+use super::{
+    assets::Asset, changes::Change, comments::Comment, cycles::Cycle, labels::Label, members::Member,
+    projects::Project, work_logs::WorkLog,
+};
 
 #[derive(SimpleObject)]
 #[graphql(complex)]
@@ -18,6 +36,46 @@ impl From<SDKTask> for Task {
     }
 }
 
+#[derive(SimpleObject)]
+pub struct TaskTree {
+    task: Task,
+    depth: i32,
+
+    total_count: i32,
+    done_count: i32,
+    progress: f64,
+
+    children: Vec<TaskTree>,
+}
+
+impl From<SDKTaskTree> for TaskTree {
+    fn from(val: SDKTaskTree) -> Self {
+        TaskTree {
+            task: val.task.into(),
+            depth: val.depth,
+            total_count: val.total_count,
+            done_count: val.done_count,
+            progress: val.progress,
+            children: val.children.into_iter().map(|child| child.into()).collect(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct BoardColumn {
+    status: TaskStatus,
+    tasks: Vec<Task>,
+}
+
+impl From<SDKTaskBoardColumn> for BoardColumn {
+    fn from(val: SDKTaskBoardColumn) -> Self {
+        BoardColumn {
+            status: val.status,
+            tasks: val.tasks.into_iter().map(|task| task.into()).collect(),
+        }
+    }
+}
+
 #[ComplexObject]
 impl Task {
     async fn owner(&self, ctx: &Context<'_>) -> Result<Member> {
@@ -60,6 +118,31 @@ impl Task {
             .map(|task| task.map(|t| t.into()))
     }
 
+    async fn workflow_status(&self, ctx: &Context<'_>) -> Result<Option<WorkflowStatus>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.task
+            .workflow_status(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+    }
+
+    async fn recurrence(&self, ctx: &Context<'_>) -> Result<Option<Recurrence>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.task.recurrence(&plexo_engine.loaders).await.map_err(|e| e.into())
+    }
+
+    async fn cycle(&self, ctx: &Context<'_>) -> Result<Option<Cycle>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.task
+            .cycle(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|cycle| cycle.map(|cycle| cycle.into()))
+    }
+
     async fn assignees(&self, ctx: &Context<'_>) -> Result<Vec<Member>> {
         let (plexo_engine, _member_id) = extract_context(ctx)?;
 
@@ -80,6 +163,16 @@ impl Task {
             .map(|labels| labels.into_iter().map(|label| label.into()).collect())
     }
 
+    async fn assets(&self, ctx: &Context<'_>) -> Result<Vec<Asset>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.task
+            .assets(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|assets| assets.into_iter().map(|asset| asset.into()).collect())
+    }
+
     async fn subtasks(&self, ctx: &Context<'_>) -> Result<Vec<Task>> {
         let (plexo_engine, _member_id) = extract_context(ctx)?;
 
@@ -90,6 +183,26 @@ impl Task {
             .map(|tasks| tasks.into_iter().map(|task| task.into()).collect())
     }
 
+    async fn blocked_by(&self, ctx: &Context<'_>) -> Result<Vec<Task>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.task
+            .blocked_by(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|tasks| tasks.into_iter().map(|task| task.into()).collect())
+    }
+
+    async fn blocking(&self, ctx: &Context<'_>) -> Result<Vec<Task>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.task
+            .blocking(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|tasks| tasks.into_iter().map(|task| task.into()).collect())
+    }
+
     async fn changes(&self, ctx: &Context<'_>) -> Result<Vec<Change>> {
         let (plexo_engine, _member_id) = extract_context(ctx)?;
 
@@ -99,4 +212,65 @@ impl Task {
             .map_err(|e| e.into())
             .map(|changes| changes.into_iter().map(|change| change.into()).collect())
     }
+
+    async fn comments(&self, ctx: &Context<'_>) -> Result<Vec<Comment>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.task
+            .comments(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|comments| comments.into_iter().map(|comment| comment.into()).collect())
+    }
+
+    async fn work_logs(&self, ctx: &Context<'_>) -> Result<Vec<WorkLog>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.task
+            .work_logs(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|work_logs| work_logs.into_iter().map(|work_log| work_log.into()).collect())
+    }
+
+    async fn custom_field_values(&self, ctx: &Context<'_>) -> Result<Vec<CustomFieldValue>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.task
+            .custom_field_values(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+    }
+
+    async fn checklist(&self, ctx: &Context<'_>) -> Result<Vec<ChecklistItem>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.task.checklist(&plexo_engine.loaders).await.map_err(|e| e.into())
+    }
+
+    async fn checklist_progress(&self, ctx: &Context<'_>) -> Result<Option<ChecklistProgress>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        plexo_engine
+            .engine
+            .checklist_progress(vec![self.task.id])
+            .await
+            .map_err(|e| e.into())
+            .map(|mut progress| progress.remove(&self.task.id))
+    }
+
+    async fn time_summary(
+        &self,
+        ctx: &Context<'_>,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<TaskTimeSummary> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        plexo_engine
+            .engine
+            .task_time_summary(self.task.id, from, to)
+            .await
+            .map_err(|e| e.into())
+    }
 }
@@ -0,0 +1,42 @@
+use async_graphql::{ComplexObject, Context, Result, SimpleObject};
+use plexo_sdk::resources::work_logs::{relations::WorkLogRelations, work_log::WorkLog as SDKWorkLog};
+
+use crate::api::graphql::commons::extract_context;
+
+use super::{members::Member, tasks::Task};
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct WorkLog {
+    #[graphql(flatten)]
+    work_log: SDKWorkLog,
+}
+
+impl From<SDKWorkLog> for WorkLog {
+    fn from(val: SDKWorkLog) -> Self {
+        WorkLog { work_log: val }
+    }
+}
+
+#[ComplexObject]
+impl WorkLog {
+    async fn member(&self, ctx: &Context<'_>) -> Result<Member> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.work_log
+            .member(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|member| member.into())
+    }
+
+    async fn task(&self, ctx: &Context<'_>) -> Result<Task> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.work_log
+            .task(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|task| task.into())
+    }
+}
@@ -0,0 +1,63 @@
+use async_graphql::{ComplexObject, Context, Result, SimpleObject};
+use plexo_sdk::resources::workflows::{
+    relations::WorkflowRelations,
+    workflow::{Workflow as SDKWorkflow, WorkflowStatus, WorkflowTransition},
+};
+
+use crate::api::graphql::commons::extract_context;
+
+use super::{members::Member, projects::Project};
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Workflow {
+    #[graphql(flatten)]
+    workflow: SDKWorkflow,
+}
+
+impl From<SDKWorkflow> for Workflow {
+    fn from(val: SDKWorkflow) -> Self {
+        Workflow { workflow: val }
+    }
+}
+
+#[ComplexObject]
+impl Workflow {
+    async fn owner(&self, ctx: &Context<'_>) -> Result<Member> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.workflow
+            .owner(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|member| member.into())
+    }
+
+    async fn project(&self, ctx: &Context<'_>) -> Result<Project> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.workflow
+            .project(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+            .map(|project| project.into())
+    }
+
+    async fn statuses(&self, ctx: &Context<'_>) -> Result<Vec<WorkflowStatus>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.workflow
+            .statuses(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+    }
+
+    async fn transitions(&self, ctx: &Context<'_>) -> Result<Vec<WorkflowTransition>> {
+        let (plexo_engine, _member_id) = extract_context(ctx)?;
+
+        self.workflow
+            .transitions(&plexo_engine.loaders)
+            .await
+            .map_err(|e| e.into())
+    }
+}
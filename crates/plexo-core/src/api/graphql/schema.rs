@@ -13,13 +13,25 @@ use super::{
         auth::AuthMutation,
         changes::ChangesGraphQLQuery,
         chats::{ChatsGraphQLMutation, ChatsGraphQLQuery, ChatsGraphQLSubscription},
+        checklists::{ChecklistsGraphQLMutation, ChecklistsGraphQLQuery, ChecklistsGraphQLSubscription},
+        comments::{CommentsGraphQLMutation, CommentsGraphQLQuery, CommentsGraphQLSubscription},
+        custom_fields::{CustomFieldsGraphQLMutation, CustomFieldsGraphQLQuery, CustomFieldsGraphQLSubscription},
+        cycles::{CyclesGraphQLMutation, CyclesGraphQLQuery, CyclesGraphQLSubscription},
+        label_groups::{LabelGroupsGraphQLMutation, LabelGroupsGraphQLQuery, LabelGroupsGraphQLSubscription},
         labels::{LabelsGraphQLMutation, LabelsGraphQLQuery, LabelsGraphQLSubscription},
         members::{MembersGraphQLMutation, MembersGraphQLQuery, MembersGraphQLSubscription},
         messages::{MessagesGraphQLMutation, MessagesGraphQLQuery, MessagesGraphQLSubscription},
         profile::{ProfileGraphQLMutation, ProfileGraphQLQuery},
+        project_templates::{
+            ProjectTemplatesGraphQLMutation, ProjectTemplatesGraphQLQuery, ProjectTemplatesGraphQLSubscription,
+        },
         projects::{ProjectsGraphQLMutation, ProjectsGraphQLQuery, ProjectsGraphQLSubscription},
+        search::SearchGraphQLQuery,
+        task_templates::{TaskTemplatesGraphQLMutation, TaskTemplatesGraphQLQuery, TaskTemplatesGraphQLSubscription},
         tasks::{TasksGraphQLMutation, TasksGraphQLQuery, TasksGraphQLSubscription},
         teams::{TeamsGraphQLMutation, TeamsGraphQLQuery, TeamsGraphQLSubscription},
+        work_logs::{WorkLogsGraphQLMutation, WorkLogsGraphQLQuery, WorkLogsGraphQLSubscription},
+        workflows::{WorkflowsGraphQLMutation, WorkflowsGraphQLQuery, WorkflowsGraphQLSubscription},
     },
     processors::ai::{AIProcessorGraphQLMutation, AIProcessorGraphQLQuery, AIProcessorGraphQLSubscription},
 };
@@ -29,6 +41,7 @@ pub struct QueryRoot(
     TasksGraphQLQuery,
     AssetsGraphQLQuery,
     LabelsGraphQLQuery,
+    LabelGroupsGraphQLQuery,
     ProjectsGraphQLQuery,
     TeamsGraphQLQuery,
     MembersGraphQLQuery,
@@ -37,6 +50,15 @@ pub struct QueryRoot(
     AIProcessorGraphQLQuery,
     ProfileGraphQLQuery,
     ChatsGraphQLQuery,
+    SearchGraphQLQuery,
+    WorkflowsGraphQLQuery,
+    WorkLogsGraphQLQuery,
+    CyclesGraphQLQuery,
+    CustomFieldsGraphQLQuery,
+    ChecklistsGraphQLQuery,
+    CommentsGraphQLQuery,
+    TaskTemplatesGraphQLQuery,
+    ProjectTemplatesGraphQLQuery,
 );
 
 #[derive(MergedObject, Default)]
@@ -45,6 +67,7 @@ pub struct MutationRoot(
     AuthMutation,
     AssetsGraphQLMutation,
     LabelsGraphQLMutation,
+    LabelGroupsGraphQLMutation,
     ProjectsGraphQLMutation,
     TeamsGraphQLMutation,
     MembersGraphQLMutation,
@@ -52,6 +75,14 @@ pub struct MutationRoot(
     ProfileGraphQLMutation,
     AIProcessorGraphQLMutation,
     ChatsGraphQLMutation,
+    WorkflowsGraphQLMutation,
+    WorkLogsGraphQLMutation,
+    CyclesGraphQLMutation,
+    CustomFieldsGraphQLMutation,
+    ChecklistsGraphQLMutation,
+    CommentsGraphQLMutation,
+    TaskTemplatesGraphQLMutation,
+    ProjectTemplatesGraphQLMutation,
     // ChangesGraphQLMutation,
 );
 
@@ -61,11 +92,20 @@ pub struct SubscriptionRoot(
     ProjectsGraphQLSubscription,
     AssetsGraphQLSubscription,
     LabelsGraphQLSubscription,
+    LabelGroupsGraphQLSubscription,
     MembersGraphQLSubscription,
     MessagesGraphQLSubscription,
     TeamsGraphQLSubscription,
     AIProcessorGraphQLSubscription,
     ChatsGraphQLSubscription,
+    WorkflowsGraphQLSubscription,
+    WorkLogsGraphQLSubscription,
+    CyclesGraphQLSubscription,
+    CustomFieldsGraphQLSubscription,
+    ChecklistsGraphQLSubscription,
+    CommentsGraphQLSubscription,
+    TaskTemplatesGraphQLSubscription,
+    ProjectTemplatesGraphQLSubscription,
 );
 
 pub trait GraphQLSchema {
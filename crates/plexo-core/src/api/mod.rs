@@ -1,2 +1,3 @@
+pub mod assets;
 pub mod graphql;
-// pub mod openapi;
+pub mod openapi;
@@ -4,6 +4,11 @@ use plexo_sdk::resources::assets::operations::{
 };
 // use plexo_sdk::resources::changes::change::Change;
 // use plexo_sdk::resources::changes::operations::{ChangeCrudOperations, CreateChangeInput, GetChangesInput, UpdateChangeInput};
+use plexo_sdk::resources::custom_fields::custom_field::{CustomField, CustomFieldValue};
+use plexo_sdk::resources::custom_fields::extensions::{CustomFieldsExtensionOperations, SetCustomFieldValuesInput};
+use plexo_sdk::resources::custom_fields::operations::{
+    CreateCustomFieldInput, CustomFieldCrudOperations, GetCustomFieldsInput, UpdateCustomFieldInput,
+};
 use plexo_sdk::resources::labels::label::Label;
 use plexo_sdk::resources::labels::operations::{
     CreateLabelInput, GetLabelsInput, LabelCrudOperations, UpdateLabelInput,
@@ -15,6 +20,7 @@ use plexo_sdk::resources::members::operations::{
 use plexo_sdk::resources::projects::operations::{
     CreateProjectInput, GetProjectsInput, ProjectCrudOperations, UpdateProjectInput,
 };
+use plexo_sdk::resources::tasks::extensions::{DeleteTasksInput, TasksExtensionOperations, UpdateTasksInput};
 use plexo_sdk::resources::tasks::operations::{CreateTaskInput, TaskCrudOperations, UpdateTaskInput};
 use plexo_sdk::resources::teams::operations::{CreateTeamInput, GetTeamsInput, TeamCrudOperations, UpdateTeamInput};
 use plexo_sdk::resources::teams::team::Team;
@@ -22,10 +28,11 @@ use plexo_sdk::resources::{projects::project::Project, tasks::task::Task};
 use poem::Result;
 use poem_openapi::param::Path;
 use poem_openapi::payload::Json;
-use poem_openapi::{ApiResponse, OpenApi};
+use poem_openapi::{ApiResponse, OpenApi, OpenApiService};
 use uuid::Uuid;
 
 use crate::core::app::Core;
+use crate::core::config::DOMAIN;
 use crate::errors::app::PlexoAppError;
 
 use super::{auth::PlexoAPIKeyAuthorization, commons::PlexoAPITags};
@@ -40,6 +47,11 @@ impl PlexoOpenAPI {
     }
 }
 
+/// The REST API and its spec, to be nested under `/api`.
+pub fn new_openapi_service(core: Core, version: &str) -> OpenApiService<PlexoOpenAPI, ()> {
+    OpenApiService::new(PlexoOpenAPI::new(core), "Plexo", version).server(format!("{}/api", *DOMAIN))
+}
+
 #[OpenApi]
 impl PlexoOpenAPI {
     #[oai(
@@ -53,12 +65,15 @@ impl PlexoOpenAPI {
     async fn create_task(
         &self,
         input: Json<CreateTaskInput>,
-        _auth: PlexoAPIKeyAuthorization,
+        auth: PlexoAPIKeyAuthorization,
     ) -> Result<CreateTaskResponse> {
+        let mut input = input.0;
+        input.owner_id = auth.0.member_id();
+
         let task = self
             .core
             .engine
-            .create_task(input.0)
+            .create_task(input)
             .await
             .map_err(PlexoAppError::SDKError)?;
 
@@ -79,6 +94,25 @@ impl PlexoOpenAPI {
         Ok(GetTaskResponse::Ok(Json(task)))
     }
 
+    #[oai(
+        path = "/tasks/key/:key",
+        method = "get",
+        tag = "PlexoAPITags::Task",
+        operation_id = "get_task_by_key"
+    )]
+    /// Retrieves a specific task by its human readable key (e.g. `PLX-42`).
+    /// Keys the task had before being moved to another project keep resolving to it.
+    async fn get_task_by_key(&self, key: Path<String>, _auth: PlexoAPIKeyAuthorization) -> Result<GetTaskResponse> {
+        let task = self
+            .core
+            .engine
+            .task_by_key(key.0)
+            .await
+            .map_err(PlexoAppError::SDKError)?;
+
+        Ok(GetTaskResponse::Ok(Json(task)))
+    }
+
     #[oai(
         path = "/tasks",
         method = "get",
@@ -141,6 +175,57 @@ impl PlexoOpenAPI {
         Ok(DeleteTaskResponse::Ok(Json(task)))
     }
 
+    #[oai(
+        path = "/tasks/batch/update",
+        method = "post",
+        tag = "PlexoAPITags::Task",
+        operation_id = "update_tasks"
+    )]
+    /// Applies the same update to every task selected by ids and/or filter, all or nothing.
+    /// This function helps triaging many tasks at once, recording one change per updated task.
+    async fn update_tasks(
+        &self,
+        input: Json<UpdateTasksInput>,
+        auth: PlexoAPIKeyAuthorization,
+    ) -> Result<UpdateTasksResponse> {
+        let mut input = input.0;
+        input.owner_id = auth.0.member_id();
+
+        let tasks = self
+            .core
+            .engine
+            .update_tasks(input)
+            .await
+            .map_err(PlexoAppError::SDKError)?;
+
+        Ok(UpdateTasksResponse::Ok(Json(tasks)))
+    }
+
+    #[oai(
+        path = "/tasks/batch/delete",
+        method = "post",
+        tag = "PlexoAPITags::Task",
+        operation_id = "delete_tasks"
+    )]
+    /// Deletes the given tasks in a single transaction, recording one change per deleted task.
+    async fn delete_tasks(
+        &self,
+        input: Json<DeleteTasksInput>,
+        auth: PlexoAPIKeyAuthorization,
+    ) -> Result<DeleteTasksResponse> {
+        let mut input = input.0;
+        input.owner_id = auth.0.member_id();
+
+        let tasks = self
+            .core
+            .engine
+            .delete_tasks(input)
+            .await
+            .map_err(PlexoAppError::SDKError)?;
+
+        Ok(DeleteTasksResponse::Ok(Json(tasks)))
+    }
+
     #[oai(
         path = "/projects",
         method = "post",
@@ -152,12 +237,15 @@ impl PlexoOpenAPI {
     async fn create_project(
         &self,
         input: Json<CreateProjectInput>,
-        _auth: PlexoAPIKeyAuthorization,
+        auth: PlexoAPIKeyAuthorization,
     ) -> Result<CreateProjectResponse> {
+        let mut input = input.0;
+        input.owner_id = auth.0.member_id();
+
         let project = self
             .core
             .engine
-            .create_project(input.0)
+            .create_project(input)
             .await
             .map_err(PlexoAppError::SDKError)?;
 
@@ -361,12 +449,15 @@ impl PlexoOpenAPI {
     async fn create_team(
         &self,
         input: Json<CreateTeamInput>,
-        _auth: PlexoAPIKeyAuthorization,
+        auth: PlexoAPIKeyAuthorization,
     ) -> Result<CreateTeamResponse> {
+        let mut input = input.0;
+        input.owner_id = auth.0.member_id();
+
         let team = self
             .core
             .engine
-            .create_team(input.0)
+            .create_team(input)
             .await
             .map_err(PlexoAppError::SDKError)?;
 
@@ -460,12 +551,15 @@ impl PlexoOpenAPI {
     async fn create_label(
         &self,
         input: Json<CreateLabelInput>,
-        _auth: PlexoAPIKeyAuthorization,
+        auth: PlexoAPIKeyAuthorization,
     ) -> Result<CreateLabelResponse> {
+        let mut input = input.0;
+        input.owner_id = auth.0.member_id();
+
         let label = self
             .core
             .engine
-            .create_label(input.0)
+            .create_label(input)
             .await
             .map_err(PlexoAppError::SDKError)?;
 
@@ -564,12 +658,15 @@ impl PlexoOpenAPI {
     async fn create_asset(
         &self,
         input: Json<CreateAssetInput>,
-        _auth: PlexoAPIKeyAuthorization,
+        auth: PlexoAPIKeyAuthorization,
     ) -> Result<CreateAssetResponse> {
+        let mut input = input.0;
+        input.owner_id = auth.0.member_id();
+
         let asset = self
             .core
             .engine
-            .create_asset(input.0)
+            .create_asset(input)
             .await
             .map_err(PlexoAppError::SDKError)?;
 
@@ -657,6 +754,167 @@ impl PlexoOpenAPI {
         Ok(DeleteAssetResponse::Ok(Json(asset)))
     }
 
+    #[oai(
+        path = "/custom_fields",
+        method = "post",
+        tag = "PlexoAPITags::CustomField",
+        operation_id = "create_custom_field"
+    )]
+    /// Defines a new typed field on a project, letting its tasks carry the extra data each team tracks.
+    /// Select fields take their allowed options on creation.
+    async fn create_custom_field(
+        &self,
+        input: Json<CreateCustomFieldInput>,
+        auth: PlexoAPIKeyAuthorization,
+    ) -> Result<CreateCustomFieldResponse> {
+        let mut input = input.0;
+        input.owner_id = auth.0.member_id();
+
+        let custom_field = self
+            .core
+            .engine
+            .create_custom_field(input)
+            .await
+            .map_err(PlexoAppError::SDKError)?;
+
+        Ok(CreateCustomFieldResponse::Ok(Json(custom_field)))
+    }
+
+    #[oai(
+        path = "/custom_fields/:id",
+        method = "get",
+        tag = "PlexoAPITags::CustomField",
+        operation_id = "get_custom_field"
+    )]
+    /// Retrieves the definition of a custom field, including its kind and options.
+    async fn get_custom_field(
+        &self,
+        id: Path<Uuid>,
+        _auth: PlexoAPIKeyAuthorization,
+    ) -> Result<GetCustomFieldResponse> {
+        let custom_field = self
+            .core
+            .engine
+            .get_custom_field(id.0)
+            .await
+            .map_err(PlexoAppError::SDKError)?;
+
+        Ok(GetCustomFieldResponse::Ok(Json(custom_field)))
+    }
+
+    #[oai(
+        path = "/custom_fields",
+        method = "get",
+        tag = "PlexoAPITags::CustomField",
+        operation_id = "get_custom_fields"
+    )]
+    /// Lists the custom fields defined across Plexo projects.
+    async fn get_custom_fields(&self, _auth: PlexoAPIKeyAuthorization) -> Result<GetCustomFieldsResponse> {
+        let custom_fields = self
+            .core
+            .engine
+            .get_custom_fields(GetCustomFieldsInput::default())
+            .await
+            .map_err(PlexoAppError::SDKError)?;
+
+        Ok(GetCustomFieldsResponse::Ok(Json(custom_fields)))
+    }
+
+    #[oai(
+        path = "/custom_fields/:id",
+        method = "put",
+        tag = "PlexoAPITags::CustomField",
+        operation_id = "update_custom_field"
+    )]
+    /// Updates a custom field. Its kind stays the same, and options removed from a select field are
+    /// removed from the task values too.
+    async fn update_custom_field(
+        &self,
+        id: Path<Uuid>,
+        input: Json<UpdateCustomFieldInput>,
+        _auth: PlexoAPIKeyAuthorization,
+    ) -> Result<UpdateCustomFieldResponse> {
+        let custom_field = self
+            .core
+            .engine
+            .update_custom_field(id.0, input.0)
+            .await
+            .map_err(PlexoAppError::SDKError)?;
+
+        Ok(UpdateCustomFieldResponse::Ok(Json(custom_field)))
+    }
+
+    #[oai(
+        path = "/custom_fields/:id",
+        method = "delete",
+        tag = "PlexoAPITags::CustomField",
+        operation_id = "delete_custom_field"
+    )]
+    /// Deletes a custom field along with the values tasks hold for it.
+    async fn delete_custom_field(
+        &self,
+        id: Path<Uuid>,
+        _auth: PlexoAPIKeyAuthorization,
+    ) -> Result<DeleteCustomFieldResponse> {
+        let custom_field = self
+            .core
+            .engine
+            .delete_custom_field(id.0)
+            .await
+            .map_err(PlexoAppError::SDKError)?;
+
+        Ok(DeleteCustomFieldResponse::Ok(Json(custom_field)))
+    }
+
+    #[oai(
+        path = "/tasks/:id/custom_fields",
+        method = "get",
+        tag = "PlexoAPITags::CustomField",
+        operation_id = "get_task_custom_field_values"
+    )]
+    /// Retrieves the custom field values a task holds.
+    async fn get_task_custom_field_values(
+        &self,
+        id: Path<Uuid>,
+        _auth: PlexoAPIKeyAuthorization,
+    ) -> Result<CustomFieldValuesResponse> {
+        let values = self
+            .core
+            .engine
+            .task_custom_field_values(id.0)
+            .await
+            .map_err(PlexoAppError::SDKError)?;
+
+        Ok(CustomFieldValuesResponse::Ok(Json(values)))
+    }
+
+    #[oai(
+        path = "/tasks/:id/custom_fields",
+        method = "put",
+        tag = "PlexoAPITags::CustomField",
+        operation_id = "set_task_custom_field_values"
+    )]
+    /// Sets custom field values of a task, checking each value against the kind of its field.
+    /// A value with none of its slots set clears the field.
+    async fn set_task_custom_field_values(
+        &self,
+        id: Path<Uuid>,
+        input: Json<SetCustomFieldValuesInput>,
+        auth: PlexoAPIKeyAuthorization,
+    ) -> Result<CustomFieldValuesResponse> {
+        let mut input = input.0;
+        input.owner_id = auth.0.member_id();
+
+        let values = self
+            .core
+            .engine
+            .set_task_custom_field_values(id.0, input)
+            .await
+            .map_err(PlexoAppError::SDKError)?;
+
+        Ok(CustomFieldValuesResponse::Ok(Json(values)))
+    }
+
     // #[oai(
     //     path = "/changes",
     //     method = "post",
@@ -822,6 +1080,20 @@ enum GetTaskResponse {
     // NotFound,
 }
 
+#[derive(ApiResponse)]
+enum UpdateTasksResponse {
+    /// Returns the updated tasks.
+    #[oai(status = 200)]
+    Ok(Json<Vec<Task>>),
+}
+
+#[derive(ApiResponse)]
+enum DeleteTasksResponse {
+    /// Returns the deleted tasks.
+    #[oai(status = 200)]
+    Ok(Json<Vec<Task>>),
+}
+
 #[derive(ApiResponse)]
 enum UpdateTaskResponse {
     /// Returns when the user is successfully created.
@@ -1040,6 +1312,52 @@ enum DeleteAssetResponse {
 //
 //
 
+#[derive(ApiResponse)]
+enum CreateCustomFieldResponse {
+    /// Returns when the custom field is successfully created.
+    #[oai(status = 200)]
+    Ok(Json<CustomField>),
+}
+
+#[derive(ApiResponse)]
+enum GetCustomFieldsResponse {
+    /// Returns the custom fields.
+    #[oai(status = 200)]
+    Ok(Json<Vec<CustomField>>),
+}
+
+#[derive(ApiResponse)]
+enum GetCustomFieldResponse {
+    /// Returns the custom field.
+    #[oai(status = 200)]
+    Ok(Json<CustomField>),
+}
+
+#[derive(ApiResponse)]
+enum UpdateCustomFieldResponse {
+    /// Returns when the custom field is successfully updated.
+    #[oai(status = 200)]
+    Ok(Json<CustomField>),
+}
+
+#[derive(ApiResponse)]
+enum DeleteCustomFieldResponse {
+    /// Returns when the custom field is successfully deleted.
+    #[oai(status = 200)]
+    Ok(Json<CustomField>),
+}
+
+#[derive(ApiResponse)]
+enum CustomFieldValuesResponse {
+    /// Returns the custom field values of the task.
+    #[oai(status = 200)]
+    Ok(Json<Vec<CustomFieldValue>>),
+}
+
+//
+//
+//
+
 //
 //
 //
@@ -1084,3 +1402,157 @@ enum DeleteAssetResponse {
 //     // #[oai(status = 404)]
 //     // NotFound,
 // }
+
+#[cfg(test)]
+mod tests {
+    use plexo_sdk::{
+        backend::engine::{SDKConfig, SDKEngine},
+        resources::members::{
+            member::MemberRole,
+            operations::{CreateMemberInputBuilder, MemberCrudOperations},
+        },
+        storage::AssetStorageConfig,
+    };
+    use poem::{
+        http::{Method, StatusCode},
+        test::TestClient,
+        Endpoint, EndpointExt, Route,
+    };
+    use serde_json::{json, Value};
+    use sqlx::PgPool;
+    use uuid::Uuid;
+
+    use super::new_openapi_service;
+    use crate::core::app::{new_core, Core};
+
+    fn config() -> SDKConfig {
+        SDKConfig {
+            database_url: "postgres://localhost/plexo".to_string(),
+            llm_api_key: String::new(),
+            llm_model_name: String::new(),
+            llm_api_base: String::new(),
+            asset_storage: AssetStorageConfig::Local {
+                root: "./data/assets".to_string(),
+            },
+            asset_max_size: 0,
+        }
+    }
+
+    fn app(core: Core) -> impl Endpoint {
+        Route::new()
+            .nest("/api", new_openapi_service(core.clone(), "test"))
+            .data(core)
+    }
+
+    #[tokio::test]
+    async fn served_routes_are_behind_auth() {
+        // Nothing here reaches the database, the pool only connects on the first query.
+        let client = TestClient::new(app(new_core(SDKEngine::new_lazy(config()).unwrap())));
+        let id = "00000000-0000-0000-0000-000000000000";
+
+        let routes: Vec<(Method, String, Option<Value>)> = vec![
+            (
+                Method::POST,
+                "/api/tasks".to_string(),
+                Some(json!({ "title": "Task", "owner_id": id })),
+            ),
+            (
+                Method::POST,
+                "/api/tasks/batch/update".to_string(),
+                Some(json!({ "ids": [], "update": {} })),
+            ),
+            (
+                Method::POST,
+                "/api/tasks/batch/delete".to_string(),
+                Some(json!({ "ids": [] })),
+            ),
+            (Method::GET, "/api/tasks/key/PLX-1".to_string(), None),
+            (
+                Method::POST,
+                "/api/projects".to_string(),
+                Some(json!({ "name": "Project", "owner_id": id })),
+            ),
+            (
+                Method::POST,
+                "/api/custom_fields".to_string(),
+                Some(json!({ "name": "Severity", "project_id": id, "kind": "Number" })),
+            ),
+            (Method::GET, "/api/custom_fields".to_string(), None),
+            (Method::GET, format!("/api/custom_fields/{id}"), None),
+            (Method::PUT, format!("/api/custom_fields/{id}"), Some(json!({}))),
+            (Method::DELETE, format!("/api/custom_fields/{id}"), None),
+            (Method::GET, format!("/api/tasks/{id}/custom_fields"), None),
+            (
+                Method::PUT,
+                format!("/api/tasks/{id}/custom_fields"),
+                Some(json!({ "values": [] })),
+            ),
+        ];
+
+        for (method, path, body) in routes {
+            for token in [None, Some("not-a-token")] {
+                let mut request = client.request(method.clone(), &path);
+
+                if let Some(token) = token {
+                    request = request.header("Authorization", token);
+                }
+
+                if let Some(body) = &body {
+                    request = request.body_json(body);
+                }
+
+                request.send().await.assert_status(StatusCode::UNAUTHORIZED);
+            }
+        }
+
+        client
+            .post("/api/tasks/batch/unknown")
+            .send()
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(migrations = "../plexo-sdk/migrations")]
+    async fn created_resources_belong_to_the_caller(pool: PgPool) {
+        let core = new_core(SDKEngine::with_pool(config(), pool));
+        let member = core
+            .engine
+            .create_member(
+                CreateMemberInputBuilder::default()
+                    .name("Member".to_string())
+                    .email("member@plexo.test".to_string())
+                    .role(MemberRole::Member)
+                    .build()
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let token = core.auth.jwt_engine.create_session_token(&member).unwrap();
+        let client = TestClient::new(app(core));
+
+        for (path, body) in [
+            (
+                "/api/projects",
+                json!({ "name": "Project", "owner_id": Uuid::new_v4() }),
+            ),
+            ("/api/tasks", json!({ "title": "Task", "owner_id": Uuid::new_v4() })),
+            ("/api/labels", json!({ "name": "Label", "owner_id": Uuid::new_v4() })),
+        ] {
+            let response = client
+                .post(path)
+                .header("Authorization", &token)
+                .body_json(&body)
+                .send()
+                .await;
+
+            response.assert_status_is_ok();
+            response
+                .json()
+                .await
+                .value()
+                .object()
+                .get("owner_id")
+                .assert_string(&member.id.to_string());
+        }
+    }
+}
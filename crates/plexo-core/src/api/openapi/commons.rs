@@ -19,4 +19,6 @@ pub enum PlexoAPITags {
     Asset,
     /// Operations about changes
     Change,
+    /// Operations about custom fields
+    CustomField,
 }
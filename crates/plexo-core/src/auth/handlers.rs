@@ -30,7 +30,7 @@ pub async fn github_sign_in_handler(plexo_core: Data<&Core>) -> impl IntoRespons
         return Response::builder()
             .status(StatusCode::INTERNAL_SERVER_ERROR)
             .header("Content-Type", "application/json")
-            .body(Body::from_json(&Error::new("Internal Server Error (github)")).unwrap());
+            .body(Body::from_json(Error::new("Internal Server Error (github)")).unwrap());
     };
 
     Redirect::temporary(url.to_string())
@@ -108,7 +108,7 @@ pub async fn github_callback_handler(
         return Response::builder()
             .status(StatusCode::INTERNAL_SERVER_ERROR)
             .header("Content-Type", "application/json")
-            .body(Body::from_json(&Error::new("Internal Server Error")).unwrap());
+            .body(Body::from_json(Error::new("Internal Server Error")).unwrap());
     };
 
     let mut session_token_cookie = Cookie::named(COOKIE_SESSION_NAME.to_string());
@@ -175,7 +175,7 @@ pub async fn email_basic_login_handler(plexo_engine: Data<&Core>, params: Json<E
         return Response::builder()
             .status(StatusCode::INTERNAL_SERVER_ERROR)
             .header("Content-Type", "application/json")
-            .body(Body::from_json(&Error::new("Internal Server Error")).unwrap());
+            .body(Body::from_json(Error::new("Internal Server Error")).unwrap());
     };
 
     let mut session_token_cookie = Cookie::named(COOKIE_SESSION_NAME.to_string());
@@ -244,14 +244,14 @@ pub async fn email_basic_register_handler(
         return Ok(Response::builder()
             .status(StatusCode::INTERNAL_SERVER_ERROR)
             .header("Content-Type", "application/json")
-            .body(Body::from_json(&Error::new("Internal Server Error")).unwrap()));
+            .body(Body::from_json(Error::new("Internal Server Error")).unwrap()));
     };
 
     let Ok(session_token) = plexo_engine.auth.jwt_engine.create_session_token(&member) else {
         return Ok(Response::builder()
             .status(StatusCode::INTERNAL_SERVER_ERROR)
             .header("Content-Type", "application/json")
-            .body(Body::from_json(&Error::new("Internal Server Error")).unwrap()));
+            .body(Body::from_json(Error::new("Internal Server Error")).unwrap()));
     };
 
     let mut session_token_cookie = Cookie::named(COOKIE_SESSION_NAME.to_string());
@@ -66,7 +66,7 @@ impl JWTEngine {
 
         // println!("token_data: {:?}", token_data);
 
-        let token_data = token_data.unwrap();
+        let token_data = token_data.map_err(|_| PlexoAppError::InvalidAuthorizationToken)?;
 
         Ok(token_data.claims)
     }
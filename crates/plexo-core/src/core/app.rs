@@ -35,6 +35,11 @@ pub async fn new_core_from_env() -> Result<Core, PlexoAppError> {
         Err(err) => info!("database migration failed: {:?}", err),
     }
 
+    Ok(new_core(engine))
+}
+
+/// Builds the core around an engine, taking the rest of its configuration from the environment.
+pub fn new_core(engine: SDKEngine) -> Core {
     let arc_engine = Arc::new(engine.clone());
 
     let loaders = Arc::new(SDKLoaders::new(arc_engine));
@@ -71,12 +76,12 @@ pub async fn new_core_from_env() -> Result<Core, PlexoAppError> {
         }
     };
 
-    Ok(Core {
+    Core {
         engine,
         auth,
         loaders,
         mail_client,
-    })
+    }
 }
 
 impl Core {
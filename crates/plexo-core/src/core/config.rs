@@ -41,6 +41,12 @@ lazy_static! {
     //
     pub static ref TRACING_LEVEL: String = var("TRACING_LEVEL").unwrap_or("info".into());
     //
+    pub static ref RECURRENCE_CHECK_INTERVAL: u64 = var("RECURRENCE_CHECK_INTERVAL").ok().and_then(|secs| secs.parse().ok()).unwrap_or(60).max(1);
+    pub static ref RANK_REBALANCE_INTERVAL: u64 = var("RANK_REBALANCE_INTERVAL").ok().and_then(|secs| secs.parse().ok()).unwrap_or(3600).max(1);
+    pub static ref CYCLE_CHECK_INTERVAL: u64 = var("CYCLE_CHECK_INTERVAL").ok().and_then(|secs| secs.parse().ok()).unwrap_or(300).max(1);
+    pub static ref TRASH_PURGE_INTERVAL: u64 = var("TRASH_PURGE_INTERVAL").ok().and_then(|secs| secs.parse().ok()).unwrap_or(3600).max(1);
+    pub static ref TRASH_RETENTION_DAYS: i64 = var("TRASH_RETENTION_DAYS").ok().and_then(|days| days.parse().ok()).filter(|days: &i64| *days >= 0).unwrap_or(30);
+    //
     pub static ref SMTP_HOST: Option<String> = var("SMTP_HOST").ok();
     pub static ref SMTP_PORT: String = var("SMTP_PORT").unwrap_or("25".into());
     pub static ref SMTP_USERNAME: Option<String> = var("SMTP_USERNAME").ok();
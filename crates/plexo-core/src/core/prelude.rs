@@ -1,27 +1,39 @@
 use super::{
     app::Core,
     config::{
-        ADMIN_EMAIL, ADMIN_NAME, ADMIN_PASSWORD, ADMIN_PHOTO_URL, ORGANIZATION_EMAIL, ORGANIZATION_HUB_ID,
-        ORGANIZATION_NAME, ORGANIZATION_PHOTO_URL, ORGANIZATION_PLAN_ID, ORGANIZATION_URL,
+        ADMIN_EMAIL, ADMIN_NAME, ADMIN_PASSWORD, ADMIN_PHOTO_URL, CYCLE_CHECK_INTERVAL, ORGANIZATION_EMAIL,
+        ORGANIZATION_HUB_ID, ORGANIZATION_NAME, ORGANIZATION_PHOTO_URL, ORGANIZATION_PLAN_ID, ORGANIZATION_URL,
+        RANK_REBALANCE_INTERVAL, RECURRENCE_CHECK_INTERVAL, TRASH_PURGE_INTERVAL, TRASH_RETENTION_DAYS,
     },
     email::FirstWelcomeTemplate,
 };
 use askama::Template;
 use plexo_sdk::{
-    common::commons::SortOrder,
+    backend::engine::SDKEngine,
+    common::{commons::SortOrder, trash::TrashOperations},
+    errors::sdk::SDKError,
     organization::operations::{Organization, OrganizationCrudOperations, OrganizationInitializationInputBuilder},
     resources::{
-        changes::change::ChangeResourceType,
+        changes::change::{ChangeOperation, ChangeResourceType, ListenEvent},
+        cycles::extensions::{CloseCycleInput, CyclesExtensionOperations},
         members::{
             extensions::{CreateMemberFromEmailInputBuilder, MembersExtensionOperations},
             member::MemberRole,
             operations::{GetMembersInput, GetMembersInputBuilder, MemberCrudOperations},
         },
+        recurrences::operations::RecurrenceOperations,
+        tasks::extensions::TasksExtensionOperations,
     },
 };
-use tokio::task;
-use tokio_stream::StreamExt;
-use tracing::info;
+use std::{future, pin::Pin, time::Duration};
+use tokio::{task, time};
+use tokio_stream::{Stream, StreamExt};
+use tracing::{error, info};
+
+/// How long task updates are gathered before recurrences are generated for them.
+const RECURRENCE_DEBOUNCE: Duration = Duration::from_secs(2);
+
+type TaskChanges = Pin<Box<dyn Stream<Item = Result<ListenEvent, SDKError>> + Send>>;
 
 impl Core {
     pub async fn prelude(&self) -> Result<Organization, Box<dyn std::error::Error>> {
@@ -40,9 +52,113 @@ impl Core {
             }
         });
 
+        self.spawn_recurrence_generator();
+        self.spawn_rank_rebalancer();
+        self.spawn_cycle_closer();
+        self.spawn_trash_purger();
+
         Ok(org)
     }
 
+    /// Materializes recurring tasks on a fixed interval, and shortly after task updates so a
+    /// completed occurrence is followed up without waiting for the next tick.
+    fn spawn_recurrence_generator(&self) {
+        let engine = self.engine.clone();
+        let period = Duration::from_secs(*RECURRENCE_CHECK_INTERVAL);
+
+        task::spawn(async move {
+            let mut ticker = time::interval(period);
+            let mut changes = match engine.listen(ChangeResourceType::Tasks).await {
+                Ok(changes) => Some(changes),
+                Err(err) => {
+                    error!(
+                        "error listening to task changes, recurrences wait for the next tick: {}",
+                        err
+                    );
+                    None
+                }
+            };
+
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = next_task_update(&mut changes) => {
+                        // Updates tend to come in bursts, the ones shortly after are handled in the same pass.
+                        let _ = time::timeout(RECURRENCE_DEBOUNCE, async {
+                            loop {
+                                next_task_update(&mut changes).await;
+                            }
+                        })
+                        .await;
+                    }
+                }
+
+                generate_task_occurrences(&engine).await;
+            }
+        });
+    }
+
+    /// Ranks tasks left without one and shortens ranks grown long from repeated reordering. The
+    /// first tick fires right away, so tasks from before ranks existed get ranked at startup.
+    fn spawn_rank_rebalancer(&self) {
+        let engine = self.engine.clone();
+        let period = Duration::from_secs(*RANK_REBALANCE_INTERVAL);
+
+        task::spawn(async move {
+            let mut ticker = time::interval(period);
+
+            loop {
+                ticker.tick().await;
+
+                match engine.rebalance_task_ranks().await {
+                    Ok(0) => {}
+                    Ok(ranked) => info!("task ranks rebalanced: {} task(s)", ranked),
+                    Err(err) => error!("error rebalancing task ranks: {}", err),
+                }
+            }
+        });
+    }
+
+    /// Closes cycles once they end, rolling their unfinished tasks over to the next cycle.
+    fn spawn_cycle_closer(&self) {
+        let engine = self.engine.clone();
+        let period = Duration::from_secs(*CYCLE_CHECK_INTERVAL);
+
+        task::spawn(async move {
+            let mut ticker = time::interval(period);
+
+            loop {
+                ticker.tick().await;
+
+                close_due_cycles(&engine).await;
+            }
+        });
+    }
+
+    /// Permanently removes what has been in the trash for longer than the retention period.
+    fn spawn_trash_purger(&self) {
+        let engine = self.engine.clone();
+        let period = Duration::from_secs(*TRASH_PURGE_INTERVAL);
+        let retention = chrono::Duration::days(*TRASH_RETENTION_DAYS);
+
+        task::spawn(async move {
+            let mut ticker = time::interval(period);
+
+            loop {
+                ticker.tick().await;
+
+                match engine.purge_trash(retention).await {
+                    Ok(purge) if purge.is_empty() => {}
+                    Ok(purge) => info!(
+                        "trash purged: {} task(s), {} project(s), {} team(s)",
+                        purge.tasks, purge.projects, purge.teams
+                    ),
+                    Err(err) => error!("error purging trash: {}", err),
+                }
+            }
+        });
+    }
+
     async fn normalize_admin_user(&self) -> Result<(), Box<dyn std::error::Error>> {
         let default_admin_email = (*ADMIN_EMAIL).clone();
         let default_admin_password = (*ADMIN_PASSWORD).clone();
@@ -146,3 +262,61 @@ impl Core {
             .map_err(|err| err.into())
     }
 }
+
+/// Waits for the next update of a task. Inserts are skipped, the generator's own occurrences are
+/// inserts. Never returns once the listener is gone.
+async fn next_task_update(changes: &mut Option<TaskChanges>) {
+    while let Some(stream) = changes.as_mut() {
+        match stream.next().await {
+            Some(Ok(event)) if event.operation == ChangeOperation::Update => return,
+            Some(Ok(_)) => {}
+            Some(Err(err)) => error!("error listening to task changes: {}", err),
+            None => {
+                error!("task changes stopped, recurrences wait for the next tick");
+                *changes = None;
+            }
+        }
+    }
+
+    future::pending().await
+}
+
+async fn generate_task_occurrences(engine: &SDKEngine) {
+    let recurrences = match engine.due_task_recurrences().await {
+        Ok(recurrences) => recurrences,
+        Err(err) => {
+            error!("error loading due recurrences: {}", err);
+            return;
+        }
+    };
+
+    for recurrence in recurrences {
+        match engine.generate_task_occurrence(recurrence.id).await {
+            Ok(Some(task)) => info!("recurring task created: {} ({})", task.title, task.id),
+            Ok(None) => {}
+            Err(err) => error!("error creating occurrence of recurrence {}: {}", recurrence.id, err),
+        }
+    }
+}
+
+async fn close_due_cycles(engine: &SDKEngine) {
+    let cycles = match engine.due_cycles().await {
+        Ok(cycles) => cycles,
+        Err(err) => {
+            error!("error loading due cycles: {}", err);
+            return;
+        }
+    };
+
+    for cycle in cycles {
+        let input = CloseCycleInput {
+            owner_id: cycle.owner_id,
+            rollover_to_id: None,
+        };
+
+        match engine.close_cycle(cycle.id, input).await {
+            Ok(cycle) => info!("cycle closed: {} ({})", cycle.name, cycle.id),
+            Err(err) => error!("error closing cycle {}: {}", cycle.id, err),
+        }
+    }
+}
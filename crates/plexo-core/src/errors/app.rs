@@ -17,12 +17,18 @@ pub enum PlexoAppError {
     EmailNotFound,
     #[error("Email already exists")]
     EmailAlreadyExists,
+    #[error("Asset file not provided")]
+    MissingAssetFile,
+    #[error("Invalid upload field: {0}")]
+    InvalidUploadField(String),
 
     #[error("SDKError error")]
     SDKError(#[from] SDKError),
 
     #[error("Poem error")]
     NotFoundPoemError(#[from] poem::error::NotFoundError),
+    #[error("Multipart error")]
+    ParseMultipartError(#[from] poem::error::ParseMultipartError),
 
     #[error("JSONWebToken error")]
     JSONWebTokenError(#[from] jsonwebtoken::errors::Error),
@@ -39,8 +45,13 @@ impl ResponseError for PlexoAppError {
             PlexoAppError::InvalidPassword => StatusCode::BAD_REQUEST,
             PlexoAppError::EmailNotFound => StatusCode::BAD_REQUEST,
             PlexoAppError::EmailAlreadyExists => StatusCode::BAD_REQUEST,
+            PlexoAppError::MissingAssetFile => StatusCode::BAD_REQUEST,
+            PlexoAppError::InvalidUploadField(_) => StatusCode::BAD_REQUEST,
+            PlexoAppError::SDKError(SDKError::ResourceNotFound) => StatusCode::NOT_FOUND,
+            PlexoAppError::SDKError(SDKError::AssetTooLarge(_)) => StatusCode::PAYLOAD_TOO_LARGE,
             PlexoAppError::SDKError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             PlexoAppError::NotFoundPoemError(_) => StatusCode::NOT_FOUND,
+            PlexoAppError::ParseMultipartError(err) => err.status(),
             PlexoAppError::JSONWebTokenError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
@@ -1,6 +1,10 @@
 use dotenv::dotenv;
 use plexo_core::{
-    api::graphql::schema::GraphQLSchema,
+    api::{
+        assets::{download_asset_handler, upload_asset_handler, upload_asset_version_handler},
+        graphql::schema::GraphQLSchema,
+        openapi::api::new_openapi_service,
+    },
     auth::handlers::{email_basic_login_handler, github_callback_handler, github_sign_in_handler, logout_handler},
     core::{
         app::new_core_from_env,
@@ -32,16 +36,23 @@ async fn main() -> Result<(), Box<dyn Error>> {
     info!("version: {}", engine_version);
 
     let graphql_schema = core.graphql_api_schema();
+    let openapi_service = new_openapi_service(core.clone(), &engine_version);
+    let openapi_docs = openapi_service.swagger_ui();
 
     let app = Route::new()
         .at("/auth/email/login", post(email_basic_login_handler))
         .at("/auth/github", get(github_sign_in_handler))
         .at("/auth/github/callback", get(github_callback_handler))
         .at("/auth/logout", get(logout_handler))
+        .at("/assets/upload", post(upload_asset_handler))
+        .at("/assets/:id/versions", post(upload_asset_version_handler))
+        .at("/assets/:id/content", get(download_asset_handler))
         .at("/version", get(version_handler))
         .at("/playground", get(graphiq_handler))
         .at("/graphql", post(graphql_handler))
-        .at("/graphql/ws", get(ws_switch_handler));
+        .at("/graphql/ws", get(ws_switch_handler))
+        .nest("/api", openapi_service)
+        .nest("/docs", openapi_docs);
 
     let app = app
         .with(Cors::new().allow_credentials(true))
@@ -49,6 +60,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
         .data(core.clone());
 
     info!("visit GraphQL Playground at {}/playground", *DOMAIN);
+    info!("visit the REST API docs at {}/docs", *DOMAIN);
 
     Server::new(TcpListener::bind(URL.to_owned()))
         .run(app)
@@ -0,0 +1,32 @@
+-- Add migration script here
+
+-- Search vectors are stored generated columns, kept up to date by Postgres on every write. sqlx
+-- can't decode tsvector, so the queries checked by sqlx list the columns of these tables instead
+-- of `*`.
+
+alter table tasks
+    add search_vector tsvector generated always as (
+        setweight(to_tsvector('simple', coalesce(title, '')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(description, '')), 'B')
+        ) stored;
+
+alter table projects
+    add search_vector tsvector generated always as (
+        setweight(to_tsvector('simple', coalesce(name, '')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(description, '')), 'B')
+        ) stored;
+
+alter table messages
+    add search_vector tsvector generated always as (
+        to_tsvector('simple', coalesce(content, ''))
+        ) stored;
+
+alter table assets
+    add search_vector tsvector generated always as (
+        setweight(to_tsvector('simple', coalesce(name, '')), 'A')
+        ) stored;
+
+create index tasks_search_vector_idx on tasks using gin (search_vector);
+create index projects_search_vector_idx on projects using gin (search_vector);
+create index messages_search_vector_idx on messages using gin (search_vector);
+create index assets_search_vector_idx on assets using gin (search_vector);
@@ -0,0 +1,16 @@
+-- Add migration script here
+
+create table task_dependencies
+(
+    task_id       uuid                                   not null
+        references tasks
+            on update cascade on delete cascade,
+    blocked_by_id uuid                                   not null
+        references tasks
+            on update cascade on delete cascade,
+    created_at    timestamp with time zone default now() not null,
+    primary key (task_id, blocked_by_id),
+    constraint task_dependencies_not_self check (task_id <> blocked_by_id)
+);
+
+create index task_dependencies_blocked_by_id_idx on task_dependencies (blocked_by_id);
@@ -0,0 +1,126 @@
+-- Add migration script here
+
+-- Tasks get a per-project sequential number and a human readable key (`PLX-42`). Every key a task
+-- has ever had is kept in `task_keys`, so links keep resolving after a task moves to another
+-- project or its project prefix is renamed.
+
+alter table projects
+    add task_counter integer default 0 not null;
+
+alter table tasks
+    add number integer;
+
+alter table tasks
+    add key varchar;
+
+create table task_keys
+(
+    key        varchar                                not null
+        primary key,
+    task_id    uuid                                   not null
+        references tasks
+            on update cascade on delete cascade,
+    created_at timestamp with time zone default now() not null
+);
+
+create index task_keys_task_id_idx on task_keys (task_id);
+
+create function task_key(prefix varchar, number integer) returns varchar
+    language sql
+    immutable
+as
+$$
+select case when nullif(trim($1), '') is null or $2 is null then null else upper(trim($1)) || '-' || $2 end
+$$;
+
+-- Backfill existing tasks in creation order.
+
+update tasks
+set number = numbered.number
+from (select id, row_number() over (partition by project_id order by created_at, count) as number
+      from tasks
+      where project_id is not null) as numbered
+where tasks.id = numbered.id;
+
+update projects
+set task_counter = coalesce((select max(number) from tasks where tasks.project_id = projects.id), 0);
+
+update tasks
+set key = task_key(projects.prefix, tasks.number)
+from projects
+where tasks.project_id = projects.id;
+
+insert into task_keys (key, task_id)
+select distinct on (key) key, id
+from tasks
+where key is not null
+order by key, created_at;
+
+create function assign_task_number() returns trigger as
+$$
+declare
+    project_prefix varchar;
+begin
+    if (tg_op = 'UPDATE' and new.project_id is not distinct from old.project_id) then
+        return new;
+    end if;
+
+    if (new.project_id is null) then
+        new.number = null;
+        new.key = null;
+        return new;
+    end if;
+
+    update projects
+    set task_counter = task_counter + 1
+    where id = new.project_id
+    returning task_counter, prefix into new.number, project_prefix;
+
+    new.key = task_key(project_prefix, new.number);
+
+    return new;
+end;
+$$ language plpgsql;
+
+create trigger trigger_tasks_assign_number
+    before insert or update of project_id
+    on tasks
+    for each row
+execute procedure assign_task_number();
+
+create function record_task_key() returns trigger as
+$$
+begin
+    if (new.key is not null and (tg_op = 'INSERT' or new.key is distinct from old.key)) then
+        insert into task_keys (key, task_id)
+        values (new.key, new.id)
+        on conflict (key) do update set task_id = excluded.task_id;
+    end if;
+
+    return null;
+end;
+$$ language plpgsql;
+
+create trigger trigger_tasks_record_key
+    after insert or update
+    on tasks
+    for each row
+execute procedure record_task_key();
+
+create function rename_task_keys() returns trigger as
+$$
+begin
+    update tasks
+    set key = task_key(new.prefix, number)
+    where project_id = new.id;
+
+    return null;
+end;
+$$ language plpgsql;
+
+create trigger trigger_projects_rename_task_keys
+    after update of prefix
+    on projects
+    for each row
+    when (new.prefix is distinct from old.prefix)
+execute procedure rename_task_keys();
@@ -0,0 +1,64 @@
+-- Add migration script here
+
+create table workflows
+(
+    id         uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at timestamp with time zone default now()             not null,
+    updated_at timestamp with time zone default now()             not null,
+    name       text                                               not null,
+    owner_id   uuid                                               not null
+        references members
+            on update cascade on delete set null,
+    project_id uuid                                               not null
+        unique
+        references projects
+            on update cascade on delete cascade
+);
+
+create table workflow_statuses
+(
+    id          uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at  timestamp with time zone default now()             not null,
+    updated_at  timestamp with time zone default now()             not null,
+    workflow_id uuid                                               not null
+        references workflows
+            on update cascade on delete cascade,
+    name        text                                               not null,
+    category    varchar                                            not null,
+    position    integer                  default 0                 not null,
+    color       varchar,
+    unique (workflow_id, name)
+);
+
+create index workflow_statuses_workflow_id_idx on workflow_statuses (workflow_id, position);
+
+create table workflow_transitions
+(
+    workflow_id    uuid                                   not null
+        references workflows
+            on update cascade on delete cascade,
+    from_status_id uuid                                   not null
+        references workflow_statuses
+            on update cascade on delete cascade,
+    to_status_id   uuid                                   not null
+        references workflow_statuses
+            on update cascade on delete cascade,
+    created_at     timestamp with time zone default now() not null,
+    primary key (from_status_id, to_status_id),
+    constraint workflow_transitions_not_self check (from_status_id <> to_status_id)
+);
+
+create index workflow_transitions_workflow_id_idx on workflow_transitions (workflow_id);
+
+alter table tasks
+    add workflow_status_id uuid
+        references workflow_statuses
+            on update cascade on delete set null;
+
+create or replace trigger trigger_workflows_table_update
+    after insert or update or delete
+    on workflows
+    for each row
+execute procedure notify_table_update();
@@ -0,0 +1,28 @@
+-- Add migration script here
+
+create table task_recurrences
+(
+    id                 uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at         timestamp with time zone default now()             not null,
+    updated_at         timestamp with time zone default now()             not null,
+    owner_id           uuid                                               not null
+        references members
+            on update cascade on delete set null,
+    task_id            uuid                                               not null
+        unique
+        references tasks
+            on update cascade on delete cascade,
+    frequency          varchar                                            not null,
+    interval           integer                  default 1                 not null,
+    by_weekday         varchar[]                default '{}'              not null,
+    by_month_day       integer[]                default '{}'              not null,
+    until              timestamp with time zone,
+    count              integer,
+    occurrences        integer                  default 1                 not null,
+    next_occurrence_at timestamp with time zone,
+    active             boolean                  default true              not null,
+    constraint task_recurrences_interval_positive check (interval > 0)
+);
+
+create index task_recurrences_next_occurrence_at_idx on task_recurrences (next_occurrence_at) where active;
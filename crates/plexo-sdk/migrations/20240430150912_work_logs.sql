@@ -0,0 +1,36 @@
+-- Add migration script here
+
+alter table tasks
+    add estimate double precision,
+    add estimate_unit varchar;
+
+create table work_logs
+(
+    id         uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at timestamp with time zone default now()             not null,
+    updated_at timestamp with time zone default now()             not null,
+    member_id  uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    task_id    uuid                                               not null
+        references tasks
+            on update cascade on delete cascade,
+    started_at timestamp with time zone default now()             not null,
+    duration   integer,
+    note       text,
+    constraint work_logs_duration_not_negative check (duration >= 0)
+);
+
+create index work_logs_task_id_idx on work_logs (task_id, started_at);
+
+create index work_logs_member_id_idx on work_logs (member_id, started_at);
+
+-- A work log without a duration is a running timer, members can only have one at a time.
+create unique index work_logs_running_timer_idx on work_logs (member_id) where duration is null;
+
+create or replace trigger trigger_work_logs_table_update
+    after insert or update or delete
+    on work_logs
+    for each row
+execute procedure notify_table_update();
@@ -0,0 +1,7 @@
+-- Add migration script here
+
+alter table tasks
+    add rank varchar collate "C";
+
+create index tasks_board_idx
+    on tasks (project_id, status, rank);
@@ -0,0 +1,53 @@
+-- Add migration script here
+
+create table cycles
+(
+    id                    uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at            timestamp with time zone default now()             not null,
+    updated_at            timestamp with time zone default now()             not null,
+    owner_id              uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    name                  varchar                                            not null,
+    goal                  text,
+    project_id            uuid
+        references projects
+            on update cascade on delete cascade,
+    team_id               uuid
+        references teams
+            on update cascade on delete cascade,
+    starts_at             timestamp with time zone                           not null,
+    ends_at               timestamp with time zone                           not null,
+    closed_at             timestamp with time zone,
+    final_scope_count     integer,
+    final_completed_count integer,
+    constraint cycles_belongs_to_project_or_team check (num_nonnulls(project_id, team_id) = 1),
+    constraint cycles_ends_after_start check (ends_at > starts_at)
+);
+
+create index cycles_project_id_idx on cycles (project_id, starts_at);
+
+create index cycles_team_id_idx on cycles (team_id, starts_at);
+
+-- Open cycles are closed by the background job once they end.
+create index cycles_ends_at_idx on cycles (ends_at) where closed_at is null;
+
+create trigger set_public_cycles_updated_at
+    before update
+    on cycles
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+create or replace trigger trigger_cycles_table_update
+    after insert or update or delete
+    on cycles
+    for each row
+execute procedure notify_table_update();
+
+alter table tasks
+    add cycle_id uuid
+        references cycles
+            on update cascade on delete set null;
+
+create index tasks_cycle_id_idx on tasks (cycle_id);
@@ -0,0 +1,61 @@
+-- Add migration script here
+
+create table custom_fields
+(
+    id          uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at  timestamp with time zone default now()             not null,
+    updated_at  timestamp with time zone default now()             not null,
+    owner_id    uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    project_id  uuid                                               not null
+        references projects
+            on update cascade on delete cascade,
+    name        varchar                                            not null,
+    description text,
+    kind        varchar                                            not null,
+    options     text[]                   default '{}'              not null,
+    constraint custom_fields_project_id_name_key unique (project_id, name)
+);
+
+create trigger set_public_custom_fields_updated_at
+    before update
+    on custom_fields
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+create or replace trigger trigger_custom_fields_table_update
+    after insert or update or delete
+    on custom_fields
+    for each row
+execute procedure notify_table_update();
+
+-- Only the column matching the field kind is set, single selects are stored in text_value.
+create table custom_field_values
+(
+    task_id       uuid                                   not null
+        references tasks
+            on update cascade on delete cascade,
+    field_id      uuid                                   not null
+        references custom_fields
+            on update cascade on delete cascade,
+    created_at    timestamp with time zone default now() not null,
+    updated_at    timestamp with time zone default now() not null,
+    text_value    text,
+    number_value  double precision,
+    date_value    timestamp with time zone,
+    member_value  uuid
+        references members
+            on update cascade on delete cascade,
+    options_value text[],
+    primary key (task_id, field_id)
+);
+
+create index custom_field_values_field_id_idx on custom_field_values (field_id);
+
+create trigger set_public_custom_field_values_updated_at
+    before update
+    on custom_field_values
+    for each row
+execute procedure set_current_timestamp_updated_at();
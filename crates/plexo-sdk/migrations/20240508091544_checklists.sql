@@ -0,0 +1,36 @@
+-- Add migration script here
+
+create table checklist_items
+(
+    id         uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at timestamp with time zone default now()             not null,
+    updated_at timestamp with time zone default now()             not null,
+    task_id    uuid                                               not null
+        references tasks
+            on update cascade on delete cascade,
+    owner_id   uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    text       text                                               not null,
+    position   integer                                            not null,
+    checked    boolean                  default false             not null,
+    checked_by uuid
+        references members
+            on update cascade on delete set null,
+    checked_at timestamp with time zone
+);
+
+create index checklist_items_task_id_idx on checklist_items (task_id, position);
+
+create trigger set_public_checklist_items_updated_at
+    before update
+    on checklist_items
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+create or replace trigger trigger_checklist_items_table_update
+    after insert or update or delete
+    on checklist_items
+    for each row
+execute procedure notify_table_update();
@@ -0,0 +1,65 @@
+-- Add migration script here
+
+create table comments
+(
+    id            uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at    timestamp with time zone default now()             not null,
+    updated_at    timestamp with time zone default now()             not null,
+    owner_id      uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    resource_id   uuid                                               not null,
+    resource_type text                                               not null,
+    parent_id     uuid
+        references comments
+            on update cascade on delete cascade,
+    body          text                                               not null,
+    edited_at     timestamp with time zone
+);
+
+create index comments_resource_idx on comments (resource_type, resource_id, created_at);
+
+create index comments_parent_id_idx on comments (parent_id);
+
+create trigger set_public_comments_updated_at
+    before update
+    on comments
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+create or replace trigger trigger_comments_table_update
+    after insert or update or delete
+    on comments
+    for each row
+execute procedure notify_table_update();
+
+create table comment_edits
+(
+    id         uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at timestamp with time zone default now()             not null,
+    comment_id uuid                                               not null
+        references comments
+            on update cascade on delete cascade,
+    editor_id  uuid
+        references members
+            on update cascade on delete set null,
+    body       text                                               not null
+);
+
+create index comment_edits_comment_id_idx on comment_edits (comment_id, created_at);
+
+create table comment_mentions
+(
+    comment_id uuid                                               not null
+        references comments
+            on update cascade on delete cascade,
+    member_id  uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    created_at timestamp with time zone default now()             not null,
+    primary key (comment_id, member_id)
+);
+
+create index comment_mentions_member_id_idx on comment_mentions (member_id);
@@ -0,0 +1,60 @@
+-- Add migration script here
+
+create table task_templates
+(
+    id          uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at  timestamp with time zone default now()             not null,
+    updated_at  timestamp with time zone default now()             not null,
+    owner_id    uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    project_id  uuid
+        references projects
+            on update cascade on delete cascade,
+    name        text                                               not null,
+    description text
+);
+
+create index task_templates_project_id_idx on task_templates (project_id);
+
+create trigger set_public_task_templates_updated_at
+    before update
+    on task_templates
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+create or replace trigger trigger_task_templates_table_update
+    after insert or update or delete
+    on task_templates
+    for each row
+execute procedure notify_table_update();
+
+create table task_template_items
+(
+    id                    uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at            timestamp with time zone default now()             not null,
+    template_id           uuid                                               not null
+        references task_templates
+            on update cascade on delete cascade,
+    parent_id             uuid
+        references task_template_items
+            on update cascade on delete cascade,
+    position              integer                                            not null,
+    title                 text                                               not null,
+    description           text,
+    priority              varchar,
+    estimate              double precision,
+    estimate_unit         varchar,
+    due_offset_days       integer,
+    label_ids             uuid[]                   default '{}'              not null,
+    assignee_ids          uuid[]                   default '{}'              not null,
+    assignee_placeholders text[]                   default '{}'              not null,
+    lead_id               uuid
+        references members
+            on update cascade on delete set null,
+    lead_placeholder      text
+);
+
+create index task_template_items_template_id_idx on task_template_items (template_id, parent_id, position);
@@ -0,0 +1,38 @@
+-- Add migration script here
+
+-- A project template is a saved clone recipe: new projects are copied from its source project
+-- with the parts the template includes. The template outlives its source, it just can't be
+-- instantiated anymore once the source is gone.
+
+create table project_templates
+(
+    id                uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at        timestamp with time zone default now()             not null,
+    updated_at        timestamp with time zone default now()             not null,
+    owner_id          uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    source_project_id uuid
+        references projects
+            on update cascade on delete set null,
+    name              text                                               not null,
+    description       text,
+    include_members   boolean                  default true              not null,
+    include_teams     boolean                  default true              not null,
+    include_tasks     boolean                  default true              not null
+);
+
+create index project_templates_source_project_id_idx on project_templates (source_project_id);
+
+create trigger set_public_project_templates_updated_at
+    before update
+    on project_templates
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+create or replace trigger trigger_project_templates_table_update
+    after insert or update or delete
+    on project_templates
+    for each row
+execute procedure notify_table_update();
@@ -0,0 +1,30 @@
+-- Add migration script here
+
+-- Deleting a task, project or team moves it to the trash (`deleted_at`) and archiving hides it
+-- (`archived_at`). Subtasks and project tasks take the same timestamp as the item they were
+-- trashed or archived with, so restoring the item brings them back. Trashed rows are purged
+-- for good after the retention period.
+
+alter table tasks
+    add deleted_at timestamp with time zone;
+
+alter table tasks
+    add archived_at timestamp with time zone;
+
+alter table projects
+    add deleted_at timestamp with time zone;
+
+alter table projects
+    add archived_at timestamp with time zone;
+
+alter table teams
+    add deleted_at timestamp with time zone;
+
+alter table teams
+    add archived_at timestamp with time zone;
+
+create index tasks_deleted_at_idx on tasks (deleted_at) where deleted_at is not null;
+
+create index projects_deleted_at_idx on projects (deleted_at) where deleted_at is not null;
+
+create index teams_deleted_at_idx on teams (deleted_at) where deleted_at is not null;
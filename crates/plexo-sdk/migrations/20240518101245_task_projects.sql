@@ -0,0 +1,14 @@
+-- Add migration script here
+
+-- The project of a task is `tasks.project_id`. `tasks_by_projects` duplicated it without being
+-- kept in sync, so tasks only linked through it take that project before it goes away.
+
+update tasks
+set project_id = linked.project_id
+from (select distinct on (task_id) task_id, project_id
+      from tasks_by_projects
+      order by task_id, project_id) as linked
+where tasks.id = linked.task_id
+  and tasks.project_id is null;
+
+drop table tasks_by_projects;
@@ -0,0 +1,15 @@
+-- Add migration script here
+
+create table assets_by_tasks
+(
+    task_id    uuid                                   not null
+        references tasks
+            on update cascade on delete cascade,
+    asset_id   uuid                                   not null
+        references assets
+            on update cascade on delete cascade,
+    created_at timestamp with time zone default now() not null,
+    primary key (task_id, asset_id)
+);
+
+create index assets_by_tasks_asset_id_idx on assets_by_tasks (asset_id);
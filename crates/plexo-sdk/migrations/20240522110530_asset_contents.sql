@@ -0,0 +1,8 @@
+-- Add migration script here
+
+alter table assets
+    add content_hash varchar,
+    add size         bigint,
+    add mime_type    varchar;
+
+create index assets_content_hash_idx on assets (content_hash);
@@ -0,0 +1,47 @@
+-- Add migration script here
+
+alter table assets
+    add version        integer default 1 not null,
+    add extracted_text text;
+
+-- Every uploaded revision of an asset. The asset row mirrors its latest (or restored) version.
+create table asset_versions
+(
+    id             uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at     timestamp with time zone default now()             not null,
+    asset_id       uuid                                               not null
+        references assets
+            on update cascade on delete cascade,
+    version        integer                                            not null,
+    author_id      uuid
+        references members
+            on update cascade on delete set null,
+    name           text                                               not null,
+    kind           varchar,
+    content_hash   varchar                                            not null,
+    size           bigint                                             not null,
+    mime_type      varchar                                            not null,
+    extracted_text text,
+    unique (asset_id, version)
+);
+
+create index asset_versions_content_hash_idx on asset_versions (content_hash);
+
+insert into asset_versions (created_at, asset_id, version, author_id, name, kind, content_hash, size, mime_type)
+select updated_at, id, version, owner_id, name, kind, content_hash, size, mime_type
+from assets
+where content_hash is not null;
+
+-- The extracted text is searched too. A generated column can't change its expression, so it's
+-- added again.
+alter table assets
+    drop search_vector;
+
+alter table assets
+    add search_vector tsvector generated always as (
+        setweight(to_tsvector('simple', coalesce(name, '')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(extracted_text, '')), 'B')
+        ) stored;
+
+create index assets_search_vector_idx on assets using gin (search_vector);
@@ -0,0 +1,86 @@
+-- Add migration script here
+
+-- Labels can belong to a project or a team, names are unique within their scope instead of
+-- globally. Labels can also be grouped, and a task has at most one label of an exclusive group
+-- (`type/bug` or `type/feature`).
+
+create table label_groups
+(
+    id          uuid                     default gen_random_uuid() not null
+        primary key,
+    created_at  timestamp with time zone default now()             not null,
+    updated_at  timestamp with time zone default now()             not null,
+    owner_id    uuid                                               not null
+        references members
+            on update cascade on delete cascade,
+    name        varchar                                            not null
+        unique,
+    description text,
+    exclusive   boolean                  default false             not null
+);
+
+create trigger set_public_label_groups_updated_at
+    before update
+    on label_groups
+    for each row
+execute procedure set_current_timestamp_updated_at();
+
+create or replace trigger trigger_label_groups_table_update
+    after insert or update or delete
+    on label_groups
+    for each row
+execute procedure notify_table_update();
+
+alter table labels
+    add project_id uuid
+        references projects
+            on update cascade on delete cascade;
+
+alter table labels
+    add team_id uuid
+        references teams
+            on update cascade on delete cascade;
+
+alter table labels
+    add group_id uuid
+        references label_groups
+            on update cascade on delete set null;
+
+alter table labels
+    add constraint labels_single_scope check (project_id is null or team_id is null);
+
+alter table labels
+    drop constraint labels_name_key;
+
+create unique index labels_global_name_key on labels (name) where project_id is null and team_id is null;
+
+create unique index labels_project_name_key on labels (project_id, name) where project_id is not null;
+
+create unique index labels_team_name_key on labels (team_id, name) where team_id is not null;
+
+create index labels_group_id_idx on labels (group_id);
+
+-- Labelling a task with a label of an exclusive group replaces the task's other label of that group.
+create function replace_exclusive_labels() returns trigger as
+$$
+begin
+    delete
+    from labels_by_tasks
+    where task_id = new.task_id
+      and label_id <> new.label_id
+      and label_id in (select others.id
+                       from labels
+                                join label_groups on label_groups.id = labels.group_id
+                                join labels others on others.group_id = labels.group_id
+                       where labels.id = new.label_id
+                         and label_groups.exclusive);
+
+    return new;
+end;
+$$ language plpgsql;
+
+create trigger trigger_labels_by_tasks_replace_exclusive
+    before insert
+    on labels_by_tasks
+    for each row
+execute procedure replace_exclusive_labels();
@@ -0,0 +1,39 @@
+-- Add migration script here
+
+-- Task keys are derived from the project prefix, two projects sharing a prefix would hand out the
+-- same keys. Later duplicates lose their prefix, their tasks keep the keys they already had, so the
+-- rename trigger that would clear them is off meanwhile.
+
+alter table projects
+    disable trigger trigger_projects_rename_task_keys;
+
+update projects
+set prefix = null
+where id in (select id
+             from (select id, row_number() over (partition by upper(trim(prefix)) order by created_at, id) as position
+                   from projects
+                   where nullif(trim(prefix), '') is not null) as prefixed
+             where position > 1);
+
+alter table projects
+    enable trigger trigger_projects_rename_task_keys;
+
+create unique index projects_prefix_key on projects (upper(trim(prefix))) where nullif(trim(prefix), '') is not null;
+
+-- A key recorded for a task is never handed over to another one, links to it keep resolving.
+create or replace function record_task_key() returns trigger as
+$$
+begin
+    if (new.key is not null and (tg_op = 'INSERT' or new.key is distinct from old.key)) then
+        insert into task_keys (key, task_id)
+        values (new.key, new.id)
+        on conflict (key) do nothing;
+
+        if (not found and exists (select 1 from task_keys where key = new.key and task_id <> new.id)) then
+            raise exception 'task key % belongs to another task', new.key using errcode = 'unique_violation';
+        end if;
+    end if;
+
+    return null;
+end;
+$$ language plpgsql;
@@ -0,0 +1,54 @@
+-- Add migration script here
+
+-- A project label only labels tasks of its project, a team label tasks of the team's projects.
+
+create function label_in_scope(label_id uuid, project_id uuid) returns boolean
+    language sql
+    stable
+as
+$$
+select coalesce((select (labels.project_id is null or labels.project_id = $2)
+                            and (labels.team_id is null or exists (select 1
+                                                                   from teams_by_projects
+                                                                   where teams_by_projects.team_id = labels.team_id
+                                                                     and teams_by_projects.project_id = $2))
+                 from labels
+                 where labels.id = $1), false)
+$$;
+
+-- The label itself when it's in scope for the project, otherwise the label of the same name that is,
+-- preferring project labels over team labels over workspace labels.
+create function scoped_label(label_id uuid, project_id uuid) returns uuid
+    language sql
+    stable
+as
+$$
+select case
+           when label_in_scope($1, $2) then $1
+           else (select targets.id
+                 from labels
+                          join labels targets on targets.name = labels.name and targets.id <> labels.id
+                 where labels.id = $1
+                   and label_in_scope(targets.id, $2)
+                 order by targets.project_id nulls last, targets.team_id nulls last
+                 limit 1)
+           end
+$$;
+
+create function check_label_scope() returns trigger as
+$$
+begin
+    if (not label_in_scope(new.label_id, (select project_id from tasks where id = new.task_id))) then
+        raise exception 'label % is out of scope for task %', new.label_id, new.task_id using errcode = 'check_violation';
+    end if;
+
+    return new;
+end;
+$$ language plpgsql;
+
+-- Fires before `trigger_labels_by_tasks_replace_exclusive`, triggers run in name order.
+create trigger trigger_labels_by_tasks_check_scope
+    before insert
+    on labels_by_tasks
+    for each row
+execute procedure check_label_scope();
@@ -0,0 +1,27 @@
+-- Add migration script here
+
+-- Resource types are stored under their PascalCase names, like the ones that came before them.
+
+update changes
+set resource_type = case resource_type
+                        when 'custom_fields' then 'CustomFields'
+                        when 'checklist_items' then 'ChecklistItems'
+                        when 'task_templates' then 'TaskTemplates'
+                        when 'project_templates' then 'ProjectTemplates'
+                        when 'work_logs' then 'WorkLogs'
+                        when 'label_groups' then 'LabelGroups'
+    end
+where resource_type in
+      ('custom_fields', 'checklist_items', 'task_templates', 'project_templates', 'work_logs', 'label_groups');
+
+update comments
+set resource_type = case resource_type
+                        when 'custom_fields' then 'CustomFields'
+                        when 'checklist_items' then 'ChecklistItems'
+                        when 'task_templates' then 'TaskTemplates'
+                        when 'project_templates' then 'ProjectTemplates'
+                        when 'work_logs' then 'WorkLogs'
+                        when 'label_groups' then 'LabelGroups'
+    end
+where resource_type in
+      ('custom_fields', 'checklist_items', 'task_templates', 'project_templates', 'work_logs', 'label_groups');
@@ -1,4 +1,4 @@
-use std::{env::var, pin::Pin, str::FromStr, time::Duration};
+use std::{env::var, pin::Pin, str::FromStr, sync::Arc, time::Duration};
 
 use async_openai::{
     config::{OpenAIConfig, OPENAI_API_BASE},
@@ -20,10 +20,12 @@ use crate::{
         GLOBAL_ORGANIZATION_SETTINGS_NAME,
     },
     resources::changes::change::{ChangeOperation, ChangeResourceType, ListenEvent}, // resources::tasks::task::Task,
+    storage::{AssetStorage, AssetStorageConfig},
 };
 // use crossbeam_channel::unbounded;
 
 const VERSION: Option<&str> = option_env!("CARGO_PKG_VERSION");
+const DEFAULT_ASSET_MAX_SIZE: u64 = 25 * 1024 * 1024;
 
 #[derive(Clone)]
 pub struct SDKConfig {
@@ -31,6 +33,9 @@ pub struct SDKConfig {
     pub llm_api_key: String,
     pub llm_model_name: String,
     pub llm_api_base: String,
+    pub asset_storage: AssetStorageConfig,
+    /// Largest accepted upload, in bytes.
+    pub asset_max_size: u64,
 }
 
 impl SDKConfig {
@@ -40,11 +45,30 @@ impl SDKConfig {
         let llm_model_name = var("OPENAI_MODEL_NAME").unwrap_or("gpt-3.5-turbo-0125".to_string());
         let llm_api_base = var("OPENAI_API_BASE").unwrap_or(OPENAI_API_BASE.to_string());
 
+        let asset_storage = match var("ASSET_STORAGE").unwrap_or("local".to_string()).as_str() {
+            "s3" => AssetStorageConfig::S3 {
+                endpoint: var("S3_ENDPOINT").unwrap(),
+                bucket: var("S3_BUCKET").unwrap(),
+                region: var("S3_REGION").unwrap_or("us-east-1".to_string()),
+                access_key_id: var("S3_ACCESS_KEY_ID").unwrap(),
+                secret_access_key: var("S3_SECRET_ACCESS_KEY").unwrap(),
+            },
+            _ => AssetStorageConfig::Local {
+                root: var("ASSET_STORAGE_PATH").unwrap_or("./data/assets".to_string()),
+            },
+        };
+        let asset_max_size = var("ASSET_MAX_SIZE")
+            .ok()
+            .and_then(|size| size.parse().ok())
+            .unwrap_or(DEFAULT_ASSET_MAX_SIZE);
+
         SDKConfig {
             database_url,
             llm_api_key,
             llm_model_name,
             llm_api_base,
+            asset_storage,
+            asset_max_size,
         }
     }
 }
@@ -55,18 +79,33 @@ pub struct SDKEngine {
     pub db_pool: Box<Pool<Postgres>>,
     // pub db_listener: PgListener,
     pub llm_client: Box<Client<OpenAIConfig>>,
+    pub asset_storage: Arc<dyn AssetStorage>,
     // pub task_event_send: crossbeam_channel::Sender<Task>,
     // pub task_event_recv: crossbeam_channel::Receiver<Task>,
 }
 
 impl SDKEngine {
     pub async fn new(config: SDKConfig) -> Result<SDKEngine, SDKError> {
-        let pool = PgPoolOptions::new()
+        let pool = Self::pool_options().connect(config.database_url.as_str()).await?;
+
+        Ok(Self::with_pool(config, pool))
+    }
+
+    /// Like `new`, but doesn't connect to the database until a query needs it.
+    pub fn new_lazy(config: SDKConfig) -> Result<SDKEngine, SDKError> {
+        let pool = Self::pool_options().connect_lazy(config.database_url.as_str())?;
+
+        Ok(Self::with_pool(config, pool))
+    }
+
+    fn pool_options() -> PgPoolOptions {
+        PgPoolOptions::new()
             .max_connections(10)
             .acquire_timeout(Duration::from_secs(60))
-            .connect(config.database_url.as_str())
-            .await?;
+    }
 
+    /// Builds an engine around a pool that is already set up.
+    pub fn with_pool(config: SDKConfig, pool: Pool<Postgres>) -> SDKEngine {
         let llm_config = OpenAIConfig::default()
             // .with_api_base(config.llm_api_base.clone())
             .with_api_key(config.llm_api_key.clone());
@@ -75,6 +114,8 @@ impl SDKEngine {
 
         let db_pool = Box::new(pool);
 
+        let asset_storage = config.asset_storage.build();
+
         // let (task_event_send, task_event_recv) = unbounded::<Task>();
         // let listener = PgListener::connect(&config.database_url).await?;
 
@@ -91,16 +132,15 @@ impl SDKEngine {
 
         // let a = db_listener.into_stream().;
 
-        let engine = SDKEngine {
+        SDKEngine {
             config,
             db_pool,
             llm_client,
+            asset_storage,
             // db_listener,
             // task_event_send,
             // task_event_recv,
-        };
-
-        Ok(engine)
+        }
     }
 
     pub async fn migrate(&self) -> Result<(), SDKError> {
@@ -144,16 +184,17 @@ impl SDKEngine {
         let mut db_listener = PgListener::connect_with(&self.db_pool).await?;
 
         db_listener
-            .listen(format!("{}_table_update", resource.to_string().to_lowercase()).as_str())
+            .listen(format!("{}_table_update", resource.table_name()).as_str())
             .await?;
 
-        let mapped_stream = db_listener.into_stream().map(|x| match x {
+        let mapped_stream = db_listener.into_stream().map(move |x| match x {
             Ok(not) => {
                 // TG_TABLE_NAME || ' ' || TG_OP || ' ' || row.id;
 
                 let mut payload = not.payload().split_whitespace();
 
-                let resource = ChangeResourceType::from_str(payload.next().unwrap()).unwrap();
+                // Each table notifies on its own channel, so the event is about the listened resource.
+                payload.next();
                 let operation = ChangeOperation::from_str(payload.next().unwrap()).unwrap();
                 let row_id = payload.next().map(|a| a.parse::<Uuid>().unwrap()).unwrap();
 
@@ -3,9 +3,9 @@ use std::sync::Arc;
 use async_graphql::dataloader::DataLoader;
 
 use crate::resources::{
-    assets::loader::AssetLoader, changes::loader::ChangeLoader, chats::loader::ChatLoader, labels::loader::LabelLoader,
-    members::loader::MemberLoader, messages::loader::MessageLoader, projects::loader::ProjectLoader,
-    tasks::loader::TaskLoader, teams::loader::TeamLoader,
+    assets::loader::AssetLoader, changes::loader::ChangeLoader, chats::loader::ChatLoader, cycles::loader::CycleLoader,
+    labels::loader::LabelLoader, members::loader::MemberLoader, messages::loader::MessageLoader,
+    projects::loader::ProjectLoader, tasks::loader::TaskLoader, teams::loader::TeamLoader,
 };
 
 use super::engine::SDKEngine;
@@ -21,6 +21,7 @@ pub struct SDKLoaders {
     pub change_loader: DataLoader<ChangeLoader>,
     pub chat_loader: DataLoader<ChatLoader>,
     pub message_loader: DataLoader<MessageLoader>,
+    pub cycle_loader: DataLoader<CycleLoader>,
 
     pub engine: Arc<SDKEngine>,
 }
@@ -37,6 +38,7 @@ impl SDKLoaders {
             change_loader: DataLoader::new(ChangeLoader::new(engine.clone()), tokio::spawn),
             chat_loader: DataLoader::new(ChatLoader::new(engine.clone()), tokio::spawn),
             message_loader: DataLoader::new(MessageLoader::new(engine.clone()), tokio::spawn),
+            cycle_loader: DataLoader::new(CycleLoader::new(engine.clone()), tokio::spawn),
 
             engine,
         }
@@ -1,2 +1,4 @@
 pub mod engine;
 pub mod loaders;
+#[cfg(test)]
+pub(crate) mod testing;
@@ -0,0 +1,73 @@
+//! Fixtures for tests running against the database `#[sqlx::test]` creates for each test.
+
+use std::env::temp_dir;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::{SDKConfig, SDKEngine},
+    resources::{
+        members::{
+            member::MemberRole,
+            operations::{CreateMemberInputBuilder, MemberCrudOperations},
+        },
+        projects::operations::{CreateProjectInputBuilder, ProjectCrudOperations},
+        tasks::{
+            operations::{CreateTaskInput, TaskCrudOperations},
+            task::Task,
+        },
+    },
+    storage::AssetStorageConfig,
+};
+
+pub(crate) fn test_engine(pool: PgPool) -> SDKEngine {
+    let config = SDKConfig {
+        database_url: String::new(),
+        llm_api_key: String::new(),
+        llm_model_name: String::new(),
+        llm_api_base: String::new(),
+        asset_storage: AssetStorageConfig::Local {
+            root: temp_dir()
+                .join(Uuid::new_v4().to_string())
+                .to_string_lossy()
+                .into_owned(),
+        },
+        asset_max_size: 1024 * 1024,
+    };
+
+    SDKEngine::with_pool(config, pool)
+}
+
+pub(crate) async fn test_member(engine: &SDKEngine) -> Uuid {
+    let input = CreateMemberInputBuilder::default()
+        .name("Member".to_string())
+        .email(format!("{}@plexo.test", Uuid::new_v4()))
+        .role(MemberRole::Member)
+        .build()
+        .unwrap();
+
+    engine.create_member(input).await.unwrap().id
+}
+
+pub(crate) async fn test_project(engine: &SDKEngine, owner_id: Uuid, prefix: &str) -> Uuid {
+    let input = CreateProjectInputBuilder::default()
+        .name(format!("Project {prefix}"))
+        .owner_id(owner_id)
+        .prefix(prefix.to_string())
+        .build()
+        .unwrap();
+
+    engine.create_project(input).await.unwrap().id
+}
+
+pub(crate) async fn test_task(engine: &SDKEngine, owner_id: Uuid, project_id: Option<Uuid>, title: &str) -> Task {
+    let input = CreateTaskInput {
+        title: title.to_string(),
+        owner_id,
+        project_id,
+        ..Default::default()
+    };
+
+    engine.create_task(input).await.unwrap()
+}
@@ -102,6 +102,7 @@ impl CognitionOperations for SDKEngine {
 
     async fn subdivide_task(&self, input: SubdivideTaskInput) -> Result<Vec<TaskSuggestion>, SDKError> {
         let task = self.get_task(input.task_id).await?;
+        let task = self.with_checklist_progress(vec![task]).await?.remove(0);
 
         let system_message = "The user pass to you one task and you should predict a list of subtasks.
         Please return only a valid json with the following struct [{
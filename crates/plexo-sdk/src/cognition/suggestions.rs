@@ -5,7 +5,9 @@ use super::{
 
 use crate::{
     backend::engine::SDKEngine,
+    errors::sdk::SDKError,
     resources::{
+        checklists::{checklist::ChecklistProgress, operations::ChecklistOperations},
         messages::message::Message,
         tasks::{
             operations::{GetTasksInputBuilder, TaskCrudOperations},
@@ -24,23 +26,34 @@ use async_stream::stream;
 use async_trait::async_trait;
 use schemars::{schema_for, JsonSchema};
 
+use serde::Serialize;
 use serde_json::{json, Value};
 use std::{collections::HashMap, pin::Pin, sync::Arc};
 use tokio_stream::{Stream, StreamExt};
 use uuid::Uuid;
 
 use tokio::sync::Mutex;
+
+/// A task as the models see it, along with the progress of its checklist.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskFingerprint {
+    #[serde(flatten)]
+    pub task: Task,
+    pub checklist_progress: Option<ChecklistProgress>,
+}
+
 #[async_trait]
 pub trait CognitionCapabilities {
     async fn chat_completion(&self, system_message: String, user_message: String) -> String;
     async fn acquire_tasks_fingerprints(&self, number_of_tasks: u32, project_id: Option<Uuid>) -> Vec<String>;
+    async fn with_checklist_progress(&self, tasks: Vec<Task>) -> Result<Vec<TaskFingerprint>, SDKError>;
     async fn chat_response(
         &self,
         system_message: String,
         messages: Vec<Message>,
     ) -> Pin<Box<dyn Stream<Item = (Option<Vec<ChatResponseToolCall>>, String)> + Send>>;
 
-    fn calculate_task_fingerprint(task: Task) -> String;
+    fn calculate_task_fingerprint(task: TaskFingerprint) -> String;
     fn calculate_task_suggestion_fingerprint(task_suggestion: TaskSuggestionInput) -> String;
     fn message_to_chat_completion(message: &Message) -> ChatCompletionRequestMessage;
 }
@@ -71,7 +84,7 @@ impl CognitionCapabilities for SDKEngine {
         response.choices.first().unwrap().message.content.clone().unwrap()
     }
 
-    fn calculate_task_fingerprint(task: Task) -> String {
+    fn calculate_task_fingerprint(task: TaskFingerprint) -> String {
         serde_json::to_string(&task).unwrap()
     }
 
@@ -107,27 +120,28 @@ impl CognitionCapabilities for SDKEngine {
 
         let tasks = self.get_tasks(filter).await.unwrap();
 
-        tasks
-            .iter()
-            .map(|r| Task {
-                id: r.id,
-                created_at: r.created_at,
-                updated_at: r.updated_at,
-                title: r.title.clone(),
-                description: r.description.clone(),
-                status: r.status,
-                priority: r.priority,
-                due_date: r.due_date,
-                project_id: r.project_id,
-                lead_id: r.lead_id,
-                owner_id: r.owner_id,
-                count: r.count,
-                parent_id: r.parent_id,
-            })
+        self.with_checklist_progress(tasks)
+            .await
+            .unwrap()
+            .into_iter()
             .map(Self::calculate_task_fingerprint)
             .collect::<Vec<String>>()
     }
 
+    async fn with_checklist_progress(&self, tasks: Vec<Task>) -> Result<Vec<TaskFingerprint>, SDKError> {
+        let mut progress = self
+            .checklist_progress(tasks.iter().map(|task| task.id).collect())
+            .await?;
+
+        Ok(tasks
+            .into_iter()
+            .map(|task| TaskFingerprint {
+                checklist_progress: progress.remove(&task.id),
+                task,
+            })
+            .collect())
+    }
+
     async fn chat_response(
         &self,
         system_message: String,
@@ -10,6 +10,10 @@ use uuid::Uuid;
 pub struct ChatResponseInput {
     pub chat_id: Uuid,
     pub message: String,
+
+    /// Adds the text of the project's files to the context of project chats.
+    #[builder(setter(strip_option), default)]
+    pub include_assets: Option<bool>,
 }
 
 #[derive(Debug, Default, Builder, Object, SimpleObject, Deserialize)]
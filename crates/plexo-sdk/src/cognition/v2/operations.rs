@@ -11,12 +11,13 @@ use crate::{
     backend::engine::SDKEngine,
     cognition::{
         operations::{SubdivideTaskInput, TaskSuggestion, TaskSuggestionInput},
-        suggestions::CognitionCapabilities,
+        suggestions::{CognitionCapabilities, TaskFingerprint},
     },
     common::commons::SortOrder,
     errors::sdk::SDKError,
     organization::operations::{Organization, OrganizationCrudOperations},
     resources::{
+        assets::{asset::Asset, operations::asset_from_row},
         chats::operations::ChatCrudOperations,
         members::{
             member::Member,
@@ -29,10 +30,7 @@ use crate::{
             operations::{GetProjectsInputBuilder, ProjectCrudOperations},
             project::Project,
         },
-        tasks::{
-            operations::{GetTasksInputBuilder, GetTasksWhereBuilder, TaskCrudOperations},
-            task::Task,
-        },
+        tasks::operations::{GetTasksInputBuilder, GetTasksWhereBuilder, TaskCrudOperations},
         teams::{
             operations::{GetTeamsInputBuilder, TeamCrudOperations},
             team::Team,
@@ -56,7 +54,7 @@ pub trait CognitionOperationsV2 {
     ) -> Result<Pin<Box<dyn Stream<Item = ChatResponseChunk> + Send>>, SDKError>;
 }
 
-fn calculate_task_fingerprint(task: &Task) -> String {
+fn calculate_task_fingerprint(task: &TaskFingerprint) -> String {
     serde_json::to_string_pretty(&task).unwrap()
 }
 
@@ -76,6 +74,19 @@ fn calculate_team_fingerprint(project: &Team) -> String {
     serde_json::to_string_pretty(&project).unwrap()
 }
 
+/// Chat context is limited, only the beginning of each file's text goes in.
+const ASSET_CONTEXT_LENGTH: usize = 4000;
+
+fn calculate_asset_fingerprint(asset: &Asset) -> String {
+    let text = asset.extracted_text.as_deref().unwrap_or_default();
+    let text = match text.char_indices().nth(ASSET_CONTEXT_LENGTH) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    };
+
+    format!("{} ({}, version {}):\n{}", asset.name, asset.kind, asset.version, text)
+}
+
 fn calculate_task_suggestion_input_fingerprint(input: &TaskSuggestionInput) -> String {
     serde_json::to_string_pretty(&input).unwrap()
 }
@@ -95,7 +106,7 @@ fn calculate_task_suggestion_fingerprint(input: &ProjectTaskSuggestionInput) ->
 #[derive(Template)]
 #[template(path = "task_suggestion.md.jinja", ext = "plain")]
 pub struct TaskSuggestionTemplate {
-    tasks: Vec<Task>,
+    tasks: Vec<TaskFingerprint>,
     initial_state: Option<TaskSuggestionInput>,
     project: Option<Project>,
     user_query: Option<String>,
@@ -104,10 +115,10 @@ pub struct TaskSuggestionTemplate {
 #[derive(Template)]
 #[template(path = "task_subdivide.md.jinja", ext = "plain")]
 pub struct TaskSubdivideTemplate {
-    parent_task: Task,
+    parent_task: TaskFingerprint,
     number_of_subtasks: u8,
     project: Option<Project>,
-    tasks: Option<Vec<Task>>,
+    tasks: Option<Vec<TaskFingerprint>>,
     user_query: Option<String>,
 }
 
@@ -131,7 +142,8 @@ pub struct ProjectSuggestionTemplate {
 pub struct ProjectRelatedChatTemplate {
     project: Project,
     members: Vec<Member>,
-    tasks: Vec<Task>,
+    tasks: Vec<TaskFingerprint>,
+    assets: Vec<Asset>,
 }
 
 #[derive(Template)]
@@ -139,7 +151,7 @@ pub struct ProjectRelatedChatTemplate {
 pub struct OrganizationRelatedChatTemplate {
     organization: Organization,
     projects: Vec<Project>,
-    tasks: Vec<Task>,
+    tasks: Vec<TaskFingerprint>,
     members: Vec<Member>,
     teams: Vec<Team>,
 }
@@ -183,7 +195,7 @@ impl CognitionOperationsV2 for SDKEngine {
         };
 
         let input_message = TaskSuggestionTemplate {
-            tasks,
+            tasks: self.with_checklist_progress(tasks).await?,
             project,
             initial_state: Some(input),
             user_query: None,
@@ -206,8 +218,9 @@ impl CognitionOperationsV2 for SDKEngine {
         let system_message = PlexoSystemTemplate {}.render().unwrap();
 
         let parent_task = self.get_task(input.task_id).await?;
+        let parent_task = self.with_checklist_progress(vec![parent_task]).await?.remove(0);
 
-        let (project, project_id) = match parent_task.project_id {
+        let (project, project_id) = match parent_task.task.project_id {
             Some(project_id) => (Some(self.get_project(project_id).await?), Some(project_id)),
             None => (None, None),
         };
@@ -226,7 +239,7 @@ impl CognitionOperationsV2 for SDKEngine {
                     )
                     .await?;
 
-                Some(tasks)
+                Some(self.with_checklist_progress(tasks).await?)
             }
             (Some(true), None) => {
                 let tasks = self
@@ -240,7 +253,7 @@ impl CognitionOperationsV2 for SDKEngine {
                     )
                     .await?;
 
-                Some(tasks)
+                Some(self.with_checklist_progress(tasks).await?)
             }
             (None, _) | (Some(false), _) => None,
         };
@@ -346,10 +359,29 @@ impl CognitionOperationsV2 for SDKEngine {
                     )
                     .await?;
 
+                let assets = match input.include_assets.unwrap_or(false) {
+                    true => sqlx::query(
+                        r#"
+                        SELECT * FROM assets
+                        WHERE project_id = $1 AND extracted_text IS NOT NULL
+                        ORDER BY updated_at DESC
+                        LIMIT 10
+                        "#,
+                    )
+                    .bind(chat.resource_id)
+                    .fetch_all(self.db_pool.as_ref())
+                    .await?
+                    .iter()
+                    .map(asset_from_row)
+                    .collect(),
+                    false => vec![],
+                };
+
                 ProjectRelatedChatTemplate {
                     project,
-                    tasks,
+                    tasks: self.with_checklist_progress(tasks).await?,
                     members,
+                    assets,
                 }
                 .render()
                 .unwrap()
@@ -404,7 +436,7 @@ impl CognitionOperationsV2 for SDKEngine {
                 OrganizationRelatedChatTemplate {
                     organization,
                     projects,
-                    tasks,
+                    tasks: self.with_checklist_progress(tasks).await?,
                     members,
                     teams,
                 }
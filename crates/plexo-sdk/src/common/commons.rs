@@ -14,11 +14,8 @@ pub enum SortOrder {
     Desc,
 }
 
-#[derive(Debug, Clone, Display, EnumString)]
-pub enum SQLComparison<T>
-where
-    T: std::default::Default,
-{
+#[derive(Debug, Clone, Display, PartialEq)]
+pub enum SQLComparison<T> {
     Equal(T),
     NotEqual(T),
     GreaterThan(T),
@@ -27,10 +24,11 @@ where
     LessThanOrEqual(T),
     Like(T),
     NotLike(T),
+    ILike(T),
     In(T),
     NotIn(T),
-    IsNull(T),
-    IsNotNull(T),
+    IsNull,
+    IsNotNull,
 }
 
 #[derive(Default, Builder, Object, InputObject, Serialize, Clone)]
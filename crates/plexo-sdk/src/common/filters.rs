@@ -0,0 +1,1045 @@
+use async_graphql::InputObject;
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use crate::{
+    common::{
+        commons::{SQLComparison, SortOrder},
+        pagination::PageCursor,
+    },
+    errors::sdk::SDKError,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SQLValue {
+    Uuid(Uuid),
+    Text(String),
+    DateTime(DateTime<Utc>),
+    Int(i32),
+    Float(f64),
+    UuidList(Vec<Uuid>),
+    TextList(Vec<String>),
+    DateTimeList(Vec<DateTime<Utc>>),
+}
+
+impl From<Uuid> for SQLValue {
+    fn from(value: Uuid) -> Self {
+        SQLValue::Uuid(value)
+    }
+}
+
+impl From<String> for SQLValue {
+    fn from(value: String) -> Self {
+        SQLValue::Text(value)
+    }
+}
+
+impl From<DateTime<Utc>> for SQLValue {
+    fn from(value: DateTime<Utc>) -> Self {
+        SQLValue::DateTime(value)
+    }
+}
+
+impl From<i32> for SQLValue {
+    fn from(value: i32) -> Self {
+        SQLValue::Int(value)
+    }
+}
+
+impl From<f64> for SQLValue {
+    fn from(value: f64) -> Self {
+        SQLValue::Float(value)
+    }
+}
+
+impl From<Vec<Uuid>> for SQLValue {
+    fn from(value: Vec<Uuid>) -> Self {
+        SQLValue::UuidList(value)
+    }
+}
+
+impl From<Vec<String>> for SQLValue {
+    fn from(value: Vec<String>) -> Self {
+        SQLValue::TextList(value)
+    }
+}
+
+impl From<Vec<DateTime<Utc>>> for SQLValue {
+    fn from(value: Vec<DateTime<Utc>>) -> Self {
+        SQLValue::DateTimeList(value)
+    }
+}
+
+impl SQLValue {
+    fn push_bind(self, builder: &mut QueryBuilder<'_, Postgres>) {
+        match self {
+            SQLValue::Uuid(value) => builder.push_bind(value),
+            SQLValue::Text(value) => builder.push_bind(value),
+            SQLValue::DateTime(value) => builder.push_bind(value),
+            SQLValue::Int(value) => builder.push_bind(value),
+            SQLValue::Float(value) => builder.push_bind(value),
+            SQLValue::UuidList(value) => builder.push_bind(value),
+            SQLValue::TextList(value) => builder.push_bind(value),
+            SQLValue::DateTimeList(value) => builder.push_bind(value),
+        };
+    }
+}
+
+/// A filter condition tree. Column names are always static strings owned by the SDK,
+/// every value coming from the caller is sent to Postgres as a bind parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SQLCondition {
+    Compare(&'static str, SQLComparison<SQLValue>),
+    Related(SQLRelation, SQLRelationMatch, Vec<Uuid>),
+    /// Static SQL parts interleaved with bound values, `parts` must have one more element than `values`.
+    Expression(&'static [&'static str], Vec<SQLValue>),
+    /// A condition placed between two static SQL parts, e.g. inside a subquery.
+    Nested(&'static str, Box<SQLCondition>, &'static str),
+    And(Vec<SQLCondition>),
+    Or(Vec<SQLCondition>),
+}
+
+/// A many-to-many join table linking the filtered resource `id` to another resource.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SQLRelation {
+    pub table: &'static str,
+    pub source_column: &'static str,
+    pub target_column: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SQLRelationMatch {
+    Any,
+    All,
+    None,
+}
+
+impl SQLCondition {
+    pub fn equal(column: &'static str, value: impl Into<SQLValue>) -> Self {
+        SQLCondition::Compare(column, SQLComparison::Equal(value.into()))
+    }
+
+    pub fn any_of(column: &'static str, values: Vec<Uuid>) -> Self {
+        SQLCondition::Compare(column, SQLComparison::In(values.into()))
+    }
+
+    pub fn compare_all(
+        column: &'static str,
+        comparisons: impl IntoIterator<Item = Option<SQLComparison<SQLValue>>>,
+    ) -> Self {
+        SQLCondition::And(
+            comparisons
+                .into_iter()
+                .flatten()
+                .map(|comparison| SQLCondition::Compare(column, comparison))
+                .collect(),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            SQLCondition::And(conditions) | SQLCondition::Or(conditions) => conditions.iter().all(|c| c.is_empty()),
+            _ => false,
+        }
+    }
+
+    pub fn push_to(self, builder: &mut QueryBuilder<'_, Postgres>) {
+        match self {
+            SQLCondition::Compare(column, comparison) => Self::push_comparison(builder, column, comparison),
+            SQLCondition::Related(relation, relation_match, ids) => {
+                Self::push_relation(builder, relation, relation_match, ids)
+            }
+            SQLCondition::Expression(parts, values) => {
+                let mut values = values.into_iter();
+
+                for (index, part) in parts.iter().enumerate() {
+                    if index > 0 {
+                        if let Some(value) = values.next() {
+                            value.push_bind(builder);
+                        }
+                    }
+
+                    builder.push(part);
+                }
+            }
+            SQLCondition::Nested(prefix, condition, suffix) => {
+                builder.push(prefix);
+                condition.push_to(builder);
+                builder.push(suffix);
+            }
+            SQLCondition::And(conditions) => Self::push_group(builder, conditions, " AND "),
+            SQLCondition::Or(conditions) => Self::push_group(builder, conditions, " OR "),
+        }
+    }
+
+    fn push_comparison(builder: &mut QueryBuilder<'_, Postgres>, column: &str, comparison: SQLComparison<SQLValue>) {
+        builder.push(column);
+
+        let (operator, value) = match comparison {
+            SQLComparison::Equal(value) => (" = ", value),
+            SQLComparison::NotEqual(value) => (" <> ", value),
+            SQLComparison::GreaterThan(value) => (" > ", value),
+            SQLComparison::GreaterThanOrEqual(value) => (" >= ", value),
+            SQLComparison::LessThan(value) => (" < ", value),
+            SQLComparison::LessThanOrEqual(value) => (" <= ", value),
+            SQLComparison::Like(value) => (" LIKE ", value),
+            SQLComparison::NotLike(value) => (" NOT LIKE ", value),
+            SQLComparison::ILike(value) => (" ILIKE ", value),
+            SQLComparison::In(value) => {
+                builder.push(" = ANY(");
+                value.push_bind(builder);
+                builder.push(")");
+                return;
+            }
+            SQLComparison::NotIn(value) => {
+                builder.push(" <> ALL(");
+                value.push_bind(builder);
+                builder.push(")");
+                return;
+            }
+            SQLComparison::IsNull => {
+                builder.push(" IS NULL");
+                return;
+            }
+            SQLComparison::IsNotNull => {
+                builder.push(" IS NOT NULL");
+                return;
+            }
+        };
+
+        builder.push(operator);
+        value.push_bind(builder);
+    }
+
+    fn push_relation(
+        builder: &mut QueryBuilder<'_, Postgres>,
+        relation: SQLRelation,
+        relation_match: SQLRelationMatch,
+        ids: Vec<Uuid>,
+    ) {
+        let SQLRelation {
+            table,
+            source_column,
+            target_column,
+        } = relation;
+
+        match relation_match {
+            SQLRelationMatch::None => builder.push("id NOT IN ("),
+            _ => builder.push("id IN ("),
+        };
+
+        builder
+            .push(format!(
+                "SELECT {source_column} FROM {table} WHERE {target_column} = ANY("
+            ))
+            .push_bind(ids.clone())
+            .push(")");
+
+        if relation_match == SQLRelationMatch::All {
+            builder
+                .push(format!(
+                    " GROUP BY {source_column} HAVING COUNT(DISTINCT {target_column}) = cardinality("
+                ))
+                .push_bind(ids)
+                .push(")");
+        }
+
+        builder.push(")");
+    }
+
+    fn push_group(builder: &mut QueryBuilder<'_, Postgres>, conditions: Vec<SQLCondition>, separator: &str) {
+        let mut conditions: Vec<SQLCondition> = conditions.into_iter().filter(|c| !c.is_empty()).collect();
+
+        if conditions.len() == 1 {
+            conditions.remove(0).push_to(builder);
+            return;
+        }
+
+        builder.push("(");
+
+        for (index, condition) in conditions.into_iter().enumerate() {
+            if index > 0 {
+                builder.push(separator);
+            }
+
+            condition.push_to(builder);
+        }
+
+        builder.push(")");
+    }
+}
+
+pub trait SQLFilter {
+    fn compile_sql(&self) -> SQLCondition;
+}
+
+/// A per-field operator object (`eq`, `in`, `isNull`, ...) used inside resource filters.
+pub trait SQLFieldFilter {
+    fn compile_sql(&self, column: &'static str) -> SQLCondition;
+}
+
+fn null_comparison(is_null: bool) -> SQLComparison<SQLValue> {
+    if is_null {
+        SQLComparison::IsNull
+    } else {
+        SQLComparison::IsNotNull
+    }
+}
+
+fn escape_like(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+#[derive(Default, Clone, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct UuidComparison {
+    #[builder(setter(strip_option), default)]
+    pub eq: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub neq: Option<Uuid>,
+    #[graphql(name = "in")]
+    #[oai(rename = "in")]
+    #[builder(setter(strip_option), default)]
+    pub is_in: Option<Vec<Uuid>>,
+    #[builder(setter(strip_option), default)]
+    pub not_in: Option<Vec<Uuid>>,
+    #[builder(setter(strip_option), default)]
+    pub is_null: Option<bool>,
+}
+
+impl From<Uuid> for UuidComparison {
+    fn from(value: Uuid) -> Self {
+        Self {
+            eq: Some(value),
+            ..Default::default()
+        }
+    }
+}
+
+impl SQLFieldFilter for UuidComparison {
+    fn compile_sql(&self, column: &'static str) -> SQLCondition {
+        SQLCondition::compare_all(
+            column,
+            [
+                self.eq.map(|v| SQLComparison::Equal(v.into())),
+                self.neq.map(|v| SQLComparison::NotEqual(v.into())),
+                self.is_in.clone().map(|v| SQLComparison::In(v.into())),
+                self.not_in.clone().map(|v| SQLComparison::NotIn(v.into())),
+                self.is_null.map(null_comparison),
+            ],
+        )
+    }
+}
+
+#[derive(Default, Clone, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct StringComparison {
+    #[builder(setter(into, strip_option), default)]
+    pub eq: Option<String>,
+    #[builder(setter(into, strip_option), default)]
+    pub neq: Option<String>,
+    #[builder(setter(into, strip_option), default)]
+    pub gt: Option<String>,
+    #[builder(setter(into, strip_option), default)]
+    pub gte: Option<String>,
+    #[builder(setter(into, strip_option), default)]
+    pub lt: Option<String>,
+    #[builder(setter(into, strip_option), default)]
+    pub lte: Option<String>,
+    #[graphql(name = "in")]
+    #[oai(rename = "in")]
+    #[builder(setter(strip_option), default)]
+    pub is_in: Option<Vec<String>>,
+    #[builder(setter(strip_option), default)]
+    pub not_in: Option<Vec<String>>,
+    /// Case-insensitive substring match, `%` and `_` are matched literally.
+    #[builder(setter(into, strip_option), default)]
+    pub contains: Option<String>,
+    /// Case-insensitive `ILIKE` pattern, `%` and `_` act as wildcards.
+    #[builder(setter(into, strip_option), default)]
+    pub ilike: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub is_null: Option<bool>,
+}
+
+impl From<String> for StringComparison {
+    fn from(value: String) -> Self {
+        Self {
+            eq: Some(value),
+            ..Default::default()
+        }
+    }
+}
+
+impl SQLFieldFilter for StringComparison {
+    fn compile_sql(&self, column: &'static str) -> SQLCondition {
+        SQLCondition::compare_all(
+            column,
+            [
+                self.eq.clone().map(|v| SQLComparison::Equal(v.into())),
+                self.neq.clone().map(|v| SQLComparison::NotEqual(v.into())),
+                self.gt.clone().map(|v| SQLComparison::GreaterThan(v.into())),
+                self.gte.clone().map(|v| SQLComparison::GreaterThanOrEqual(v.into())),
+                self.lt.clone().map(|v| SQLComparison::LessThan(v.into())),
+                self.lte.clone().map(|v| SQLComparison::LessThanOrEqual(v.into())),
+                self.is_in.clone().map(|v| SQLComparison::In(v.into())),
+                self.not_in.clone().map(|v| SQLComparison::NotIn(v.into())),
+                self.contains
+                    .as_ref()
+                    .map(|v| SQLComparison::ILike(format!("%{}%", escape_like(v)).into())),
+                self.ilike.clone().map(|v| SQLComparison::ILike(v.into())),
+                self.is_null.map(null_comparison),
+            ],
+        )
+    }
+}
+
+#[derive(Default, Clone, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct DateTimeComparison {
+    #[builder(setter(strip_option), default)]
+    pub eq: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub neq: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub gt: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub gte: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub lt: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub lte: Option<DateTime<Utc>>,
+    #[graphql(name = "in")]
+    #[oai(rename = "in")]
+    #[builder(setter(strip_option), default)]
+    pub is_in: Option<Vec<DateTime<Utc>>>,
+    #[builder(setter(strip_option), default)]
+    pub not_in: Option<Vec<DateTime<Utc>>>,
+    #[builder(setter(strip_option), default)]
+    pub is_null: Option<bool>,
+}
+
+impl From<DateTime<Utc>> for DateTimeComparison {
+    fn from(value: DateTime<Utc>) -> Self {
+        Self {
+            eq: Some(value),
+            ..Default::default()
+        }
+    }
+}
+
+impl SQLFieldFilter for DateTimeComparison {
+    fn compile_sql(&self, column: &'static str) -> SQLCondition {
+        SQLCondition::compare_all(
+            column,
+            [
+                self.eq.map(|v| SQLComparison::Equal(v.into())),
+                self.neq.map(|v| SQLComparison::NotEqual(v.into())),
+                self.gt.map(|v| SQLComparison::GreaterThan(v.into())),
+                self.gte.map(|v| SQLComparison::GreaterThanOrEqual(v.into())),
+                self.lt.map(|v| SQLComparison::LessThan(v.into())),
+                self.lte.map(|v| SQLComparison::LessThanOrEqual(v.into())),
+                self.is_in.clone().map(|v| SQLComparison::In(v.into())),
+                self.not_in.clone().map(|v| SQLComparison::NotIn(v.into())),
+                self.is_null.map(null_comparison),
+            ],
+        )
+    }
+}
+
+#[derive(Default, Clone, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct NumberComparison {
+    #[builder(setter(strip_option), default)]
+    pub eq: Option<f64>,
+    #[builder(setter(strip_option), default)]
+    pub neq: Option<f64>,
+    #[builder(setter(strip_option), default)]
+    pub gt: Option<f64>,
+    #[builder(setter(strip_option), default)]
+    pub gte: Option<f64>,
+    #[builder(setter(strip_option), default)]
+    pub lt: Option<f64>,
+    #[builder(setter(strip_option), default)]
+    pub lte: Option<f64>,
+    #[builder(setter(strip_option), default)]
+    pub is_null: Option<bool>,
+}
+
+impl From<f64> for NumberComparison {
+    fn from(value: f64) -> Self {
+        Self {
+            eq: Some(value),
+            ..Default::default()
+        }
+    }
+}
+
+impl SQLFieldFilter for NumberComparison {
+    fn compile_sql(&self, column: &'static str) -> SQLCondition {
+        SQLCondition::compare_all(
+            column,
+            [
+                self.eq.map(|v| SQLComparison::Equal(v.into())),
+                self.neq.map(|v| SQLComparison::NotEqual(v.into())),
+                self.gt.map(|v| SQLComparison::GreaterThan(v.into())),
+                self.gte.map(|v| SQLComparison::GreaterThanOrEqual(v.into())),
+                self.lt.map(|v| SQLComparison::LessThan(v.into())),
+                self.lte.map(|v| SQLComparison::LessThanOrEqual(v.into())),
+                self.is_null.map(null_comparison),
+            ],
+        )
+    }
+}
+
+/// Matches resources linked through a join table to any, all or none of the given ids.
+#[derive(Default, Clone, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct RelationComparison {
+    #[builder(setter(strip_option), default)]
+    pub any: Option<Vec<Uuid>>,
+    #[builder(setter(strip_option), default)]
+    pub all: Option<Vec<Uuid>>,
+    #[builder(setter(strip_option), default)]
+    pub none: Option<Vec<Uuid>>,
+}
+
+impl RelationComparison {
+    pub fn compile_sql(&self, relation: SQLRelation) -> SQLCondition {
+        let mut conditions = Vec::new();
+
+        if let Some(any) = &self.any {
+            conditions.push(SQLCondition::Related(relation, SQLRelationMatch::Any, any.clone()));
+        }
+
+        if let Some(all) = self.all.as_ref().filter(|all| !all.is_empty()) {
+            let mut all = all.clone();
+            all.sort();
+            all.dedup();
+
+            conditions.push(SQLCondition::Related(relation, SQLRelationMatch::All, all));
+        }
+
+        if let Some(none) = self.none.as_ref().filter(|none| !none.is_empty()) {
+            conditions.push(SQLCondition::Related(relation, SQLRelationMatch::None, none.clone()));
+        }
+
+        SQLCondition::And(conditions)
+    }
+}
+
+/// Declares an `eq`/`neq`/`in`/`notIn`/`isNull` operator object for a resource enum
+/// stored as its string representation.
+macro_rules! enum_comparison {
+    ($name:ident, $enum:ty) => {
+        #[derive(Default, Clone, derive_builder::Builder, poem_openapi::Object, async_graphql::InputObject)]
+        #[builder(pattern = "owned")]
+        pub struct $name {
+            #[builder(setter(strip_option), default)]
+            pub eq: Option<$enum>,
+            #[builder(setter(strip_option), default)]
+            pub neq: Option<$enum>,
+            #[graphql(name = "in")]
+            #[oai(rename = "in")]
+            #[builder(setter(strip_option), default)]
+            pub is_in: Option<Vec<$enum>>,
+            #[builder(setter(strip_option), default)]
+            pub not_in: Option<Vec<$enum>>,
+            #[builder(setter(strip_option), default)]
+            pub is_null: Option<bool>,
+        }
+
+        impl From<$enum> for $name {
+            fn from(value: $enum) -> Self {
+                Self {
+                    eq: Some(value),
+                    ..Default::default()
+                }
+            }
+        }
+
+        impl $crate::common::filters::SQLFieldFilter for $name {
+            fn compile_sql(&self, column: &'static str) -> $crate::common::filters::SQLCondition {
+                use $crate::common::commons::SQLComparison;
+
+                let to_strings = |values: &Vec<$enum>| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+
+                $crate::common::filters::SQLCondition::compare_all(
+                    column,
+                    [
+                        self.eq.map(|v| SQLComparison::Equal(v.to_string().into())),
+                        self.neq.map(|v| SQLComparison::NotEqual(v.to_string().into())),
+                        self.is_in
+                            .as_ref()
+                            .map(|v| SQLComparison::In(to_strings(v).into())),
+                        self.not_in
+                            .as_ref()
+                            .map(|v| SQLComparison::NotIn(to_strings(v).into())),
+                        self.is_null.map(|is_null| {
+                            if is_null {
+                                SQLComparison::IsNull
+                            } else {
+                                SQLComparison::IsNotNull
+                            }
+                        }),
+                    ],
+                )
+            }
+        }
+    };
+}
+
+pub(crate) use enum_comparison;
+
+/// Builds a `SELECT * FROM <table>` query from a resource filter, validating the
+/// requested sort column against the resource allowlist.
+#[derive(Clone)]
+pub struct SQLSelect {
+    table: &'static str,
+    sortable_columns: &'static [&'static str],
+    computed_column: Option<(&'static str, SQLCondition)>,
+
+    condition: Option<SQLCondition>,
+    sort_by: Option<String>,
+    sort_order: Option<SortOrder>,
+    keyset: Option<SQLKeyset>,
+    limit: Option<i32>,
+    offset: Option<i32>,
+}
+
+/// Keyset pagination over the `(sort column, id)` order, rows strictly after `cursor` are
+/// returned, walking the order backwards when `reversed` is set.
+#[derive(Clone)]
+struct SQLKeyset {
+    cursor: Option<PageCursor>,
+    reversed: bool,
+}
+
+const DEFAULT_KEYSET_COLUMN: &str = "created_at";
+
+impl SQLSelect {
+    pub fn new(table: &'static str, sortable_columns: &'static [&'static str]) -> Self {
+        Self {
+            table,
+            sortable_columns,
+            computed_column: None,
+            condition: None,
+            sort_by: None,
+            sort_order: None,
+            keyset: None,
+            limit: None,
+            offset: None,
+        }
+    }
+
+    pub fn filter(mut self, filter: Option<&impl SQLFilter>) -> Self {
+        self.condition = filter.map(|f| f.compile_sql());
+        self
+    }
+
+    pub fn sort(mut self, sort_by: Option<String>, sort_order: Option<SortOrder>) -> Self {
+        self.sort_by = sort_by;
+        self.sort_order = sort_order;
+        self
+    }
+
+    pub fn keyset(mut self, cursor: Option<PageCursor>, reversed: bool) -> Self {
+        self.keyset = Some(SQLKeyset { cursor, reversed });
+        self
+    }
+
+    /// Selects `expression AS name` next to the table columns, making `name` a valid sort column.
+    pub fn computed_column(mut self, name: &'static str, expression: SQLCondition) -> Self {
+        self.computed_column = Some((name, expression));
+        self
+    }
+
+    pub fn paginate(mut self, limit: Option<i32>, offset: Option<i32>) -> Self {
+        self.limit = limit;
+        self.offset = offset;
+        self
+    }
+
+    /// The validated sort column, keyset pagination falls back to `created_at` when none is given.
+    pub fn sort_column(&self) -> Result<Option<&'static str>, SDKError> {
+        let sort_by = match (&self.sort_by, &self.keyset) {
+            (Some(sort_by), _) => sort_by.as_str(),
+            (None, Some(_)) => DEFAULT_KEYSET_COLUMN,
+            (None, None) => return Ok(None),
+        };
+
+        let computed_column = self.computed_column.as_ref().map(|(name, _)| *name);
+
+        match self
+            .sortable_columns
+            .iter()
+            .copied()
+            .chain(computed_column)
+            .find(|c| *c == sort_by)
+        {
+            Some(column) => Ok(Some(column)),
+            None => Err(SDKError::InvalidSortColumn(sort_by.to_string())),
+        }
+    }
+
+    pub fn build_count(&self) -> QueryBuilder<'static, Postgres> {
+        let mut builder = QueryBuilder::new(format!("SELECT COUNT(*) FROM {}", self.table));
+
+        if let Some(condition) = self.condition.clone().filter(|c| !c.is_empty()) {
+            builder.push(" WHERE ");
+            condition.push_to(&mut builder);
+        }
+
+        builder
+    }
+
+    pub fn build(self) -> Result<QueryBuilder<'static, Postgres>, SDKError> {
+        let sort_column = self.sort_column()?;
+
+        let mut builder = QueryBuilder::new("SELECT * FROM ");
+
+        match self.computed_column {
+            Some((name, expression)) => {
+                builder.push(format!("(SELECT {}.*, ", self.table));
+                expression.push_to(&mut builder);
+                builder.push(format!(" AS {name} FROM {0}) AS {0}", self.table));
+            }
+            None => {
+                builder.push(self.table);
+            }
+        }
+
+        let mut has_condition = false;
+
+        if let Some(condition) = self.condition.filter(|c| !c.is_empty()) {
+            builder.push(" WHERE ");
+            condition.push_to(&mut builder);
+            has_condition = true;
+        }
+
+        let ascending = matches!(self.sort_order.unwrap_or_default(), SortOrder::Asc);
+
+        match (sort_column, self.keyset) {
+            (Some(column), Some(keyset)) => {
+                let ascending = ascending != keyset.reversed;
+                let nulls_last = !keyset.reversed;
+
+                if let Some(cursor) = keyset.cursor {
+                    builder.push(if has_condition { " AND " } else { " WHERE " });
+                    Self::push_keyset(&mut builder, column, cursor, ascending, nulls_last);
+                }
+
+                let direction = if ascending { " ASC" } else { " DESC" };
+
+                builder
+                    .push(" ORDER BY ")
+                    .push(column)
+                    .push(direction)
+                    .push(if nulls_last { " NULLS LAST" } else { " NULLS FIRST" })
+                    .push(", id")
+                    .push(direction);
+            }
+            (Some(column), None) => {
+                builder
+                    .push(" ORDER BY ")
+                    .push(column)
+                    .push(if ascending { " ASC" } else { " DESC" });
+            }
+            (None, _) => {}
+        }
+
+        if let Some(limit) = self.limit {
+            builder.push(" LIMIT ").push_bind(i64::from(limit));
+        }
+
+        if let Some(offset) = self.offset {
+            builder.push(" OFFSET ").push_bind(i64::from(offset));
+        }
+
+        Ok(builder)
+    }
+
+    fn push_keyset(
+        builder: &mut QueryBuilder<'_, Postgres>,
+        column: &str,
+        cursor: PageCursor,
+        ascending: bool,
+        nulls_last: bool,
+    ) {
+        let operator = if ascending { " > " } else { " < " };
+
+        builder.push("(");
+
+        match cursor.value {
+            Some(value) => {
+                builder.push(column).push(operator);
+                value.clone().push_bind(builder);
+
+                builder.push(" OR (").push(column).push(" = ");
+                value.push_bind(builder);
+
+                builder.push(" AND id").push(operator).push_bind(cursor.id).push(")");
+
+                if nulls_last {
+                    builder.push(" OR ").push(column).push(" IS NULL");
+                }
+            }
+            None => {
+                builder
+                    .push("(")
+                    .push(column)
+                    .push(" IS NULL AND id")
+                    .push(operator)
+                    .push_bind(cursor.id)
+                    .push(")");
+
+                if !nulls_last {
+                    builder.push(" OR ").push(column).push(" IS NOT NULL");
+                }
+            }
+        }
+
+        builder.push(")");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::custom_fields::operations::{
+        CustomFieldComparisonBuilder, CustomFieldOptionsComparisonBuilder,
+    };
+    use crate::resources::tasks::operations::{GetTasksWhere, GetTasksWhereBuilder, TaskStatusComparisonBuilder};
+    use crate::resources::tasks::task::TaskStatus;
+
+    const MALICIOUS: &str = "x' OR '1'='1'; DROP TABLE tasks; --";
+
+    fn tasks_select(filter: &GetTasksWhere) -> SQLSelect {
+        SQLSelect::new("tasks", &["created_at", "title"]).filter(Some(filter))
+    }
+
+    #[test]
+    fn filter_values_are_bound_as_parameters() {
+        let filter = GetTasksWhereBuilder::default()
+            .title(MALICIOUS.to_string())
+            .description("UNION SELECT password_hash FROM members".to_string())
+            .build()
+            .unwrap();
+
+        let builder = tasks_select(&filter).build().unwrap();
+
+        assert_eq!(
+            builder.sql(),
+            "SELECT * FROM tasks WHERE (title = $1 AND description = $2)"
+        );
+        assert!(!builder.sql().contains("DROP"));
+        assert!(!builder.sql().contains("UNION"));
+    }
+
+    #[test]
+    fn filter_values_are_kept_verbatim_as_data() {
+        let filter = GetTasksWhereBuilder::default()
+            .title(MALICIOUS.to_string())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            filter.compile_sql(),
+            SQLCondition::And(vec![SQLCondition::And(vec![SQLCondition::Compare(
+                "title",
+                SQLComparison::Equal(SQLValue::Text(MALICIOUS.to_string()))
+            )])])
+        );
+    }
+
+    #[test]
+    fn nested_filters_are_parameterized() {
+        let filter = GetTasksWhereBuilder::default()
+            ._or(vec![
+                GetTasksWhereBuilder::default()
+                    .title(MALICIOUS.to_string())
+                    .build()
+                    .unwrap(),
+                GetTasksWhereBuilder::default().ids(vec![Uuid::nil()]).build().unwrap(),
+            ])
+            .build()
+            .unwrap();
+
+        let builder = tasks_select(&filter).build().unwrap();
+
+        assert_eq!(builder.sql(), "SELECT * FROM tasks WHERE (title = $1 OR id = ANY($2))");
+    }
+
+    #[test]
+    fn empty_filters_are_omitted() {
+        let filter = GetTasksWhereBuilder::default()._and(vec![]).build().unwrap();
+
+        let builder = tasks_select(&filter).paginate(Some(10), Some(20)).build().unwrap();
+
+        assert_eq!(builder.sql(), "SELECT * FROM tasks LIMIT $1 OFFSET $2");
+    }
+
+    #[test]
+    fn sort_by_is_validated_against_allowlist() {
+        let filter = GetTasksWhere::default();
+
+        let builder = tasks_select(&filter)
+            .sort(Some("created_at".to_string()), Some(SortOrder::Desc))
+            .build()
+            .unwrap();
+
+        assert_eq!(builder.sql(), "SELECT * FROM tasks ORDER BY created_at DESC");
+
+        let result = tasks_select(&filter)
+            .sort(Some("created_at; DROP TABLE tasks".to_string()), None)
+            .build();
+
+        assert!(matches!(result, Err(SDKError::InvalidSortColumn(_))));
+    }
+
+    #[test]
+    fn field_operators_are_compiled() {
+        let filter = GetTasksWhereBuilder::default()
+            .due_date(DateTimeComparisonBuilder::default().lt(Utc::now()).build().unwrap())
+            .status(
+                TaskStatusComparisonBuilder::default()
+                    .is_in(vec![TaskStatus::ToDo, TaskStatus::InProgress])
+                    .build()
+                    .unwrap(),
+            )
+            .lead_id(UuidComparisonBuilder::default().is_null(true).build().unwrap())
+            .title(
+                StringComparisonBuilder::default()
+                    .contains("login")
+                    .neq("logout")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        let builder = tasks_select(&filter).build().unwrap();
+
+        assert_eq!(
+            builder.sql(),
+            "SELECT * FROM tasks WHERE (status = ANY($1) AND (title <> $2 AND title ILIKE $3) AND due_date < $4 AND lead_id IS NULL)"
+        );
+    }
+
+    #[test]
+    fn contains_escapes_like_wildcards() {
+        let comparison = StringComparisonBuilder::default()
+            .contains("100%_done")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            comparison.compile_sql("title"),
+            SQLCondition::And(vec![SQLCondition::Compare(
+                "title",
+                SQLComparison::ILike(SQLValue::Text("%100\\%\\_done%".to_string()))
+            )])
+        );
+    }
+
+    #[test]
+    fn relational_filters_are_parameterized() {
+        let filter = GetTasksWhereBuilder::default()
+            .label_ids(
+                RelationComparisonBuilder::default()
+                    .all(vec![Uuid::nil(), Uuid::nil()])
+                    .none(vec![Uuid::max()])
+                    .build()
+                    .unwrap(),
+            )
+            .ancestor_id(Uuid::nil())
+            .is_overdue(true)
+            .build()
+            .unwrap();
+
+        let builder = tasks_select(&filter).build().unwrap();
+
+        assert_eq!(
+            builder.sql(),
+            "SELECT * FROM tasks WHERE (\
+             (id IN (SELECT task_id FROM labels_by_tasks WHERE label_id = ANY($1) \
+             GROUP BY task_id HAVING COUNT(DISTINCT label_id) = cardinality($2)) \
+             AND id NOT IN (SELECT task_id FROM labels_by_tasks WHERE label_id = ANY($3))) \
+             AND id IN (WITH RECURSIVE descendants AS (SELECT id FROM tasks WHERE parent_id = $4 \
+             UNION SELECT tasks.id FROM tasks JOIN descendants ON tasks.parent_id = descendants.id) \
+             SELECT id FROM descendants) \
+             AND (due_date < now() AND COALESCE(status, '') <> ALL($5)))"
+        );
+    }
+
+    #[test]
+    fn keyset_pages_follow_sort_column_and_id() {
+        let filter = GetTasksWhere::default();
+        let cursor = PageCursor {
+            value: Some(SQLValue::Text("Done".to_string())),
+            id: Uuid::nil(),
+        };
+
+        let builder = tasks_select(&filter)
+            .sort(Some("title".to_string()), Some(SortOrder::Desc))
+            .keyset(Some(cursor), false)
+            .paginate(Some(11), None)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            builder.sql(),
+            "SELECT * FROM tasks WHERE (title < $1 OR (title = $2 AND id < $3) OR title IS NULL) \
+             ORDER BY title DESC NULLS LAST, id DESC LIMIT $4"
+        );
+
+        let builder = tasks_select(&filter).keyset(None, true).build().unwrap();
+
+        assert_eq!(
+            builder.sql(),
+            "SELECT * FROM tasks ORDER BY created_at DESC NULLS FIRST, id DESC"
+        );
+    }
+
+    #[test]
+    fn custom_field_filters_and_sorts_are_parameterized() {
+        let filter = GetTasksWhereBuilder::default()
+            .custom_fields(vec![CustomFieldComparisonBuilder::default()
+                .field_id(Uuid::nil())
+                .number(NumberComparisonBuilder::default().gte(3.0).build().unwrap())
+                .options(
+                    CustomFieldOptionsComparisonBuilder::default()
+                        .none(vec![MALICIOUS.to_string()])
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap()])
+            .build()
+            .unwrap();
+
+        let builder = tasks_select(&filter)
+            .computed_column(
+                "custom_field_sort",
+                SQLCondition::Expression(
+                    &["(SELECT number_value WHERE field_id = ", ")"],
+                    vec![Uuid::nil().into()],
+                ),
+            )
+            .sort(Some("custom_field_sort".to_string()), None)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            builder.sql(),
+            "SELECT * FROM (SELECT tasks.*, (SELECT number_value WHERE field_id = $1) AS custom_field_sort FROM tasks) AS tasks \
+             WHERE (id IN (SELECT task_id FROM custom_field_values WHERE (field_id = $2 AND number_value >= $3)) \
+             AND id NOT IN (SELECT task_id FROM custom_field_values WHERE (field_id = $4 AND options_value && $5))) \
+             ORDER BY custom_field_sort ASC"
+        );
+    }
+}
@@ -1 +1,5 @@
 pub mod commons;
+pub mod filters;
+pub mod pagination;
+pub mod ranks;
+pub mod trash;
@@ -0,0 +1,137 @@
+use async_graphql::connection::{CursorType, OpaqueCursor};
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgRow, Pool, Postgres, Row};
+use uuid::Uuid;
+
+use crate::{
+    common::{
+        commons::SortOrder,
+        filters::{SQLFilter, SQLSelect, SQLValue},
+    },
+    errors::sdk::SDKError,
+};
+
+const DEFAULT_PAGE_SIZE: usize = 100;
+/// Larger `first`/`last` values are capped to this.
+pub const MAX_PAGE_SIZE: usize = 500;
+
+/// Position of a row in a sorted list: the value of its sort column and its id.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageCursor {
+    pub value: Option<SQLValue>,
+    pub id: Uuid,
+}
+
+impl PageCursor {
+    fn from_row(row: &PgRow, column: &str) -> Result<Self, SDKError> {
+        let value = if let Ok(value) = row.try_get::<Option<Uuid>, _>(column) {
+            value.map(SQLValue::from)
+        } else if let Ok(value) = row.try_get::<Option<DateTime<Utc>>, _>(column) {
+            value.map(SQLValue::from)
+        } else if let Ok(value) = row.try_get::<Option<String>, _>(column) {
+            value.map(SQLValue::from)
+        } else if let Ok(value) = row.try_get::<Option<i32>, _>(column) {
+            value.map(SQLValue::from)
+        } else if let Ok(value) = row.try_get::<Option<f64>, _>(column) {
+            value.map(SQLValue::from)
+        } else {
+            return Err(SDKError::InvalidSortColumn(column.to_string()));
+        };
+
+        Ok(Self {
+            value,
+            id: row.try_get("id")?,
+        })
+    }
+}
+
+impl CursorType for PageCursor {
+    type Error = <OpaqueCursor<PageCursor> as CursorType>::Error;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        OpaqueCursor::<PageCursor>::decode_cursor(s).map(|cursor| cursor.0)
+    }
+
+    fn encode_cursor(&self) -> String {
+        OpaqueCursor(self.clone()).encode_cursor()
+    }
+}
+
+#[derive(Default, Builder)]
+#[builder(pattern = "owned")]
+pub struct PageInput<F> {
+    #[builder(setter(strip_option), default)]
+    pub filter: Option<F>,
+
+    #[builder(setter(strip_option), default)]
+    pub sort_by: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub sort_order: Option<SortOrder>,
+
+    #[builder(setter(strip_option), default)]
+    pub first: Option<usize>,
+    #[builder(setter(strip_option), default)]
+    pub after: Option<PageCursor>,
+    #[builder(setter(strip_option), default)]
+    pub last: Option<usize>,
+    #[builder(setter(strip_option), default)]
+    pub before: Option<PageCursor>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub edges: Vec<(PageCursor, T)>,
+    pub has_previous_page: bool,
+    pub has_next_page: bool,
+    pub total_count: i64,
+}
+
+impl SQLSelect {
+    /// Fetches one page of rows using `(sort column, id)` keyset pagination. `last`/`before`
+    /// take precedence over `first`/`after`, and `first` defaults to 100 rows. Pages hold at most
+    /// [`MAX_PAGE_SIZE`] rows.
+    pub async fn fetch_page<F: SQLFilter, T>(
+        self,
+        pool: &Pool<Postgres>,
+        input: PageInput<F>,
+        map: impl Fn(&PgRow) -> T,
+    ) -> Result<Page<T>, SDKError> {
+        let select = self.filter(input.filter.as_ref()).sort(input.sort_by, input.sort_order);
+
+        let total_count: i64 = select.build_count().build_query_scalar().fetch_one(pool).await?;
+
+        let (cursor, size, reversed) = match input.last {
+            Some(last) => (input.before, last, true),
+            None => (input.after, input.first.unwrap_or(DEFAULT_PAGE_SIZE), false),
+        };
+
+        let size = size.min(MAX_PAGE_SIZE);
+        let has_cursor = cursor.is_some();
+        let limit = i32::try_from(size.saturating_add(1)).unwrap_or(i32::MAX);
+
+        let select = select.keyset(cursor, reversed).paginate(Some(limit), None);
+        let column = select.sort_column()?.unwrap_or("id");
+
+        let rows = select.build()?.build().fetch_all(pool).await?;
+        let has_more = rows.len() > size;
+
+        let mut edges = rows
+            .iter()
+            .take(size)
+            .map(|row| Ok((PageCursor::from_row(row, column)?, map(row))))
+            .collect::<Result<Vec<_>, SDKError>>()?;
+
+        if reversed {
+            edges.reverse();
+        }
+
+        Ok(Page {
+            edges,
+            has_previous_page: if reversed { has_more } else { has_cursor },
+            has_next_page: if reversed { has_cursor } else { has_more },
+            total_count,
+        })
+    }
+}
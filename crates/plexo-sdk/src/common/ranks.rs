@@ -0,0 +1,138 @@
+//! Fractional indexing over base62 strings. Ranks compare byte by byte (the `rank` column uses
+//! the "C" collation), never end with the zero digit, and there is always a rank between two
+//! different ones, so moving an item only rewrites that item's rank.
+
+const DIGITS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const BASE: usize = DIGITS.len();
+
+/// Ranks longer than this are shortened by the periodic rebalancing.
+pub const MAX_RANK_LENGTH: i32 = 16;
+
+/// A rank strictly between `lower` and `upper`, where `None` stands for the start and the end.
+/// Returns `None` if `lower` is not below `upper` or either is not a valid rank.
+pub fn rank_between(lower: Option<&str>, upper: Option<&str>) -> Option<String> {
+    let lower = lower.unwrap_or("");
+
+    if !is_valid_rank(lower, true) || upper.is_some_and(|upper| !is_valid_rank(upper, false)) {
+        return None;
+    }
+
+    if upper.is_some_and(|upper| lower >= upper) {
+        return None;
+    }
+
+    Some(midpoint(lower.as_bytes(), upper.map(str::as_bytes)))
+}
+
+/// `count` evenly spaced ranks, all of the same length before trailing zeros are dropped.
+pub fn spread_ranks(count: usize) -> Vec<String> {
+    let mut length = 1;
+    let mut space = BASE as u128;
+
+    while space <= 2 * (count as u128 + 1) {
+        length += 1;
+        space *= BASE as u128;
+    }
+
+    (1..=count as u128)
+        .map(|position| {
+            let mut value = position * space / (count as u128 + 1);
+            let mut digits = vec![DIGITS[0]; length];
+
+            for digit in digits.iter_mut().rev() {
+                *digit = DIGITS[(value % BASE as u128) as usize];
+                value /= BASE as u128;
+            }
+
+            while digits.last() == Some(&DIGITS[0]) {
+                digits.pop();
+            }
+
+            String::from_utf8(digits).unwrap()
+        })
+        .collect()
+}
+
+fn is_valid_rank(rank: &str, allow_empty: bool) -> bool {
+    (allow_empty || !rank.is_empty())
+        && !rank.ends_with(DIGITS[0] as char)
+        && rank.bytes().all(|byte| DIGITS.contains(&byte))
+}
+
+fn digit(byte: u8) -> usize {
+    DIGITS.iter().position(|digit| *digit == byte).unwrap()
+}
+
+fn midpoint(lower: &[u8], upper: Option<&[u8]>) -> String {
+    if let Some(upper) = upper {
+        let shared = upper
+            .iter()
+            .enumerate()
+            .take_while(|(index, byte)| lower.get(*index).copied().unwrap_or(DIGITS[0]) == **byte)
+            .count();
+
+        if shared > 0 {
+            let rest = midpoint(lower.get(shared..).unwrap_or_default(), Some(&upper[shared..]));
+
+            return String::from_utf8(upper[..shared].to_vec()).unwrap() + &rest;
+        }
+    }
+
+    let lower_digit = lower.first().map_or(0, |byte| digit(*byte));
+    let upper_digit = upper.and_then(|upper| upper.first()).map_or(BASE, |byte| digit(*byte));
+
+    if upper_digit - lower_digit > 1 {
+        return (DIGITS[(lower_digit + upper_digit).div_ceil(2)] as char).to_string();
+    }
+
+    match upper {
+        Some(upper) if upper.len() > 1 => (upper[0] as char).to_string(),
+        _ => (DIGITS[lower_digit] as char).to_string() + &midpoint(lower.get(1..).unwrap_or_default(), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_between_neighbours() {
+        assert_eq!(rank_between(None, None).as_deref(), Some("V"));
+        assert_eq!(rank_between(Some("V"), None).as_deref(), Some("l"));
+        assert_eq!(rank_between(None, Some("1")).as_deref(), Some("0V"));
+        assert_eq!(rank_between(Some("a"), Some("b")).as_deref(), Some("aV"));
+        assert_eq!(rank_between(Some("az"), Some("b")).as_deref(), Some("azV"));
+
+        assert_eq!(rank_between(Some("b"), Some("a")), None);
+        assert_eq!(rank_between(Some("a"), Some("a")), None);
+        assert_eq!(rank_between(Some("a0"), None), None);
+    }
+
+    #[test]
+    fn repeated_inserts_stay_ordered() {
+        let (mut lower, upper) = (String::from("a"), String::from("b"));
+
+        for _ in 0..200 {
+            let rank = rank_between(Some(&lower), Some(&upper)).unwrap();
+            assert!(lower < rank && rank < upper);
+            lower = rank;
+        }
+
+        let mut upper = String::from("b");
+
+        for _ in 0..200 {
+            let rank = rank_between(None, Some(&upper)).unwrap();
+            assert!(rank < upper);
+            upper = rank;
+        }
+    }
+
+    #[test]
+    fn spreads_ranks_in_order() {
+        let ranks = spread_ranks(1000);
+
+        assert_eq!(ranks.len(), 1000);
+        assert!(ranks.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ranks.iter().all(|rank| rank_between(Some(rank), None).is_some()));
+    }
+}
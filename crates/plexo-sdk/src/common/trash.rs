@@ -0,0 +1,225 @@
+//! Archiving and the trash. Archived and deleted rows stay in their tables with `archived_at` or
+//! `deleted_at` set, and reads leave them out unless the filter opts in. Subtasks and the tasks of
+//! a project share the timestamp of the item they were archived or deleted with, which is how a
+//! restore finds them again.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    common::{
+        filters::{SQLCondition, SQLFilter},
+        pagination::PageInput,
+    },
+    errors::sdk::SDKError,
+};
+
+#[async_trait]
+pub trait TrashOperations {
+    /// Permanently removes the tasks, projects and teams that have been in the trash for longer
+    /// than `retention`.
+    async fn purge_trash(&self, retention: Duration) -> Result<TrashPurge, SDKError>;
+}
+
+/// How many rows of each kind a purge removed.
+#[derive(Debug, Clone, Default)]
+pub struct TrashPurge {
+    pub tasks: u64,
+    pub projects: u64,
+    pub teams: u64,
+}
+
+impl TrashPurge {
+    pub fn is_empty(&self) -> bool {
+        self.tasks == 0 && self.projects == 0 && self.teams == 0
+    }
+}
+
+#[async_trait]
+impl TrashOperations for SDKEngine {
+    async fn purge_trash(&self, retention: Duration) -> Result<TrashPurge, SDKError> {
+        let deleted_before = Utc::now() - retention;
+
+        let mut tx = self.db_pool.begin().await?;
+
+        // Subtasks restored on their own outlive their parent.
+        sqlx::query(
+            r#"
+            UPDATE tasks
+            SET parent_id = NULL
+            WHERE parent_id IN (SELECT id FROM tasks WHERE deleted_at < $1)
+                AND (deleted_at IS NULL OR deleted_at >= $1)
+            "#,
+        )
+        .bind(deleted_before)
+        .execute(&mut *tx)
+        .await?;
+
+        let tasks = sqlx::query("DELETE FROM tasks WHERE deleted_at < $1")
+            .bind(deleted_before)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        // Tasks restored on their own outlive the project they were deleted with.
+        sqlx::query(
+            r#"
+            UPDATE tasks
+            SET project_id = NULL
+            WHERE project_id IN (SELECT id FROM projects WHERE deleted_at < $1)
+            "#,
+        )
+        .bind(deleted_before)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM teams_by_projects
+            WHERE project_id IN (SELECT id FROM projects WHERE deleted_at < $1)
+                OR team_id IN (SELECT id FROM teams WHERE deleted_at < $1)
+            "#,
+        )
+        .bind(deleted_before)
+        .execute(&mut *tx)
+        .await?;
+
+        let projects = sqlx::query("DELETE FROM projects WHERE deleted_at < $1")
+            .bind(deleted_before)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        let teams = sqlx::query("DELETE FROM teams WHERE deleted_at < $1")
+            .bind(deleted_before)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        tx.commit().await?;
+
+        Ok(TrashPurge { tasks, projects, teams })
+    }
+}
+
+/// Resource filters with the opt-in flags for archived and deleted rows. The flags are only read
+/// on the top level filter, see [`VisibleFilter`].
+pub(crate) trait TrashFilter: SQLFilter {
+    fn include_archived(&self) -> bool;
+    fn include_deleted(&self) -> bool;
+}
+
+/// Wraps a resource filter to leave out archived and deleted rows, unless the filter asks for
+/// them.
+pub(crate) struct VisibleFilter<F>(pub Option<F>);
+
+impl<F: TrashFilter> SQLFilter for VisibleFilter<F> {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
+
+        if let Some(filter) = &self.0 {
+            conditions.push(filter.compile_sql());
+        }
+
+        if !self.0.as_ref().is_some_and(|filter| filter.include_archived()) {
+            conditions.push(SQLCondition::Expression(&["archived_at IS NULL"], vec![]));
+        }
+
+        if !self.0.as_ref().is_some_and(|filter| filter.include_deleted()) {
+            conditions.push(SQLCondition::Expression(&["deleted_at IS NULL"], vec![]));
+        }
+
+        SQLCondition::And(conditions)
+    }
+}
+
+impl<F> VisibleFilter<F> {
+    pub(crate) fn page(input: PageInput<F>) -> PageInput<VisibleFilter<F>> {
+        PageInput {
+            filter: Some(VisibleFilter(input.filter)),
+            sort_by: input.sort_by,
+            sort_order: input.sort_order,
+            first: input.first,
+            after: input.after,
+            last: input.last,
+            before: input.before,
+        }
+    }
+}
+
+/// The two ways of hiding a row, named after their timestamp column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TrashColumn {
+    Archived,
+    Deleted,
+}
+
+impl TrashColumn {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            TrashColumn::Archived => "archived_at",
+            TrashColumn::Deleted => "deleted_at",
+        }
+    }
+}
+
+/// Stamps the given tasks and all their subtasks, leaving the ones already hidden with their own
+/// timestamp. Returns the timestamp of the transaction.
+pub(crate) async fn hide_task_trees(
+    conn: &mut PgConnection,
+    task_ids: &[Uuid],
+    column: TrashColumn,
+) -> Result<DateTime<Utc>, SDKError> {
+    let hidden_at: DateTime<Utc> = sqlx::query_scalar("SELECT now()").fetch_one(&mut *conn).await?;
+
+    sqlx::query(&format!(
+        r#"
+        WITH RECURSIVE tree AS (
+            SELECT id FROM tasks WHERE id = ANY($1)
+            UNION
+            SELECT tasks.id FROM tasks JOIN tree ON tasks.parent_id = tree.id
+        )
+        UPDATE tasks
+        SET {column} = $2
+        WHERE id IN (SELECT id FROM tree) AND {column} IS NULL
+        "#,
+        column = column.name(),
+    ))
+    .bind(task_ids)
+    .bind(hidden_at)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(hidden_at)
+}
+
+/// Clears the timestamp of the given tasks and of their subtasks hidden at the same time.
+pub(crate) async fn restore_task_trees(
+    conn: &mut PgConnection,
+    task_ids: &[Uuid],
+    column: TrashColumn,
+    hidden_at: DateTime<Utc>,
+) -> Result<(), SDKError> {
+    sqlx::query(&format!(
+        r#"
+        WITH RECURSIVE tree AS (
+            SELECT id FROM tasks WHERE id = ANY($1)
+            UNION
+            SELECT tasks.id FROM tasks JOIN tree ON tasks.parent_id = tree.id
+        )
+        UPDATE tasks
+        SET {column} = NULL
+        WHERE id IN (SELECT id FROM tree) AND {column} = $2
+        "#,
+        column = column.name(),
+    ))
+    .bind(task_ids)
+    .bind(hidden_at)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
@@ -8,6 +8,48 @@ pub enum SDKError {
     ResourceNotFound,
     #[error("Invalid Resource Type")]
     InvalidResourceType,
+    #[error("Invalid sort column: {0}")]
+    InvalidSortColumn(String),
+    #[error("Task dependency would create a cycle")]
+    TaskDependencyCycle,
+    #[error("Task is blocked by {0} unfinished task(s)")]
+    TaskBlocked(i64),
+    #[error("Invalid workflow status: {0}")]
+    InvalidWorkflowStatus(String),
+    #[error("Invalid workflow transition from {0} to {1}")]
+    InvalidWorkflowTransition(String, String),
+    #[error("Workflow does not allow moving from {0} to {1}")]
+    WorkflowTransitionNotAllowed(String, String),
+    #[error("Invalid recurrence rule: {0}")]
+    InvalidRecurrenceRule(String),
+    #[error("Invalid task position: {0}")]
+    InvalidTaskPosition(String),
+    #[error("Invalid cycle: {0}")]
+    InvalidCycle(String),
+    #[error("Invalid custom field: {0}")]
+    InvalidCustomField(String),
+    #[error("Invalid custom field value: {0}")]
+    InvalidCustomFieldValue(String),
+    #[error("Invalid task template: {0}")]
+    InvalidTaskTemplate(String),
+    #[error("Invalid comment: {0}")]
+    InvalidComment(String),
+    #[error("Invalid project copy: {0}")]
+    InvalidProjectCopy(String),
+    #[error("Invalid project prefix: {0}")]
+    InvalidProjectPrefix(String),
+    #[error("Invalid task move: {0}")]
+    InvalidTaskMove(String),
+    #[error("Invalid asset key: {0}")]
+    InvalidAssetKey(String),
+    #[error("Asset is larger than {0} bytes")]
+    AssetTooLarge(u64),
+    #[error("Asset storage error: {0}")]
+    AssetStorageError(String),
+    #[error("Invalid label: {0}")]
+    InvalidLabel(String),
+    #[error("No timer is running")]
+    TimerNotRunning,
     #[error("Error at stream LLM Stream")]
     LLMStreamError,
     #[error("SQLX Error")]
@@ -16,6 +58,10 @@ pub enum SDKError {
     MigrateError(#[from] sqlx::migrate::MigrateError),
     #[error("Serde JSON Error")]
     SerdeJSONError(#[from] serde_json::Error),
+    #[error("IO Error")]
+    IOError(#[from] std::io::Error),
+    #[error("Reqwest Error")]
+    ReqwestError(#[from] reqwest::Error),
     #[error("OpenAI Error")]
     OpenAIError(#[from] async_openai::error::OpenAIError),
 }
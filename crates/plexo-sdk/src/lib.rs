@@ -4,3 +4,5 @@ pub mod common;
 pub mod errors;
 pub mod organization;
 pub mod resources;
+pub mod search;
+pub mod storage;
@@ -20,6 +20,36 @@ pub struct Asset {
     pub owner_id: Uuid,
 
     pub project_id: Option<Uuid>,
+
+    /// Hex SHA-256 of the uploaded content, also its storage key. `None` for assets without
+    /// content.
+    pub content_hash: Option<String>,
+    pub size: Option<i64>,
+    pub mime_type: Option<String>,
+
+    /// Number of the current version, see `AssetVersion`.
+    pub version: i32,
+    /// Plain text of Text and PDF contents, indexed for search.
+    pub extracted_text: Option<String>,
+}
+
+/// An uploaded revision of an asset's content. Restoring one uploads it again as the newest
+/// version, so the history is never rewritten.
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKAssetVersion")]
+pub struct AssetVersion {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+
+    pub asset_id: Uuid,
+    pub version: i32,
+    pub author_id: Option<Uuid>,
+
+    pub name: String,
+    pub kind: AssetKind,
+    pub content_hash: String,
+    pub size: i64,
+    pub mime_type: String,
 }
 
 #[derive(
@@ -1,3 +1,29 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use derive_builder::Builder;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, PgConnection, Row};
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    errors::sdk::SDKError,
+    storage::{
+        content_hash,
+        extract::extract_text,
+        sniff::{asset_kind, sniff_mime_type},
+        ByteStream,
+    },
+};
+
+use super::{
+    asset::{Asset, AssetKind, AssetVersion},
+    operations::{delete_unreferenced_content, lock_asset_content, AssetCrudOperations},
+};
+
+#[allow(dead_code)]
 trait AssetImplementation {
     fn alt_text(&self) -> String;
     fn icon_base64(&self) -> String;
@@ -5,3 +31,417 @@ trait AssetImplementation {
 
     fn render(&self) -> String;
 }
+
+#[derive(Clone, Default, Builder, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UploadAssetInput {
+    pub name: String,
+    pub owner_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+}
+
+#[async_trait]
+pub trait AssetsExtensionOperations {
+    /// Stores `content` and creates its asset, with the kind sniffed from the content. Content
+    /// already stored for another asset isn't stored again.
+    async fn upload_asset(&self, input: UploadAssetInput, content: Bytes) -> Result<Asset, SDKError>;
+    /// Stores `content` as the newest version of an asset.
+    async fn upload_asset_version(&self, id: Uuid, author_id: Uuid, content: Bytes) -> Result<Asset, SDKError>;
+    /// Makes the content of an older version current again, as a new version.
+    async fn restore_asset_version(&self, id: Uuid, version: i32, author_id: Uuid) -> Result<Asset, SDKError>;
+    async fn get_asset_versions(&self, asset_id: Uuid) -> Result<Vec<AssetVersion>, SDKError>;
+    /// Streams the content of a version of an asset, the current one by default.
+    async fn download_asset(&self, id: Uuid, version: Option<i32>) -> Result<(AssetVersion, ByteStream), SDKError>;
+}
+
+struct UploadedContent {
+    content: Bytes,
+    content_hash: String,
+    size: i64,
+    mime_type: &'static str,
+    kind: AssetKind,
+    extracted_text: Option<String>,
+}
+
+async fn read_asset_content(engine: &SDKEngine, content: Bytes) -> Result<UploadedContent, SDKError> {
+    if content.len() as u64 > engine.config.asset_max_size {
+        return Err(SDKError::AssetTooLarge(engine.config.asset_max_size));
+    }
+
+    let content_hash = content_hash(&content);
+    let mime_type = sniff_mime_type(&content);
+    let kind = asset_kind(mime_type);
+
+    // Parsing a PDF is CPU bound, and a malformed one only costs its text.
+    let size = content.len() as i64;
+    let extracted_text = tokio::task::spawn_blocking({
+        let content = content.clone();
+        move || extract_text(kind, &content)
+    })
+    .await
+    .unwrap_or_default();
+
+    Ok(UploadedContent {
+        content,
+        content_hash,
+        size,
+        mime_type,
+        kind,
+        extracted_text,
+    })
+}
+
+/// Stores the content under the lock of its hash, which is held until the version referencing it
+/// commits, so `delete_asset` can't remove it in between. Content a version already references is
+/// in storage, so it isn't written again. Returns whether the content was written.
+async fn store_asset_content(
+    engine: &SDKEngine,
+    conn: &mut PgConnection,
+    stored: &UploadedContent,
+) -> Result<bool, SDKError> {
+    lock_asset_content(&mut *conn, &stored.content_hash).await?;
+
+    let stored_before: bool =
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM asset_versions WHERE content_hash = $1)")
+            .bind(&stored.content_hash)
+            .fetch_one(conn)
+            .await?;
+
+    if stored_before {
+        return Ok(false);
+    }
+
+    engine
+        .asset_storage
+        .put(&stored.content_hash, stored.content.clone())
+        .await?;
+
+    Ok(true)
+}
+
+/// Deletes the content an upload wrote when the upload doesn't commit, so no object is left that
+/// no version references. The upload's own error is what the caller gets.
+async fn discard_failed_upload<T>(
+    engine: &SDKEngine,
+    written: bool,
+    content_hash: &str,
+    upload: Result<T, SDKError>,
+) -> Result<T, SDKError> {
+    if upload.is_err() && written {
+        let _ = delete_unreferenced_content(engine, content_hash).await;
+    }
+
+    upload
+}
+
+/// Records the current content of an asset as its version.
+async fn insert_asset_version(conn: &mut PgConnection, asset_id: Uuid, author_id: Uuid) -> Result<(), SDKError> {
+    sqlx::query(
+        r#"
+        INSERT INTO asset_versions (asset_id, version, author_id, name, kind, content_hash, size, mime_type, extracted_text)
+        SELECT id, version, $2, name, kind, content_hash, size, mime_type, extracted_text
+        FROM assets
+        WHERE id = $1
+        "#,
+    )
+    .bind(asset_id)
+    .bind(author_id)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Replaces the content of a locked asset, numbering it after the latest version.
+async fn set_asset_content(
+    conn: &mut PgConnection,
+    asset_id: Uuid,
+    content_hash: &str,
+    size: i64,
+    mime_type: &str,
+    kind: Option<&str>,
+    extracted_text: Option<&str>,
+) -> Result<(), SDKError> {
+    sqlx::query(
+        r#"
+        UPDATE assets
+        SET
+            content_hash = $2,
+            size = $3,
+            mime_type = $4,
+            kind = $5,
+            extracted_text = $6,
+            version = (SELECT COALESCE(MAX(version), 0) + 1 FROM asset_versions WHERE asset_id = $1)
+        WHERE id = $1
+        "#,
+    )
+    .bind(asset_id)
+    .bind(content_hash)
+    .bind(size)
+    .bind(mime_type)
+    .bind(kind)
+    .bind(extracted_text)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+fn asset_version_from_row(version_info: &PgRow) -> AssetVersion {
+    AssetVersion {
+        id: version_info.get("id"),
+        created_at: version_info.get("created_at"),
+        asset_id: version_info.get("asset_id"),
+        version: version_info.get("version"),
+        author_id: version_info.get("author_id"),
+        name: version_info.get("name"),
+        kind: version_info
+            .get::<'_, Option<String>, _>("kind")
+            .and_then(|kind| AssetKind::from_str(&kind).ok())
+            .unwrap_or_default(),
+        content_hash: version_info.get("content_hash"),
+        size: version_info.get("size"),
+        mime_type: version_info.get("mime_type"),
+    }
+}
+
+#[async_trait]
+impl AssetsExtensionOperations for SDKEngine {
+    async fn upload_asset(&self, input: UploadAssetInput, content: Bytes) -> Result<Asset, SDKError> {
+        let stored = &read_asset_content(self, content).await?;
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let written = store_asset_content(self, &mut tx, stored).await?;
+
+        let upload = async move {
+            let asset_id = sqlx::query_scalar::<_, Uuid>(
+                r#"
+                INSERT INTO assets (name, owner_id, kind, project_id, content_hash, size, mime_type, extracted_text)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                RETURNING id
+                "#,
+            )
+            .bind(input.name)
+            .bind(input.owner_id)
+            .bind(stored.kind.to_string())
+            .bind(input.project_id)
+            .bind(&stored.content_hash)
+            .bind(stored.size)
+            .bind(stored.mime_type)
+            .bind(&stored.extracted_text)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            insert_asset_version(&mut tx, asset_id, input.owner_id).await?;
+
+            tx.commit().await?;
+
+            Ok(asset_id)
+        };
+
+        let asset_id = discard_failed_upload(self, written, &stored.content_hash, upload.await).await?;
+
+        self.get_asset(asset_id).await
+    }
+
+    async fn upload_asset_version(&self, id: Uuid, author_id: Uuid, content: Bytes) -> Result<Asset, SDKError> {
+        let stored = &read_asset_content(self, content).await?;
+
+        let mut tx = self.db_pool.begin().await?;
+
+        sqlx::query("SELECT id FROM assets WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        let written = store_asset_content(self, &mut tx, stored).await?;
+
+        let upload = async move {
+            set_asset_content(
+                &mut tx,
+                id,
+                &stored.content_hash,
+                stored.size,
+                stored.mime_type,
+                Some(&stored.kind.to_string()),
+                stored.extracted_text.as_deref(),
+            )
+            .await?;
+
+            insert_asset_version(&mut tx, id, author_id).await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        };
+
+        discard_failed_upload(self, written, &stored.content_hash, upload.await).await?;
+
+        self.get_asset(id).await
+    }
+
+    async fn restore_asset_version(&self, id: Uuid, version: i32, author_id: Uuid) -> Result<Asset, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        sqlx::query("SELECT id FROM assets WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        let version_info = sqlx::query("SELECT * FROM asset_versions WHERE asset_id = $1 AND version = $2")
+            .bind(id)
+            .bind(version)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        set_asset_content(
+            &mut tx,
+            id,
+            version_info.get("content_hash"),
+            version_info.get("size"),
+            version_info.get("mime_type"),
+            version_info.get("kind"),
+            version_info.get("extracted_text"),
+        )
+        .await?;
+
+        insert_asset_version(&mut tx, id, author_id).await?;
+
+        tx.commit().await?;
+
+        self.get_asset(id).await
+    }
+
+    async fn get_asset_versions(&self, asset_id: Uuid) -> Result<Vec<AssetVersion>, SDKError> {
+        let versions_info = sqlx::query(
+            r#"
+            SELECT * FROM asset_versions
+            WHERE asset_id = $1
+            ORDER BY version DESC
+            "#,
+        )
+        .bind(asset_id)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(versions_info.iter().map(asset_version_from_row).collect())
+    }
+
+    async fn download_asset(&self, id: Uuid, version: Option<i32>) -> Result<(AssetVersion, ByteStream), SDKError> {
+        let version_info = sqlx::query(
+            r#"
+            SELECT asset_versions.* FROM asset_versions
+            JOIN assets ON assets.id = asset_versions.asset_id
+            WHERE asset_versions.asset_id = $1 AND asset_versions.version = COALESCE($2, assets.version)
+            "#,
+        )
+        .bind(id)
+        .bind(version)
+        .fetch_optional(self.db_pool.as_ref())
+        .await?
+        .ok_or(SDKError::ResourceNotFound)?;
+
+        let version = asset_version_from_row(&version_info);
+        let content = self.asset_storage.get(&version.content_hash).await?;
+
+        Ok((version, content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+    use tokio_stream::StreamExt;
+
+    use super::*;
+    use crate::backend::testing::{test_engine, test_member};
+
+    fn upload_input(owner_id: Uuid, project_id: Option<Uuid>) -> UploadAssetInput {
+        UploadAssetInput {
+            name: "notes.txt".to_string(),
+            owner_id,
+            project_id,
+        }
+    }
+
+    #[sqlx::test]
+    async fn failed_uploads_leave_no_content_behind(pool: PgPool) {
+        let engine = test_engine(pool);
+        let content = Bytes::from_static(b"never committed");
+
+        // Nobody owns it, so the asset row is rejected after the content was stored.
+        let upload = engine
+            .upload_asset(upload_input(Uuid::new_v4(), None), content.clone())
+            .await;
+        assert!(upload.is_err());
+
+        let stored = engine.asset_storage.get(&content_hash(&content)).await;
+        assert!(matches!(stored, Err(SDKError::ResourceNotFound)));
+    }
+
+    #[sqlx::test]
+    async fn shared_content_goes_away_with_its_last_asset(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+        let content = Bytes::from_static(b"shared content");
+        let key = content_hash(&content);
+
+        let first = engine
+            .upload_asset(upload_input(owner_id, None), content.clone())
+            .await
+            .unwrap();
+        let second = engine
+            .upload_asset(upload_input(owner_id, None), content.clone())
+            .await
+            .unwrap();
+
+        engine.delete_asset(first.id).await.unwrap();
+        assert!(engine.asset_storage.get(&key).await.is_ok());
+
+        engine.delete_asset(second.id).await.unwrap();
+        assert!(matches!(
+            engine.asset_storage.get(&key).await,
+            Err(SDKError::ResourceNotFound)
+        ));
+    }
+
+    #[sqlx::test]
+    async fn restoring_a_version_makes_it_current_as_a_new_version(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+        let first = Bytes::from_static(b"first draft");
+
+        let asset = engine
+            .upload_asset(upload_input(owner_id, None), first.clone())
+            .await
+            .unwrap();
+        engine
+            .upload_asset_version(asset.id, owner_id, Bytes::from_static(b"second draft"))
+            .await
+            .unwrap();
+
+        let restored = engine.restore_asset_version(asset.id, 1, owner_id).await.unwrap();
+        assert_eq!(restored.version, 3);
+        assert_eq!(restored.content_hash, Some(content_hash(&first)));
+
+        let versions = engine.get_asset_versions(asset.id).await.unwrap();
+        assert_eq!(
+            versions.iter().map(|version| version.version).collect::<Vec<_>>(),
+            vec![3, 2, 1]
+        );
+
+        let (current, content) = engine.download_asset(asset.id, None).await.unwrap();
+        let content: Vec<Bytes> = content.collect::<Result<_, _>>().await.unwrap();
+        assert_eq!(current.version, 3);
+        assert_eq!(content.concat(), first.to_vec());
+
+        let err = engine.restore_asset_version(asset.id, 9, owner_id).await.unwrap_err();
+        assert!(matches!(err, SDKError::ResourceNotFound));
+    }
+}
@@ -24,7 +24,9 @@ impl Loader<Uuid> for AssetLoader {
     async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
         let assets = sqlx::query!(
             r#"
-            SELECT * FROM assets WHERE id  = ANY($1)
+            SELECT id, created_at, updated_at, name, owner_id, kind, project_id, content_hash, size,
+                mime_type, version, extracted_text
+            FROM assets WHERE id  = ANY($1)
             "#,
             &keys
         )
@@ -46,6 +48,11 @@ impl Loader<Uuid> for AssetLoader {
                         owner_id: asset.owner_id,
                         kind: AssetKind::from_str(&asset.kind.clone().unwrap_or_default()).unwrap_or_default(),
                         project_id: asset.project_id,
+                        content_hash: asset.content_hash.clone(),
+                        size: asset.size,
+                        mime_type: asset.mime_type.clone(),
+                        version: asset.version,
+                        extracted_text: asset.extracted_text.clone(),
                     },
                 )
             })
@@ -5,19 +5,26 @@ use async_trait::async_trait;
 use derive_builder::Builder;
 use poem_openapi::Object;
 use serde::Serialize;
-use sqlx::Row;
+use sqlx::{postgres::PgRow, PgConnection, Row};
 use uuid::Uuid;
 
 use crate::backend::engine::SDKEngine;
 use crate::common::commons::SortOrder;
+use crate::common::filters::{
+    enum_comparison, SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect, StringComparison, UuidComparison,
+};
+use crate::common::pagination::{Page, PageInput};
 use crate::errors::sdk::SDKError;
 use crate::resources::assets::asset::{Asset, AssetKind};
 
+const ASSETS_SORTABLE_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "name", "kind"];
+
 #[async_trait]
 pub trait AssetCrudOperations {
     async fn create_asset(&self, input: CreateAssetInput) -> Result<Asset, SDKError>;
     async fn get_asset(&self, id: Uuid) -> Result<Asset, SDKError>;
     async fn get_assets(&self, input: GetAssetsInput) -> Result<Vec<Asset>, SDKError>;
+    async fn get_assets_page(&self, input: PageInput<GetAssetsWhere>) -> Result<Page<Asset>, SDKError>;
     async fn update_asset(&self, id: Uuid, input: UpdateAssetInput) -> Result<Asset, SDKError>;
     async fn delete_asset(&self, id: Uuid) -> Result<Asset, SDKError>;
 }
@@ -69,14 +76,14 @@ pub struct GetAssetsInput {
 pub struct GetAssetsWhere {
     #[builder(setter(strip_option), default)]
     pub ids: Option<Vec<Uuid>>,
-    #[builder(setter(strip_option), default)]
-    pub owner_id: Option<Uuid>,
-    #[builder(setter(strip_option), default)]
-    pub name: Option<String>,
-    #[builder(setter(strip_option), default)]
-    pub kind: Option<AssetKind>,
-    #[builder(setter(strip_option), default)]
-    pub project_id: Option<Uuid>,
+    #[builder(setter(into, strip_option), default)]
+    pub owner_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub name: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub kind: Option<AssetKindComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub project_id: Option<UuidComparison>,
 
     #[oai(skip)]
     #[builder(setter(strip_option), default)]
@@ -86,57 +93,41 @@ pub struct GetAssetsWhere {
     pub _or: Option<Vec<GetAssetsWhere>>,
 }
 
-impl GetAssetsWhere {
-    pub fn compile_sql(&self) -> String {
-        let mut where_clause = Vec::new();
+enum_comparison!(AssetKindComparison, AssetKind);
+
+impl SQLFilter for GetAssetsWhere {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
 
         if let Some(ids) = &self.ids {
-            where_clause.push(format!(
-                "id = ANY(array[{}]::uuid[])",
-                ids.iter()
-                    .map(|x| format!("'{}'", x))
-                    .collect::<Vec<String>>()
-                    .join(",")
-            ));
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
+        }
+
+        if let Some(owner_id) = &self.owner_id {
+            conditions.push(owner_id.compile_sql("owner_id"));
         }
 
         if let Some(name) = &self.name {
-            where_clause.push(format!("name = '{}'", name));
+            conditions.push(name.compile_sql("name"));
         }
 
         if let Some(kind) = &self.kind {
-            where_clause.push(format!("kind = '{}'", kind));
+            conditions.push(kind.compile_sql("kind"));
         }
 
         if let Some(project_id) = &self.project_id {
-            where_clause.push(format!("project_id = '{}'", project_id));
+            conditions.push(project_id.compile_sql("project_id"));
         }
 
-        if let Some(owner_id) = &self.owner_id {
-            where_clause.push(format!("owner_id = '{}'", owner_id));
-        }
-
-        if let Some(_and) = &self._and {
-            where_clause.push(format!(
-                "({})",
-                _and.iter()
-                    .map(|x| x.compile_sql())
-                    .collect::<Vec<String>>()
-                    .join(" AND ")
-            ));
+        if let Some(ands) = &self._and {
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
         }
 
-        if let Some(_or) = &self._or {
-            where_clause.push(format!(
-                "({})",
-                _or.iter()
-                    .map(|x| x.compile_sql())
-                    .collect::<Vec<String>>()
-                    .join(" OR ")
-            ));
+        if let Some(ors) = &self._or {
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
         }
 
-        where_clause.join(" AND ")
+        SQLCondition::And(conditions)
     }
 }
 
@@ -147,7 +138,8 @@ impl AssetCrudOperations for SDKEngine {
             r#"
             INSERT INTO assets (name, owner_id, kind, project_id)
             VALUES ($1, $2, $3, $4)
-            RETURNING *
+            RETURNING id, created_at, updated_at, name, owner_id, kind, project_id, content_hash,
+                size, mime_type, version, extracted_text
             "#,
             input.name,
             input.owner_id,
@@ -165,13 +157,20 @@ impl AssetCrudOperations for SDKEngine {
             owner_id: asset_final_info.owner_id,
             kind: AssetKind::from_str(&asset_final_info.kind.unwrap_or_default()).unwrap_or_default(),
             project_id: asset_final_info.project_id,
+            content_hash: asset_final_info.content_hash,
+            size: asset_final_info.size,
+            mime_type: asset_final_info.mime_type,
+            version: asset_final_info.version,
+            extracted_text: asset_final_info.extracted_text,
         })
     }
 
     async fn get_asset(&self, id: Uuid) -> Result<Asset, SDKError> {
         let asset_info = sqlx::query!(
             r#"
-            SELECT * FROM assets WHERE id = $1
+            SELECT id, created_at, updated_at, name, owner_id, kind, project_id, content_hash, size,
+                mime_type, version, extracted_text
+            FROM assets WHERE id = $1
             "#,
             id,
         )
@@ -186,53 +185,34 @@ impl AssetCrudOperations for SDKEngine {
             owner_id: asset_info.owner_id,
             kind: AssetKind::from_str(&asset_info.kind.unwrap_or_default()).unwrap_or_default(),
             project_id: asset_info.project_id,
+            content_hash: asset_info.content_hash,
+            size: asset_info.size,
+            mime_type: asset_info.mime_type,
+            version: asset_info.version,
+            extracted_text: asset_info.extracted_text,
         })
     }
 
     async fn get_assets(&self, input: GetAssetsInput) -> Result<Vec<Asset>, SDKError> {
-        let mut query = "SELECT * FROM assets ".to_string();
-
-        if let Some(filter) = input.filter {
-            query.push_str(&format!("WHERE {} ", filter.compile_sql()));
-        }
-
-        if let Some(sort_by) = input.sort_by {
-            query.push_str(&format!("ORDER BY {} ", sort_by));
-        }
-
-        if let Some(sort_order) = input.sort_order {
-            query.push_str(&format!("{} ", sort_order));
-        }
+        let mut query = SQLSelect::new("assets", ASSETS_SORTABLE_COLUMNS)
+            .filter(input.filter.as_ref())
+            .sort(input.sort_by, input.sort_order)
+            .paginate(input.limit, input.offset)
+            .build()?;
 
-        if let Some(limit) = input.limit {
-            query.push_str(&format!("LIMIT {} ", limit));
-        }
-
-        if let Some(offset) = input.offset {
-            query.push_str(&format!("OFFSET {} ", offset));
-        }
+        let assets_info = query.build().fetch_all(self.db_pool.as_ref()).await?;
 
-        let assets_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
-
-        let assets = assets_info
-            .into_iter()
-            .map(|asset_info| Asset {
-                id: asset_info.get("id"),
-                created_at: asset_info.get("created_at"),
-                updated_at: asset_info.get("updated_at"),
-                name: asset_info.get("name"),
-                owner_id: asset_info.get("owner_id"),
-                kind: asset_info
-                    .get::<'_, Option<String>, _>("kind")
-                    .and_then(|a| AssetKind::from_str(&a).ok())
-                    .unwrap_or_default(),
-                project_id: asset_info.get("project_id"),
-            })
-            .collect::<Vec<Asset>>();
+        let assets = assets_info.iter().map(asset_from_row).collect::<Vec<Asset>>();
 
         Ok(assets)
     }
 
+    async fn get_assets_page(&self, input: PageInput<GetAssetsWhere>) -> Result<Page<Asset>, SDKError> {
+        SQLSelect::new("assets", ASSETS_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), input, asset_from_row)
+            .await
+    }
+
     async fn update_asset(&self, id: Uuid, input: UpdateAssetInput) -> Result<Asset, SDKError> {
         let asset_final_info = sqlx::query!(
             r#"
@@ -242,7 +222,8 @@ impl AssetCrudOperations for SDKEngine {
                 kind = COALESCE($2, kind),
                 project_id = COALESCE($3, project_id)
             WHERE id = $4
-            RETURNING *
+            RETURNING id, created_at, updated_at, name, owner_id, kind, project_id, content_hash,
+                size, mime_type, version, extracted_text
             "#,
             input.name,
             input.kind.map(|k| k.to_string()),
@@ -260,20 +241,48 @@ impl AssetCrudOperations for SDKEngine {
             owner_id: asset_final_info.owner_id,
             kind: AssetKind::from_str(&asset_final_info.kind.unwrap_or_default()).unwrap_or_default(),
             project_id: asset_final_info.project_id,
+            content_hash: asset_final_info.content_hash,
+            size: asset_final_info.size,
+            mime_type: asset_final_info.mime_type,
+            version: asset_final_info.version,
+            extracted_text: asset_final_info.extracted_text,
         })
     }
 
     async fn delete_asset(&self, id: Uuid) -> Result<Asset, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        // Locked so a version uploaded meanwhile either lands before the content is listed or fails.
+        sqlx::query("SELECT id FROM assets WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(SDKError::ResourceNotFound)?;
+
+        let content_hashes =
+            sqlx::query_scalar::<_, String>("SELECT DISTINCT content_hash FROM asset_versions WHERE asset_id = $1")
+                .bind(id)
+                .fetch_all(&mut *tx)
+                .await?;
+
         let asset_info = sqlx::query!(
             r#"
             DELETE FROM assets WHERE id = $1
-            RETURNING *
+            RETURNING id, created_at, updated_at, name, owner_id, kind, project_id, content_hash,
+                size, mime_type, version, extracted_text
             "#,
             id,
         )
-        .fetch_one(self.db_pool.as_ref())
+        .fetch_one(&mut *tx)
         .await?;
 
+        tx.commit().await?;
+
+        // Identical uploads share their stored content, it goes away with the last version using it.
+        for content_hash in content_hashes {
+            delete_unreferenced_content(self, &content_hash).await?;
+        }
+
         Ok(Asset {
             id: asset_info.id,
             created_at: asset_info.created_at,
@@ -282,6 +291,64 @@ impl AssetCrudOperations for SDKEngine {
             owner_id: asset_info.owner_id,
             kind: AssetKind::from_str(&asset_info.kind.unwrap_or_default()).unwrap_or_default(),
             project_id: asset_info.project_id,
+            content_hash: asset_info.content_hash,
+            size: asset_info.size,
+            mime_type: asset_info.mime_type,
+            version: asset_info.version,
+            extracted_text: asset_info.extracted_text,
         })
     }
 }
+
+/// Serializes storing and deleting the content of a hash until the transaction ends.
+pub(crate) async fn lock_asset_content(conn: &mut PgConnection, content_hash: &str) -> Result<(), SDKError> {
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+        .bind(content_hash)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+/// Deletes stored content no asset version references anymore.
+pub(crate) async fn delete_unreferenced_content(engine: &SDKEngine, content_hash: &str) -> Result<(), SDKError> {
+    let mut tx = engine.db_pool.begin().await?;
+
+    lock_asset_content(&mut tx, content_hash).await?;
+
+    let shared = sqlx::query!(
+        r#"SELECT EXISTS (SELECT 1 FROM asset_versions WHERE content_hash = $1) AS "shared!""#,
+        content_hash,
+    )
+    .fetch_one(&mut *tx)
+    .await?
+    .shared;
+
+    if !shared {
+        engine.asset_storage.delete(content_hash).await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+pub(crate) fn asset_from_row(asset_info: &PgRow) -> Asset {
+    Asset {
+        id: asset_info.get("id"),
+        created_at: asset_info.get("created_at"),
+        updated_at: asset_info.get("updated_at"),
+        name: asset_info.get("name"),
+        owner_id: asset_info.get("owner_id"),
+        kind: asset_info
+            .get::<'_, Option<String>, _>("kind")
+            .and_then(|a| AssetKind::from_str(&a).ok())
+            .unwrap_or_default(),
+        project_id: asset_info.get("project_id"),
+        content_hash: asset_info.get("content_hash"),
+        size: asset_info.get("size"),
+        mime_type: asset_info.get("mime_type"),
+        version: asset_info.get("version"),
+        extracted_text: asset_info.get("extracted_text"),
+    }
+}
@@ -1,14 +1,22 @@
 use async_trait::async_trait;
+use uuid::Uuid;
 
 use crate::{
-    backend::loaders::SDKLoaders, errors::sdk::SDKError, resources::assets::asset::Asset,
-    resources::members::member::Member, resources::projects::project::Project,
+    backend::loaders::SDKLoaders,
+    errors::sdk::SDKError,
+    resources::assets::asset::{Asset, AssetVersion},
+    resources::assets::extensions::AssetsExtensionOperations,
+    resources::members::member::Member,
+    resources::projects::project::Project,
+    resources::tasks::task::Task,
 };
 
 #[async_trait]
 pub trait AssetRelations {
     async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
     async fn project(&self, loaders: &SDKLoaders) -> Result<Option<Project>, SDKError>;
+    async fn tasks(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError>;
+    async fn versions(&self, loaders: &SDKLoaders) -> Result<Vec<AssetVersion>, SDKError>;
 }
 
 #[async_trait]
@@ -24,8 +32,32 @@ impl AssetRelations for Asset {
             return Ok(None);
         };
 
-        let data = loaders.project_loader.load_one(project_id).await.unwrap().unwrap();
+        let data = loaders.project_loader.load_one(project_id).await.unwrap();
 
-        Ok(Some(data))
+        Ok(data)
+    }
+
+    async fn tasks(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT task_id FROM assets_by_tasks
+            WHERE asset_id = $1
+            ORDER BY created_at, task_id
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await?
+        .into_iter()
+        .map(|task| task.task_id)
+        .collect();
+
+        let tasks_map = loaders.task_loader.load_many(ids.clone()).await.unwrap();
+
+        Ok(ids.into_iter().filter_map(|id| tasks_map.get(&id).cloned()).collect())
+    }
+
+    async fn versions(&self, loaders: &SDKLoaders) -> Result<Vec<AssetVersion>, SDKError> {
+        loaders.engine.get_asset_versions(self.id).await
     }
 }
@@ -38,6 +38,8 @@ pub enum ChangeOperation {
     Insert,
     Update,
     Delete,
+    /// A task moved to another project, recorded once for the task and the subtasks that moved with it.
+    Move,
 }
 
 #[derive(Debug, Enum, OpenApiEnum, Copy, Clone, Display, EnumString, Deserialize, Serialize, Eq, PartialEq)]
@@ -52,4 +54,49 @@ pub enum ChangeResourceType {
     Labels,
     Changes,
     Chats,
+    Workflows,
+    Cycles,
+    CustomFields,
+    ChecklistItems,
+    Comments,
+    TaskTemplates,
+    ProjectTemplates,
+    WorkLogs,
+    LabelGroups,
+}
+
+impl ChangeResourceType {
+    /// The table notifying about this resource, e.g. `custom_fields` for `CustomFields`.
+    pub fn table_name(&self) -> String {
+        let mut table_name = String::new();
+
+        for (i, c) in self.to_string().char_indices() {
+            if i > 0 && c.is_ascii_uppercase() {
+                table_name.push('_');
+            }
+
+            table_name.push(c.to_ascii_lowercase());
+        }
+
+        table_name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn resource_types_keep_pascal_case_names_and_map_to_tables() {
+        assert_eq!(ChangeResourceType::Tasks.table_name(), "tasks");
+        assert_eq!(ChangeResourceType::CustomFields.to_string(), "CustomFields");
+        assert_eq!(ChangeResourceType::CustomFields.table_name(), "custom_fields");
+        assert_eq!(ChangeResourceType::ProjectTemplates.table_name(), "project_templates");
+        assert_eq!(
+            ChangeResourceType::from_str("WorkLogs").unwrap(),
+            ChangeResourceType::WorkLogs
+        );
+    }
 }
@@ -5,18 +5,23 @@ use async_trait::async_trait;
 use derive_builder::Builder;
 use poem_openapi::Object;
 use serde::Serialize;
-use sqlx::Row;
+use sqlx::{postgres::PgRow, PgConnection, Row};
 use uuid::Uuid;
 
+use crate::common::filters::{enum_comparison, SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect, UuidComparison};
+use crate::common::pagination::{Page, PageInput};
 use crate::{backend::engine::SDKEngine, common::commons::SortOrder, errors::sdk::SDKError};
 
 use super::change::{Change, ChangeOperation, ChangeResourceType};
 
+const CHANGES_SORTABLE_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "operation", "resource_type"];
+
 #[async_trait]
 pub trait ChangeCrudOperations {
     async fn create_change(&self, input: CreateChangeInput) -> Result<Change, SDKError>;
     async fn get_change(&self, id: Uuid) -> Result<Change, SDKError>;
     async fn get_changes(&self, input: GetChangesInput) -> Result<Vec<Change>, SDKError>;
+    async fn get_changes_page(&self, input: PageInput<GetChangesWhere>) -> Result<Page<Change>, SDKError>;
     async fn update_change(&self, id: Uuid, input: UpdateChangeInput) -> Result<Change, SDKError>;
     async fn delete_change(&self, id: Uuid) -> Result<Change, SDKError>;
 }
@@ -73,14 +78,14 @@ pub struct GetChangesInput {
 pub struct GetChangesWhere {
     #[builder(setter(strip_option), default)]
     pub ids: Option<Vec<Uuid>>,
-    #[builder(setter(strip_option), default)]
-    pub owner_id: Option<Uuid>,
-    #[builder(setter(strip_option), default)]
-    pub resource_id: Option<Uuid>,
-    #[builder(setter(strip_option), default)]
-    pub operation: Option<ChangeOperation>,
-    #[builder(setter(strip_option), default)]
-    pub resource_type: Option<ChangeResourceType>,
+    #[builder(setter(into, strip_option), default)]
+    pub owner_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub resource_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub operation: Option<ChangeOperationComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub resource_type: Option<ChangeResourceTypeComparison>,
 
     #[oai(skip)]
     #[builder(setter(strip_option), default)]
@@ -90,88 +95,52 @@ pub struct GetChangesWhere {
     pub _or: Option<Vec<GetChangesWhere>>,
 }
 
-impl GetChangesWhere {
-    pub fn compile_sql(&self) -> String {
-        let mut and_clauses = Vec::new();
-        let mut or_clauses = Vec::new();
+enum_comparison!(ChangeOperationComparison, ChangeOperation);
+
+enum_comparison!(ChangeResourceTypeComparison, ChangeResourceType);
+
+impl SQLFilter for GetChangesWhere {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
 
         if let Some(ids) = &self.ids {
-            and_clauses.push(format!(
-                "id = ANY(array[{}]::uuid[])",
-                ids.iter()
-                    .map(|x| format!("'{}'", x))
-                    .collect::<Vec<String>>()
-                    .join(",")
-            ));
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
         }
 
         if let Some(owner_id) = &self.owner_id {
-            and_clauses.push(format!("owner_id = '{}'", owner_id));
+            conditions.push(owner_id.compile_sql("owner_id"));
         }
+
         if let Some(resource_id) = &self.resource_id {
-            and_clauses.push(format!("resource_id = '{}'", resource_id));
+            conditions.push(resource_id.compile_sql("resource_id"));
         }
+
         if let Some(operation) = &self.operation {
-            and_clauses.push(format!("operation = '{}'", operation));
+            conditions.push(operation.compile_sql("operation"));
         }
+
         if let Some(resource_type) = &self.resource_type {
-            and_clauses.push(format!("resource_type = '{}'", resource_type));
+            conditions.push(resource_type.compile_sql("resource_type"));
         }
 
         if let Some(ands) = &self._and {
-            for and in ands {
-                and_clauses.push(and.compile_sql());
-            }
-        }
-        if let Some(ors) = &self._or {
-            for or in ors {
-                or_clauses.push(or.compile_sql());
-            }
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
         }
 
-        let mut where_clause = String::new();
-        if !and_clauses.is_empty() {
-            where_clause.push_str(&format!("({})", and_clauses.join(" AND ")));
-        }
-        if !or_clauses.is_empty() {
-            if !where_clause.is_empty() {
-                where_clause.push_str(" OR ");
-            }
-            where_clause.push_str(&format!("({})", or_clauses.join(" OR ")));
+        if let Some(ors) = &self._or {
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
         }
 
-        where_clause
+        SQLCondition::And(conditions)
     }
 }
 
 #[async_trait]
 impl ChangeCrudOperations for SDKEngine {
     async fn create_change(&self, input: CreateChangeInput) -> Result<Change, SDKError> {
-        let change_info = sqlx::query!(
-            r#"
-            INSERT INTO changes (owner_id, resource_id, operation, resource_type, diff_json)
-            VALUES ($1, $2, $3, $4, $5)
-            RETURNING *
-            "#,
-            input.owner_id,
-            input.resource_id,
-            input.operation.to_string(),
-            input.resource_type.to_string(),
-            input.diff_json,
-        )
-        .fetch_one(self.db_pool.as_ref())
-        .await?;
+        let mut conn = self.db_pool.acquire().await?;
 
-        Ok(Change {
-            id: change_info.id,
-            created_at: change_info.created_at,
-            updated_at: change_info.updated_at,
-            owner_id: change_info.owner_id,
-            resource_id: change_info.resource_id,
-            operation: ChangeOperation::from_str(change_info.operation.as_str()).unwrap(),
-            resource_type: ChangeResourceType::from_str(change_info.resource_type.as_str()).unwrap(),
-            diff_json: change_info.diff_json,
-        })
+        insert_change(&mut conn, input).await
     }
 
     async fn get_change(&self, id: Uuid) -> Result<Change, SDKError> {
@@ -198,48 +167,25 @@ impl ChangeCrudOperations for SDKEngine {
     }
 
     async fn get_changes(&self, input: GetChangesInput) -> Result<Vec<Change>, SDKError> {
-        let mut query = "SELECT * FROM changes ".to_string();
+        let mut query = SQLSelect::new("changes", CHANGES_SORTABLE_COLUMNS)
+            .filter(input.filter.as_ref())
+            .sort(input.sort_by, input.sort_order)
+            .paginate(input.limit, input.offset)
+            .build()?;
 
-        if let Some(filter) = input.filter {
-            query.push_str(format!("WHERE {} ", filter.compile_sql()).as_str());
-        }
+        let changes_info = query.build().fetch_all(self.db_pool.as_ref()).await?;
 
-        if let Some(sort_by) = input.sort_by {
-            query.push_str(format!("ORDER BY {} ", sort_by).as_str());
-        }
-
-        if let Some(sort_order) = input.sort_order {
-            query.push_str(format!("{} ", sort_order).as_str());
-        }
-
-        if let Some(limit) = input.limit {
-            query.push_str(format!("LIMIT {} ", limit).as_str());
-        }
-
-        if let Some(offset) = input.offset {
-            query.push_str(format!("OFFSET {} ", offset).as_str());
-        }
-
-        let changes_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
-
-        let changes = changes_info
-            .into_iter()
-            .map(|change_info| Change {
-                id: change_info.get("id"),
-                created_at: change_info.get("created_at"),
-                updated_at: change_info.get("updated_at"),
-                owner_id: change_info.get("owner_id"),
-                resource_id: change_info.get("resource_id"),
-                operation: ChangeOperation::from_str(change_info.get::<'_, String, _>("operation").as_str()).unwrap(),
-                resource_type: ChangeResourceType::from_str(change_info.get::<'_, String, _>("resource_type").as_str())
-                    .unwrap(),
-                diff_json: change_info.get("diff_json"),
-            })
-            .collect();
+        let changes = changes_info.iter().map(change_from_row).collect();
 
         Ok(changes)
     }
 
+    async fn get_changes_page(&self, input: PageInput<GetChangesWhere>) -> Result<Page<Change>, SDKError> {
+        SQLSelect::new("changes", CHANGES_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), input, change_from_row)
+            .await
+    }
+
     async fn update_change(&self, id: Uuid, input: UpdateChangeInput) -> Result<Change, SDKError> {
         let change_info = sqlx::query!(
             r#"
@@ -298,3 +244,46 @@ impl ChangeCrudOperations for SDKEngine {
         })
     }
 }
+
+/// Records a change using the given connection, so it can be part of the transaction that made it.
+pub(crate) async fn insert_change(conn: &mut PgConnection, input: CreateChangeInput) -> Result<Change, SDKError> {
+    let change_info = sqlx::query!(
+        r#"
+        INSERT INTO changes (owner_id, resource_id, operation, resource_type, diff_json)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+        input.owner_id,
+        input.resource_id,
+        input.operation.to_string(),
+        input.resource_type.to_string(),
+        input.diff_json,
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(Change {
+        id: change_info.id,
+        created_at: change_info.created_at,
+        updated_at: change_info.updated_at,
+        owner_id: change_info.owner_id,
+        resource_id: change_info.resource_id,
+        operation: ChangeOperation::from_str(change_info.operation.as_str()).unwrap(),
+        resource_type: ChangeResourceType::from_str(change_info.resource_type.as_str()).unwrap(),
+        diff_json: change_info.diff_json,
+    })
+}
+
+fn change_from_row(change_info: &PgRow) -> Change {
+    Change {
+        id: change_info.get("id"),
+        created_at: change_info.get("created_at"),
+        updated_at: change_info.get("updated_at"),
+        owner_id: change_info.get("owner_id"),
+        resource_id: change_info.get("resource_id"),
+        operation: ChangeOperation::from_str(change_info.get::<'_, String, _>("operation").as_str()).unwrap(),
+        resource_type: ChangeResourceType::from_str(change_info.get::<'_, String, _>("resource_type").as_str())
+            .unwrap(),
+        diff_json: change_info.get("diff_json"),
+    }
+}
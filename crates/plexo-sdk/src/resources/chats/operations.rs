@@ -6,19 +6,24 @@ use async_trait::async_trait;
 use derive_builder::Builder;
 use poem_openapi::Object;
 use serde::Serialize;
-use sqlx::Row;
+use sqlx::{postgres::PgRow, Row};
 use uuid::Uuid;
 
 use crate::backend::engine::SDKEngine;
 use crate::common::commons::SortOrder;
+use crate::common::filters::{enum_comparison, SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect, UuidComparison};
+use crate::common::pagination::{Page, PageInput};
 use crate::errors::sdk::SDKError;
 use crate::resources::chats::chat::{Chat, ChatStatus};
 
+const CHATS_SORTABLE_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "status"];
+
 #[async_trait]
 pub trait ChatCrudOperations {
     async fn create_chat(&self, input: CreateChatInput) -> Result<Chat, SDKError>;
     async fn get_chat(&self, id: Uuid) -> Result<Chat, SDKError>;
     async fn get_chats(&self, input: Option<GetChatsInput>) -> Result<Vec<Chat>, SDKError>;
+    async fn get_chats_page(&self, input: PageInput<GetChatsWhere>) -> Result<Page<Chat>, SDKError>;
     async fn update_chat(&self, id: Uuid, input: UpdateChatInput) -> Result<Chat, SDKError>;
     async fn delete_chat(&self, id: Uuid) -> Result<Chat, SDKError>;
 }
@@ -65,12 +70,12 @@ pub struct UpdateChatInput {
 pub struct GetChatsWhere {
     #[builder(setter(strip_option), default)]
     pub ids: Option<Vec<Uuid>>,
-    #[builder(setter(strip_option), default)]
-    pub owner_id: Option<Uuid>,
-    #[builder(setter(strip_option), default)]
-    pub resource_id: Option<Uuid>,
-    #[builder(setter(strip_option), default)]
-    pub status: Option<ChatStatus>,
+    #[builder(setter(into, strip_option), default)]
+    pub owner_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub resource_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub status: Option<ChatStatusComparison>,
 
     #[oai(skip)]
     #[builder(setter(strip_option), default)]
@@ -81,43 +86,37 @@ pub struct GetChatsWhere {
     pub _or: Option<Vec<GetChatsWhere>>,
 }
 
-impl GetChatsWhere {
-    pub fn compile_sql(&self) -> String {
+enum_comparison!(ChatStatusComparison, ChatStatus);
+
+impl SQLFilter for GetChatsWhere {
+    fn compile_sql(&self) -> SQLCondition {
         let mut conditions = Vec::new();
 
         if let Some(ids) = &self.ids {
-            conditions.push(format!(
-                "id = ANY(array[{}]::uuid[])",
-                ids.iter()
-                    .map(|x| format!("'{}'", x))
-                    .collect::<Vec<String>>()
-                    .join(",")
-            ));
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
         }
 
         if let Some(owner_id) = &self.owner_id {
-            conditions.push(format!("owner_id = '{}'", owner_id));
+            conditions.push(owner_id.compile_sql("owner_id"));
         }
 
         if let Some(resource_id) = &self.resource_id {
-            conditions.push(format!("resource_id = '{}'", resource_id));
+            conditions.push(resource_id.compile_sql("resource_id"));
         }
 
         if let Some(status) = &self.status {
-            conditions.push(format!("status = '{}'", status));
+            conditions.push(status.compile_sql("status"));
         }
 
         if let Some(ands) = &self._and {
-            let and_conditions: Vec<String> = ands.iter().map(|and| and.compile_sql()).collect();
-            conditions.push(format!("({})", and_conditions.join(" AND ")));
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
         }
 
         if let Some(ors) = &self._or {
-            let or_conditions: Vec<String> = ors.iter().map(|or| or.compile_sql()).collect();
-            conditions.push(format!("({})", or_conditions.join(" OR ")));
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
         }
 
-        conditions.join(" AND ")
+        SQLCondition::And(conditions)
     }
 }
 
@@ -238,56 +237,41 @@ impl ChatCrudOperations for SDKEngine {
     }
 
     async fn get_chats(&self, input: Option<GetChatsInput>) -> Result<Vec<Chat>, SDKError> {
-        let mut query = "SELECT * FROM chats".to_string();
-
-        if let Some(input) = input {
-            if let Some(filter) = input.filter {
-                let where_clause = filter.compile_sql();
-                if !where_clause.is_empty() {
-                    query += " WHERE ";
-                    query += &where_clause;
-                }
-            }
-
-            if let Some(sort_by) = input.sort_by {
-                query += " ORDER BY ";
-                query += &sort_by;
-            }
-
-            if let Some(sort_order) = input.sort_order {
-                query += " ";
-                query += &sort_order.to_string();
-            }
-
-            if let Some(limit) = input.limit {
-                query += " LIMIT ";
-                query += &limit.to_string();
-            }
-
-            if let Some(offset) = input.offset {
-                query += " OFFSET ";
-                query += &offset.to_string();
-            }
-        }
+        let select = SQLSelect::new("chats", CHATS_SORTABLE_COLUMNS);
 
-        let chats_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
-
-        let chats = chats_info
-            .iter()
-            .map(|x| Chat {
-                id: x.get("id"),
-                owner_id: x.get("owner_id"),
-                resource_id: x.get("resource_id"),
-                resource_type: x.get("resource_type"),
-                status: x
-                    .get::<'_, Option<String>, _>("status")
-                    .and_then(|a| ChatStatus::from_str(&a).ok())
-                    .unwrap_or_default(),
-                created_at: x.get("created_at"),
-                updated_at: x.get("updated_at"),
-            })
-            .collect::<Vec<Chat>>();
+        let select = match input {
+            Some(input) => select
+                .filter(input.filter.as_ref())
+                .sort(input.sort_by, input.sort_order)
+                .paginate(input.limit, input.offset),
+            None => select,
+        };
+
+        let chats_info = select.build()?.build().fetch_all(self.db_pool.as_ref()).await?;
+
+        let chats = chats_info.iter().map(chat_from_row).collect::<Vec<Chat>>();
 
         Ok(chats)
     }
+
+    async fn get_chats_page(&self, input: PageInput<GetChatsWhere>) -> Result<Page<Chat>, SDKError> {
+        SQLSelect::new("chats", CHATS_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), input, chat_from_row)
+            .await
+    }
+}
+
+fn chat_from_row(chat_info: &PgRow) -> Chat {
+    Chat {
+        id: chat_info.get("id"),
+        owner_id: chat_info.get("owner_id"),
+        resource_id: chat_info.get("resource_id"),
+        resource_type: chat_info.get("resource_type"),
+        status: chat_info
+            .get::<'_, Option<String>, _>("status")
+            .and_then(|a| ChatStatus::from_str(&a).ok())
+            .unwrap_or_default(),
+        created_at: chat_info.get("created_at"),
+        updated_at: chat_info.get("updated_at"),
+    }
 }
@@ -0,0 +1,31 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use uuid::Uuid;
+
+use serde::Serialize;
+
+/// A lightweight to-do inside a task, without the owner, status and history of a subtask.
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKChecklistItem")]
+pub struct ChecklistItem {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub task_id: Uuid,
+    pub owner_id: Uuid,
+    pub text: String,
+    pub position: i32,
+
+    pub checked: bool,
+    pub checked_by: Option<Uuid>,
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Copy, Serialize)]
+pub struct ChecklistProgress {
+    pub checked: i32,
+    pub total: i32,
+}
@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, PgConnection, Row};
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+
+use super::checklist::{ChecklistItem, ChecklistProgress};
+
+#[async_trait]
+pub trait ChecklistOperations {
+    async fn add_checklist_item(&self, input: AddChecklistItemInput) -> Result<ChecklistItem, SDKError>;
+    async fn get_checklist_items(&self, task_id: Uuid) -> Result<Vec<ChecklistItem>, SDKError>;
+    /// Moves an item to `position` in the checklist of its task, positions past the end move it last.
+    async fn reorder_checklist_item(&self, id: Uuid, position: i32) -> Result<ChecklistItem, SDKError>;
+    /// Checks or unchecks an item, flipping it when `checked` is not given.
+    async fn toggle_checklist_item(&self, id: Uuid, input: ToggleChecklistItemInput)
+        -> Result<ChecklistItem, SDKError>;
+    async fn delete_checklist_item(&self, id: Uuid) -> Result<ChecklistItem, SDKError>;
+    /// Checked and total items per task, tasks without a checklist are left out.
+    async fn checklist_progress(&self, task_ids: Vec<Uuid>) -> Result<HashMap<Uuid, ChecklistProgress>, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct AddChecklistItemInput {
+    pub task_id: Uuid,
+    pub text: String,
+
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    /// Defaults to the end of the checklist.
+    #[builder(setter(strip_option), default)]
+    pub position: Option<i32>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct ToggleChecklistItemInput {
+    #[graphql(skip)]
+    pub member_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub checked: Option<bool>,
+}
+
+#[async_trait]
+impl ChecklistOperations for SDKEngine {
+    async fn add_checklist_item(&self, input: AddChecklistItemInput) -> Result<ChecklistItem, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let mut checklist = lock_checklist(&mut tx, input.task_id).await?;
+        let position = clamp_position(input.position, checklist.len());
+
+        let item_info = sqlx::query(
+            r#"
+            INSERT INTO checklist_items (task_id, owner_id, text, position)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(input.task_id)
+        .bind(input.owner_id)
+        .bind(input.text)
+        .bind(position as i32)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let item = checklist_item_from_row(&item_info);
+
+        checklist.insert(position, item.id);
+        renumber_checklist(&mut tx, &checklist).await?;
+
+        tx.commit().await?;
+
+        Ok(item)
+    }
+
+    async fn get_checklist_items(&self, task_id: Uuid) -> Result<Vec<ChecklistItem>, SDKError> {
+        let items_info = sqlx::query(
+            r#"
+            SELECT * FROM checklist_items
+            WHERE task_id = $1
+            ORDER BY position, created_at
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(items_info.iter().map(checklist_item_from_row).collect())
+    }
+
+    async fn reorder_checklist_item(&self, id: Uuid, position: i32) -> Result<ChecklistItem, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let task_id: Uuid = sqlx::query_scalar("SELECT task_id FROM checklist_items WHERE id = $1")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let mut checklist = lock_checklist(&mut tx, task_id).await?;
+        checklist.retain(|item_id| *item_id != id);
+
+        let position = clamp_position(Some(position), checklist.len());
+
+        checklist.insert(position, id);
+        renumber_checklist(&mut tx, &checklist).await?;
+
+        let item_info = sqlx::query("SELECT * FROM checklist_items WHERE id = $1")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(checklist_item_from_row(&item_info))
+    }
+
+    async fn toggle_checklist_item(
+        &self,
+        id: Uuid,
+        input: ToggleChecklistItemInput,
+    ) -> Result<ChecklistItem, SDKError> {
+        // Checking an item that is already checked keeps who checked it and when.
+        let item_info = sqlx::query(
+            r#"
+            UPDATE checklist_items
+            SET
+                checked = COALESCE($2, NOT checked),
+                checked_by = CASE
+                    WHEN NOT COALESCE($2, NOT checked) THEN NULL
+                    WHEN checked THEN checked_by
+                    ELSE $3
+                END,
+                checked_at = CASE
+                    WHEN NOT COALESCE($2, NOT checked) THEN NULL
+                    WHEN checked THEN checked_at
+                    ELSE now()
+                END
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(input.checked)
+        .bind(input.member_id)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(checklist_item_from_row(&item_info))
+    }
+
+    async fn delete_checklist_item(&self, id: Uuid) -> Result<ChecklistItem, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let item = checklist_item_from_row(
+            &sqlx::query("DELETE FROM checklist_items WHERE id = $1 RETURNING *")
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await?,
+        );
+
+        let checklist = lock_checklist(&mut tx, item.task_id).await?;
+        renumber_checklist(&mut tx, &checklist).await?;
+
+        tx.commit().await?;
+
+        Ok(item)
+    }
+
+    async fn checklist_progress(&self, task_ids: Vec<Uuid>) -> Result<HashMap<Uuid, ChecklistProgress>, SDKError> {
+        let progress_info = sqlx::query(
+            r#"
+            SELECT
+                task_id,
+                COUNT(*) FILTER (WHERE checked)::integer AS checked,
+                COUNT(*)::integer AS total
+            FROM checklist_items
+            WHERE task_id = ANY($1)
+            GROUP BY task_id
+            "#,
+        )
+        .bind(task_ids)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(progress_info
+            .iter()
+            .map(|progress| {
+                (
+                    progress.get("task_id"),
+                    ChecklistProgress {
+                        checked: progress.get("checked"),
+                        total: progress.get("total"),
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+fn clamp_position(position: Option<i32>, len: usize) -> usize {
+    position.map_or(len, |position| (position.max(0) as usize).min(len))
+}
+
+/// Locks the items of a task checklist and returns their ids in checklist order.
+async fn lock_checklist(conn: &mut PgConnection, task_id: Uuid) -> Result<Vec<Uuid>, SDKError> {
+    let ids = sqlx::query_scalar(
+        r#"
+        SELECT id FROM checklist_items
+        WHERE task_id = $1
+        ORDER BY position, created_at
+        FOR UPDATE
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(conn)
+    .await?;
+
+    Ok(ids)
+}
+
+/// Numbers the items from zero in the given order, only touching the ones that moved.
+async fn renumber_checklist(conn: &mut PgConnection, checklist: &[Uuid]) -> Result<(), SDKError> {
+    sqlx::query(
+        r#"
+        UPDATE checklist_items SET position = items.position - 1
+        FROM unnest($1::uuid[]) WITH ORDINALITY AS items(id, position)
+        WHERE checklist_items.id = items.id AND checklist_items.position <> items.position - 1
+        "#,
+    )
+    .bind(checklist)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+pub(crate) fn checklist_item_from_row(item_info: &PgRow) -> ChecklistItem {
+    ChecklistItem {
+        id: item_info.get("id"),
+        created_at: item_info.get("created_at"),
+        updated_at: item_info.get("updated_at"),
+        task_id: item_info.get("task_id"),
+        owner_id: item_info.get("owner_id"),
+        text: item_info.get("text"),
+        position: item_info.get("position"),
+        checked: item_info.get("checked"),
+        checked_by: item_info.get("checked_by"),
+        checked_at: item_info.get("checked_at"),
+    }
+}
@@ -0,0 +1,116 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use uuid::Uuid;
+
+use serde::Serialize;
+
+use crate::resources::changes::change::ChangeResourceType;
+
+/// A markdown comment left by a member on any resource, replies point to the comment they answer.
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKComment")]
+pub struct Comment {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub owner_id: Uuid,
+    pub resource_id: Uuid,
+    pub resource_type: ChangeResourceType,
+    pub parent_id: Option<Uuid>,
+
+    pub body: String,
+    /// Set once the body has been edited, the previous bodies are kept as comment edits.
+    pub edited_at: Option<DateTime<Utc>>,
+}
+
+/// A previous body of a comment, saved when it was replaced.
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKCommentEdit")]
+pub struct CommentEdit {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+
+    pub comment_id: Uuid,
+    pub editor_id: Option<Uuid>,
+
+    pub body: String,
+}
+
+/// Returns the handles mentioned in a markdown body as `@handle` or `@name@domain`, lowercased and
+/// in order of first appearance. Code spans, fenced code blocks and email addresses are skipped.
+pub fn parse_mentions(body: &str) -> Vec<String> {
+    let is_handle_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | '+');
+    let is_domain_char = |c: char| c.is_alphanumeric() || matches!(c, '.' | '-');
+
+    let mut handles: Vec<String> = Vec::new();
+    let mut in_fence = false;
+
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+
+        if in_fence {
+            continue;
+        }
+
+        let mut in_code = false;
+        let mut previous = None;
+
+        for (index, c) in line.char_indices() {
+            match c {
+                '`' => in_code = !in_code,
+                '@' if !in_code && !previous.is_some_and(is_handle_char) => {
+                    let rest = &line[index + 1..];
+                    let local = rest[..rest.find(|c| !is_handle_char(c)).unwrap_or(rest.len())].trim_end_matches('.');
+
+                    if local.is_empty() {
+                        previous = Some(c);
+                        continue;
+                    }
+
+                    let mut handle = local.to_lowercase();
+
+                    if let Some(domain) = rest[local.len()..].strip_prefix('@') {
+                        let domain =
+                            domain[..domain.find(|c| !is_domain_char(c)).unwrap_or(domain.len())].trim_end_matches('.');
+
+                        if domain.contains('.') {
+                            handle = format!("{handle}@{}", domain.to_lowercase());
+                        }
+                    }
+
+                    if !handles.contains(&handle) {
+                        handles.push(handle);
+                    }
+                }
+                _ => {}
+            }
+
+            previous = Some(c);
+        }
+    }
+
+    handles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mentions_are_parsed_outside_code() {
+        let body = "Thanks @Ana, can @bob.smith check?\n\
+                    Ping `@not_me` or write to someone@example.com.\n\
+                    ```\n@neither\n```\n\
+                    cc @carol@plexo.app. and @ana again, @ alone";
+
+        assert_eq!(parse_mentions(body), vec!["ana", "bob.smith", "carol@plexo.app"]);
+    }
+}
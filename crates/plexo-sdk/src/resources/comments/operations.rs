@@ -0,0 +1,449 @@
+use std::str::FromStr;
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, PgConnection, Row};
+use uuid::Uuid;
+
+use crate::common::filters::{
+    enum_comparison, SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect, StringComparison, UuidComparison,
+};
+use crate::common::pagination::{Page, PageInput};
+use crate::resources::changes::change::ChangeResourceType;
+use crate::{backend::engine::SDKEngine, common::commons::SortOrder, errors::sdk::SDKError};
+
+use super::comment::{parse_mentions, Comment, CommentEdit};
+
+const COMMENTS_SORTABLE_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "edited_at"];
+
+#[async_trait]
+pub trait CommentCrudOperations {
+    /// Comments on a resource that exists, replies must be on the same resource as their parent.
+    async fn create_comment(&self, input: CreateCommentInput) -> Result<Comment, SDKError>;
+    async fn get_comment(&self, id: Uuid) -> Result<Comment, SDKError>;
+    async fn get_comments(&self, input: GetCommentsInput) -> Result<Vec<Comment>, SDKError>;
+    async fn get_comments_page(&self, input: PageInput<GetCommentsWhere>) -> Result<Page<Comment>, SDKError>;
+    /// Every comment on a resource, threads and replies, oldest first.
+    async fn get_resource_comments(
+        &self,
+        resource_type: ChangeResourceType,
+        resource_id: Uuid,
+    ) -> Result<Vec<Comment>, SDKError>;
+    /// Replaces the body, keeping the previous one as an edit and updating the mentions. Only the
+    /// author of a comment can edit it.
+    async fn update_comment(&self, id: Uuid, input: UpdateCommentInput) -> Result<Comment, SDKError>;
+    /// Deletes a comment along with its replies. Only the author of a comment can delete it.
+    async fn delete_comment(&self, id: Uuid, member_id: Uuid) -> Result<Comment, SDKError>;
+    async fn get_comment_edits(&self, comment_id: Uuid) -> Result<Vec<CommentEdit>, SDKError>;
+    async fn get_comment_mentions(&self, comment_id: Uuid) -> Result<Vec<Uuid>, SDKError>;
+}
+
+#[derive(Clone, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateCommentInput {
+    pub resource_id: Uuid,
+    pub resource_type: ChangeResourceType,
+    pub body: String,
+
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateCommentInput {
+    pub body: String,
+
+    #[graphql(skip)]
+    pub editor_id: Uuid,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetCommentsInput {
+    #[builder(setter(strip_option), default)]
+    pub filter: Option<GetCommentsWhere>,
+
+    #[builder(setter(strip_option), default)]
+    pub sort_by: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub sort_order: Option<SortOrder>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetCommentsWhere {
+    #[builder(setter(strip_option), default)]
+    pub ids: Option<Vec<Uuid>>,
+    #[builder(setter(into, strip_option), default)]
+    pub owner_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub resource_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub resource_type: Option<CommentResourceTypeComparison>,
+    /// `isNull: true` keeps the comments starting a thread.
+    #[builder(setter(into, strip_option), default)]
+    pub parent_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub body: Option<StringComparison>,
+    /// Keeps the comments mentioning this member.
+    #[builder(setter(strip_option), default)]
+    pub mentioned_member_id: Option<Uuid>,
+
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _and: Option<Vec<GetCommentsWhere>>,
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _or: Option<Vec<GetCommentsWhere>>,
+}
+
+enum_comparison!(CommentResourceTypeComparison, ChangeResourceType);
+
+impl SQLFilter for GetCommentsWhere {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
+
+        if let Some(ids) = &self.ids {
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
+        }
+
+        if let Some(owner_id) = &self.owner_id {
+            conditions.push(owner_id.compile_sql("owner_id"));
+        }
+
+        if let Some(resource_id) = &self.resource_id {
+            conditions.push(resource_id.compile_sql("resource_id"));
+        }
+
+        if let Some(resource_type) = &self.resource_type {
+            conditions.push(resource_type.compile_sql("resource_type"));
+        }
+
+        if let Some(parent_id) = &self.parent_id {
+            conditions.push(parent_id.compile_sql("parent_id"));
+        }
+
+        if let Some(body) = &self.body {
+            conditions.push(body.compile_sql("body"));
+        }
+
+        if let Some(member_id) = self.mentioned_member_id {
+            conditions.push(SQLCondition::Expression(
+                &["id IN (SELECT comment_id FROM comment_mentions WHERE member_id = ", ")"],
+                vec![member_id.into()],
+            ));
+        }
+
+        if let Some(ands) = &self._and {
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
+        }
+
+        if let Some(ors) = &self._or {
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
+        }
+
+        SQLCondition::And(conditions)
+    }
+}
+
+#[async_trait]
+impl CommentCrudOperations for SDKEngine {
+    async fn create_comment(&self, input: CreateCommentInput) -> Result<Comment, SDKError> {
+        validate_comment_body(&input.body)?;
+
+        let mut tx = self.db_pool.begin().await?;
+
+        if let Some(parent_id) = input.parent_id {
+            let parent = comment_from_row(
+                &sqlx::query("SELECT * FROM comments WHERE id = $1")
+                    .bind(parent_id)
+                    .fetch_one(&mut *tx)
+                    .await?,
+            );
+
+            if parent.resource_id != input.resource_id || parent.resource_type != input.resource_type {
+                return Err(SDKError::InvalidComment(
+                    "a reply must be on the same resource as its parent".to_string(),
+                ));
+            }
+        }
+
+        // The table name comes from the resource type enum, never from the caller.
+        let resource_exists: bool = sqlx::query_scalar(&format!(
+            "SELECT EXISTS (SELECT 1 FROM {} WHERE id = $1)",
+            input.resource_type.to_string().to_lowercase()
+        ))
+        .bind(input.resource_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if !resource_exists {
+            return Err(SDKError::ResourceNotFound);
+        }
+
+        let comment_info = sqlx::query(
+            r#"
+            INSERT INTO comments (owner_id, resource_id, resource_type, parent_id, body)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(input.owner_id)
+        .bind(input.resource_id)
+        .bind(input.resource_type.to_string())
+        .bind(input.parent_id)
+        .bind(&input.body)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let comment = comment_from_row(&comment_info);
+
+        record_mentions(&mut tx, comment.id, &comment.body).await?;
+
+        tx.commit().await?;
+
+        Ok(comment)
+    }
+
+    async fn get_comment(&self, id: Uuid) -> Result<Comment, SDKError> {
+        let comment_info = sqlx::query("SELECT * FROM comments WHERE id = $1")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(comment_from_row(&comment_info))
+    }
+
+    async fn get_comments(&self, input: GetCommentsInput) -> Result<Vec<Comment>, SDKError> {
+        let mut query = SQLSelect::new("comments", COMMENTS_SORTABLE_COLUMNS)
+            .filter(input.filter.as_ref())
+            .sort(input.sort_by.or(Some("created_at".to_string())), input.sort_order)
+            .paginate(input.limit, input.offset)
+            .build()?;
+
+        let comments_info = query.build().fetch_all(self.db_pool.as_ref()).await?;
+
+        Ok(comments_info.iter().map(comment_from_row).collect())
+    }
+
+    async fn get_comments_page(&self, input: PageInput<GetCommentsWhere>) -> Result<Page<Comment>, SDKError> {
+        SQLSelect::new("comments", COMMENTS_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), input, comment_from_row)
+            .await
+    }
+
+    async fn get_resource_comments(
+        &self,
+        resource_type: ChangeResourceType,
+        resource_id: Uuid,
+    ) -> Result<Vec<Comment>, SDKError> {
+        let comments_info = sqlx::query(
+            r#"
+            SELECT * FROM comments
+            WHERE resource_type = $1 AND resource_id = $2
+            ORDER BY created_at
+            "#,
+        )
+        .bind(resource_type.to_string())
+        .bind(resource_id)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(comments_info.iter().map(comment_from_row).collect())
+    }
+
+    async fn update_comment(&self, id: Uuid, input: UpdateCommentInput) -> Result<Comment, SDKError> {
+        validate_comment_body(&input.body)?;
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let comment = comment_from_row(
+            &sqlx::query("SELECT * FROM comments WHERE id = $1 FOR UPDATE")
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await?,
+        );
+
+        check_comment_author(&comment, input.editor_id)?;
+
+        if comment.body == input.body {
+            return Ok(comment);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO comment_edits (comment_id, editor_id, body)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(id)
+        .bind(input.editor_id)
+        .bind(&comment.body)
+        .execute(&mut *tx)
+        .await?;
+
+        let comment_info = sqlx::query(
+            r#"
+            UPDATE comments
+            SET body = $1, edited_at = now()
+            WHERE id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(&input.body)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let comment = comment_from_row(&comment_info);
+
+        record_mentions(&mut tx, comment.id, &comment.body).await?;
+
+        tx.commit().await?;
+
+        Ok(comment)
+    }
+
+    async fn delete_comment(&self, id: Uuid, member_id: Uuid) -> Result<Comment, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let comment = comment_from_row(
+            &sqlx::query("SELECT * FROM comments WHERE id = $1 FOR UPDATE")
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await?,
+        );
+
+        check_comment_author(&comment, member_id)?;
+
+        sqlx::query("DELETE FROM comments WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(comment)
+    }
+
+    async fn get_comment_edits(&self, comment_id: Uuid) -> Result<Vec<CommentEdit>, SDKError> {
+        let edits_info = sqlx::query(
+            r#"
+            SELECT * FROM comment_edits
+            WHERE comment_id = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(comment_id)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(edits_info
+            .iter()
+            .map(|edit_info| CommentEdit {
+                id: edit_info.get("id"),
+                created_at: edit_info.get("created_at"),
+                comment_id: edit_info.get("comment_id"),
+                editor_id: edit_info.get("editor_id"),
+                body: edit_info.get("body"),
+            })
+            .collect())
+    }
+
+    async fn get_comment_mentions(&self, comment_id: Uuid) -> Result<Vec<Uuid>, SDKError> {
+        let member_ids = sqlx::query_scalar(
+            r#"
+            SELECT member_id FROM comment_mentions
+            WHERE comment_id = $1
+            ORDER BY created_at, member_id
+            "#,
+        )
+        .bind(comment_id)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(member_ids)
+    }
+}
+
+fn validate_comment_body(body: &str) -> Result<(), SDKError> {
+    if body.trim().is_empty() {
+        return Err(SDKError::InvalidComment("the body can't be empty".to_string()));
+    }
+
+    Ok(())
+}
+
+fn check_comment_author(comment: &Comment, member_id: Uuid) -> Result<(), SDKError> {
+    if comment.owner_id != member_id {
+        return Err(SDKError::InvalidComment(
+            "only its author can change a comment".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Resolves the `@handle` mentions of a body to members and makes them the mentions of the comment.
+/// A handle matches a member email, the part of it before the `@`, or the name without spaces.
+async fn record_mentions(conn: &mut PgConnection, comment_id: Uuid, body: &str) -> Result<(), SDKError> {
+    let handles = parse_mentions(body);
+
+    let member_ids: Vec<Uuid> = sqlx::query_scalar(
+        r#"
+        SELECT id FROM members
+        WHERE lower(email) = ANY($1)
+            OR lower(split_part(email, '@', 1)) = ANY($1)
+            OR lower(replace(name, ' ', '')) = ANY($1)
+        "#,
+    )
+    .bind(&handles)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    sqlx::query("DELETE FROM comment_mentions WHERE comment_id = $1 AND member_id <> ALL($2)")
+        .bind(comment_id)
+        .bind(&member_ids)
+        .execute(&mut *conn)
+        .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO comment_mentions (comment_id, member_id)
+        SELECT $1, unnest($2::uuid[])
+        ON CONFLICT DO NOTHING
+        "#,
+    )
+    .bind(comment_id)
+    .bind(&member_ids)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+pub(crate) fn comment_from_row(comment_info: &PgRow) -> Comment {
+    Comment {
+        id: comment_info.get("id"),
+        created_at: comment_info.get("created_at"),
+        updated_at: comment_info.get("updated_at"),
+        owner_id: comment_info.get("owner_id"),
+        resource_id: comment_info.get("resource_id"),
+        resource_type: ChangeResourceType::from_str(comment_info.get("resource_type")).unwrap(),
+        parent_id: comment_info.get("parent_id"),
+        body: comment_info.get("body"),
+        edited_at: comment_info.get("edited_at"),
+    }
+}
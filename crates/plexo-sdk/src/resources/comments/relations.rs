@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+
+use crate::{backend::loaders::SDKLoaders, errors::sdk::SDKError, resources::members::member::Member};
+
+use super::{
+    comment::{Comment, CommentEdit},
+    operations::{comment_from_row, CommentCrudOperations},
+};
+
+#[async_trait]
+pub trait CommentRelations {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
+    async fn parent(&self, loaders: &SDKLoaders) -> Result<Option<Comment>, SDKError>;
+    async fn replies(&self, loaders: &SDKLoaders) -> Result<Vec<Comment>, SDKError>;
+    async fn edits(&self, loaders: &SDKLoaders) -> Result<Vec<CommentEdit>, SDKError>;
+    async fn mentions(&self, loaders: &SDKLoaders) -> Result<Vec<Member>, SDKError>;
+}
+
+#[async_trait]
+impl CommentRelations for Comment {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
+        let data = loaders.member_loader.load_one(self.owner_id).await.unwrap().unwrap();
+
+        Ok(data)
+    }
+
+    async fn parent(&self, loaders: &SDKLoaders) -> Result<Option<Comment>, SDKError> {
+        let Some(parent_id) = self.parent_id else {
+            return Ok(None);
+        };
+
+        loaders.engine.get_comment(parent_id).await.map(Some)
+    }
+
+    async fn replies(&self, loaders: &SDKLoaders) -> Result<Vec<Comment>, SDKError> {
+        let replies_info = sqlx::query(
+            r#"
+            SELECT * FROM comments
+            WHERE parent_id = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(self.id)
+        .fetch_all(&*loaders.engine.db_pool)
+        .await?;
+
+        Ok(replies_info.iter().map(comment_from_row).collect())
+    }
+
+    async fn edits(&self, loaders: &SDKLoaders) -> Result<Vec<CommentEdit>, SDKError> {
+        loaders.engine.get_comment_edits(self.id).await
+    }
+
+    async fn mentions(&self, loaders: &SDKLoaders) -> Result<Vec<Member>, SDKError> {
+        let ids = loaders.engine.get_comment_mentions(self.id).await?;
+
+        let members_map = loaders.member_loader.load_many(ids.clone()).await.unwrap();
+
+        Ok(ids.iter().filter_map(|id| members_map.get(id).cloned()).collect())
+    }
+}
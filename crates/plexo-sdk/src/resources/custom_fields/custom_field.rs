@@ -0,0 +1,58 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+/// A typed field defined by a project for its tasks.
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKCustomField")]
+pub struct CustomField {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub name: String,
+    pub owner_id: Uuid,
+    pub project_id: Uuid,
+
+    pub description: Option<String>,
+    pub kind: CustomFieldKind,
+
+    /// Allowed values of single and multi select fields.
+    pub options: Vec<String>,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum CustomFieldKind {
+    #[default]
+    Text,
+    Number,
+    Date,
+    SingleSelect,
+    MultiSelect,
+    Member,
+}
+
+/// The value of a custom field on a task, only the slot matching the field kind is set.
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKCustomFieldValue")]
+pub struct CustomFieldValue {
+    pub task_id: Uuid,
+    pub field_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    /// Set for text and single select fields.
+    pub text: Option<String>,
+    pub number: Option<f64>,
+    pub date: Option<DateTime<Utc>>,
+    pub member_id: Option<Uuid>,
+    pub options: Option<Vec<String>>,
+}
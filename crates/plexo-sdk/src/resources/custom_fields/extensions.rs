@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use serde_json::json;
+use sqlx::{postgres::PgRow, PgConnection, Row};
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    errors::sdk::SDKError,
+    resources::changes::{
+        change::{ChangeOperation, ChangeResourceType},
+        operations::{insert_change, CreateChangeInput},
+    },
+};
+
+use super::{
+    custom_field::{CustomField, CustomFieldKind, CustomFieldValue},
+    operations::custom_field_from_row,
+};
+
+#[async_trait]
+pub trait CustomFieldsExtensionOperations {
+    /// Sets custom field values of a task, a value with none of its slots set clears the field.
+    /// Fields must belong to the task project and values must match the field kind.
+    async fn set_task_custom_field_values(
+        &self,
+        task_id: Uuid,
+        input: SetCustomFieldValuesInput,
+    ) -> Result<Vec<CustomFieldValue>, SDKError>;
+    async fn task_custom_field_values(&self, task_id: Uuid) -> Result<Vec<CustomFieldValue>, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct SetCustomFieldValuesInput {
+    #[graphql(skip)]
+    #[oai(skip)]
+    pub owner_id: Uuid,
+
+    pub values: Vec<CustomFieldValueInput>,
+}
+
+/// Text and single select fields take `text`, multi select fields take `options`.
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CustomFieldValueInput {
+    pub field_id: Uuid,
+
+    #[builder(setter(into, strip_option), default)]
+    pub text: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub number: Option<f64>,
+    #[builder(setter(strip_option), default)]
+    pub date: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub member_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub options: Option<Vec<String>>,
+}
+
+#[async_trait]
+impl CustomFieldsExtensionOperations for SDKEngine {
+    async fn set_task_custom_field_values(
+        &self,
+        task_id: Uuid,
+        input: SetCustomFieldValuesInput,
+    ) -> Result<Vec<CustomFieldValue>, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let project_id: Option<Uuid> = sqlx::query_scalar("SELECT project_id FROM tasks WHERE id = $1 FOR UPDATE")
+            .bind(task_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let field_ids: Vec<Uuid> = input.values.iter().map(|value| value.field_id).collect();
+
+        let fields: HashMap<Uuid, CustomField> = sqlx::query("SELECT * FROM custom_fields WHERE id = ANY($1)")
+            .bind(&field_ids)
+            .fetch_all(&mut *tx)
+            .await?
+            .iter()
+            .map(custom_field_from_row)
+            .map(|field| (field.id, field))
+            .collect();
+
+        for value in input.values.iter() {
+            let Some(field) = fields
+                .get(&value.field_id)
+                .filter(|field| Some(field.project_id) == project_id)
+            else {
+                return Err(SDKError::InvalidCustomFieldValue(format!(
+                    "field {} is not defined by the task project",
+                    value.field_id
+                )));
+            };
+
+            match validate_custom_field_value(field, value.clone())? {
+                Some(value) => {
+                    sqlx::query!(
+                        r#"
+                        INSERT INTO custom_field_values (task_id, field_id, text_value, number_value, date_value, member_value, options_value)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7)
+                        ON CONFLICT (task_id, field_id) DO UPDATE
+                        SET
+                            text_value = excluded.text_value,
+                            number_value = excluded.number_value,
+                            date_value = excluded.date_value,
+                            member_value = excluded.member_value,
+                            options_value = excluded.options_value
+                        "#,
+                        task_id,
+                        value.field_id,
+                        value.text,
+                        value.number,
+                        value.date,
+                        value.member_id,
+                        value.options.as_deref(),
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                None => {
+                    sqlx::query!(
+                        r#"
+                        DELETE FROM custom_field_values
+                        WHERE task_id = $1 AND field_id = $2
+                        "#,
+                        task_id,
+                        value.field_id,
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+                }
+            }
+        }
+
+        let values = select_custom_field_values(&mut tx, task_id).await?;
+
+        insert_change(
+            &mut tx,
+            CreateChangeInput {
+                owner_id: input.owner_id,
+                resource_id: task_id,
+                operation: ChangeOperation::Update,
+                resource_type: ChangeResourceType::Tasks,
+                diff_json: serde_json::to_string(&json!({
+                    "input": { "custom_fields": input.values },
+                    "result": values,
+                }))?,
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(values)
+    }
+
+    async fn task_custom_field_values(&self, task_id: Uuid) -> Result<Vec<CustomFieldValue>, SDKError> {
+        let mut conn = self.db_pool.acquire().await?;
+
+        select_custom_field_values(&mut conn, task_id).await
+    }
+}
+
+/// Checks `value` against the field kind, returning `None` when it clears the field.
+fn validate_custom_field_value(
+    field: &CustomField,
+    mut value: CustomFieldValueInput,
+) -> Result<Option<CustomFieldValueInput>, SDKError> {
+    let invalid = |reason: String| Err(SDKError::InvalidCustomFieldValue(format!("{}: {reason}", field.name)));
+
+    let slots = [
+        (CustomFieldKind::Text, value.text.is_some()),
+        (CustomFieldKind::Number, value.number.is_some()),
+        (CustomFieldKind::Date, value.date.is_some()),
+        (CustomFieldKind::Member, value.member_id.is_some()),
+        (CustomFieldKind::MultiSelect, value.options.is_some()),
+    ];
+
+    let slot_kind = match field.kind {
+        CustomFieldKind::SingleSelect => CustomFieldKind::Text,
+        kind => kind,
+    };
+
+    if slots.iter().any(|(kind, is_set)| *is_set && *kind != slot_kind) {
+        return invalid(format!("{} fields only take a {slot_kind} value", field.kind));
+    }
+
+    match field.kind {
+        CustomFieldKind::Number if value.number.is_some_and(|number| !number.is_finite()) => {
+            return invalid("numbers must be finite".to_string());
+        }
+        CustomFieldKind::SingleSelect => {
+            if let Some(text) = value.text.as_ref().filter(|text| !field.options.contains(text)) {
+                return invalid(format!("{text} is not one of the field options"));
+            }
+        }
+        CustomFieldKind::MultiSelect => {
+            if let Some(options) = value.options.as_mut() {
+                if let Some(option) = options.iter().find(|option| !field.options.contains(option)) {
+                    return invalid(format!("{option} is not one of the field options"));
+                }
+
+                let mut seen = Vec::new();
+                options.retain(|option| {
+                    let first = !seen.contains(option);
+                    seen.push(option.clone());
+                    first
+                });
+
+                if options.is_empty() {
+                    value.options = None;
+                }
+            }
+        }
+        _ => {}
+    }
+
+    let is_set = value.text.is_some()
+        || value.number.is_some()
+        || value.date.is_some()
+        || value.member_id.is_some()
+        || value.options.is_some();
+
+    Ok(is_set.then_some(value))
+}
+
+async fn select_custom_field_values(conn: &mut PgConnection, task_id: Uuid) -> Result<Vec<CustomFieldValue>, SDKError> {
+    let values_info = sqlx::query(
+        r#"
+        SELECT custom_field_values.* FROM custom_field_values
+        JOIN custom_fields ON custom_fields.id = custom_field_values.field_id
+        WHERE custom_field_values.task_id = $1
+        ORDER BY custom_fields.created_at, custom_fields.id
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(conn)
+    .await?;
+
+    Ok(values_info.iter().map(custom_field_value_from_row).collect())
+}
+
+pub(crate) fn custom_field_value_from_row(value_info: &PgRow) -> CustomFieldValue {
+    CustomFieldValue {
+        task_id: value_info.get("task_id"),
+        field_id: value_info.get("field_id"),
+        created_at: value_info.get("created_at"),
+        updated_at: value_info.get("updated_at"),
+        text: value_info.get("text_value"),
+        number: value_info.get("number_value"),
+        date: value_info.get("date_value"),
+        member_id: value_info.get("member_value"),
+        options: value_info.get("options_value"),
+    }
+}
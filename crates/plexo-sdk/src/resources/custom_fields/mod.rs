@@ -0,0 +1,4 @@
+pub mod custom_field;
+pub mod extensions;
+pub mod operations;
+pub mod relations;
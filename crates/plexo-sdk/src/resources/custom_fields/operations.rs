@@ -0,0 +1,489 @@
+use std::str::FromStr;
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, PgConnection, Pool, Postgres, Row};
+use uuid::Uuid;
+
+use crate::common::filters::{
+    enum_comparison, DateTimeComparison, NumberComparison, SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect,
+    StringComparison, UuidComparison,
+};
+use crate::common::pagination::{Page, PageInput};
+use crate::{backend::engine::SDKEngine, common::commons::SortOrder, errors::sdk::SDKError};
+
+use super::custom_field::{CustomField, CustomFieldKind};
+
+const CUSTOM_FIELDS_SORTABLE_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "name", "kind"];
+
+/// Tasks sorted by `custom_fields.<field id>` select the field value under this name.
+const CUSTOM_FIELD_SORT_COLUMN: &str = "custom_field_sort";
+const CUSTOM_FIELD_SORT_PREFIX: &str = "custom_fields.";
+
+#[async_trait]
+pub trait CustomFieldCrudOperations {
+    async fn create_custom_field(&self, input: CreateCustomFieldInput) -> Result<CustomField, SDKError>;
+    async fn get_custom_field(&self, id: Uuid) -> Result<CustomField, SDKError>;
+    async fn get_custom_fields(&self, input: GetCustomFieldsInput) -> Result<Vec<CustomField>, SDKError>;
+    async fn get_custom_fields_page(
+        &self,
+        input: PageInput<GetCustomFieldsWhere>,
+    ) -> Result<Page<CustomField>, SDKError>;
+    async fn update_custom_field(&self, id: Uuid, input: UpdateCustomFieldInput) -> Result<CustomField, SDKError>;
+    async fn delete_custom_field(&self, id: Uuid) -> Result<CustomField, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateCustomFieldInput {
+    pub name: String,
+    pub project_id: Uuid,
+    pub kind: CustomFieldKind,
+
+    #[graphql(skip)]
+    #[oai(skip)]
+    pub owner_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+    /// Required for single and multi select fields, not allowed for the other kinds.
+    #[builder(setter(strip_option), default)]
+    pub options: Option<Vec<String>>,
+}
+
+/// The kind of a field can't change. Options removed from a select field are removed from the
+/// task values too.
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateCustomFieldInput {
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub options: Option<Vec<String>>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetCustomFieldsInput {
+    #[builder(setter(strip_option), default)]
+    pub filter: Option<GetCustomFieldsWhere>,
+
+    #[builder(setter(strip_option), default)]
+    pub sort_by: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub sort_order: Option<SortOrder>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetCustomFieldsWhere {
+    #[builder(setter(strip_option), default)]
+    pub ids: Option<Vec<Uuid>>,
+    #[builder(setter(into, strip_option), default)]
+    pub name: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub owner_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub project_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub kind: Option<CustomFieldKindComparison>,
+
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _and: Option<Vec<GetCustomFieldsWhere>>,
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _or: Option<Vec<GetCustomFieldsWhere>>,
+}
+
+enum_comparison!(CustomFieldKindComparison, CustomFieldKind);
+
+impl SQLFilter for GetCustomFieldsWhere {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
+
+        if let Some(ids) = &self.ids {
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
+        }
+
+        if let Some(name) = &self.name {
+            conditions.push(name.compile_sql("name"));
+        }
+
+        if let Some(owner_id) = &self.owner_id {
+            conditions.push(owner_id.compile_sql("owner_id"));
+        }
+
+        if let Some(project_id) = &self.project_id {
+            conditions.push(project_id.compile_sql("project_id"));
+        }
+
+        if let Some(kind) = &self.kind {
+            conditions.push(kind.compile_sql("kind"));
+        }
+
+        if let Some(ands) = &self._and {
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
+        }
+
+        if let Some(ors) = &self._or {
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
+        }
+
+        SQLCondition::And(conditions)
+    }
+}
+
+/// Matches tasks by the value they hold for one custom field. Text comparisons apply to text and
+/// single select fields, a task without a value only matches `isSet: false`.
+#[derive(Default, Clone, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct CustomFieldComparison {
+    pub field_id: Uuid,
+
+    #[builder(setter(into, strip_option), default)]
+    pub text: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub number: Option<NumberComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub date: Option<DateTimeComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub member_id: Option<UuidComparison>,
+    #[builder(setter(strip_option), default)]
+    pub options: Option<CustomFieldOptionsComparison>,
+    #[builder(setter(strip_option), default)]
+    pub is_set: Option<bool>,
+}
+
+/// Matches multi select values holding any, all or none of the given options.
+#[derive(Default, Clone, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct CustomFieldOptionsComparison {
+    #[builder(setter(strip_option), default)]
+    pub any: Option<Vec<String>>,
+    #[builder(setter(strip_option), default)]
+    pub all: Option<Vec<String>>,
+    #[builder(setter(strip_option), default)]
+    pub none: Option<Vec<String>>,
+}
+
+impl CustomFieldComparison {
+    pub fn compile_sql(&self) -> SQLCondition {
+        let field = SQLCondition::equal("field_id", self.field_id);
+        let mut matches = vec![field.clone()];
+
+        if let Some(text) = &self.text {
+            matches.push(text.compile_sql("text_value"));
+        }
+
+        if let Some(number) = &self.number {
+            matches.push(number.compile_sql("number_value"));
+        }
+
+        if let Some(date) = &self.date {
+            matches.push(date.compile_sql("date_value"));
+        }
+
+        if let Some(member_id) = &self.member_id {
+            matches.push(member_id.compile_sql("member_value"));
+        }
+
+        if let Some(any) = self.options.as_ref().and_then(|options| options.any.clone()) {
+            matches.push(SQLCondition::Expression(&["options_value && ", ""], vec![any.into()]));
+        }
+
+        if let Some(all) = self.options.as_ref().and_then(|options| options.all.clone()) {
+            matches.push(SQLCondition::Expression(&["options_value @> ", ""], vec![all.into()]));
+        }
+
+        let mut conditions = Vec::new();
+
+        if matches.len() > 1 || self.is_set == Some(true) {
+            conditions.push(SQLCondition::Nested(
+                "id IN (SELECT task_id FROM custom_field_values WHERE ",
+                Box::new(SQLCondition::And(matches)),
+                ")",
+            ));
+        }
+
+        if self.is_set == Some(false) {
+            conditions.push(SQLCondition::Nested(
+                "id NOT IN (SELECT task_id FROM custom_field_values WHERE ",
+                Box::new(field.clone()),
+                ")",
+            ));
+        }
+
+        if let Some(none) = self.options.as_ref().and_then(|options| options.none.clone()) {
+            conditions.push(SQLCondition::Nested(
+                "id NOT IN (SELECT task_id FROM custom_field_values WHERE ",
+                Box::new(SQLCondition::And(vec![
+                    field,
+                    SQLCondition::Expression(&["options_value && ", ""], vec![none.into()]),
+                ])),
+                ")",
+            ));
+        }
+
+        SQLCondition::And(conditions)
+    }
+}
+
+#[async_trait]
+impl CustomFieldCrudOperations for SDKEngine {
+    async fn create_custom_field(&self, input: CreateCustomFieldInput) -> Result<CustomField, SDKError> {
+        let options = input.options.unwrap_or_default();
+
+        validate_custom_field_options(input.kind, &options)?;
+
+        let custom_field_info = sqlx::query(
+            r#"
+            INSERT INTO custom_fields (name, owner_id, project_id, description, kind, options)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(input.name)
+        .bind(input.owner_id)
+        .bind(input.project_id)
+        .bind(input.description)
+        .bind(input.kind.to_string())
+        .bind(options)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(custom_field_from_row(&custom_field_info))
+    }
+
+    async fn get_custom_field(&self, id: Uuid) -> Result<CustomField, SDKError> {
+        let custom_field_info = sqlx::query("SELECT * FROM custom_fields WHERE id = $1")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(custom_field_from_row(&custom_field_info))
+    }
+
+    async fn get_custom_fields(&self, input: GetCustomFieldsInput) -> Result<Vec<CustomField>, SDKError> {
+        let mut query = SQLSelect::new("custom_fields", CUSTOM_FIELDS_SORTABLE_COLUMNS)
+            .filter(input.filter.as_ref())
+            .sort(input.sort_by, input.sort_order)
+            .paginate(input.limit, input.offset)
+            .build()?;
+
+        let custom_fields_info = query.build().fetch_all(self.db_pool.as_ref()).await?;
+
+        Ok(custom_fields_info.iter().map(custom_field_from_row).collect())
+    }
+
+    async fn get_custom_fields_page(
+        &self,
+        input: PageInput<GetCustomFieldsWhere>,
+    ) -> Result<Page<CustomField>, SDKError> {
+        SQLSelect::new("custom_fields", CUSTOM_FIELDS_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), input, custom_field_from_row)
+            .await
+    }
+
+    async fn update_custom_field(&self, id: Uuid, input: UpdateCustomFieldInput) -> Result<CustomField, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let custom_field = custom_field_from_row(
+            &sqlx::query("SELECT * FROM custom_fields WHERE id = $1 FOR UPDATE")
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await?,
+        );
+
+        if let Some(options) = &input.options {
+            validate_custom_field_options(custom_field.kind, options)?;
+            prune_custom_field_values(&mut tx, &custom_field, options).await?;
+        }
+
+        let custom_field_info = sqlx::query(
+            r#"
+            UPDATE custom_fields
+            SET
+                name = COALESCE($1, name),
+                description = COALESCE($2, description),
+                options = COALESCE($3, options)
+            WHERE id = $4
+            RETURNING *
+            "#,
+        )
+        .bind(input.name)
+        .bind(input.description)
+        .bind(input.options)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(custom_field_from_row(&custom_field_info))
+    }
+
+    async fn delete_custom_field(&self, id: Uuid) -> Result<CustomField, SDKError> {
+        let custom_field_info = sqlx::query("DELETE FROM custom_fields WHERE id = $1 RETURNING *")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(custom_field_from_row(&custom_field_info))
+    }
+}
+
+fn validate_custom_field_options(kind: CustomFieldKind, options: &[String]) -> Result<(), SDKError> {
+    match kind {
+        CustomFieldKind::SingleSelect | CustomFieldKind::MultiSelect => {
+            if options.is_empty() || options.iter().any(|option| option.trim().is_empty()) {
+                return Err(SDKError::InvalidCustomField(
+                    "select fields need at least one non-empty option".to_string(),
+                ));
+            }
+
+            if let Some(option) = options
+                .iter()
+                .enumerate()
+                .find_map(|(index, option)| options[..index].contains(option).then_some(option))
+            {
+                return Err(SDKError::InvalidCustomField(format!("option {option} is repeated")));
+            }
+        }
+        _ if !options.is_empty() => {
+            return Err(SDKError::InvalidCustomField(format!(
+                "{kind} fields don't take options"
+            )));
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Drops the options no longer allowed by a select field from the task values.
+async fn prune_custom_field_values(
+    conn: &mut PgConnection,
+    custom_field: &CustomField,
+    options: &[String],
+) -> Result<(), SDKError> {
+    match custom_field.kind {
+        CustomFieldKind::SingleSelect => {
+            sqlx::query!(
+                r#"
+                DELETE FROM custom_field_values
+                WHERE field_id = $1 AND text_value <> ALL($2)
+                "#,
+                custom_field.id,
+                options,
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+        CustomFieldKind::MultiSelect => {
+            sqlx::query!(
+                r#"
+                UPDATE custom_field_values
+                SET options_value = ARRAY(SELECT option FROM unnest(options_value) AS option WHERE option = ANY($2))
+                WHERE field_id = $1 AND NOT options_value <@ $2
+                "#,
+                custom_field.id,
+                options,
+            )
+            .execute(&mut *conn)
+            .await?;
+
+            sqlx::query!(
+                r#"
+                DELETE FROM custom_field_values
+                WHERE field_id = $1 AND cardinality(options_value) = 0
+                "#,
+                custom_field.id,
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Resolves a `custom_fields.<field id>` task sort into a computed column holding the field
+/// value, other sorts are returned unchanged. Member fields sort by the member name.
+pub(crate) async fn sort_by_custom_field(
+    pool: &Pool<Postgres>,
+    select: SQLSelect,
+    sort_by: Option<String>,
+) -> Result<(SQLSelect, Option<String>), SDKError> {
+    let Some(field_id) = sort_by
+        .as_deref()
+        .and_then(|sort_by| sort_by.strip_prefix(CUSTOM_FIELD_SORT_PREFIX))
+    else {
+        return Ok((select, sort_by));
+    };
+
+    let invalid_sort = || SDKError::InvalidSortColumn(sort_by.clone().unwrap_or_default());
+
+    let field_id = Uuid::parse_str(field_id).map_err(|_| invalid_sort())?;
+
+    let kind = sqlx::query_scalar::<_, String>("SELECT kind FROM custom_fields WHERE id = $1")
+        .bind(field_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(invalid_sort)?;
+
+    let parts: &'static [&'static str] = match CustomFieldKind::from_str(&kind).unwrap_or_default() {
+        CustomFieldKind::Text | CustomFieldKind::SingleSelect => &[
+            "(SELECT text_value FROM custom_field_values WHERE task_id = tasks.id AND field_id = ",
+            ")",
+        ],
+        CustomFieldKind::Number => &[
+            "(SELECT number_value FROM custom_field_values WHERE task_id = tasks.id AND field_id = ",
+            ")",
+        ],
+        CustomFieldKind::Date => &[
+            "(SELECT date_value FROM custom_field_values WHERE task_id = tasks.id AND field_id = ",
+            ")",
+        ],
+        CustomFieldKind::MultiSelect => &[
+            "(SELECT array_to_string(options_value, ',') FROM custom_field_values WHERE task_id = tasks.id AND field_id = ",
+            ")",
+        ],
+        CustomFieldKind::Member => &[
+            "(SELECT members.name FROM custom_field_values JOIN members ON members.id = member_value WHERE task_id = tasks.id AND field_id = ",
+            ")",
+        ],
+    };
+
+    let select = select.computed_column(
+        CUSTOM_FIELD_SORT_COLUMN,
+        SQLCondition::Expression(parts, vec![field_id.into()]),
+    );
+
+    Ok((select, Some(CUSTOM_FIELD_SORT_COLUMN.to_string())))
+}
+
+pub(crate) fn custom_field_from_row(custom_field_info: &PgRow) -> CustomField {
+    CustomField {
+        id: custom_field_info.get("id"),
+        created_at: custom_field_info.get("created_at"),
+        updated_at: custom_field_info.get("updated_at"),
+        name: custom_field_info.get("name"),
+        owner_id: custom_field_info.get("owner_id"),
+        project_id: custom_field_info.get("project_id"),
+        description: custom_field_info.get("description"),
+        kind: CustomFieldKind::from_str(&custom_field_info.get::<'_, String, _>("kind")).unwrap_or_default(),
+        options: custom_field_info.get("options"),
+    }
+}
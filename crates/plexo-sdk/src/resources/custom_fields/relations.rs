@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+
+use crate::{
+    backend::loaders::SDKLoaders,
+    errors::sdk::SDKError,
+    resources::{members::member::Member, projects::project::Project},
+};
+
+use super::custom_field::CustomField;
+
+#[async_trait]
+pub trait CustomFieldRelations {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Project, SDKError>;
+}
+
+#[async_trait]
+impl CustomFieldRelations for CustomField {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
+        let data = loaders.member_loader.load_one(self.owner_id).await.unwrap().unwrap();
+
+        Ok(data)
+    }
+
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Project, SDKError> {
+        let data = loaders
+            .project_loader
+            .load_one(self.project_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::SQLXError(sqlx::Error::RowNotFound))?;
+
+        Ok(data)
+    }
+}
@@ -0,0 +1,31 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+/// A time-boxed iteration of a project or a team.
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKCycle")]
+pub struct Cycle {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub name: String,
+    pub owner_id: Uuid,
+
+    pub goal: Option<String>,
+    pub project_id: Option<Uuid>,
+    pub team_id: Option<Uuid>,
+
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+
+    /// Scope and completed counts frozen when the cycle closed, before unfinished tasks rolled over.
+    pub final_scope_count: Option<i32>,
+    pub final_completed_count: Option<i32>,
+}
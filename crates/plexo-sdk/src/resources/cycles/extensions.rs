@@ -0,0 +1,322 @@
+use async_graphql::{InputObject, SimpleObject};
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use serde_json::json;
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    errors::sdk::SDKError,
+    resources::{
+        changes::{
+            change::{ChangeOperation, ChangeResourceType},
+            operations::{insert_change, CreateChangeInput},
+        },
+        tasks::{operations::task_from_row, task::TaskStatus},
+    },
+};
+
+use super::{
+    cycle::Cycle,
+    operations::{check_cycle_tasks, cycle_from_row},
+};
+
+#[async_trait]
+pub trait CyclesExtensionOperations {
+    /// Closes a cycle and moves its unfinished tasks to `rollover_to_id`, or to the next open
+    /// cycle of the same project or team. Without one, the tasks are taken out of any cycle.
+    async fn close_cycle(&self, id: Uuid, input: CloseCycleInput) -> Result<Cycle, SDKError>;
+    /// Open cycles past their end, oldest first.
+    async fn due_cycles(&self) -> Result<Vec<Cycle>, SDKError>;
+    async fn cycle_counts(&self, id: Uuid) -> Result<CycleCounts, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CloseCycleInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub rollover_to_id: Option<Uuid>,
+}
+
+/// Canceled tasks are left out of the scope. Closed cycles report the counts they had when they
+/// closed, open cycles their current ones.
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+pub struct CycleCounts {
+    pub scope_count: i32,
+    pub completed_count: i32,
+}
+
+#[async_trait]
+impl CyclesExtensionOperations for SDKEngine {
+    async fn close_cycle(&self, id: Uuid, input: CloseCycleInput) -> Result<Cycle, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let cycle = cycle_from_row(
+            &sqlx::query("SELECT * FROM cycles WHERE id = $1 FOR UPDATE")
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await?,
+        );
+
+        if cycle.closed_at.is_some() {
+            return Err(SDKError::InvalidCycle(format!("cycle {} is already closed", cycle.id)));
+        }
+
+        let rollover_to_id: Option<Uuid> = match input.rollover_to_id {
+            Some(rollover_to_id) => {
+                let open = sqlx::query_scalar::<_, bool>("SELECT closed_at IS NULL FROM cycles WHERE id = $1")
+                    .bind(rollover_to_id)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+
+                if rollover_to_id == id || open != Some(true) {
+                    return Err(SDKError::InvalidCycle(format!(
+                        "tasks can't roll over to cycle {rollover_to_id}"
+                    )));
+                }
+
+                Some(rollover_to_id)
+            }
+            None => {
+                sqlx::query_scalar(
+                    r#"
+                SELECT id FROM cycles
+                WHERE closed_at IS NULL AND id <> $1
+                    AND project_id IS NOT DISTINCT FROM $2 AND team_id IS NOT DISTINCT FROM $3
+                    AND starts_at >= $4
+                ORDER BY starts_at, created_at
+                LIMIT 1
+                "#,
+                )
+                .bind(id)
+                .bind(cycle.project_id)
+                .bind(cycle.team_id)
+                .bind(cycle.starts_at)
+                .fetch_optional(&mut *tx)
+                .await?
+            }
+        };
+
+        let finished_statuses = vec![TaskStatus::Done.to_string(), TaskStatus::Canceled.to_string()];
+
+        let counts_info = sqlx::query(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE status IS DISTINCT FROM $2)::integer AS scope_count,
+                COUNT(*) FILTER (WHERE status = $3)::integer AS completed_count
+            FROM tasks
+            WHERE cycle_id = $1 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .bind(TaskStatus::Canceled.to_string())
+        .bind(TaskStatus::Done.to_string())
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let rolled_over_tasks: Vec<_> = sqlx::query(
+            r#"
+            UPDATE tasks SET cycle_id = $2
+            WHERE cycle_id = $1 AND deleted_at IS NULL AND (status IS NULL OR status <> ALL($3))
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(rollover_to_id)
+        .bind(&finished_statuses)
+        .fetch_all(&mut *tx)
+        .await?
+        .iter()
+        .map(task_from_row)
+        .collect();
+
+        if let Some(rollover_to_id) = rollover_to_id {
+            let task_ids: Vec<Uuid> = rolled_over_tasks.iter().map(|task| task.id).collect();
+
+            check_cycle_tasks(&mut tx, rollover_to_id, &task_ids).await?;
+        }
+
+        for task in rolled_over_tasks.iter() {
+            insert_change(
+                &mut tx,
+                CreateChangeInput {
+                    owner_id: input.owner_id,
+                    resource_id: task.id,
+                    operation: ChangeOperation::Update,
+                    resource_type: ChangeResourceType::Tasks,
+                    diff_json: serde_json::to_string(&json!({
+                        "input": { "rollover": { "from_cycle_id": id, "to_cycle_id": rollover_to_id } },
+                        "result": task,
+                    }))?,
+                },
+            )
+            .await?;
+        }
+
+        let cycle = cycle_from_row(
+            &sqlx::query(
+                r#"
+                UPDATE cycles
+                SET closed_at = now(), final_scope_count = $2, final_completed_count = $3
+                WHERE id = $1
+                RETURNING *
+                "#,
+            )
+            .bind(id)
+            .bind(counts_info.get::<i32, _>("scope_count"))
+            .bind(counts_info.get::<i32, _>("completed_count"))
+            .fetch_one(&mut *tx)
+            .await?,
+        );
+
+        insert_change(
+            &mut tx,
+            CreateChangeInput {
+                owner_id: input.owner_id,
+                resource_id: cycle.id,
+                operation: ChangeOperation::Update,
+                resource_type: ChangeResourceType::Cycles,
+                diff_json: serde_json::to_string(&json!({
+                    "input": { "close": input },
+                    "result": cycle,
+                    "rolled_over": rolled_over_tasks.iter().map(|task| task.id).collect::<Vec<_>>(),
+                }))?,
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(cycle)
+    }
+
+    async fn due_cycles(&self) -> Result<Vec<Cycle>, SDKError> {
+        let cycles_info = sqlx::query(
+            r#"
+            SELECT * FROM cycles
+            WHERE closed_at IS NULL AND ends_at <= now()
+            ORDER BY ends_at
+            "#,
+        )
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(cycles_info.iter().map(cycle_from_row).collect())
+    }
+
+    async fn cycle_counts(&self, id: Uuid) -> Result<CycleCounts, SDKError> {
+        let counts_info = sqlx::query(
+            r#"
+            SELECT
+                COALESCE(cycles.final_scope_count, COUNT(tasks.id) FILTER (WHERE tasks.status IS DISTINCT FROM $2)::integer) AS scope_count,
+                COALESCE(cycles.final_completed_count, COUNT(tasks.id) FILTER (WHERE tasks.status = $3)::integer) AS completed_count
+            FROM cycles
+            LEFT JOIN tasks ON tasks.cycle_id = cycles.id AND tasks.deleted_at IS NULL
+            WHERE cycles.id = $1
+            GROUP BY cycles.id
+            "#,
+        )
+        .bind(id)
+        .bind(TaskStatus::Canceled.to_string())
+        .bind(TaskStatus::Done.to_string())
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(CycleCounts {
+            scope_count: counts_info.get("scope_count"),
+            completed_count: counts_info.get("completed_count"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::backend::testing::{test_engine, test_member, test_project, test_task};
+    use crate::resources::cycles::operations::{CreateCycleInput, CycleCrudOperations};
+    use crate::resources::tasks::operations::{CreateTaskInput, TaskCrudOperations};
+
+    #[sqlx::test]
+    async fn closing_a_cycle_rolls_unfinished_tasks_over_to_the_next(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+        let project_id = test_project(&engine, owner_id, "CYC").await;
+
+        let open = test_task(&engine, owner_id, Some(project_id), "Open").await.id;
+        let mut finished = Vec::new();
+        for status in [TaskStatus::Done, TaskStatus::Canceled] {
+            let task = engine
+                .create_task(CreateTaskInput {
+                    title: status.to_string(),
+                    owner_id,
+                    project_id: Some(project_id),
+                    status: Some(status),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            finished.push(task.id);
+        }
+
+        let now = Utc::now();
+        let cycle_input = |name: &str, starts_at, tasks| CreateCycleInput {
+            name: name.to_string(),
+            owner_id,
+            starts_at,
+            ends_at: starts_at + Duration::days(14),
+            project_id: Some(project_id),
+            tasks,
+            ..Default::default()
+        };
+
+        let current = engine
+            .create_cycle(cycle_input(
+                "Current",
+                now - Duration::days(14),
+                Some([vec![open], finished.clone()].concat()),
+            ))
+            .await
+            .unwrap();
+        let next = engine.create_cycle(cycle_input("Next", now, None)).await.unwrap();
+
+        assert_eq!(
+            engine.due_cycles().await.unwrap().iter().map(|cycle| cycle.id).collect::<Vec<_>>(),
+            vec![current.id]
+        );
+
+        let closed = engine
+            .close_cycle(
+                current.id,
+                CloseCycleInput {
+                    owner_id,
+                    rollover_to_id: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert!(closed.closed_at.is_some());
+
+        assert_eq!(engine.get_task(open).await.unwrap().cycle_id, Some(next.id));
+        for id in finished {
+            assert_eq!(engine.get_task(id).await.unwrap().cycle_id, Some(current.id));
+        }
+
+        let counts = engine.cycle_counts(current.id).await.unwrap();
+        assert_eq!((counts.scope_count, counts.completed_count), (2, 1));
+
+        let err = engine
+            .close_cycle(current.id, CloseCycleInput::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SDKError::InvalidCycle(..)));
+    }
+}
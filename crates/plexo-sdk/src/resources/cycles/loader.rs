@@ -0,0 +1,60 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::dataloader::Loader;
+
+use uuid::Uuid;
+
+use crate::backend::engine::SDKEngine;
+
+use super::cycle::Cycle;
+
+pub struct CycleLoader(Arc<SDKEngine>);
+
+impl CycleLoader {
+    pub fn new(e: Arc<SDKEngine>) -> Self {
+        Self(e)
+    }
+}
+
+impl Loader<Uuid> for CycleLoader {
+    type Value = Cycle;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let cycles = sqlx::query!(
+            r#"
+            SELECT * FROM cycles WHERE id = ANY($1)
+            "#,
+            &keys
+        )
+        .fetch_all(&*self.0.db_pool)
+        .await
+        .unwrap();
+
+        let cycles_map: HashMap<Uuid, Cycle> = cycles
+            .iter()
+            .map(|cycle| {
+                (
+                    cycle.id,
+                    Cycle {
+                        id: cycle.id,
+                        created_at: cycle.created_at,
+                        updated_at: cycle.updated_at,
+                        name: cycle.name.clone(),
+                        owner_id: cycle.owner_id,
+                        goal: cycle.goal.clone(),
+                        project_id: cycle.project_id,
+                        team_id: cycle.team_id,
+                        starts_at: cycle.starts_at,
+                        ends_at: cycle.ends_at,
+                        closed_at: cycle.closed_at,
+                        final_scope_count: cycle.final_scope_count,
+                        final_completed_count: cycle.final_completed_count,
+                    },
+                )
+            })
+            .collect();
+
+        Ok(cycles_map)
+    }
+}
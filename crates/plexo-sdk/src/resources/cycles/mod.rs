@@ -0,0 +1,5 @@
+pub mod cycle;
+pub mod extensions;
+pub mod loader;
+pub mod operations;
+pub mod relations;
@@ -0,0 +1,448 @@
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, PgConnection, Row};
+use uuid::Uuid;
+
+use crate::common::filters::{
+    DateTimeComparison, SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect, StringComparison, UuidComparison,
+};
+use crate::common::pagination::{Page, PageInput};
+use crate::{
+    backend::engine::SDKEngine,
+    common::commons::{SortOrder, UpdateListInput},
+    errors::sdk::SDKError,
+};
+
+use super::cycle::Cycle;
+
+const CYCLES_SORTABLE_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "name", "starts_at", "ends_at"];
+
+#[async_trait]
+pub trait CycleCrudOperations {
+    async fn create_cycle(&self, input: CreateCycleInput) -> Result<Cycle, SDKError>;
+    async fn get_cycle(&self, id: Uuid) -> Result<Cycle, SDKError>;
+    async fn get_cycles(&self, input: GetCyclesInput) -> Result<Vec<Cycle>, SDKError>;
+    async fn get_cycles_page(&self, input: PageInput<GetCyclesWhere>) -> Result<Page<Cycle>, SDKError>;
+    async fn update_cycle(&self, id: Uuid, input: UpdateCycleInput) -> Result<Cycle, SDKError>;
+    async fn delete_cycle(&self, id: Uuid) -> Result<Cycle, SDKError>;
+}
+
+/// A cycle belongs to exactly one of `project_id` or `team_id`.
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateCycleInput {
+    pub name: String,
+
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+
+    #[builder(setter(strip_option), default)]
+    pub goal: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub team_id: Option<Uuid>,
+
+    #[builder(setter(strip_option), default)]
+    pub tasks: Option<Vec<Uuid>>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateCycleInput {
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub goal: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub starts_at: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub ends_at: Option<DateTime<Utc>>,
+
+    #[builder(setter(strip_option), default)]
+    pub tasks: Option<UpdateListInput>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetCyclesInput {
+    #[builder(setter(strip_option), default)]
+    pub filter: Option<GetCyclesWhere>,
+
+    #[builder(setter(strip_option), default)]
+    pub sort_by: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub sort_order: Option<SortOrder>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetCyclesWhere {
+    #[builder(setter(strip_option), default)]
+    pub ids: Option<Vec<Uuid>>,
+    #[builder(setter(into, strip_option), default)]
+    pub name: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub owner_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub project_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub team_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub starts_at: Option<DateTimeComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub ends_at: Option<DateTimeComparison>,
+    #[builder(setter(strip_option), default)]
+    pub is_closed: Option<bool>,
+
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _and: Option<Vec<GetCyclesWhere>>,
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _or: Option<Vec<GetCyclesWhere>>,
+}
+
+impl SQLFilter for GetCyclesWhere {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
+
+        if let Some(ids) = &self.ids {
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
+        }
+
+        if let Some(name) = &self.name {
+            conditions.push(name.compile_sql("name"));
+        }
+
+        if let Some(owner_id) = &self.owner_id {
+            conditions.push(owner_id.compile_sql("owner_id"));
+        }
+
+        if let Some(project_id) = &self.project_id {
+            conditions.push(project_id.compile_sql("project_id"));
+        }
+
+        if let Some(team_id) = &self.team_id {
+            conditions.push(team_id.compile_sql("team_id"));
+        }
+
+        if let Some(starts_at) = &self.starts_at {
+            conditions.push(starts_at.compile_sql("starts_at"));
+        }
+
+        if let Some(ends_at) = &self.ends_at {
+            conditions.push(ends_at.compile_sql("ends_at"));
+        }
+
+        if let Some(is_closed) = self.is_closed {
+            conditions.push(SQLCondition::Expression(
+                match is_closed {
+                    true => &["closed_at IS NOT NULL"],
+                    false => &["closed_at IS NULL"],
+                },
+                vec![],
+            ));
+        }
+
+        if let Some(ands) = &self._and {
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
+        }
+
+        if let Some(ors) = &self._or {
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
+        }
+
+        SQLCondition::And(conditions)
+    }
+}
+
+#[async_trait]
+impl CycleCrudOperations for SDKEngine {
+    async fn create_cycle(&self, input: CreateCycleInput) -> Result<Cycle, SDKError> {
+        if input.project_id.is_some() == input.team_id.is_some() {
+            return Err(SDKError::InvalidCycle(
+                "a cycle belongs to either a project or a team".to_string(),
+            ));
+        }
+
+        validate_cycle_dates(input.starts_at, input.ends_at)?;
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let cycle_info = sqlx::query!(
+            r#"
+            INSERT INTO cycles (name, owner_id, goal, project_id, team_id, starts_at, ends_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+            input.name,
+            input.owner_id,
+            input.goal,
+            input.project_id,
+            input.team_id,
+            input.starts_at,
+            input.ends_at,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let Some(tasks) = input.tasks {
+            set_cycle_tasks(&mut tx, cycle_info.id, &tasks).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(Cycle {
+            id: cycle_info.id,
+            created_at: cycle_info.created_at,
+            updated_at: cycle_info.updated_at,
+            name: cycle_info.name,
+            owner_id: cycle_info.owner_id,
+            goal: cycle_info.goal,
+            project_id: cycle_info.project_id,
+            team_id: cycle_info.team_id,
+            starts_at: cycle_info.starts_at,
+            ends_at: cycle_info.ends_at,
+            closed_at: cycle_info.closed_at,
+            final_scope_count: cycle_info.final_scope_count,
+            final_completed_count: cycle_info.final_completed_count,
+        })
+    }
+
+    async fn get_cycle(&self, id: Uuid) -> Result<Cycle, SDKError> {
+        let cycle_info = sqlx::query!(
+            r#"
+            SELECT * FROM cycles
+            WHERE id = $1
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(Cycle {
+            id: cycle_info.id,
+            created_at: cycle_info.created_at,
+            updated_at: cycle_info.updated_at,
+            name: cycle_info.name,
+            owner_id: cycle_info.owner_id,
+            goal: cycle_info.goal,
+            project_id: cycle_info.project_id,
+            team_id: cycle_info.team_id,
+            starts_at: cycle_info.starts_at,
+            ends_at: cycle_info.ends_at,
+            closed_at: cycle_info.closed_at,
+            final_scope_count: cycle_info.final_scope_count,
+            final_completed_count: cycle_info.final_completed_count,
+        })
+    }
+
+    async fn get_cycles(&self, input: GetCyclesInput) -> Result<Vec<Cycle>, SDKError> {
+        let mut query = SQLSelect::new("cycles", CYCLES_SORTABLE_COLUMNS)
+            .filter(input.filter.as_ref())
+            .sort(input.sort_by, input.sort_order)
+            .paginate(input.limit, input.offset)
+            .build()?;
+
+        let cycles_info = query.build().fetch_all(self.db_pool.as_ref()).await?;
+
+        let cycles = cycles_info.iter().map(cycle_from_row).collect();
+
+        Ok(cycles)
+    }
+
+    async fn get_cycles_page(&self, input: PageInput<GetCyclesWhere>) -> Result<Page<Cycle>, SDKError> {
+        SQLSelect::new("cycles", CYCLES_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), input, cycle_from_row)
+            .await
+    }
+
+    async fn update_cycle(&self, id: Uuid, input: UpdateCycleInput) -> Result<Cycle, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let cycle_info = sqlx::query!(
+            r#"
+            UPDATE cycles
+            SET
+                name = COALESCE($1, name),
+                goal = COALESCE($2, goal),
+                starts_at = COALESCE($3, starts_at),
+                ends_at = COALESCE($4, ends_at)
+            WHERE id = $5
+            RETURNING *
+            "#,
+            input.name,
+            input.goal,
+            input.starts_at,
+            input.ends_at,
+            id,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        validate_cycle_dates(cycle_info.starts_at, cycle_info.ends_at)?;
+
+        if let Some(tasks) = input.tasks {
+            set_cycle_tasks(&mut tx, id, &tasks.add).await?;
+
+            sqlx::query!(
+                r#"
+                UPDATE tasks SET cycle_id = NULL
+                WHERE cycle_id = $1 AND id = ANY($2)
+                "#,
+                id,
+                &tasks.remove,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(Cycle {
+            id: cycle_info.id,
+            created_at: cycle_info.created_at,
+            updated_at: cycle_info.updated_at,
+            name: cycle_info.name,
+            owner_id: cycle_info.owner_id,
+            goal: cycle_info.goal,
+            project_id: cycle_info.project_id,
+            team_id: cycle_info.team_id,
+            starts_at: cycle_info.starts_at,
+            ends_at: cycle_info.ends_at,
+            closed_at: cycle_info.closed_at,
+            final_scope_count: cycle_info.final_scope_count,
+            final_completed_count: cycle_info.final_completed_count,
+        })
+    }
+
+    async fn delete_cycle(&self, id: Uuid) -> Result<Cycle, SDKError> {
+        let cycle_info = sqlx::query!(
+            r#"
+            DELETE FROM cycles WHERE id = $1
+            RETURNING *
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(Cycle {
+            id: cycle_info.id,
+            created_at: cycle_info.created_at,
+            updated_at: cycle_info.updated_at,
+            name: cycle_info.name,
+            owner_id: cycle_info.owner_id,
+            goal: cycle_info.goal,
+            project_id: cycle_info.project_id,
+            team_id: cycle_info.team_id,
+            starts_at: cycle_info.starts_at,
+            ends_at: cycle_info.ends_at,
+            closed_at: cycle_info.closed_at,
+            final_scope_count: cycle_info.final_scope_count,
+            final_completed_count: cycle_info.final_completed_count,
+        })
+    }
+}
+
+fn validate_cycle_dates(starts_at: DateTime<Utc>, ends_at: DateTime<Utc>) -> Result<(), SDKError> {
+    if ends_at <= starts_at {
+        return Err(SDKError::InvalidCycle("a cycle must end after it starts".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Fails unless the cycle is open and the tasks belong to its project, or to a project of its team.
+pub(crate) async fn check_cycle_tasks(
+    conn: &mut PgConnection,
+    cycle_id: Uuid,
+    task_ids: &[Uuid],
+) -> Result<(), SDKError> {
+    let cycle_info = sqlx::query!(
+        r#"
+        SELECT
+            closed_at IS NOT NULL AS "closed!",
+            (
+                SELECT COUNT(*) FROM tasks
+                WHERE tasks.id = ANY($2) AND NOT COALESCE(
+                    tasks.project_id = cycles.project_id
+                        OR EXISTS (
+                            SELECT 1 FROM teams_by_projects
+                            WHERE teams_by_projects.team_id = cycles.team_id
+                                AND teams_by_projects.project_id = tasks.project_id
+                        ),
+                    false
+                )
+            ) AS "outside!"
+        FROM cycles
+        WHERE id = $1
+        "#,
+        cycle_id,
+        task_ids,
+    )
+    .fetch_optional(conn)
+    .await?
+    .ok_or(SDKError::ResourceNotFound)?;
+
+    if cycle_info.closed {
+        return Err(SDKError::InvalidCycle(format!("cycle {cycle_id} is closed")));
+    }
+
+    if cycle_info.outside > 0 {
+        return Err(SDKError::InvalidCycle(format!(
+            "{} task(s) belong to another project or team than cycle {cycle_id}",
+            cycle_info.outside
+        )));
+    }
+
+    Ok(())
+}
+
+async fn set_cycle_tasks(conn: &mut PgConnection, cycle_id: Uuid, task_ids: &[Uuid]) -> Result<(), SDKError> {
+    check_cycle_tasks(&mut *conn, cycle_id, task_ids).await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE tasks SET cycle_id = $1
+        WHERE id = ANY($2)
+        "#,
+        cycle_id,
+        task_ids,
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+pub(crate) fn cycle_from_row(cycle_info: &PgRow) -> Cycle {
+    Cycle {
+        id: cycle_info.get("id"),
+        created_at: cycle_info.get("created_at"),
+        updated_at: cycle_info.get("updated_at"),
+        name: cycle_info.get("name"),
+        owner_id: cycle_info.get("owner_id"),
+        goal: cycle_info.get("goal"),
+        project_id: cycle_info.get("project_id"),
+        team_id: cycle_info.get("team_id"),
+        starts_at: cycle_info.get("starts_at"),
+        ends_at: cycle_info.get("ends_at"),
+        closed_at: cycle_info.get("closed_at"),
+        final_scope_count: cycle_info.get("final_scope_count"),
+        final_completed_count: cycle_info.get("final_completed_count"),
+    }
+}
@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    backend::loaders::SDKLoaders,
+    errors::sdk::SDKError,
+    resources::{members::member::Member, projects::project::Project, tasks::task::Task, teams::team::Team},
+};
+
+use super::cycle::Cycle;
+
+#[async_trait]
+pub trait CycleRelations {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Option<Project>, SDKError>;
+    async fn team(&self, loaders: &SDKLoaders) -> Result<Option<Team>, SDKError>;
+    async fn tasks(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError>;
+}
+
+#[async_trait]
+impl CycleRelations for Cycle {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
+        let data = loaders.member_loader.load_one(self.owner_id).await.unwrap().unwrap();
+
+        Ok(data)
+    }
+
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Option<Project>, SDKError> {
+        let Some(project_id) = self.project_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.project_loader.load_one(project_id).await.unwrap();
+
+        Ok(data)
+    }
+
+    async fn team(&self, loaders: &SDKLoaders) -> Result<Option<Team>, SDKError> {
+        let Some(team_id) = self.team_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.team_loader.load_one(team_id).await.unwrap();
+
+        Ok(data)
+    }
+
+    async fn tasks(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT id FROM tasks
+            WHERE cycle_id = $1
+            ORDER BY created_at, count, id
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await?
+        .into_iter()
+        .map(|t| t.id)
+        .collect();
+
+        let tasks_map = loaders.task_loader.load_many(ids.clone()).await.unwrap();
+
+        Ok(ids.into_iter().filter_map(|id| tasks_map.get(&id).cloned()).collect())
+    }
+}
@@ -0,0 +1,22 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKLabelGroup")]
+pub struct LabelGroup {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub name: String,
+    pub owner_id: Uuid,
+
+    pub description: Option<String>,
+    /// A task has at most one label of an exclusive group, adding another one replaces it.
+    pub exclusive: bool,
+}
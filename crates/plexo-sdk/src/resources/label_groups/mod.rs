@@ -0,0 +1,3 @@
+pub mod label_group;
+pub mod operations;
+pub mod relations;
@@ -0,0 +1,284 @@
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, PgConnection, Row};
+use uuid::Uuid;
+
+use crate::common::filters::{SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect, StringComparison};
+use crate::common::pagination::{Page, PageInput};
+use crate::{backend::engine::SDKEngine, common::commons::SortOrder, errors::sdk::SDKError};
+
+use super::label_group::LabelGroup;
+
+const LABEL_GROUPS_SORTABLE_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "name"];
+
+#[async_trait]
+pub trait LabelGroupCrudOperations {
+    async fn create_label_group(&self, input: CreateLabelGroupInput) -> Result<LabelGroup, SDKError>;
+    async fn get_label_group(&self, id: Uuid) -> Result<LabelGroup, SDKError>;
+    async fn get_label_groups(&self, input: GetLabelGroupsInput) -> Result<Vec<LabelGroup>, SDKError>;
+    async fn get_label_groups_page(&self, input: PageInput<GetLabelGroupsWhere>) -> Result<Page<LabelGroup>, SDKError>;
+    async fn update_label_group(&self, id: Uuid, input: UpdateLabelGroupInput) -> Result<LabelGroup, SDKError>;
+    async fn delete_label_group(&self, id: Uuid) -> Result<LabelGroup, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateLabelGroupInput {
+    pub name: String,
+
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub exclusive: Option<bool>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateLabelGroupInput {
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+    /// Making a group exclusive fails while a task has more than one of its labels.
+    #[builder(setter(strip_option), default)]
+    pub exclusive: Option<bool>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetLabelGroupsInput {
+    #[builder(setter(strip_option), default)]
+    pub filter: Option<GetLabelGroupsWhere>,
+
+    #[builder(setter(strip_option), default)]
+    pub sort_by: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub sort_order: Option<SortOrder>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetLabelGroupsWhere {
+    #[builder(setter(strip_option), default)]
+    pub ids: Option<Vec<Uuid>>,
+    #[builder(setter(into, strip_option), default)]
+    pub name: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub description: Option<StringComparison>,
+
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _and: Option<Vec<GetLabelGroupsWhere>>,
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _or: Option<Vec<GetLabelGroupsWhere>>,
+}
+
+impl SQLFilter for GetLabelGroupsWhere {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
+
+        if let Some(ids) = &self.ids {
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
+        }
+
+        if let Some(name) = &self.name {
+            conditions.push(name.compile_sql("name"));
+        }
+
+        if let Some(description) = &self.description {
+            conditions.push(description.compile_sql("description"));
+        }
+
+        if let Some(ands) = &self._and {
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
+        }
+
+        if let Some(ors) = &self._or {
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
+        }
+
+        SQLCondition::And(conditions)
+    }
+}
+
+/// Fails if the group is exclusive and a task has more than one of its labels.
+pub(crate) async fn check_exclusive_group(conn: &mut PgConnection, group_id: Uuid) -> Result<(), SDKError> {
+    let conflicts = sqlx::query!(
+        r#"
+        SELECT COUNT(*) AS "conflicts!" FROM (
+            SELECT labels_by_tasks.task_id FROM labels_by_tasks
+            JOIN labels ON labels.id = labels_by_tasks.label_id
+            JOIN label_groups ON label_groups.id = labels.group_id
+            WHERE label_groups.id = $1 AND label_groups.exclusive
+            GROUP BY labels_by_tasks.task_id
+            HAVING COUNT(*) > 1
+        ) AS conflicting_tasks
+        "#,
+        group_id,
+    )
+    .fetch_one(conn)
+    .await?
+    .conflicts;
+
+    if conflicts > 0 {
+        return Err(SDKError::InvalidLabel(format!(
+            "{} task(s) have more than one label of the exclusive group",
+            conflicts
+        )));
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl LabelGroupCrudOperations for SDKEngine {
+    async fn create_label_group(&self, input: CreateLabelGroupInput) -> Result<LabelGroup, SDKError> {
+        let label_group_info = sqlx::query!(
+            r#"
+            INSERT INTO label_groups (name, description, exclusive, owner_id)
+            VALUES ($1, $2, COALESCE($3, false), $4)
+            RETURNING *
+            "#,
+            input.name,
+            input.description,
+            input.exclusive,
+            input.owner_id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(LabelGroup {
+            id: label_group_info.id,
+            created_at: label_group_info.created_at,
+            updated_at: label_group_info.updated_at,
+            name: label_group_info.name,
+            owner_id: label_group_info.owner_id,
+            description: label_group_info.description,
+            exclusive: label_group_info.exclusive,
+        })
+    }
+
+    async fn get_label_group(&self, id: Uuid) -> Result<LabelGroup, SDKError> {
+        let label_group_info = sqlx::query!(
+            r#"
+            SELECT * FROM label_groups
+            WHERE id = $1
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(LabelGroup {
+            id: label_group_info.id,
+            created_at: label_group_info.created_at,
+            updated_at: label_group_info.updated_at,
+            name: label_group_info.name,
+            owner_id: label_group_info.owner_id,
+            description: label_group_info.description,
+            exclusive: label_group_info.exclusive,
+        })
+    }
+
+    async fn get_label_groups(&self, input: GetLabelGroupsInput) -> Result<Vec<LabelGroup>, SDKError> {
+        let mut query = SQLSelect::new("label_groups", LABEL_GROUPS_SORTABLE_COLUMNS)
+            .filter(input.filter.as_ref())
+            .sort(input.sort_by, input.sort_order)
+            .paginate(input.limit, input.offset)
+            .build()?;
+
+        let label_groups_info = query.build().fetch_all(self.db_pool.as_ref()).await?;
+
+        let label_groups = label_groups_info.iter().map(label_group_from_row).collect();
+
+        Ok(label_groups)
+    }
+
+    async fn get_label_groups_page(&self, input: PageInput<GetLabelGroupsWhere>) -> Result<Page<LabelGroup>, SDKError> {
+        SQLSelect::new("label_groups", LABEL_GROUPS_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), input, label_group_from_row)
+            .await
+    }
+
+    async fn update_label_group(&self, id: Uuid, input: UpdateLabelGroupInput) -> Result<LabelGroup, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let label_group_info = sqlx::query!(
+            r#"
+            UPDATE label_groups
+            SET
+                name = COALESCE($1, name),
+                description = COALESCE($2, description),
+                exclusive = COALESCE($3, exclusive)
+            WHERE id = $4
+            RETURNING *
+            "#,
+            input.name,
+            input.description,
+            input.exclusive,
+            id,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        check_exclusive_group(&mut tx, id).await?;
+
+        tx.commit().await?;
+
+        Ok(LabelGroup {
+            id: label_group_info.id,
+            created_at: label_group_info.created_at,
+            updated_at: label_group_info.updated_at,
+            name: label_group_info.name,
+            owner_id: label_group_info.owner_id,
+            description: label_group_info.description,
+            exclusive: label_group_info.exclusive,
+        })
+    }
+
+    async fn delete_label_group(&self, id: Uuid) -> Result<LabelGroup, SDKError> {
+        let label_group_info = sqlx::query!(
+            r#"
+            DELETE FROM label_groups WHERE id = $1
+            RETURNING *
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(LabelGroup {
+            id: label_group_info.id,
+            created_at: label_group_info.created_at,
+            updated_at: label_group_info.updated_at,
+            name: label_group_info.name,
+            owner_id: label_group_info.owner_id,
+            description: label_group_info.description,
+            exclusive: label_group_info.exclusive,
+        })
+    }
+}
+
+fn label_group_from_row(label_group_info: &PgRow) -> LabelGroup {
+    LabelGroup {
+        id: label_group_info.get("id"),
+        created_at: label_group_info.get("created_at"),
+        updated_at: label_group_info.get("updated_at"),
+        name: label_group_info.get("name"),
+        owner_id: label_group_info.get("owner_id"),
+        description: label_group_info.get("description"),
+        exclusive: label_group_info.get("exclusive"),
+    }
+}
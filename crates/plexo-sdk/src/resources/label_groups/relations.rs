@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    backend::loaders::SDKLoaders,
+    errors::sdk::SDKError,
+    resources::{labels::label::Label, members::member::Member},
+};
+
+use super::label_group::LabelGroup;
+
+#[async_trait]
+pub trait LabelGroupRelations {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
+    async fn labels(&self, loaders: &SDKLoaders) -> Result<Vec<Label>, SDKError>;
+}
+
+#[async_trait]
+impl LabelGroupRelations for LabelGroup {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
+        let data = loaders.member_loader.load_one(self.owner_id).await.unwrap().unwrap();
+
+        Ok(data)
+    }
+
+    async fn labels(&self, loaders: &SDKLoaders) -> Result<Vec<Label>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT id FROM labels
+            WHERE group_id = $1
+            ORDER BY name
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await?
+        .into_iter()
+        .map(|label| label.id)
+        .collect();
+
+        let labels_map = loaders.label_loader.load_many(ids.clone()).await.unwrap();
+
+        let labels: Vec<Label> = ids.into_iter().filter_map(|id| labels_map.get(&id).cloned()).collect();
+
+        Ok(labels)
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+
+use super::{label::Label, operations::LabelCrudOperations};
+
+#[async_trait]
+pub trait LabelsExtensionOperations {
+    /// Moves every task and task template of `source` to `target` and deletes `source`. The target
+    /// is a workspace label or has the same scope as the source.
+    async fn merge_labels(&self, source_id: Uuid, target_id: Uuid) -> Result<Label, SDKError>;
+}
+
+#[async_trait]
+impl LabelsExtensionOperations for SDKEngine {
+    async fn merge_labels(&self, source_id: Uuid, target_id: Uuid) -> Result<Label, SDKError> {
+        if source_id == target_id {
+            return Err(SDKError::InvalidLabel(
+                "a label can't be merged into itself".to_string(),
+            ));
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let scopes: HashMap<Uuid, (Option<Uuid>, Option<Uuid>)> =
+            sqlx::query("SELECT id, project_id, team_id FROM labels WHERE id = ANY($1) FOR UPDATE")
+                .bind(vec![source_id, target_id])
+                .fetch_all(&mut *tx)
+                .await?
+                .iter()
+                .map(|label_info| {
+                    (
+                        label_info.get("id"),
+                        (label_info.get("project_id"), label_info.get("team_id")),
+                    )
+                })
+                .collect();
+
+        let (Some(source_scope), Some(target_scope)) = (scopes.get(&source_id), scopes.get(&target_id)) else {
+            return Err(SDKError::ResourceNotFound);
+        };
+
+        // Every task of the source must stay in scope for the target.
+        if *target_scope != (None, None) && source_scope != target_scope {
+            return Err(SDKError::InvalidLabel(
+                "labels of different projects or teams can't be merged".to_string(),
+            ));
+        }
+
+        // Tasks already labelled with the target keep a single link.
+        sqlx::query(
+            r#"
+            INSERT INTO labels_by_tasks (label_id, task_id)
+            SELECT $2, task_id FROM labels_by_tasks
+            WHERE label_id = $1
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(source_id)
+        .bind(target_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM labels_by_tasks WHERE label_id = $1")
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE task_template_items
+            SET label_ids = ARRAY(SELECT DISTINCT unnest(array_replace(label_ids, $1, $2)))
+            WHERE $1 = ANY(label_ids)
+            "#,
+        )
+        .bind(source_id)
+        .bind(target_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM labels WHERE id = $1")
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        self.get_label(target_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::backend::testing::{test_engine, test_member, test_project};
+    use crate::common::commons::UpdateListInput;
+    use crate::resources::label_groups::operations::{CreateLabelGroupInput, LabelGroupCrudOperations};
+    use crate::resources::labels::operations::CreateLabelInput;
+    use crate::resources::tasks::operations::{CreateTaskInput, TaskCrudOperations, UpdateTaskInput};
+
+    async fn task_labels(engine: &SDKEngine, task_id: Uuid) -> Vec<Uuid> {
+        sqlx::query_scalar("SELECT label_id FROM labels_by_tasks WHERE task_id = $1 ORDER BY label_id")
+            .bind(task_id)
+            .fetch_all(engine.db_pool.as_ref())
+            .await
+            .unwrap()
+    }
+
+    fn sorted(mut ids: Vec<Uuid>) -> Vec<Uuid> {
+        ids.sort();
+        ids
+    }
+
+    #[sqlx::test]
+    async fn exclusive_groups_replace_labels_and_merges_keep_one_link(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+
+        let group = engine
+            .create_label_group(CreateLabelGroupInput {
+                name: "type".to_string(),
+                owner_id,
+                exclusive: Some(true),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let label = |name: &str, group_id: Option<Uuid>, project_id: Option<Uuid>| CreateLabelInput {
+            name: name.to_string(),
+            owner_id,
+            group_id,
+            project_id,
+            ..Default::default()
+        };
+        let bug = engine.create_label(label("bug", Some(group.id), None)).await.unwrap().id;
+        let feature = engine.create_label(label("feature", Some(group.id), None)).await.unwrap().id;
+        let urgent = engine.create_label(label("urgent", None, None)).await.unwrap().id;
+        let asap = engine.create_label(label("asap", None, None)).await.unwrap().id;
+
+        let task = engine
+            .create_task(CreateTaskInput {
+                title: "Labelled".to_string(),
+                owner_id,
+                labels: Some(vec![bug, urgent, asap]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        engine
+            .update_task(
+                task.id,
+                UpdateTaskInput {
+                    labels: Some(UpdateListInput {
+                        add: vec![feature],
+                        remove: vec![],
+                    }),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(task_labels(&engine, task.id).await, sorted(vec![feature, urgent, asap]));
+
+        engine.merge_labels(asap, urgent).await.unwrap();
+        assert_eq!(task_labels(&engine, task.id).await, sorted(vec![feature, urgent]));
+        assert!(engine.get_label(asap).await.is_err());
+
+        let project_id = test_project(&engine, owner_id, "LBL").await;
+        let scoped = engine
+            .create_label(label("scoped", None, Some(project_id)))
+            .await
+            .unwrap()
+            .id;
+        let err = engine.merge_labels(urgent, scoped).await.unwrap_err();
+        assert!(matches!(err, SDKError::InvalidLabel(..)));
+    }
+}
@@ -6,6 +6,8 @@ use poem_openapi::Object;
 use serde::Serialize;
 use uuid::Uuid;
 
+/// A label belongs to a project, a team or, without either, to the whole workspace. Names are
+/// unique within that scope.
 #[derive(Debug, SimpleObject, Object, Clone, Serialize)]
 #[graphql(name = "SDKLabel")]
 pub struct Label {
@@ -18,4 +20,8 @@ pub struct Label {
 
     pub description: Option<String>,
     pub color: Option<String>,
+
+    pub project_id: Option<Uuid>,
+    pub team_id: Option<Uuid>,
+    pub group_id: Option<Uuid>,
 }
@@ -46,6 +46,9 @@ impl Loader<Uuid> for LabelLoader {
                         owner_id: label.owner_id,
                         description: label.description.clone(),
                         color: label.color.clone(),
+                        project_id: label.project_id,
+                        team_id: label.team_id,
+                        group_id: label.group_id,
                     },
                 )
             })
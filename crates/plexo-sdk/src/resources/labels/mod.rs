@@ -1,3 +1,4 @@
+pub mod extensions;
 pub mod label;
 pub mod loader;
 pub mod operations;
@@ -3,22 +3,29 @@ use async_trait::async_trait;
 use derive_builder::Builder;
 use poem_openapi::Object;
 use serde::Serialize;
-use sqlx::Row;
+use sqlx::{postgres::PgRow, PgConnection, Row};
 use uuid::Uuid;
 
+use crate::common::filters::{SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect, StringComparison, UuidComparison};
+use crate::common::pagination::{Page, PageInput};
+use crate::resources::label_groups::operations::check_exclusive_group;
 use crate::{backend::engine::SDKEngine, common::commons::SortOrder, errors::sdk::SDKError};
 
 use super::label::Label;
 
+const LABELS_SORTABLE_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "name", "color"];
+
 #[async_trait]
 pub trait LabelCrudOperations {
     async fn create_label(&self, input: CreateLabelInput) -> Result<Label, SDKError>;
     async fn get_label(&self, id: Uuid) -> Result<Label, SDKError>;
     async fn get_labels(&self, input: GetLabelsInput) -> Result<Vec<Label>, SDKError>;
+    async fn get_labels_page(&self, input: PageInput<GetLabelsWhere>) -> Result<Page<Label>, SDKError>;
     async fn update_label(&self, id: Uuid, input: UpdateLabelInput) -> Result<Label, SDKError>;
     async fn delete_label(&self, id: Uuid) -> Result<Label, SDKError>;
 }
 
+/// A label is scoped to at most one of `project_id` or `team_id`.
 #[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
 #[builder(pattern = "owned")]
 pub struct CreateLabelInput {
@@ -31,6 +38,13 @@ pub struct CreateLabelInput {
     pub description: Option<String>,
     #[builder(setter(strip_option), default)]
     pub color: Option<String>,
+
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub team_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub group_id: Option<Uuid>,
 }
 
 #[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
@@ -42,6 +56,8 @@ pub struct UpdateLabelInput {
     pub description: Option<String>,
     #[builder(setter(strip_option), default)]
     pub color: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub group_id: Option<Uuid>,
 }
 
 #[derive(Default, Builder, Object, InputObject)]
@@ -66,12 +82,18 @@ pub struct GetLabelsInput {
 pub struct GetLabelsWhere {
     #[builder(setter(strip_option), default)]
     pub ids: Option<Vec<Uuid>>,
-    #[builder(setter(strip_option), default)]
-    pub name: Option<String>,
-    #[builder(setter(strip_option), default)]
-    pub description: Option<String>,
-    #[builder(setter(strip_option), default)]
-    pub color: Option<String>,
+    #[builder(setter(into, strip_option), default)]
+    pub name: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub description: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub color: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub project_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub team_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub group_id: Option<UuidComparison>,
 
     #[oai(skip)]
     #[builder(setter(strip_option), default)]
@@ -81,70 +103,128 @@ pub struct GetLabelsWhere {
     pub _or: Option<Vec<GetLabelsWhere>>,
 }
 
-impl GetLabelsWhere {
-    pub fn compile_sql(&self) -> String {
-        let mut and_clauses = Vec::new();
-        let mut or_clauses = Vec::new();
+impl SQLFilter for GetLabelsWhere {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
 
         if let Some(ids) = &self.ids {
-            and_clauses.push(format!(
-                "id = ANY(array[{}]::uuid[])",
-                ids.iter()
-                    .map(|x| format!("'{}'", x))
-                    .collect::<Vec<String>>()
-                    .join(",")
-            ));
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
         }
 
         if let Some(name) = &self.name {
-            and_clauses.push(format!("name = '{}'", name));
+            conditions.push(name.compile_sql("name"));
         }
+
         if let Some(description) = &self.description {
-            and_clauses.push(format!("description = '{}'", description));
+            conditions.push(description.compile_sql("description"));
         }
+
         if let Some(color) = &self.color {
-            and_clauses.push(format!("color = '{}'", color));
+            conditions.push(color.compile_sql("color"));
         }
 
-        if let Some(ands) = &self._and {
-            for and in ands {
-                and_clauses.push(and.compile_sql());
-            }
+        if let Some(project_id) = &self.project_id {
+            conditions.push(project_id.compile_sql("project_id"));
         }
-        if let Some(ors) = &self._or {
-            for or in ors {
-                or_clauses.push(or.compile_sql());
-            }
+
+        if let Some(team_id) = &self.team_id {
+            conditions.push(team_id.compile_sql("team_id"));
+        }
+
+        if let Some(group_id) = &self.group_id {
+            conditions.push(group_id.compile_sql("group_id"));
         }
 
-        let mut where_clause = String::new();
-        if !and_clauses.is_empty() {
-            where_clause.push_str(&format!("({})", and_clauses.join(" AND ")));
+        if let Some(ands) = &self._and {
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
         }
-        if !or_clauses.is_empty() {
-            if !where_clause.is_empty() {
-                where_clause.push_str(" OR ");
-            }
-            where_clause.push_str(&format!("({})", or_clauses.join(" OR ")));
+
+        if let Some(ors) = &self._or {
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
         }
 
-        where_clause
+        SQLCondition::And(conditions)
     }
 }
 
+/// Fails if one of `labels` can't label tasks of `project_id`.
+pub(crate) async fn check_label_scope(
+    conn: &mut PgConnection,
+    project_id: Option<Uuid>,
+    labels: &[Uuid],
+) -> Result<(), SDKError> {
+    let out_of_scope = sqlx::query!(
+        r#"
+        SELECT name FROM labels
+        WHERE id = ANY($1) AND NOT label_in_scope(id, $2)
+        "#,
+        labels,
+        project_id,
+    )
+    .fetch_all(conn)
+    .await?;
+
+    if let Some(label) = out_of_scope.first() {
+        return Err(SDKError::InvalidLabel(format!(
+            "{} belongs to another project or team",
+            label.name
+        )));
+    }
+
+    Ok(())
+}
+
+/// Relinks the labels of tasks that changed project to the labels of the same name in scope for
+/// the new project, and unlinks the ones without such a label.
+pub(crate) async fn rescope_task_labels(conn: &mut PgConnection, task_ids: &[Uuid]) -> Result<(), SDKError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO labels_by_tasks (label_id, task_id)
+        SELECT DISTINCT scoped_label(labels_by_tasks.label_id, tasks.project_id), tasks.id
+        FROM labels_by_tasks
+        JOIN tasks ON tasks.id = labels_by_tasks.task_id
+        WHERE tasks.id = ANY($1)
+            AND NOT label_in_scope(labels_by_tasks.label_id, tasks.project_id)
+            AND scoped_label(labels_by_tasks.label_id, tasks.project_id) IS NOT NULL
+        ON CONFLICT DO NOTHING
+        "#,
+        task_ids,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM labels_by_tasks
+        USING tasks
+        WHERE tasks.id = labels_by_tasks.task_id
+            AND tasks.id = ANY($1)
+            AND NOT label_in_scope(labels_by_tasks.label_id, tasks.project_id)
+        "#,
+        task_ids,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
 #[async_trait]
 impl LabelCrudOperations for SDKEngine {
     async fn create_label(&self, input: CreateLabelInput) -> Result<Label, SDKError> {
         let label_info = sqlx::query!(
             r#"
-            INSERT INTO labels (name, description, color, owner_id)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO labels (name, description, color, owner_id, project_id, team_id, group_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             RETURNING *
             "#,
             input.name,
             input.description,
             input.color,
             input.owner_id,
+            input.project_id,
+            input.team_id,
+            input.group_id,
         )
         .fetch_one(self.db_pool.as_ref())
         .await?;
@@ -157,6 +237,9 @@ impl LabelCrudOperations for SDKEngine {
             owner_id: label_info.owner_id,
             description: label_info.description,
             color: label_info.color,
+            project_id: label_info.project_id,
+            team_id: label_info.team_id,
+            group_id: label_info.group_id,
         })
     }
 
@@ -179,69 +262,62 @@ impl LabelCrudOperations for SDKEngine {
             owner_id: label_info.owner_id,
             description: label_info.description,
             color: label_info.color,
+            project_id: label_info.project_id,
+            team_id: label_info.team_id,
+            group_id: label_info.group_id,
         })
     }
 
     async fn get_labels(&self, input: GetLabelsInput) -> Result<Vec<Label>, SDKError> {
-        let mut query = "SELECT * FROM labels ".to_string();
-
-        if let Some(filter) = input.filter {
-            query.push_str(format!("WHERE {} ", filter.compile_sql()).as_str());
-        }
+        let mut query = SQLSelect::new("labels", LABELS_SORTABLE_COLUMNS)
+            .filter(input.filter.as_ref())
+            .sort(input.sort_by, input.sort_order)
+            .paginate(input.limit, input.offset)
+            .build()?;
 
-        if let Some(sort_by) = input.sort_by {
-            query.push_str(format!("ORDER BY {} ", sort_by).as_str());
-        }
-
-        if let Some(sort_order) = input.sort_order {
-            query.push_str(format!("{} ", sort_order).as_str());
-        }
+        let labels_info = query.build().fetch_all(self.db_pool.as_ref()).await?;
 
-        if let Some(limit) = input.limit {
-            query.push_str(format!("LIMIT {} ", limit).as_str());
-        }
-
-        if let Some(offset) = input.offset {
-            query.push_str(format!("OFFSET {} ", offset).as_str());
-        }
-
-        let labels_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
-
-        let labels = labels_info
-            .into_iter()
-            .map(|label_info| Label {
-                id: label_info.get("id"),
-                created_at: label_info.get("created_at"),
-                updated_at: label_info.get("updated_at"),
-                name: label_info.get("name"),
-                owner_id: label_info.get("owner_id"),
-                description: label_info.get("description"),
-                color: label_info.get("color"),
-            })
-            .collect();
+        let labels = labels_info.iter().map(label_from_row).collect();
 
         Ok(labels)
     }
 
+    async fn get_labels_page(&self, input: PageInput<GetLabelsWhere>) -> Result<Page<Label>, SDKError> {
+        SQLSelect::new("labels", LABELS_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), input, label_from_row)
+            .await
+    }
+
     async fn update_label(&self, id: Uuid, input: UpdateLabelInput) -> Result<Label, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
         let label_info = sqlx::query!(
             r#"
             UPDATE labels
             SET
                 name = COALESCE($1, name),
                 description = COALESCE($2, description),
-                color = COALESCE($3, color)
-            WHERE id = $4
+                color = COALESCE($3, color),
+                group_id = COALESCE($4, group_id)
+            WHERE id = $5
             RETURNING *
             "#,
             input.name,
             input.description,
             input.color,
+            input.group_id,
             id,
         )
-        .fetch_one(self.db_pool.as_ref())
+        .fetch_one(&mut *tx)
         .await?;
 
+        // Tasks may already carry another label of the group it joins.
+        if let Some(group_id) = input.group_id {
+            check_exclusive_group(&mut tx, group_id).await?;
+        }
+
+        tx.commit().await?;
+
         Ok(Label {
             id: label_info.id,
             created_at: label_info.created_at,
@@ -250,6 +326,9 @@ impl LabelCrudOperations for SDKEngine {
             owner_id: label_info.owner_id,
             description: label_info.description,
             color: label_info.color,
+            project_id: label_info.project_id,
+            team_id: label_info.team_id,
+            group_id: label_info.group_id,
         })
     }
 
@@ -272,6 +351,24 @@ impl LabelCrudOperations for SDKEngine {
             owner_id: label_info.owner_id,
             description: label_info.description,
             color: label_info.color,
+            project_id: label_info.project_id,
+            team_id: label_info.team_id,
+            group_id: label_info.group_id,
         })
     }
 }
+
+fn label_from_row(label_info: &PgRow) -> Label {
+    Label {
+        id: label_info.get("id"),
+        created_at: label_info.get("created_at"),
+        updated_at: label_info.get("updated_at"),
+        name: label_info.get("name"),
+        owner_id: label_info.get("owner_id"),
+        description: label_info.get("description"),
+        color: label_info.get("color"),
+        project_id: label_info.get("project_id"),
+        team_id: label_info.get("team_id"),
+        group_id: label_info.get("group_id"),
+    }
+}
@@ -4,7 +4,13 @@ use uuid::Uuid;
 use crate::{
     backend::loaders::SDKLoaders,
     errors::sdk::SDKError,
-    resources::{members::member::Member, tasks::task::Task},
+    resources::{
+        label_groups::{label_group::LabelGroup, operations::LabelGroupCrudOperations},
+        members::member::Member,
+        projects::project::Project,
+        tasks::task::Task,
+        teams::team::Team,
+    },
 };
 
 use super::label::Label;
@@ -13,6 +19,9 @@ use super::label::Label;
 pub trait LabelRelations {
     async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
     async fn tasks(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError>;
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Option<Project>, SDKError>;
+    async fn team(&self, loaders: &SDKLoaders) -> Result<Option<Team>, SDKError>;
+    async fn group(&self, loaders: &SDKLoaders) -> Result<Option<LabelGroup>, SDKError>;
 }
 
 #[async_trait]
@@ -40,8 +49,36 @@ impl LabelRelations for Label {
 
         let tasks_map = loaders.task_loader.load_many(ids.clone()).await.unwrap();
 
-        let tasks: &Vec<Task> = &ids.into_iter().map(|id| tasks_map.get(&id).unwrap().clone()).collect();
+        let tasks: &Vec<Task> = &ids.into_iter().filter_map(|id| tasks_map.get(&id).cloned()).collect();
 
         Ok(tasks.clone())
     }
+
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Option<Project>, SDKError> {
+        let Some(project_id) = self.project_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.project_loader.load_one(project_id).await.unwrap();
+
+        Ok(data)
+    }
+
+    async fn team(&self, loaders: &SDKLoaders) -> Result<Option<Team>, SDKError> {
+        let Some(team_id) = self.team_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.team_loader.load_one(team_id).await.unwrap();
+
+        Ok(data)
+    }
+
+    async fn group(&self, loaders: &SDKLoaders) -> Result<Option<LabelGroup>, SDKError> {
+        let Some(group_id) = self.group_id else {
+            return Ok(None);
+        };
+
+        loaders.engine.get_label_group(group_id).await.map(Some)
+    }
 }
@@ -6,18 +6,23 @@ use async_trait::async_trait;
 use derive_builder::Builder;
 use poem_openapi::Object;
 use serde::Serialize;
-use sqlx::Row;
+use sqlx::{postgres::PgRow, Row};
 use uuid::Uuid;
 
+use crate::common::filters::{enum_comparison, SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect, StringComparison};
+use crate::common::pagination::{Page, PageInput};
 use crate::{backend::engine::SDKEngine, common::commons::SortOrder, errors::sdk::SDKError};
 
 use super::member::{Member, MemberRole};
 
+const MEMBERS_SORTABLE_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "name", "email", "role"];
+
 #[async_trait]
 pub trait MemberCrudOperations {
     async fn create_member(&self, input: CreateMemberInput) -> Result<Member, SDKError>;
     async fn get_member(&self, id: Uuid) -> Result<Member, SDKError>;
     async fn get_members(&self, input: GetMembersInput) -> Result<Vec<Member>, SDKError>;
+    async fn get_members_page(&self, input: PageInput<GetMembersWhere>) -> Result<Page<Member>, SDKError>;
     async fn update_member(&self, id: Uuid, input: UpdateMemberInput) -> Result<Member, SDKError>;
     async fn delete_member(&self, id: Uuid) -> Result<Member, SDKError>;
 }
@@ -80,18 +85,18 @@ pub struct GetMembersInput {
 pub struct GetMembersWhere {
     #[builder(setter(strip_option), default)]
     ids: Option<Vec<Uuid>>,
-    #[builder(setter(strip_option), default)]
-    name: Option<String>,
-    #[builder(setter(strip_option), default)]
-    email: Option<String>,
-    #[builder(setter(strip_option), default)]
-    role: Option<MemberRole>,
-    #[builder(setter(strip_option), default)]
-    github_id: Option<String>,
-    #[builder(setter(strip_option), default)]
-    google_id: Option<String>,
-    #[builder(setter(strip_option), default)]
-    photo_url: Option<String>,
+    #[builder(setter(into, strip_option), default)]
+    name: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    email: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    role: Option<MemberRoleComparison>,
+    #[builder(setter(into, strip_option), default)]
+    github_id: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    google_id: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    photo_url: Option<StringComparison>,
 
     #[oai(skip)]
     #[builder(setter(strip_option), default)]
@@ -101,64 +106,49 @@ pub struct GetMembersWhere {
     _or: Option<Vec<GetMembersWhere>>,
 }
 
-impl GetMembersWhere {
-    pub fn compile_sql(&self) -> String {
-        let mut where_clause = String::new();
-        let mut and_clauses = Vec::new();
-        let mut or_clauses = Vec::new();
+enum_comparison!(MemberRoleComparison, MemberRole);
+
+impl SQLFilter for GetMembersWhere {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
 
         if let Some(ids) = &self.ids {
-            and_clauses.push(format!(
-                "id = ANY(array[{}]::uuid[])",
-                ids.iter()
-                    .map(|x| format!("'{}'", x))
-                    .collect::<Vec<String>>()
-                    .join(",")
-            ));
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
         }
 
         if let Some(name) = &self.name {
-            and_clauses.push(format!("name = '{}'", name));
+            conditions.push(name.compile_sql("name"));
         }
+
         if let Some(email) = &self.email {
-            and_clauses.push(format!("email = '{}'", email));
+            conditions.push(email.compile_sql("email"));
         }
+
         if let Some(role) = &self.role {
-            and_clauses.push(format!("role = '{}'", role));
+            conditions.push(role.compile_sql("role"));
         }
+
         if let Some(github_id) = &self.github_id {
-            and_clauses.push(format!("github_id = '{}'", github_id));
+            conditions.push(github_id.compile_sql("github_id"));
         }
+
         if let Some(google_id) = &self.google_id {
-            and_clauses.push(format!("google_id = '{}'", google_id));
-        }
-        if let Some(photo_url) = &self.photo_url {
-            and_clauses.push(format!("photo_url = '{}'", photo_url));
+            conditions.push(google_id.compile_sql("google_id"));
         }
 
-        if let Some(and) = &self._and {
-            for and_clause in and {
-                and_clauses.push(and_clause.compile_sql());
-            }
-        }
-        if let Some(or) = &self._or {
-            for or_clause in or {
-                or_clauses.push(or_clause.compile_sql());
-            }
+        if let Some(photo_url) = &self.photo_url {
+            conditions.push(photo_url.compile_sql("photo_url"));
         }
 
-        if !and_clauses.is_empty() {
-            where_clause.push_str(&format!("({})", and_clauses.join(" AND ")));
+        if let Some(ands) = &self._and {
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
         }
 
-        if !or_clauses.is_empty() {
-            if !where_clause.is_empty() {
-                where_clause.push_str(" OR ");
-            }
-            where_clause.push_str(&format!("({})", or_clauses.join(" OR ")));
+        if let Some(ors) = &self._or {
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
         }
 
-        where_clause
+        SQLCondition::And(conditions)
     }
 }
 
@@ -233,52 +223,25 @@ impl MemberCrudOperations for SDKEngine {
     }
 
     async fn get_members(&self, input: GetMembersInput) -> Result<Vec<Member>, SDKError> {
-        let mut query = "SELECT * FROM members ".to_string();
-
-        if let Some(filter) = input.filter {
-            query.push_str(format!("WHERE {} ", filter.compile_sql()).as_str());
-        }
-
-        if let Some(sort_by) = input.sort_by {
-            query.push_str(format!("ORDER BY {} ", sort_by).as_str());
-        }
-
-        if let Some(sort_order) = input.sort_order {
-            query.push_str(format!("{} ", sort_order).as_str());
-        }
+        let mut query = SQLSelect::new("members", MEMBERS_SORTABLE_COLUMNS)
+            .filter(input.filter.as_ref())
+            .sort(input.sort_by, input.sort_order)
+            .paginate(input.limit, input.offset)
+            .build()?;
 
-        if let Some(limit) = input.limit {
-            query.push_str(format!("LIMIT {} ", limit).as_str());
-        }
+        let members_info = query.build().fetch_all(self.db_pool.as_ref()).await?;
 
-        if let Some(offset) = input.offset {
-            query.push_str(format!("OFFSET {} ", offset).as_str());
-        }
-
-        let members_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
-
-        let members = members_info
-            .iter()
-            .map(|x| Member {
-                id: x.get("id"),
-                created_at: x.get("created_at"),
-                updated_at: x.get("updated_at"),
-                name: x.get("name"),
-                email: x.get("email"),
-                role: x
-                    .get::<'_, Option<String>, _>("role")
-                    .and_then(|a| MemberRole::from_str(&a).ok())
-                    .unwrap_or_default(),
-                github_id: x.get("github_id"),
-                google_id: x.get("google_id"),
-                photo_url: x.get("photo_url"),
-                password_hash: x.get("password_hash"),
-            })
-            .collect::<Vec<Member>>();
+        let members = members_info.iter().map(member_from_row).collect::<Vec<Member>>();
 
         Ok(members)
     }
 
+    async fn get_members_page(&self, input: PageInput<GetMembersWhere>) -> Result<Page<Member>, SDKError> {
+        SQLSelect::new("members", MEMBERS_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), input, member_from_row)
+            .await
+    }
+
     async fn update_member(&self, id: Uuid, input: UpdateMemberInput) -> Result<Member, SDKError> {
         let member_final_info = sqlx::query!(
             r#"
@@ -403,3 +366,21 @@ impl MemberCrudOperations for SDKEngine {
         Ok(member)
     }
 }
+
+fn member_from_row(member_info: &PgRow) -> Member {
+    Member {
+        id: member_info.get("id"),
+        created_at: member_info.get("created_at"),
+        updated_at: member_info.get("updated_at"),
+        name: member_info.get("name"),
+        email: member_info.get("email"),
+        role: member_info
+            .get::<'_, Option<String>, _>("role")
+            .and_then(|a| MemberRole::from_str(&a).ok())
+            .unwrap_or_default(),
+        github_id: member_info.get("github_id"),
+        google_id: member_info.get("google_id"),
+        photo_url: member_info.get("photo_url"),
+        password_hash: member_info.get("password_hash"),
+    }
+}
@@ -19,7 +19,9 @@ impl Loader<Uuid> for MessageLoader {
     async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
         let messages = sqlx::query!(
             r#"
-            SELECT * FROM messages WHERE id = ANY($1)
+            SELECT id, created_at, updated_at, owner_id, chat_id, parent_id, resource_id,
+                resource_type, content, status
+            FROM messages WHERE id = ANY($1)
             "#,
             &keys
         )
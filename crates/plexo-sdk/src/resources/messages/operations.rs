@@ -1,5 +1,7 @@
 use crate::backend::engine::SDKEngine;
 use crate::common::commons::SortOrder;
+use crate::common::filters::{SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect, UuidComparison};
+use crate::common::pagination::{Page, PageInput};
 use crate::errors::sdk::SDKError;
 use crate::resources::messages::message::{Message, MessageStatus};
 
@@ -8,15 +10,18 @@ use async_trait::async_trait;
 use derive_builder::Builder;
 use poem_openapi::Object;
 use serde::Serialize;
-use sqlx::Row;
+use sqlx::{postgres::PgRow, Row};
 use std::str::FromStr;
 use uuid::Uuid;
 
+const MESSAGES_SORTABLE_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "status"];
+
 #[async_trait]
 pub trait MessageCrudOperations {
     async fn create_message(&self, input: CreateMessageInput) -> Result<Message, SDKError>;
     async fn get_message(&self, id: Uuid) -> Result<Message, SDKError>;
     async fn get_messages(&self, input: GetMessagesInput) -> Result<Vec<Message>, SDKError>;
+    async fn get_messages_page(&self, input: PageInput<GetMessagesWhere>) -> Result<Page<Message>, SDKError>;
     async fn update_message(&self, id: Uuid, input: UpdateMessageInput) -> Result<Message, SDKError>;
     async fn delete_message(&self, id: Uuid) -> Result<Message, SDKError>;
 }
@@ -26,12 +31,12 @@ pub trait MessageCrudOperations {
 pub struct GetMessagesWhere {
     #[builder(setter(strip_option), default)]
     pub ids: Option<Vec<Uuid>>,
-    #[builder(setter(strip_option), default)]
-    pub chat_id: Option<Uuid>,
-    #[builder(setter(strip_option), default)]
-    pub parent_id: Option<Uuid>,
-    #[builder(setter(strip_option), default)]
-    pub resource_id: Option<Uuid>,
+    #[builder(setter(into, strip_option), default)]
+    pub chat_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub parent_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub resource_id: Option<UuidComparison>,
 
     #[oai(skip)]
     #[builder(setter(strip_option), default)]
@@ -83,52 +88,35 @@ pub struct UpdateMessageInput {
     pub status: Option<MessageStatus>,
 }
 
-impl GetMessagesWhere {
-    pub fn compile_sql(&self) -> String {
-        let mut where_clause = Vec::new();
+impl SQLFilter for GetMessagesWhere {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
 
         if let Some(ids) = &self.ids {
-            where_clause.push(format!(
-                "id = ANY(array[{}]::uuid[])",
-                ids.iter()
-                    .map(|x| format!("'{}'", x))
-                    .collect::<Vec<String>>()
-                    .join(",")
-            ));
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
         }
 
         if let Some(chat_id) = &self.chat_id {
-            where_clause.push(format!("chat_id = '{}'", chat_id));
+            conditions.push(chat_id.compile_sql("chat_id"));
         }
 
         if let Some(parent_id) = &self.parent_id {
-            where_clause.push(format!("parent_id = '{}'", parent_id));
+            conditions.push(parent_id.compile_sql("parent_id"));
         }
 
         if let Some(resource_id) = &self.resource_id {
-            where_clause.push(format!("resource_id = '{}'", resource_id));
+            conditions.push(resource_id.compile_sql("resource_id"));
         }
 
-        if let Some(_and) = &self._and {
-            where_clause.push(format!(
-                "({})",
-                _and.iter()
-                    .map(|x| x.compile_sql())
-                    .collect::<Vec<String>>()
-                    .join(" AND ")
-            ));
+        if let Some(ands) = &self._and {
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
         }
-        if let Some(_or) = &self._or {
-            where_clause.push(format!(
-                "({})",
-                _or.iter()
-                    .map(|x| x.compile_sql())
-                    .collect::<Vec<String>>()
-                    .join(" OR ")
-            ));
+
+        if let Some(ors) = &self._or {
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
         }
 
-        where_clause.join(" AND ")
+        SQLCondition::And(conditions)
     }
 }
 
@@ -141,7 +129,8 @@ impl MessageCrudOperations for SDKEngine {
             r#"
             INSERT INTO messages (owner_id, chat_id, resource_id, resource_type, parent_id, status, content)
             VALUES ($1, $2, $3, $4, $5, $6, $7)
-            RETURNING *
+            RETURNING id, created_at, updated_at, owner_id, chat_id, parent_id, resource_id,
+                resource_type, content, status
             "#,
             input.owner_id,
             input.chat_id,
@@ -174,7 +163,9 @@ impl MessageCrudOperations for SDKEngine {
     async fn get_message(&self, id: Uuid) -> Result<Message, SDKError> {
         let message = sqlx::query!(
             r#"
-            SELECT * FROM messages WHERE id = $1
+            SELECT id, created_at, updated_at, owner_id, chat_id, parent_id, resource_id,
+                resource_type, content, status
+            FROM messages WHERE id = $1
             "#,
             id
         )
@@ -204,7 +195,8 @@ impl MessageCrudOperations for SDKEngine {
             UPDATE messages
             SET content = COALESCE($1, content), status = COALESCE($2, status)
             WHERE id = $3
-            RETURNING *
+            RETURNING id, created_at, updated_at, owner_id, chat_id, parent_id, resource_id,
+                resource_type, content, status
             "#,
             input.content,
             input.status.map(|s| s.to_string()),
@@ -234,7 +226,8 @@ impl MessageCrudOperations for SDKEngine {
         let message = sqlx::query!(
             r#"
             DELETE FROM messages WHERE id = $1
-            RETURNING *
+            RETURNING id, created_at, updated_at, owner_id, chat_id, parent_id, resource_id,
+                resource_type, content, status
             "#,
             id
         )
@@ -257,47 +250,38 @@ impl MessageCrudOperations for SDKEngine {
     }
 
     async fn get_messages(&self, input: GetMessagesInput) -> Result<Vec<Message>, SDKError> {
-        let mut query = "SELECT * FROM messages ".to_string();
-
-        if let Some(filter) = input.filter {
-            query.push_str(format!("WHERE {} ", filter.compile_sql()).as_str());
-        }
-
-        if let Some(sort_by) = input.sort_by {
-            query.push_str(format!("ORDER BY {} ", sort_by).as_str());
-        }
+        let mut query = SQLSelect::new("messages", MESSAGES_SORTABLE_COLUMNS)
+            .filter(input.filter.as_ref())
+            .sort(input.sort_by, input.sort_order)
+            .paginate(input.limit, input.offset)
+            .build()?;
 
-        if let Some(sort_order) = input.sort_order {
-            query.push_str(format!("{} ", sort_order).as_str());
-        }
+        let messages = query.build().fetch_all(self.db_pool.as_ref()).await?;
 
-        if let Some(limit) = input.limit {
-            query.push_str(format!("LIMIT {} ", limit).as_str());
-        }
+        let messages = messages.iter().map(message_from_row).collect();
 
-        if let Some(offset) = input.offset {
-            query.push_str(format!("OFFSET {} ", offset).as_str());
-        }
+        Ok(messages)
+    }
 
-        let messages = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
-
-        let messages = messages
-            .iter()
-            .map(|x| Message {
-                id: x.get("id"),
-                created_at: x.get("created_at"),
-                updated_at: x.get("updated_at"),
-                owner_id: x.get("owner_id"),
-                chat_id: x.get("chat_id"),
-                content: x.get("content"),
-                parent_id: x.get("parent_id"),
-                status: x
-                    .get::<'_, Option<String>, _>("status")
-                    .and_then(|a| MessageStatus::from_str(&a).ok())
-                    .unwrap_or_default(),
-            })
-            .collect();
+    async fn get_messages_page(&self, input: PageInput<GetMessagesWhere>) -> Result<Page<Message>, SDKError> {
+        SQLSelect::new("messages", MESSAGES_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), input, message_from_row)
+            .await
+    }
+}
 
-        Ok(messages)
+fn message_from_row(message_info: &PgRow) -> Message {
+    Message {
+        id: message_info.get("id"),
+        created_at: message_info.get("created_at"),
+        updated_at: message_info.get("updated_at"),
+        owner_id: message_info.get("owner_id"),
+        chat_id: message_info.get("chat_id"),
+        content: message_info.get("content"),
+        parent_id: message_info.get("parent_id"),
+        status: message_info
+            .get::<'_, Option<String>, _>("status")
+            .and_then(|a| MessageStatus::from_str(&a).ok())
+            .unwrap_or_default(),
     }
 }
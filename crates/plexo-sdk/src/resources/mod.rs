@@ -1,9 +1,19 @@
 pub mod assets;
 pub mod changes;
 pub mod chats;
+pub mod checklists;
+pub mod comments;
+pub mod custom_fields;
+pub mod cycles;
+pub mod label_groups;
 pub mod labels;
 pub mod members;
 pub mod messages;
+pub mod project_templates;
 pub mod projects;
+pub mod recurrences;
+pub mod task_templates;
 pub mod tasks;
 pub mod teams;
+pub mod work_logs;
+pub mod workflows;
@@ -0,0 +1,108 @@
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    errors::sdk::SDKError,
+    resources::{
+        changes::{
+            change::{ChangeOperation, ChangeResourceType},
+            operations::{insert_change, CreateChangeInput},
+        },
+        projects::extensions::{copy_project, CloneProjectInput, ProjectCopySummary},
+    },
+};
+
+use super::operations::project_template_from_row;
+
+#[async_trait]
+pub trait ProjectTemplatesExtensionOperations {
+    /// Creates a project from a template, copying the parts of the source project the template
+    /// includes. Fails while the source project is archived or in the trash, and once it's purged.
+    async fn instantiate_project_template(
+        &self,
+        template_id: Uuid,
+        input: InstantiateProjectTemplateInput,
+    ) -> Result<ProjectCopySummary, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct InstantiateProjectTemplateInput {
+    pub name: String,
+
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub prefix: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+    /// Due dates are shifted by the distance between this date and the start of the source.
+    #[builder(setter(strip_option), default)]
+    pub start_date: Option<DateTime<Utc>>,
+}
+
+#[async_trait]
+impl ProjectTemplatesExtensionOperations for SDKEngine {
+    async fn instantiate_project_template(
+        &self,
+        template_id: Uuid,
+        input: InstantiateProjectTemplateInput,
+    ) -> Result<ProjectCopySummary, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let template = project_template_from_row(
+            &sqlx::query("SELECT * FROM project_templates WHERE id = $1")
+                .bind(template_id)
+                .fetch_one(&mut *tx)
+                .await?,
+        );
+
+        let source_id = template.source_project_id.ok_or_else(|| {
+            SDKError::InvalidProjectCopy("the source project of the template no longer exists".to_string())
+        })?;
+
+        let summary = copy_project(
+            &mut tx,
+            source_id,
+            &CloneProjectInput {
+                name: input.name.clone(),
+                owner_id: input.owner_id,
+                prefix: input.prefix.clone(),
+                description: input.description.clone(),
+                start_date: input.start_date,
+                include_members: Some(template.include_members),
+                include_teams: Some(template.include_teams),
+                include_tasks: Some(template.include_tasks),
+            },
+        )
+        .await?;
+
+        insert_change(
+            &mut tx,
+            CreateChangeInput {
+                owner_id: input.owner_id,
+                resource_id: summary.project.id,
+                operation: ChangeOperation::Insert,
+                resource_type: ChangeResourceType::Projects,
+                diff_json: serde_json::to_string(&json!({
+                    "template_id": template_id,
+                    "input": input,
+                    "result": summary,
+                }))?,
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(summary)
+    }
+}
@@ -0,0 +1,4 @@
+pub mod extensions;
+pub mod operations;
+pub mod project_template;
+pub mod relations;
@@ -0,0 +1,249 @@
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, Row};
+use uuid::Uuid;
+
+use crate::common::filters::{SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect, StringComparison, UuidComparison};
+use crate::common::pagination::{Page, PageInput};
+use crate::{backend::engine::SDKEngine, common::commons::SortOrder, errors::sdk::SDKError};
+
+use super::project_template::ProjectTemplate;
+
+const PROJECT_TEMPLATES_SORTABLE_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "name"];
+
+#[async_trait]
+pub trait ProjectTemplateCrudOperations {
+    async fn create_project_template(&self, input: CreateProjectTemplateInput) -> Result<ProjectTemplate, SDKError>;
+    async fn get_project_template(&self, id: Uuid) -> Result<ProjectTemplate, SDKError>;
+    async fn get_project_templates(&self, input: GetProjectTemplatesInput) -> Result<Vec<ProjectTemplate>, SDKError>;
+    async fn get_project_templates_page(
+        &self,
+        input: PageInput<GetProjectTemplatesWhere>,
+    ) -> Result<Page<ProjectTemplate>, SDKError>;
+    async fn update_project_template(
+        &self,
+        id: Uuid,
+        input: UpdateProjectTemplateInput,
+    ) -> Result<ProjectTemplate, SDKError>;
+    async fn delete_project_template(&self, id: Uuid) -> Result<ProjectTemplate, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateProjectTemplateInput {
+    pub name: String,
+    pub source_project_id: Uuid,
+
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub include_members: Option<bool>,
+    #[builder(setter(strip_option), default)]
+    pub include_teams: Option<bool>,
+    #[builder(setter(strip_option), default)]
+    pub include_tasks: Option<bool>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateProjectTemplateInput {
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub source_project_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub include_members: Option<bool>,
+    #[builder(setter(strip_option), default)]
+    pub include_teams: Option<bool>,
+    #[builder(setter(strip_option), default)]
+    pub include_tasks: Option<bool>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetProjectTemplatesInput {
+    #[builder(setter(strip_option), default)]
+    pub filter: Option<GetProjectTemplatesWhere>,
+
+    #[builder(setter(strip_option), default)]
+    pub sort_by: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub sort_order: Option<SortOrder>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetProjectTemplatesWhere {
+    #[builder(setter(strip_option), default)]
+    pub ids: Option<Vec<Uuid>>,
+    #[builder(setter(into, strip_option), default)]
+    pub name: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub owner_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub source_project_id: Option<UuidComparison>,
+
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _and: Option<Vec<GetProjectTemplatesWhere>>,
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _or: Option<Vec<GetProjectTemplatesWhere>>,
+}
+
+impl SQLFilter for GetProjectTemplatesWhere {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
+
+        if let Some(ids) = &self.ids {
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
+        }
+
+        if let Some(name) = &self.name {
+            conditions.push(name.compile_sql("name"));
+        }
+
+        if let Some(owner_id) = &self.owner_id {
+            conditions.push(owner_id.compile_sql("owner_id"));
+        }
+
+        if let Some(source_project_id) = &self.source_project_id {
+            conditions.push(source_project_id.compile_sql("source_project_id"));
+        }
+
+        if let Some(ands) = &self._and {
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
+        }
+
+        if let Some(ors) = &self._or {
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
+        }
+
+        SQLCondition::And(conditions)
+    }
+}
+
+#[async_trait]
+impl ProjectTemplateCrudOperations for SDKEngine {
+    async fn create_project_template(&self, input: CreateProjectTemplateInput) -> Result<ProjectTemplate, SDKError> {
+        let template_info = sqlx::query(
+            r#"
+            INSERT INTO project_templates (
+                name, owner_id, source_project_id, description, include_members, include_teams, include_tasks
+            )
+            VALUES ($1, $2, $3, $4, COALESCE($5, true), COALESCE($6, true), COALESCE($7, true))
+            RETURNING *
+            "#,
+        )
+        .bind(input.name)
+        .bind(input.owner_id)
+        .bind(input.source_project_id)
+        .bind(input.description)
+        .bind(input.include_members)
+        .bind(input.include_teams)
+        .bind(input.include_tasks)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(project_template_from_row(&template_info))
+    }
+
+    async fn get_project_template(&self, id: Uuid) -> Result<ProjectTemplate, SDKError> {
+        let template_info = sqlx::query("SELECT * FROM project_templates WHERE id = $1")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(project_template_from_row(&template_info))
+    }
+
+    async fn get_project_templates(&self, input: GetProjectTemplatesInput) -> Result<Vec<ProjectTemplate>, SDKError> {
+        let mut query = SQLSelect::new("project_templates", PROJECT_TEMPLATES_SORTABLE_COLUMNS)
+            .filter(input.filter.as_ref())
+            .sort(input.sort_by, input.sort_order)
+            .paginate(input.limit, input.offset)
+            .build()?;
+
+        let templates_info = query.build().fetch_all(self.db_pool.as_ref()).await?;
+
+        Ok(templates_info.iter().map(project_template_from_row).collect())
+    }
+
+    async fn get_project_templates_page(
+        &self,
+        input: PageInput<GetProjectTemplatesWhere>,
+    ) -> Result<Page<ProjectTemplate>, SDKError> {
+        SQLSelect::new("project_templates", PROJECT_TEMPLATES_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), input, project_template_from_row)
+            .await
+    }
+
+    async fn update_project_template(
+        &self,
+        id: Uuid,
+        input: UpdateProjectTemplateInput,
+    ) -> Result<ProjectTemplate, SDKError> {
+        let template_info = sqlx::query(
+            r#"
+            UPDATE project_templates
+            SET
+                name = COALESCE($1, name),
+                description = COALESCE($2, description),
+                source_project_id = COALESCE($3, source_project_id),
+                include_members = COALESCE($4, include_members),
+                include_teams = COALESCE($5, include_teams),
+                include_tasks = COALESCE($6, include_tasks)
+            WHERE id = $7
+            RETURNING *
+            "#,
+        )
+        .bind(input.name)
+        .bind(input.description)
+        .bind(input.source_project_id)
+        .bind(input.include_members)
+        .bind(input.include_teams)
+        .bind(input.include_tasks)
+        .bind(id)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(project_template_from_row(&template_info))
+    }
+
+    async fn delete_project_template(&self, id: Uuid) -> Result<ProjectTemplate, SDKError> {
+        let template_info = sqlx::query("DELETE FROM project_templates WHERE id = $1 RETURNING *")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(project_template_from_row(&template_info))
+    }
+}
+
+pub(crate) fn project_template_from_row(template_info: &PgRow) -> ProjectTemplate {
+    ProjectTemplate {
+        id: template_info.get("id"),
+        created_at: template_info.get("created_at"),
+        updated_at: template_info.get("updated_at"),
+        owner_id: template_info.get("owner_id"),
+        source_project_id: template_info.get("source_project_id"),
+        name: template_info.get("name"),
+        description: template_info.get("description"),
+        include_members: template_info.get("include_members"),
+        include_teams: template_info.get("include_teams"),
+        include_tasks: template_info.get("include_tasks"),
+    }
+}
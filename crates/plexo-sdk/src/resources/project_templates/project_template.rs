@@ -0,0 +1,31 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use uuid::Uuid;
+
+use serde::Serialize;
+
+/// A saved way of starting projects, instantiated as a copy of its source project. The template
+/// follows the live source project rather than a snapshot of it: later changes to the source show
+/// up in new projects, and the template can't be used while the source is archived or in the
+/// trash. Once the source is purged the template is kept without one, and can't be used anymore.
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKProjectTemplate")]
+pub struct ProjectTemplate {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub owner_id: Uuid,
+    /// Unset once the source project is purged.
+    pub source_project_id: Option<Uuid>,
+
+    pub name: String,
+    pub description: Option<String>,
+
+    /// The settings, workflow and custom fields of the source are always copied.
+    pub include_members: bool,
+    pub include_teams: bool,
+    pub include_tasks: bool,
+}
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+
+use crate::{
+    backend::loaders::SDKLoaders,
+    errors::sdk::SDKError,
+    resources::{members::member::Member, projects::project::Project},
+};
+
+use super::project_template::ProjectTemplate;
+
+#[async_trait]
+pub trait ProjectTemplateRelations {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
+    async fn source_project(&self, loaders: &SDKLoaders) -> Result<Option<Project>, SDKError>;
+}
+
+#[async_trait]
+impl ProjectTemplateRelations for ProjectTemplate {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
+        let data = loaders.member_loader.load_one(self.owner_id).await.unwrap().unwrap();
+
+        Ok(data)
+    }
+
+    async fn source_project(&self, loaders: &SDKLoaders) -> Result<Option<Project>, SDKError> {
+        let Some(source_project_id) = self.source_project_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.project_loader.load_one(source_project_id).await.unwrap();
+
+        Ok(data)
+    }
+}
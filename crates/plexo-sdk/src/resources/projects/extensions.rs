@@ -0,0 +1,767 @@
+use std::collections::HashMap;
+
+use async_graphql::{InputObject, SimpleObject};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use serde_json::json;
+use sqlx::{PgConnection, Row};
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    common::trash::{hide_task_trees, restore_task_trees, TrashColumn},
+    errors::sdk::SDKError,
+    resources::changes::{
+        change::{ChangeOperation, ChangeResourceType},
+        operations::{insert_change, CreateChangeInput},
+    },
+};
+
+use super::{
+    operations::{check_project_prefix, project_from_row},
+    project::{Project, ProjectStatus},
+};
+
+#[async_trait]
+pub trait ProjectsExtensionOperations {
+    /// Copies a project in one transaction: its settings, workflow and custom fields, and
+    /// optionally its members, teams and task tree. A given `start_date` shifts the due dates by
+    /// the distance to the start (or creation) of the source project. Archived and deleted projects
+    /// can't be copied.
+    async fn clone_project(&self, source_id: Uuid, input: CloneProjectInput) -> Result<ProjectCopySummary, SDKError>;
+    /// Archives a project together with its tasks.
+    async fn archive_project(&self, id: Uuid) -> Result<Project, SDKError>;
+    /// Brings a project back from the trash or the archive, with the tasks that went with it.
+    async fn restore_project(&self, id: Uuid) -> Result<Project, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CloneProjectInput {
+    pub name: String,
+
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    /// Task keys are unique, so the copy only gets a prefix when one is given.
+    #[builder(setter(strip_option), default)]
+    pub prefix: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub start_date: Option<DateTime<Utc>>,
+
+    #[builder(setter(strip_option), default)]
+    pub include_members: Option<bool>,
+    #[builder(setter(strip_option), default)]
+    pub include_teams: Option<bool>,
+    #[builder(setter(strip_option), default)]
+    pub include_tasks: Option<bool>,
+}
+
+/// The new project of a copy and how many of each part were copied into it.
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKProjectCopySummary")]
+pub struct ProjectCopySummary {
+    pub project: Project,
+
+    pub members: i32,
+    pub teams: i32,
+    pub workflow_statuses: i32,
+    pub custom_fields: i32,
+    pub tasks: i32,
+    /// The distinct labels of the copied tasks, the project's own labels are copied with it.
+    pub labels: i32,
+}
+
+#[async_trait]
+impl ProjectsExtensionOperations for SDKEngine {
+    async fn clone_project(&self, source_id: Uuid, input: CloneProjectInput) -> Result<ProjectCopySummary, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let summary = copy_project(&mut tx, source_id, &input).await?;
+
+        insert_change(
+            &mut tx,
+            CreateChangeInput {
+                owner_id: input.owner_id,
+                resource_id: summary.project.id,
+                operation: ChangeOperation::Insert,
+                resource_type: ChangeResourceType::Projects,
+                diff_json: serde_json::to_string(&json!({
+                    "source_id": source_id,
+                    "input": input,
+                    "result": summary,
+                }))?,
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(summary)
+    }
+
+    async fn archive_project(&self, id: Uuid) -> Result<Project, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let project_info = sqlx::query(
+            r#"
+            UPDATE projects
+            SET archived_at = now()
+            WHERE id = $1 AND archived_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let task_ids: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM tasks WHERE project_id = $1")
+            .bind(id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        hide_task_trees(&mut tx, &task_ids, TrashColumn::Archived).await?;
+
+        tx.commit().await?;
+
+        Ok(project_from_row(&project_info))
+    }
+
+    async fn restore_project(&self, id: Uuid) -> Result<Project, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let project = project_from_row(
+            &sqlx::query("SELECT * FROM projects WHERE id = $1 FOR UPDATE")
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await?,
+        );
+
+        let task_ids: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM tasks WHERE project_id = $1")
+            .bind(id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        if let Some(deleted_at) = project.deleted_at {
+            restore_task_trees(&mut tx, &task_ids, TrashColumn::Deleted, deleted_at).await?;
+        }
+
+        if let Some(archived_at) = project.archived_at {
+            restore_task_trees(&mut tx, &task_ids, TrashColumn::Archived, archived_at).await?;
+        }
+
+        let project_info = sqlx::query(
+            r#"
+            UPDATE projects
+            SET archived_at = NULL, deleted_at = NULL
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(project_from_row(&project_info))
+    }
+}
+
+/// Copies a project inside the caller's transaction, see
+/// [`ProjectsExtensionOperations::clone_project`].
+pub(crate) async fn copy_project(
+    conn: &mut PgConnection,
+    source_id: Uuid,
+    input: &CloneProjectInput,
+) -> Result<ProjectCopySummary, SDKError> {
+    if input.name.trim().is_empty() {
+        return Err(SDKError::InvalidProjectCopy("project names can't be empty".to_string()));
+    }
+
+    let source = project_from_row(
+        &sqlx::query("SELECT * FROM projects WHERE id = $1")
+            .bind(source_id)
+            .fetch_one(&mut *conn)
+            .await?,
+    );
+
+    if source.archived_at.is_some() || source.deleted_at.is_some() {
+        return Err(SDKError::InvalidProjectCopy(
+            "archived and deleted projects can't be copied".to_string(),
+        ));
+    }
+
+    let prefix = input
+        .prefix
+        .as_deref()
+        .map(str::trim)
+        .filter(|prefix| !prefix.is_empty());
+
+    if let Some(prefix) = prefix {
+        check_project_prefix(conn, None, prefix).await?;
+    }
+
+    let shift = input
+        .start_date
+        .map(|start_date| start_date - source.start_date.unwrap_or(source.created_at))
+        .unwrap_or_else(Duration::zero);
+
+    let project = project_from_row(
+        &sqlx::query(
+            r#"
+            INSERT INTO projects (name, prefix, owner_id, description, lead_id, start_date, due_date, status, visibility)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING *
+            "#,
+        )
+        .bind(input.name.trim())
+        .bind(prefix)
+        .bind(input.owner_id)
+        .bind(input.description.as_ref().or(source.description.as_ref()))
+        .bind(source.lead_id)
+        .bind(input.start_date.or(source.start_date))
+        .bind(source.due_date.map(|due_date| due_date + shift))
+        .bind(ProjectStatus::default().to_string())
+        .bind(source.visibility.to_string())
+        .fetch_one(&mut *conn)
+        .await?,
+    );
+
+    let mut members = 0;
+
+    if input.include_members.unwrap_or(true) {
+        members = sqlx::query(
+            r#"
+            INSERT INTO members_by_projects (member_id, project_id)
+            SELECT member_id, $2 FROM members_by_projects
+            WHERE project_id = $1
+            "#,
+        )
+        .bind(source_id)
+        .bind(project.id)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected() as i32;
+    }
+
+    let mut teams = 0;
+
+    if input.include_teams.unwrap_or(true) {
+        teams = sqlx::query(
+            r#"
+            INSERT INTO teams_by_projects (team_id, project_id)
+            SELECT team_id, $2 FROM teams_by_projects
+            WHERE project_id = $1
+            "#,
+        )
+        .bind(source_id)
+        .bind(project.id)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected() as i32;
+    }
+
+    // The copied tasks are relinked to the copies of the project's own labels.
+    sqlx::query(
+        r#"
+        INSERT INTO labels (name, description, color, owner_id, project_id, group_id)
+        SELECT name, description, color, $3, $2, group_id FROM labels
+        WHERE project_id = $1
+        "#,
+    )
+    .bind(source_id)
+    .bind(project.id)
+    .bind(input.owner_id)
+    .execute(&mut *conn)
+    .await?;
+
+    let statuses = copy_workflow(conn, source_id, project.id, input.owner_id).await?;
+    let fields = copy_custom_fields(conn, source_id, project.id, input.owner_id).await?;
+
+    let mut tasks = HashMap::new();
+
+    if input.include_tasks.unwrap_or(true) {
+        tasks = copy_tasks(conn, source_id, &project, &statuses, shift, input.owner_id).await?;
+
+        let (old_ids, new_ids): (Vec<Uuid>, Vec<Uuid>) = tasks.iter().map(|(old, new)| (*old, *new)).unzip();
+        let (old_field_ids, new_field_ids): (Vec<Uuid>, Vec<Uuid>) =
+            fields.iter().map(|(old, new)| (*old, *new)).unzip();
+
+        sqlx::query(
+            r#"
+            INSERT INTO labels_by_tasks (label_id, task_id)
+            SELECT DISTINCT scoped_label(labels_by_tasks.label_id, $3), copies.new_id FROM labels_by_tasks
+            JOIN unnest($1::uuid[], $2::uuid[]) AS copies(old_id, new_id) ON copies.old_id = labels_by_tasks.task_id
+            WHERE scoped_label(labels_by_tasks.label_id, $3) IS NOT NULL
+            "#,
+        )
+        .bind(&old_ids)
+        .bind(&new_ids)
+        .bind(project.id)
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO tasks_by_assignees (assignee_id, task_id)
+            SELECT tasks_by_assignees.assignee_id, copies.new_id FROM tasks_by_assignees
+            JOIN unnest($1::uuid[], $2::uuid[]) AS copies(old_id, new_id) ON copies.old_id = tasks_by_assignees.task_id
+            "#,
+        )
+        .bind(&old_ids)
+        .bind(&new_ids)
+        .execute(&mut *conn)
+        .await?;
+
+        // Only dependencies between tasks of the project are copied.
+        sqlx::query(
+            r#"
+            INSERT INTO task_dependencies (task_id, blocked_by_id)
+            SELECT tasks.new_id, blockers.new_id FROM task_dependencies
+            JOIN unnest($1::uuid[], $2::uuid[]) AS tasks(old_id, new_id) ON tasks.old_id = task_dependencies.task_id
+            JOIN unnest($1::uuid[], $2::uuid[]) AS blockers(old_id, new_id)
+                ON blockers.old_id = task_dependencies.blocked_by_id
+            "#,
+        )
+        .bind(&old_ids)
+        .bind(&new_ids)
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO checklist_items (task_id, owner_id, text, position)
+            SELECT copies.new_id, $3, checklist_items.text, checklist_items.position FROM checklist_items
+            JOIN unnest($1::uuid[], $2::uuid[]) AS copies(old_id, new_id) ON copies.old_id = checklist_items.task_id
+            "#,
+        )
+        .bind(&old_ids)
+        .bind(&new_ids)
+        .bind(input.owner_id)
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO custom_field_values (
+                task_id, field_id, text_value, number_value, date_value, member_value, options_value
+            )
+            SELECT
+                tasks.new_id, fields.new_id, custom_field_values.text_value, custom_field_values.number_value,
+                custom_field_values.date_value, custom_field_values.member_value, custom_field_values.options_value
+            FROM custom_field_values
+            JOIN unnest($1::uuid[], $2::uuid[]) AS tasks(old_id, new_id) ON tasks.old_id = custom_field_values.task_id
+            JOIN unnest($3::uuid[], $4::uuid[]) AS fields(old_id, new_id) ON fields.old_id = custom_field_values.field_id
+            "#,
+        )
+        .bind(&old_ids)
+        .bind(&new_ids)
+        .bind(&old_field_ids)
+        .bind(&new_field_ids)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    let labels: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(DISTINCT label_id) FROM labels_by_tasks
+        WHERE task_id = ANY($1)
+        "#,
+    )
+    .bind(tasks.values().copied().collect::<Vec<_>>())
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(ProjectCopySummary {
+        project,
+        members,
+        teams,
+        workflow_statuses: statuses.len() as i32,
+        custom_fields: fields.len() as i32,
+        tasks: tasks.len() as i32,
+        labels: labels as i32,
+    })
+}
+
+/// Copies the workflow of a project, returning the ids of the copied statuses by source id.
+async fn copy_workflow(
+    conn: &mut PgConnection,
+    source_id: Uuid,
+    project_id: Uuid,
+    owner_id: Uuid,
+) -> Result<HashMap<Uuid, Uuid>, SDKError> {
+    let mut statuses = HashMap::new();
+
+    let Some(workflow_info) = sqlx::query("SELECT * FROM workflows WHERE project_id = $1")
+        .bind(source_id)
+        .fetch_optional(&mut *conn)
+        .await?
+    else {
+        return Ok(statuses);
+    };
+
+    let workflow_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO workflows (name, owner_id, project_id)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(workflow_info.get::<String, _>("name"))
+    .bind(owner_id)
+    .bind(project_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let statuses_info = sqlx::query("SELECT * FROM workflow_statuses WHERE workflow_id = $1")
+        .bind(workflow_info.get::<Uuid, _>("id"))
+        .fetch_all(&mut *conn)
+        .await?;
+
+    for status_info in statuses_info {
+        let status_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO workflow_statuses (workflow_id, name, category, position, color)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+        )
+        .bind(workflow_id)
+        .bind(status_info.get::<String, _>("name"))
+        .bind(status_info.get::<String, _>("category"))
+        .bind(status_info.get::<i32, _>("position"))
+        .bind(status_info.get::<Option<String>, _>("color"))
+        .fetch_one(&mut *conn)
+        .await?;
+
+        statuses.insert(status_info.get("id"), status_id);
+    }
+
+    let (old_ids, new_ids): (Vec<Uuid>, Vec<Uuid>) = statuses.iter().map(|(old, new)| (*old, *new)).unzip();
+
+    sqlx::query(
+        r#"
+        INSERT INTO workflow_transitions (workflow_id, from_status_id, to_status_id)
+        SELECT $1, origins.new_id, targets.new_id FROM workflow_transitions
+        JOIN unnest($2::uuid[], $3::uuid[]) AS origins(old_id, new_id)
+            ON origins.old_id = workflow_transitions.from_status_id
+        JOIN unnest($2::uuid[], $3::uuid[]) AS targets(old_id, new_id)
+            ON targets.old_id = workflow_transitions.to_status_id
+        "#,
+    )
+    .bind(workflow_id)
+    .bind(&old_ids)
+    .bind(&new_ids)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(statuses)
+}
+
+/// Copies the custom fields of a project, returning the ids of the copies by source id.
+async fn copy_custom_fields(
+    conn: &mut PgConnection,
+    source_id: Uuid,
+    project_id: Uuid,
+    owner_id: Uuid,
+) -> Result<HashMap<Uuid, Uuid>, SDKError> {
+    let fields_info = sqlx::query(
+        r#"
+        INSERT INTO custom_fields (owner_id, project_id, name, description, kind, options)
+        SELECT $3, $2, name, description, kind, options FROM custom_fields
+        WHERE project_id = $1
+        RETURNING id, name
+        "#,
+    )
+    .bind(source_id)
+    .bind(project_id)
+    .bind(owner_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    // Field names are unique in a project, so they pair the copies with their sources.
+    let source_fields: HashMap<String, Uuid> = sqlx::query("SELECT id, name FROM custom_fields WHERE project_id = $1")
+        .bind(source_id)
+        .fetch_all(&mut *conn)
+        .await?
+        .iter()
+        .map(|field_info| (field_info.get("name"), field_info.get("id")))
+        .collect();
+
+    Ok(fields_info
+        .iter()
+        .filter_map(|field_info| {
+            source_fields
+                .get(&field_info.get::<String, _>("name"))
+                .map(|source_id| (*source_id, field_info.get("id")))
+        })
+        .collect())
+}
+
+/// Copies the tasks of a project and their subtasks without a project, in number order so the
+/// copies keep their numbers, and returns the ids of the copies by source id. Archived and deleted
+/// tasks stay behind.
+async fn copy_tasks(
+    conn: &mut PgConnection,
+    source_id: Uuid,
+    project: &Project,
+    statuses: &HashMap<Uuid, Uuid>,
+    shift: Duration,
+    owner_id: Uuid,
+) -> Result<HashMap<Uuid, Uuid>, SDKError> {
+    let tasks_info = sqlx::query(
+        r#"
+        WITH RECURSIVE tree AS (
+            SELECT tasks.*, ARRAY[tasks.id] AS path FROM tasks
+            WHERE project_id = $1 AND archived_at IS NULL AND deleted_at IS NULL
+            UNION ALL
+            SELECT tasks.*, tree.path || tasks.id FROM tasks
+            JOIN tree ON tasks.parent_id = tree.id
+            WHERE tasks.project_id IS NULL AND tasks.archived_at IS NULL AND tasks.deleted_at IS NULL
+                AND NOT tasks.id = ANY(tree.path)
+        )
+        SELECT * FROM tree
+        ORDER BY number NULLS LAST, created_at, count, id
+        "#,
+    )
+    .bind(source_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let copies: HashMap<Uuid, Uuid> = tasks_info
+        .iter()
+        .map(|task_info| (task_info.get("id"), Uuid::new_v4()))
+        .collect();
+
+    for task_info in &tasks_info {
+        sqlx::query(
+            r#"
+            INSERT INTO tasks (
+                id, title, description, owner_id, status, priority, due_date, project_id, lead_id, labels,
+                workflow_status_id, estimate, estimate_unit, rank
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            "#,
+        )
+        .bind(copies[&task_info.get::<Uuid, _>("id")])
+        .bind(task_info.get::<Option<String>, _>("title"))
+        .bind(task_info.get::<Option<String>, _>("description"))
+        .bind(owner_id)
+        .bind(task_info.get::<Option<String>, _>("status"))
+        .bind(task_info.get::<Option<String>, _>("priority"))
+        .bind(
+            task_info
+                .get::<Option<DateTime<Utc>>, _>("due_date")
+                .map(|due_date| due_date + shift),
+        )
+        .bind(task_info.get::<Option<Uuid>, _>("project_id").map(|_| project.id))
+        .bind(task_info.get::<Option<Uuid>, _>("lead_id"))
+        .bind(task_info.get::<Option<serde_json::Value>, _>("labels"))
+        .bind(
+            task_info
+                .get::<Option<Uuid>, _>("workflow_status_id")
+                .and_then(|status_id| statuses.get(&status_id)),
+        )
+        .bind(task_info.get::<Option<f64>, _>("estimate"))
+        .bind(task_info.get::<Option<String>, _>("estimate_unit"))
+        .bind(task_info.get::<Option<String>, _>("rank"))
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    // Parents are set once every copy exists, subtasks of other projects stay behind.
+    let (task_ids, parent_ids): (Vec<Uuid>, Vec<Uuid>) = tasks_info
+        .iter()
+        .filter_map(|task_info| {
+            let parent_id = task_info.get::<Option<Uuid>, _>("parent_id")?;
+
+            Some((copies[&task_info.get::<Uuid, _>("id")], *copies.get(&parent_id)?))
+        })
+        .unzip();
+
+    sqlx::query(
+        r#"
+        UPDATE tasks
+        SET parent_id = parents.parent_id
+        FROM unnest($1::uuid[], $2::uuid[]) AS parents(task_id, parent_id)
+        WHERE tasks.id = parents.task_id
+        "#,
+    )
+    .bind(&task_ids)
+    .bind(&parent_ids)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(copies)
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::backend::testing::{test_engine, test_member, test_project};
+    use crate::resources::projects::operations::ProjectCrudOperations;
+    use crate::resources::tasks::{
+        extensions::TasksExtensionOperations,
+        operations::{task_from_row, CreateTaskInput, TaskCrudOperations},
+        task::Task,
+    };
+    use crate::resources::workflows::{
+        operations::{CreateWorkflowInput, CreateWorkflowStatusInput, WorkflowCrudOperations},
+        workflow::WorkflowStatusCategory,
+    };
+
+    async fn project_tasks(engine: &SDKEngine, project_id: Uuid) -> Vec<Task> {
+        sqlx::query(
+            r#"
+            WITH RECURSIVE tree AS (
+                SELECT * FROM tasks WHERE project_id = $1
+                UNION ALL
+                SELECT tasks.* FROM tasks JOIN tree ON tasks.parent_id = tree.id
+            )
+            SELECT * FROM tree ORDER BY title
+            "#,
+        )
+        .bind(project_id)
+        .fetch_all(engine.db_pool.as_ref())
+        .await
+        .unwrap()
+        .iter()
+        .map(task_from_row)
+        .collect()
+    }
+
+    #[sqlx::test]
+    async fn clone_copies_visible_task_trees_into_the_copied_workflow(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+        let source_id = test_project(&engine, owner_id, "SRC").await;
+
+        engine
+            .create_workflow(CreateWorkflowInput {
+                name: "Flow".to_string(),
+                project_id: source_id,
+                owner_id,
+                statuses: vec![
+                    CreateWorkflowStatusInput {
+                        name: "Open".to_string(),
+                        category: WorkflowStatusCategory::Unstarted,
+                        ..Default::default()
+                    },
+                    CreateWorkflowStatusInput {
+                        name: "Closed".to_string(),
+                        category: WorkflowStatusCategory::Completed,
+                        ..Default::default()
+                    },
+                ],
+                transitions: None,
+            })
+            .await
+            .unwrap();
+
+        let source = engine.get_project(source_id).await.unwrap();
+        let due_date = source.created_at + Duration::days(3);
+
+        let parent = engine
+            .create_task(CreateTaskInput {
+                title: "Parent".to_string(),
+                owner_id,
+                project_id: Some(source_id),
+                due_date: Some(due_date),
+                subtasks: Some(vec![CreateTaskInput {
+                    title: "Subtask".to_string(),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let blocker = engine
+            .create_task(CreateTaskInput {
+                title: "Blocker".to_string(),
+                owner_id,
+                project_id: Some(source_id),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        engine.add_task_dependency(parent.id, blocker.id).await.unwrap();
+
+        let archived = engine
+            .create_task(CreateTaskInput {
+                title: "Archived".to_string(),
+                owner_id,
+                project_id: Some(source_id),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        engine.archive_task(archived.id).await.unwrap();
+
+        let summary = engine
+            .clone_project(
+                source_id,
+                CloneProjectInput {
+                    name: "Copy".to_string(),
+                    owner_id,
+                    prefix: Some("DST".to_string()),
+                    start_date: Some(source.created_at + Duration::days(7)),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(summary.tasks, 3);
+        assert_eq!(summary.workflow_statuses, 2);
+
+        let copies = project_tasks(&engine, summary.project.id).await;
+        let titles = copies.iter().map(|task| task.title.as_str()).collect::<Vec<_>>();
+        assert_eq!(titles, vec!["Blocker", "Parent", "Subtask"]);
+
+        let (blocker_copy, parent_copy, subtask_copy) = (&copies[0], &copies[1], &copies[2]);
+        assert_eq!(subtask_copy.parent_id, Some(parent_copy.id));
+        assert_eq!(parent_copy.due_date, Some(due_date + Duration::days(7)));
+
+        let copied_statuses: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT workflow_statuses.id FROM workflow_statuses
+            JOIN workflows ON workflows.id = workflow_statuses.workflow_id
+            WHERE workflows.project_id = $1
+            "#,
+        )
+        .bind(summary.project.id)
+        .fetch_all(engine.db_pool.as_ref())
+        .await
+        .unwrap();
+        assert!(copied_statuses.contains(&parent_copy.workflow_status_id.unwrap()));
+
+        let blockers: Vec<Uuid> = sqlx::query_scalar("SELECT blocked_by_id FROM task_dependencies WHERE task_id = $1")
+            .bind(parent_copy.id)
+            .fetch_all(engine.db_pool.as_ref())
+            .await
+            .unwrap();
+        assert_eq!(blockers, vec![blocker_copy.id]);
+
+        engine.archive_project(source_id).await.unwrap();
+        let err = engine
+            .clone_project(
+                source_id,
+                CloneProjectInput {
+                    name: "Copy of archived".to_string(),
+                    owner_id,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SDKError::InvalidProjectCopy(..)));
+    }
+}
@@ -23,7 +23,9 @@ impl Loader<Uuid> for ProjectLoader {
     async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
         let projects = sqlx::query!(
             r#"
-            SELECT * FROM projects WHERE id  = ANY($1)
+            SELECT id, created_at, updated_at, name, prefix, owner_id, description, lead_id,
+                start_date, due_date, status, visibility, task_counter, deleted_at, archived_at
+            FROM projects WHERE id = ANY($1) AND archived_at IS NULL AND deleted_at IS NULL
             "#,
             &keys
         )
@@ -58,6 +60,8 @@ impl Loader<Uuid> for ProjectLoader {
                             .clone()
                             .and_then(|a| ProjectVisibility::from_str(&a).ok())
                             .unwrap_or_default(),
+                        archived_at: project.archived_at,
+                        deleted_at: project.deleted_at,
                     },
                 )
             })
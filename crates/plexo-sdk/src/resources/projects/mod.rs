@@ -1,3 +1,4 @@
+pub mod extensions;
 pub mod loader;
 pub mod operations;
 pub mod project;
@@ -6,9 +6,14 @@ use chrono::{DateTime, Utc};
 use derive_builder::Builder;
 use poem_openapi::Object;
 use serde::Serialize;
-use sqlx::Row;
+use sqlx::{postgres::PgRow, PgConnection, Row};
 use uuid::Uuid;
 
+use crate::common::filters::{
+    DateTimeComparison, SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect, StringComparison, UuidComparison,
+};
+use crate::common::pagination::{Page, PageInput};
+use crate::common::trash::{hide_task_trees, TrashColumn, TrashFilter, VisibleFilter};
 use crate::{
     backend::engine::SDKEngine,
     common::commons::{SortOrder, UpdateListInput},
@@ -17,11 +22,23 @@ use crate::{
 
 use super::project::{Project, ProjectStatus, ProjectVisibility};
 
+const PROJECTS_SORTABLE_COLUMNS: &[&str] = &[
+    "id",
+    "created_at",
+    "updated_at",
+    "name",
+    "prefix",
+    "status",
+    "start_date",
+    "due_date",
+];
+
 #[async_trait]
 pub trait ProjectCrudOperations {
     async fn create_project(&self, input: CreateProjectInput) -> Result<Project, SDKError>;
     async fn get_project(&self, id: Uuid) -> Result<Project, SDKError>;
     async fn get_projects(&self, input: GetProjectsInput) -> Result<Vec<Project>, SDKError>;
+    async fn get_projects_page(&self, input: PageInput<GetProjectsWhere>) -> Result<Page<Project>, SDKError>;
     async fn update_project(&self, id: Uuid, input: UpdateProjectInput) -> Result<Project, SDKError>;
     async fn delete_project(&self, id: Uuid) -> Result<Project, SDKError>;
 }
@@ -105,20 +122,27 @@ pub struct GetProjectsInput {
 pub struct GetProjectsWhere {
     #[builder(setter(strip_option), default)]
     pub ids: Option<Vec<Uuid>>,
-    #[builder(setter(strip_option), default)]
-    pub name: Option<String>,
-    #[builder(setter(strip_option), default)]
-    pub prefix: Option<String>,
-    #[builder(setter(strip_option), default)]
-    pub owner_id: Option<Uuid>,
-    #[builder(setter(strip_option), default)]
-    pub description: Option<String>,
-    #[builder(setter(strip_option), default)]
-    pub lead_id: Option<Uuid>,
-    #[builder(setter(strip_option), default)]
-    pub start_date: Option<DateTime<Utc>>,
-    #[builder(setter(strip_option), default)]
-    pub due_date: Option<DateTime<Utc>>,
+    #[builder(setter(into, strip_option), default)]
+    pub name: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub prefix: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub owner_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub description: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub lead_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub start_date: Option<DateTimeComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub due_date: Option<DateTimeComparison>,
+
+    /// Archived projects are left out unless set, only read on the top level filter.
+    #[builder(setter(strip_option), default)]
+    pub include_archived: Option<bool>,
+    /// Projects in the trash are left out unless set, only read on the top level filter.
+    #[builder(setter(strip_option), default)]
+    pub include_deleted: Option<bool>,
 
     #[oai(skip)]
     #[builder(setter(strip_option), default)]
@@ -129,82 +153,173 @@ pub struct GetProjectsWhere {
     pub _or: Option<Vec<GetProjectsWhere>>,
 }
 
-impl GetProjectsWhere {
-    pub fn compile_sql(&self) -> String {
-        let mut where_clause = Vec::new();
+impl SQLFilter for GetProjectsWhere {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
 
         if let Some(ids) = &self.ids {
-            where_clause.push(format!(
-                "id = ANY(array[{}]::uuid[])",
-                ids.iter()
-                    .map(|x| format!("'{}'", x))
-                    .collect::<Vec<String>>()
-                    .join(",")
-            ));
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
         }
 
         if let Some(name) = &self.name {
-            where_clause.push(format!("name = '{}'", name));
+            conditions.push(name.compile_sql("name"));
         }
+
         if let Some(prefix) = &self.prefix {
-            where_clause.push(format!("prefix = '{}'", prefix));
+            conditions.push(prefix.compile_sql("prefix"));
         }
+
         if let Some(owner_id) = &self.owner_id {
-            where_clause.push(format!("owner_id = '{}'", owner_id));
+            conditions.push(owner_id.compile_sql("owner_id"));
         }
+
         if let Some(description) = &self.description {
-            where_clause.push(format!("description = '{}'", description));
+            conditions.push(description.compile_sql("description"));
         }
+
         if let Some(lead_id) = &self.lead_id {
-            where_clause.push(format!("lead_id = '{}'", lead_id));
+            conditions.push(lead_id.compile_sql("lead_id"));
         }
+
         if let Some(start_date) = &self.start_date {
-            where_clause.push(format!("start_date = '{}'", start_date));
+            conditions.push(start_date.compile_sql("start_date"));
         }
+
         if let Some(due_date) = &self.due_date {
-            where_clause.push(format!("due_date = '{}'", due_date));
+            conditions.push(due_date.compile_sql("due_date"));
         }
-        if let Some(_and) = &self._and {
-            where_clause.push(format!(
-                "({})",
-                _and.iter()
-                    .map(|x| x.compile_sql())
-                    .collect::<Vec<String>>()
-                    .join(" AND ")
-            ));
+
+        if let Some(ands) = &self._and {
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
         }
-        if let Some(_or) = &self._or {
-            where_clause.push(format!(
-                "({})",
-                _or.iter()
-                    .map(|x| x.compile_sql())
-                    .collect::<Vec<String>>()
-                    .join(" OR ")
-            ));
+
+        if let Some(ors) = &self._or {
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
         }
 
-        where_clause.join(" AND ")
+        SQLCondition::And(conditions)
+    }
+}
+
+impl TrashFilter for GetProjectsWhere {
+    fn include_archived(&self) -> bool {
+        self.include_archived.unwrap_or(false)
+    }
+
+    fn include_deleted(&self) -> bool {
+        self.include_deleted.unwrap_or(false)
+    }
+}
+
+/// Fails if `prefix` is used by another project, or by keys tasks outside `project_id` had before.
+/// Task keys are never reassigned, so a reused prefix would hand out keys that already exist. Keys
+/// the project's own tasks had only count when another task of the project has the same number,
+/// the higher ones are skipped by `skip_project_task_keys`.
+pub(crate) async fn check_project_prefix(
+    conn: &mut PgConnection,
+    project_id: Option<Uuid>,
+    prefix: &str,
+) -> Result<(), SDKError> {
+    let taken: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM projects
+            WHERE upper(trim(prefix)) = upper($1) AND id IS DISTINCT FROM $2
+        ) OR EXISTS (
+            SELECT 1 FROM task_keys
+            JOIN tasks ON tasks.id = task_keys.task_id
+            WHERE left(task_keys.key, length($1) + 1) = upper($1) || '-'
+                AND substr(task_keys.key, length($1) + 2) ~ '^[0-9]+$'
+                AND (
+                    tasks.project_id IS DISTINCT FROM $2
+                    OR EXISTS (
+                        SELECT 1 FROM tasks AS others
+                        WHERE others.project_id = $2
+                            AND others.id <> tasks.id
+                            AND others.number::text = substr(task_keys.key, length($1) + 2)
+                    )
+                )
+        )
+        "#,
+    )
+    .bind(prefix)
+    .bind(project_id)
+    .fetch_one(conn)
+    .await?;
+
+    if taken {
+        return Err(SDKError::InvalidProjectPrefix(format!("{prefix} is already used")));
+    }
+
+    Ok(())
+}
+
+/// Turns the unique violation raised when `prefix` was taken after `check_project_prefix` passed,
+/// by another project or by the keys of another task, into an `InvalidProjectPrefix` error.
+fn prefix_taken(err: sqlx::Error, prefix: Option<&str>) -> SDKError {
+    match (err.as_database_error(), prefix) {
+        (Some(db_err), Some(prefix)) if db_err.is_unique_violation() => {
+            SDKError::InvalidProjectPrefix(format!("{prefix} is already used"))
+        }
+        _ => SDKError::SQLXError(err),
     }
 }
 
+/// Moves the task counter of a project taking `prefix` past the keys with that prefix its tasks
+/// already had, so new tasks don't get a key that belongs to one of them.
+async fn skip_project_task_keys(conn: &mut PgConnection, project_id: Uuid, prefix: &str) -> Result<(), SDKError> {
+    sqlx::query(
+        r#"
+        UPDATE projects
+        SET task_counter = GREATEST(
+            task_counter,
+            (
+                SELECT COALESCE(max(substr(task_keys.key, length($1) + 2)::integer), 0)
+                FROM task_keys
+                JOIN tasks ON tasks.id = task_keys.task_id
+                WHERE tasks.project_id = $2
+                    AND left(task_keys.key, length($1) + 1) = upper($1) || '-'
+                    AND substr(task_keys.key, length($1) + 2) ~ '^[0-9]+$'
+            )
+        )
+        WHERE id = $2
+        "#,
+    )
+    .bind(prefix)
+    .bind(project_id)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
 #[async_trait]
 impl ProjectCrudOperations for SDKEngine {
     async fn create_project(&self, input: CreateProjectInput) -> Result<Project, SDKError> {
         let mut tx = self.db_pool.as_ref().begin().await?;
 
+        let prefix = input.prefix.as_deref().map(str::trim);
+
+        if let Some(prefix) = prefix.filter(|prefix| !prefix.is_empty()) {
+            check_project_prefix(&mut tx, None, prefix).await?;
+        }
+
         let project = sqlx::query!(
             r#"
-            INSERT INTO projects (name, description, owner_id, status)
-            VALUES ($1, $2, $3, $4)
-            RETURNING *
+            INSERT INTO projects (name, prefix, description, owner_id, status)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, created_at, updated_at, name, prefix, owner_id, description, lead_id,
+                start_date, due_date, status, visibility, task_counter, deleted_at, archived_at
             "#,
             input.name,
+            prefix,
             input.description,
             input.owner_id,
             input.status.unwrap_or_default().to_string(),
         )
         .fetch_one(&mut *tx)
-        .await?;
+        .await
+        .map_err(|err| prefix_taken(err, prefix))?;
 
         if let Some(members) = input.members {
             for member in members {
@@ -259,13 +374,17 @@ impl ProjectCrudOperations for SDKEngine {
                 .visibility
                 .and_then(|a| ProjectVisibility::from_str(&a).ok())
                 .unwrap_or_default(),
+            archived_at: project.archived_at,
+            deleted_at: project.deleted_at,
         })
     }
 
     async fn get_project(&self, id: Uuid) -> Result<Project, SDKError> {
         let project_info = sqlx::query!(
             r#"
-            SELECT * FROM projects WHERE id = $1
+            SELECT id, created_at, updated_at, name, prefix, owner_id, description, lead_id,
+                start_date, due_date, status, visibility, task_counter, deleted_at, archived_at
+            FROM projects WHERE id = $1 AND archived_at IS NULL AND deleted_at IS NULL
             "#,
             id,
         )
@@ -291,12 +410,21 @@ impl ProjectCrudOperations for SDKEngine {
                 .visibility
                 .and_then(|a| ProjectVisibility::from_str(&a).ok())
                 .unwrap_or_default(),
+            archived_at: project_info.archived_at,
+            deleted_at: project_info.deleted_at,
         })
     }
 
     async fn update_project(&self, id: Uuid, input: UpdateProjectInput) -> Result<Project, SDKError> {
         let mut tx = self.db_pool.as_ref().begin().await?;
 
+        let prefix = input.prefix.as_deref().map(str::trim);
+
+        if let Some(prefix) = prefix.filter(|prefix| !prefix.is_empty()) {
+            check_project_prefix(&mut tx, Some(id), prefix).await?;
+            skip_project_task_keys(&mut tx, id, prefix).await?;
+        }
+
         let project_final_info = sqlx::query!(
             r#"
             UPDATE projects
@@ -310,11 +438,12 @@ impl ProjectCrudOperations for SDKEngine {
                 status = COALESCE($7, status),
                 visibility = COALESCE($8, visibility)
             WHERE id = $9
-            RETURNING *
+            RETURNING id, created_at, updated_at, name, prefix, owner_id, description, lead_id,
+                start_date, due_date, status, visibility, task_counter, deleted_at, archived_at
             "#,
             input.name,
             input.description,
-            input.prefix,
+            prefix,
             input.lead_id,
             input.start_date,
             input.due_date,
@@ -323,7 +452,8 @@ impl ProjectCrudOperations for SDKEngine {
             id,
         )
         .fetch_one(&mut *tx)
-        .await?;
+        .await
+        .map_err(|err| prefix_taken(err, prefix))?;
 
         if let Some(members) = input.members {
             for member in members.add {
@@ -406,45 +536,36 @@ impl ProjectCrudOperations for SDKEngine {
                 .visibility
                 .and_then(|a| ProjectVisibility::from_str(&a).ok())
                 .unwrap_or_default(),
+            archived_at: project_final_info.archived_at,
+            deleted_at: project_final_info.deleted_at,
         })
     }
 
     async fn delete_project(&self, id: Uuid) -> Result<Project, SDKError> {
         let mut tx = self.db_pool.as_ref().begin().await?;
 
-        sqlx::query!(
-            r#"
-            UPDATE tasks
-            SET
-                project_id = NULL
-            WHERE project_id = $1
-            "#,
-            id,
-        )
-        .execute(&mut *tx)
-        .await?;
-
-        sqlx::query!(
-            r#"
-                DELETE FROM teams_by_projects
-                WHERE project_id = $1
-                "#,
-            id,
-        )
-        .execute(&mut *tx)
-        .await?;
-
+        // The project goes to the trash with its tasks and keeps its members and teams, see
+        // `restore_project`.
         let project_info = sqlx::query!(
             r#"
-            DELETE FROM projects WHERE id = $1
-            RETURNING *
+            UPDATE projects
+            SET deleted_at = now()
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING id, created_at, updated_at, name, prefix, owner_id, description, lead_id,
+                start_date, due_date, status, visibility, task_counter, deleted_at, archived_at
             "#,
             id,
         )
-        //.fetch_one(self.db_pool.as_ref())
         .fetch_one(&mut *tx)
         .await?;
 
+        let task_ids: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM tasks WHERE project_id = $1")
+            .bind(id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        hide_task_trees(&mut tx, &task_ids, TrashColumn::Deleted).await?;
+
         tx.commit().await?;
 
         Ok(Project {
@@ -466,58 +587,53 @@ impl ProjectCrudOperations for SDKEngine {
                 .visibility
                 .and_then(|a| ProjectVisibility::from_str(&a).ok())
                 .unwrap_or_default(),
+            archived_at: project_info.archived_at,
+            deleted_at: project_info.deleted_at,
         })
     }
 
     async fn get_projects(&self, input: GetProjectsInput) -> Result<Vec<Project>, SDKError> {
-        let mut query = "SELECT * FROM projects ".to_string();
+        let mut query = SQLSelect::new("projects", PROJECTS_SORTABLE_COLUMNS)
+            .filter(Some(&VisibleFilter(input.filter)))
+            .sort(input.sort_by, input.sort_order)
+            .paginate(input.limit, input.offset)
+            .build()?;
 
-        if let Some(filter) = input.filter {
-            query.push_str(format!("WHERE {} ", filter.compile_sql()).as_str());
-        }
+        let projects_info = query.build().fetch_all(self.db_pool.as_ref()).await?;
 
-        if let Some(sort_by) = input.sort_by {
-            query.push_str(format!("ORDER BY {} ", sort_by).as_str());
-        }
+        let projects = projects_info.iter().map(project_from_row).collect::<Vec<Project>>();
 
-        if let Some(sort_order) = input.sort_order {
-            query.push_str(format!("{} ", sort_order).as_str());
-        }
-
-        if let Some(limit) = input.limit {
-            query.push_str(format!("LIMIT {} ", limit).as_str());
-        }
-
-        if let Some(offset) = input.offset {
-            query.push_str(format!("OFFSET {} ", offset).as_str());
-        }
+        Ok(projects)
+    }
 
-        let projects_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
-
-        let projects = projects_info
-            .iter()
-            .map(|x| Project {
-                id: x.get("id"),
-                created_at: x.get("created_at"),
-                updated_at: x.get("updated_at"),
-                name: x.get("name"),
-                prefix: x.get("prefix"),
-                owner_id: x.get("owner_id"),
-                description: x.get("description"),
-                lead_id: x.get("lead_id"),
-                start_date: x.get("start_date"),
-                due_date: x.get("due_date"),
-                status: x
-                    .get::<'_, Option<String>, _>("status")
-                    .and_then(|a| ProjectStatus::from_str(&a).ok())
-                    .unwrap_or_default(),
-                visibility: x
-                    .get::<'_, Option<String>, _>("visibility")
-                    .and_then(|a| ProjectVisibility::from_str(&a).ok())
-                    .unwrap_or_default(),
-            })
-            .collect::<Vec<Project>>();
+    async fn get_projects_page(&self, input: PageInput<GetProjectsWhere>) -> Result<Page<Project>, SDKError> {
+        SQLSelect::new("projects", PROJECTS_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), VisibleFilter::page(input), project_from_row)
+            .await
+    }
+}
 
-        Ok(projects)
+pub(crate) fn project_from_row(project_info: &PgRow) -> Project {
+    Project {
+        id: project_info.get("id"),
+        created_at: project_info.get("created_at"),
+        updated_at: project_info.get("updated_at"),
+        name: project_info.get("name"),
+        prefix: project_info.get("prefix"),
+        owner_id: project_info.get("owner_id"),
+        description: project_info.get("description"),
+        lead_id: project_info.get("lead_id"),
+        start_date: project_info.get("start_date"),
+        due_date: project_info.get("due_date"),
+        status: project_info
+            .get::<'_, Option<String>, _>("status")
+            .and_then(|a| ProjectStatus::from_str(&a).ok())
+            .unwrap_or_default(),
+        visibility: project_info
+            .get::<'_, Option<String>, _>("visibility")
+            .and_then(|a| ProjectVisibility::from_str(&a).ok())
+            .unwrap_or_default(),
+        archived_at: project_info.get("archived_at"),
+        deleted_at: project_info.get("deleted_at"),
     }
 }
@@ -27,6 +27,11 @@ pub struct Project {
     pub lead_id: Option<Uuid>,
     pub start_date: Option<DateTime<Utc>>,
     pub due_date: Option<DateTime<Utc>>,
+
+    /// Set while the project is archived, together with its tasks.
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Set while the project is in the trash, together with its tasks.
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 #[derive(
@@ -9,9 +9,12 @@ use crate::{
     resources::{
         assets::asset::{Asset, AssetKind},
         changes::change::{Change, ChangeOperation, ChangeResourceType},
+        comments::{comment::Comment, operations::CommentCrudOperations},
+        custom_fields::{custom_field::CustomField, operations::custom_field_from_row},
         members::member::Member,
-        tasks::task::{Task, TaskPriority, TaskStatus},
+        tasks::task::{Task, TaskEstimateUnit, TaskPriority, TaskStatus},
         teams::team::Team,
+        workflows::workflow::Workflow,
     },
 };
 
@@ -26,6 +29,9 @@ pub trait ProjectRelations {
     async fn members(&self, loaders: &SDKLoaders) -> Result<Vec<Member>, SDKError>;
     async fn assets(&self, loaders: &SDKLoaders) -> Result<Vec<Asset>, SDKError>;
     async fn teams(&self, loaders: &SDKLoaders) -> Result<Vec<Team>, SDKError>;
+    async fn workflow(&self, loaders: &SDKLoaders) -> Result<Option<Workflow>, SDKError>;
+    async fn custom_fields(&self, loaders: &SDKLoaders) -> Result<Vec<CustomField>, SDKError>;
+    async fn comments(&self, loaders: &SDKLoaders) -> Result<Vec<Comment>, SDKError>;
 
     async fn changes(&self, loaders: &SDKLoaders) -> Result<Vec<Change>, SDKError>;
 }
@@ -51,8 +57,11 @@ impl ProjectRelations for Project {
     async fn tasks(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError> {
         let tasks = sqlx::query!(
             r#"
-        SELECT * FROM tasks
-        WHERE project_id = $1"#,
+        SELECT id, created_at, updated_at, title, description, owner_id, status, priority, due_date,
+            project_id, lead_id, labels, count, parent_id, number, key, workflow_status_id,
+            estimate, estimate_unit, rank, cycle_id, deleted_at, archived_at
+        FROM tasks
+        WHERE project_id = $1 AND archived_at IS NULL AND deleted_at IS NULL"#,
             &self.id
         )
         .fetch_all(&*loaders.engine.db_pool)
@@ -83,6 +92,18 @@ impl ProjectRelations for Project {
                 owner_id: task.owner_id,
                 count: task.count,
                 parent_id: task.parent_id,
+                number: task.number,
+                key: task.key.clone(),
+                workflow_status_id: task.workflow_status_id,
+                estimate: task.estimate,
+                estimate_unit: task
+                    .estimate_unit
+                    .clone()
+                    .and_then(|unit| TaskEstimateUnit::from_str(&unit).ok()),
+                rank: task.rank.clone(),
+                cycle_id: task.cycle_id,
+                archived_at: task.archived_at,
+                deleted_at: task.deleted_at,
             })
             .collect())
     }
@@ -115,7 +136,9 @@ impl ProjectRelations for Project {
     async fn assets(&self, loaders: &SDKLoaders) -> Result<Vec<Asset>, SDKError> {
         let assets = sqlx::query!(
             r#"
-        SELECT * FROM assets
+        SELECT id, created_at, updated_at, name, owner_id, kind, project_id, content_hash, size,
+            mime_type, version, extracted_text
+        FROM assets
         WHERE project_id = $1"#,
             &self.id
         )
@@ -137,10 +160,52 @@ impl ProjectRelations for Project {
                     .and_then(|a| AssetKind::from_str(&a).ok())
                     .unwrap_or_default(),
                 project_id: asset.project_id,
+                content_hash: asset.content_hash.clone(),
+                size: asset.size,
+                mime_type: asset.mime_type.clone(),
+                version: asset.version,
+                extracted_text: asset.extracted_text.clone(),
             })
             .collect())
     }
 
+    async fn workflow(&self, loaders: &SDKLoaders) -> Result<Option<Workflow>, SDKError> {
+        let workflow = sqlx::query!(
+            r#"
+            SELECT * FROM workflows
+            WHERE project_id = $1
+            "#,
+            &self.id
+        )
+        .fetch_optional(&*loaders.engine.db_pool)
+        .await?
+        .map(|workflow| Workflow {
+            id: workflow.id,
+            created_at: workflow.created_at,
+            updated_at: workflow.updated_at,
+            name: workflow.name,
+            owner_id: workflow.owner_id,
+            project_id: workflow.project_id,
+        });
+
+        Ok(workflow)
+    }
+
+    async fn custom_fields(&self, loaders: &SDKLoaders) -> Result<Vec<CustomField>, SDKError> {
+        let custom_fields_info = sqlx::query(
+            r#"
+            SELECT * FROM custom_fields
+            WHERE project_id = $1
+            ORDER BY created_at, id
+            "#,
+        )
+        .bind(self.id)
+        .fetch_all(&*loaders.engine.db_pool)
+        .await?;
+
+        Ok(custom_fields_info.iter().map(custom_field_from_row).collect())
+    }
+
     async fn teams(&self, loaders: &SDKLoaders) -> Result<Vec<Team>, SDKError> {
         let ids: Vec<Uuid> = sqlx::query!(
             r#"
@@ -158,7 +223,7 @@ impl ProjectRelations for Project {
 
         let teams_map = loaders.team_loader.load_many(ids.clone()).await.unwrap();
 
-        let teams: &Vec<Team> = &ids.into_iter().map(|id| teams_map.get(&id).unwrap().clone()).collect();
+        let teams: &Vec<Team> = &ids.into_iter().filter_map(|id| teams_map.get(&id).cloned()).collect();
 
         Ok(teams.clone())
     }
@@ -188,4 +253,11 @@ impl ProjectRelations for Project {
             })
             .collect())
     }
+
+    async fn comments(&self, loaders: &SDKLoaders) -> Result<Vec<Comment>, SDKError> {
+        loaders
+            .engine
+            .get_resource_comments(ChangeResourceType::Projects, self.id)
+            .await
+    }
 }
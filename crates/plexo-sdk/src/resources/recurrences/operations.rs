@@ -0,0 +1,658 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use serde_json::json;
+use sqlx::{postgres::PgRow, PgConnection, Row};
+use uuid::Uuid;
+
+use crate::backend::engine::SDKEngine;
+use crate::errors::sdk::SDKError;
+use crate::resources::changes::change::{ChangeOperation, ChangeResourceType};
+use crate::resources::changes::operations::{insert_change, CreateChangeInput};
+use crate::resources::tasks::operations::{insert_task, task_from_row, CreateTaskInput};
+use crate::resources::tasks::task::{Task, TaskStatus};
+use crate::resources::workflows::workflow::WorkflowStatusCategory;
+
+use super::recurrence::{Recurrence, RecurrenceFrequency, RecurrenceRule, RecurrenceWeekday};
+
+#[async_trait]
+pub trait RecurrenceOperations {
+    async fn get_task_recurrence(&self, task_id: Uuid) -> Result<Option<Recurrence>, SDKError>;
+    async fn set_task_recurrence(&self, task_id: Uuid, input: SetTaskRecurrenceInput) -> Result<Recurrence, SDKError>;
+    async fn update_task_recurrence(
+        &self,
+        task_id: Uuid,
+        input: UpdateTaskRecurrenceInput,
+    ) -> Result<Recurrence, SDKError>;
+    async fn stop_task_recurrence(&self, task_id: Uuid) -> Result<Recurrence, SDKError>;
+
+    /// Active recurrences whose next occurrence is due, or whose latest occurrence was completed.
+    async fn due_task_recurrences(&self) -> Result<Vec<Recurrence>, SDKError>;
+    /// Creates the next occurrence of a due recurrence, copying the labels, assignees and subtasks
+    /// of its latest occurrence. Returns `None` if the recurrence is no longer due.
+    async fn generate_task_occurrence(&self, recurrence_id: Uuid) -> Result<Option<Task>, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct SetTaskRecurrenceInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    /// A rule in RRULE notation, e.g. `FREQ=MONTHLY;BYMONTHDAY=1,15;COUNT=6`. The other fields
+    /// override the parts of this rule.
+    #[builder(setter(strip_option), default)]
+    pub rrule: Option<String>,
+
+    #[builder(setter(strip_option), default)]
+    pub frequency: Option<RecurrenceFrequency>,
+    #[builder(setter(strip_option), default)]
+    pub interval: Option<i32>,
+    #[builder(setter(strip_option), default)]
+    pub by_weekday: Option<Vec<RecurrenceWeekday>>,
+    #[builder(setter(strip_option), default)]
+    pub by_month_day: Option<Vec<i32>>,
+    #[builder(setter(strip_option), default)]
+    pub until: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub count: Option<i32>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateTaskRecurrenceInput {
+    /// Replaces the whole rule, the only way to drop an end date or count.
+    #[builder(setter(strip_option), default)]
+    pub rrule: Option<String>,
+
+    #[builder(setter(strip_option), default)]
+    pub frequency: Option<RecurrenceFrequency>,
+    #[builder(setter(strip_option), default)]
+    pub interval: Option<i32>,
+    #[builder(setter(strip_option), default)]
+    pub by_weekday: Option<Vec<RecurrenceWeekday>>,
+    #[builder(setter(strip_option), default)]
+    pub by_month_day: Option<Vec<i32>>,
+    #[builder(setter(strip_option), default)]
+    pub until: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub count: Option<i32>,
+}
+
+impl UpdateTaskRecurrenceInput {
+    fn apply(self, rule: RecurrenceRule) -> Result<RecurrenceRule, SDKError> {
+        let mut rule = match self.rrule {
+            Some(rrule) => RecurrenceRule::parse(&rrule)?,
+            None => rule,
+        };
+
+        if let Some(frequency) = self.frequency {
+            rule.frequency = frequency;
+        }
+
+        if let Some(interval) = self.interval {
+            rule.interval = interval;
+        }
+
+        if let Some(by_weekday) = self.by_weekday {
+            rule.by_weekday = by_weekday;
+        }
+
+        if let Some(by_month_day) = self.by_month_day {
+            rule.by_month_day = by_month_day;
+        }
+
+        if let Some(until) = self.until {
+            rule.until = Some(until);
+            rule.count = None;
+        }
+
+        if let Some(count) = self.count {
+            rule.count = Some(count);
+            rule.until = None;
+        }
+
+        rule.validate()?;
+
+        Ok(rule)
+    }
+}
+
+impl From<SetTaskRecurrenceInput> for UpdateTaskRecurrenceInput {
+    fn from(input: SetTaskRecurrenceInput) -> Self {
+        UpdateTaskRecurrenceInput {
+            rrule: input.rrule,
+            frequency: input.frequency,
+            interval: input.interval,
+            by_weekday: input.by_weekday,
+            by_month_day: input.by_month_day,
+            until: input.until,
+            count: input.count,
+        }
+    }
+}
+
+#[async_trait]
+impl RecurrenceOperations for SDKEngine {
+    async fn get_task_recurrence(&self, task_id: Uuid) -> Result<Option<Recurrence>, SDKError> {
+        let recurrence_info = sqlx::query(
+            r#"
+            SELECT * FROM task_recurrences
+            WHERE task_id = $1
+            "#,
+        )
+        .bind(task_id)
+        .fetch_optional(self.db_pool.as_ref())
+        .await?;
+
+        Ok(recurrence_info.as_ref().map(recurrence_from_row))
+    }
+
+    async fn set_task_recurrence(&self, task_id: Uuid, input: SetTaskRecurrenceInput) -> Result<Recurrence, SDKError> {
+        if input.rrule.is_none() && input.frequency.is_none() {
+            return Err(SDKError::InvalidRecurrenceRule("missing frequency".to_string()));
+        }
+
+        let owner_id = input.owner_id;
+        let rule = UpdateTaskRecurrenceInput::from(input).apply(RecurrenceRule {
+            interval: 1,
+            ..Default::default()
+        })?;
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let anchor = occurrence_anchor(&mut tx, task_id).await?;
+        let next_occurrence_at = rule.next_occurrence(anchor, anchor, 1);
+
+        let recurrence_info = sqlx::query(
+            r#"
+            INSERT INTO task_recurrences (owner_id, task_id, frequency, interval, by_weekday, by_month_day, until, count, next_occurrence_at, active)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (task_id) DO UPDATE SET
+                owner_id = excluded.owner_id,
+                frequency = excluded.frequency,
+                interval = excluded.interval,
+                by_weekday = excluded.by_weekday,
+                by_month_day = excluded.by_month_day,
+                until = excluded.until,
+                count = excluded.count,
+                occurrences = 1,
+                next_occurrence_at = excluded.next_occurrence_at,
+                active = excluded.active,
+                updated_at = now()
+            RETURNING *
+            "#,
+        )
+        .bind(owner_id)
+        .bind(task_id)
+        .bind(rule.frequency.to_string())
+        .bind(rule.interval)
+        .bind(rule.by_weekday.iter().map(|day| day.to_string()).collect::<Vec<_>>())
+        .bind(&rule.by_month_day)
+        .bind(rule.until)
+        .bind(rule.count)
+        .bind(next_occurrence_at)
+        .bind(next_occurrence_at.is_some())
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(recurrence_from_row(&recurrence_info))
+    }
+
+    async fn update_task_recurrence(
+        &self,
+        task_id: Uuid,
+        input: UpdateTaskRecurrenceInput,
+    ) -> Result<Recurrence, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let recurrence_info = sqlx::query(
+            r#"
+            SELECT * FROM task_recurrences
+            WHERE task_id = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(task_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let recurrence = recurrence_from_row(&recurrence_info);
+        let rule = input.apply(recurrence_rule(&recurrence))?;
+
+        let anchor = occurrence_anchor(&mut tx, task_id).await?;
+        let next_occurrence_at = rule.next_occurrence(anchor, anchor, recurrence.occurrences);
+
+        let recurrence_info = sqlx::query(
+            r#"
+            UPDATE task_recurrences
+            SET
+                frequency = $1,
+                interval = $2,
+                by_weekday = $3,
+                by_month_day = $4,
+                until = $5,
+                count = $6,
+                next_occurrence_at = $7,
+                active = $8,
+                updated_at = now()
+            WHERE id = $9
+            RETURNING *
+            "#,
+        )
+        .bind(rule.frequency.to_string())
+        .bind(rule.interval)
+        .bind(rule.by_weekday.iter().map(|day| day.to_string()).collect::<Vec<_>>())
+        .bind(&rule.by_month_day)
+        .bind(rule.until)
+        .bind(rule.count)
+        .bind(recurrence.active.then_some(next_occurrence_at).flatten())
+        .bind(recurrence.active && next_occurrence_at.is_some())
+        .bind(recurrence.id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(recurrence_from_row(&recurrence_info))
+    }
+
+    async fn stop_task_recurrence(&self, task_id: Uuid) -> Result<Recurrence, SDKError> {
+        let recurrence_info = sqlx::query(
+            r#"
+            UPDATE task_recurrences
+            SET active = false, next_occurrence_at = NULL, updated_at = now()
+            WHERE task_id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(task_id)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(recurrence_from_row(&recurrence_info))
+    }
+
+    async fn due_task_recurrences(&self) -> Result<Vec<Recurrence>, SDKError> {
+        let recurrences_info = sqlx::query(
+            r#"
+            SELECT task_recurrences.* FROM task_recurrences
+            JOIN tasks ON tasks.id = task_recurrences.task_id
+            LEFT JOIN workflow_statuses ON workflow_statuses.id = tasks.workflow_status_id
+            WHERE task_recurrences.active
+                AND task_recurrences.next_occurrence_at IS NOT NULL
+                AND tasks.archived_at IS NULL AND tasks.deleted_at IS NULL
+                AND (
+                    task_recurrences.next_occurrence_at <= now()
+                    OR COALESCE(workflow_statuses.category = ANY($1), tasks.status = ANY($2))
+                )
+            ORDER BY task_recurrences.next_occurrence_at
+            "#,
+        )
+        .bind(closed_categories())
+        .bind(closed_statuses())
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(recurrences_info.iter().map(recurrence_from_row).collect())
+    }
+
+    async fn generate_task_occurrence(&self, recurrence_id: Uuid) -> Result<Option<Task>, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        // Skips recurrences another generator is working on, so running several instances is safe.
+        let recurrence_info = sqlx::query(
+            r#"
+            SELECT task_recurrences.* FROM task_recurrences
+            JOIN tasks ON tasks.id = task_recurrences.task_id
+            LEFT JOIN workflow_statuses ON workflow_statuses.id = tasks.workflow_status_id
+            WHERE task_recurrences.id = $1
+                AND task_recurrences.active
+                AND task_recurrences.next_occurrence_at IS NOT NULL
+                AND tasks.archived_at IS NULL AND tasks.deleted_at IS NULL
+                AND (
+                    task_recurrences.next_occurrence_at <= now()
+                    OR COALESCE(workflow_statuses.category = ANY($2), tasks.status = ANY($3))
+                )
+            FOR UPDATE OF task_recurrences SKIP LOCKED
+            "#,
+        )
+        .bind(recurrence_id)
+        .bind(closed_categories())
+        .bind(closed_statuses())
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(recurrence) = recurrence_info.as_ref().map(recurrence_from_row) else {
+            return Ok(None);
+        };
+
+        let due_date = recurrence.next_occurrence_at.unwrap();
+        let input = occurrence_input(&mut tx, recurrence.task_id, due_date).await?;
+
+        let task = insert_task(&mut tx, input).await?;
+
+        let occurrences = recurrence.occurrences + 1;
+        let next_occurrence_at = recurrence_rule(&recurrence).next_occurrence(due_date, Utc::now(), occurrences);
+
+        sqlx::query(
+            r#"
+            UPDATE task_recurrences
+            SET task_id = $1, occurrences = $2, next_occurrence_at = $3, active = $4, updated_at = now()
+            WHERE id = $5
+            "#,
+        )
+        .bind(task.id)
+        .bind(occurrences)
+        .bind(next_occurrence_at)
+        .bind(next_occurrence_at.is_some())
+        .bind(recurrence.id)
+        .execute(&mut *tx)
+        .await?;
+
+        insert_change(
+            &mut tx,
+            CreateChangeInput {
+                owner_id: recurrence.owner_id,
+                resource_id: task.id,
+                operation: ChangeOperation::Insert,
+                resource_type: ChangeResourceType::Tasks,
+                diff_json: serde_json::to_string(&json!({
+                    "recurrence_id": recurrence.id,
+                    "previous_task_id": recurrence.task_id,
+                    "result": task,
+                }))?,
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Some(task))
+    }
+}
+
+/// The date a series continues from: the due date of its latest occurrence, or now.
+async fn occurrence_anchor(conn: &mut PgConnection, task_id: Uuid) -> Result<DateTime<Utc>, SDKError> {
+    let task_info = sqlx::query(
+        r#"
+        SELECT due_date FROM tasks
+        WHERE id = $1
+        "#,
+    )
+    .bind(task_id)
+    .fetch_one(conn)
+    .await?;
+
+    Ok(task_info
+        .get::<'_, Option<DateTime<Utc>>, _>("due_date")
+        .unwrap_or_else(Utc::now))
+}
+
+/// Builds a copy of a task and its subtasks due on `due_date`. Subtask due dates keep their
+/// distance to the due date of the task, and every copy starts over as to do.
+async fn occurrence_input(
+    conn: &mut PgConnection,
+    task_id: Uuid,
+    due_date: DateTime<Utc>,
+) -> Result<CreateTaskInput, SDKError> {
+    let tasks_info = sqlx::query(
+        r#"
+        WITH RECURSIVE tree AS (
+            SELECT tasks.*, 0 AS depth, ARRAY[tasks.id] AS path FROM tasks
+            WHERE tasks.id = $1
+            UNION ALL
+            SELECT tasks.*, tree.depth + 1, tree.path || tasks.id FROM tasks
+            JOIN tree ON tasks.parent_id = tree.id
+            WHERE NOT tasks.id = ANY(tree.path) AND tasks.archived_at IS NULL AND tasks.deleted_at IS NULL
+        )
+        SELECT * FROM tree ORDER BY depth, created_at, count, id
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let tasks = tasks_info.iter().map(task_from_row).collect::<Vec<_>>();
+    let task_ids = tasks.iter().map(|task| task.id).collect::<Vec<_>>();
+
+    let mut labels: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    let mut assignees: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+
+    for label_info in sqlx::query(
+        r#"
+        SELECT task_id, label_id FROM labels_by_tasks
+        WHERE task_id = ANY($1)
+        "#,
+    )
+    .bind(&task_ids)
+    .fetch_all(&mut *conn)
+    .await?
+    {
+        labels
+            .entry(label_info.get("task_id"))
+            .or_default()
+            .push(label_info.get("label_id"));
+    }
+
+    for assignee_info in sqlx::query(
+        r#"
+        SELECT task_id, assignee_id FROM tasks_by_assignees
+        WHERE task_id = ANY($1)
+        "#,
+    )
+    .bind(&task_ids)
+    .fetch_all(&mut *conn)
+    .await?
+    {
+        assignees
+            .entry(assignee_info.get("task_id"))
+            .or_default()
+            .push(assignee_info.get("assignee_id"));
+    }
+
+    let root = tasks.first().ok_or(SDKError::SQLXError(sqlx::Error::RowNotFound))?;
+    let shift = root.due_date.map(|root_due_date| due_date - root_due_date);
+
+    let positions: HashMap<Uuid, usize> = tasks
+        .iter()
+        .enumerate()
+        .map(|(position, task)| (task.id, position))
+        .collect();
+
+    let mut inputs = tasks
+        .iter()
+        .enumerate()
+        .map(|(position, task)| {
+            Some(CreateTaskInput {
+                title: task.title.clone(),
+                owner_id: task.owner_id,
+                status: Some(TaskStatus::ToDo),
+                priority: Some(task.priority),
+                description: task.description.clone(),
+                due_date: match position {
+                    0 => Some(due_date),
+                    _ => task
+                        .due_date
+                        .zip(shift)
+                        .map(|(task_due_date, shift)| task_due_date + shift),
+                },
+                project_id: task.project_id,
+                lead_id: task.lead_id,
+                parent_id: if position == 0 { task.parent_id } else { None },
+                estimate: task.estimate,
+                estimate_unit: task.estimate_unit,
+                cycle_id: None,
+                labels: labels.remove(&task.id),
+                assignees: assignees.remove(&task.id),
+                subtasks: None,
+                assets: None,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // Rows come sorted by depth, so walking them backwards completes every child before its parent.
+    for position in (1..tasks.len()).rev() {
+        let parent = tasks[position]
+            .parent_id
+            .and_then(|parent_id| positions.get(&parent_id));
+
+        if let Some(&parent) = parent {
+            let child = inputs[position].take().unwrap();
+            let parent = inputs[parent].as_mut().unwrap();
+
+            parent.subtasks.get_or_insert_with(Vec::new).insert(0, child);
+        }
+    }
+
+    Ok(inputs[0].take().unwrap())
+}
+
+/// Workflow categories that close an occurrence early, so the next one is due right away.
+fn closed_categories() -> Vec<String> {
+    [WorkflowStatusCategory::Completed, WorkflowStatusCategory::Canceled]
+        .iter()
+        .map(|category| category.to_string())
+        .collect()
+}
+
+/// Task statuses that close an occurrence of a task without a workflow status.
+fn closed_statuses() -> Vec<String> {
+    [TaskStatus::Done, TaskStatus::Canceled]
+        .iter()
+        .map(|status| status.to_string())
+        .collect()
+}
+
+fn recurrence_rule(recurrence: &Recurrence) -> RecurrenceRule {
+    RecurrenceRule {
+        frequency: recurrence.frequency,
+        interval: recurrence.interval,
+        by_weekday: recurrence.by_weekday.clone(),
+        by_month_day: recurrence.by_month_day.clone(),
+        until: recurrence.until,
+        count: recurrence.count,
+    }
+}
+
+pub(crate) fn recurrence_from_row(recurrence_info: &PgRow) -> Recurrence {
+    let mut recurrence = Recurrence {
+        id: recurrence_info.get("id"),
+        created_at: recurrence_info.get("created_at"),
+        updated_at: recurrence_info.get("updated_at"),
+        owner_id: recurrence_info.get("owner_id"),
+        task_id: recurrence_info.get("task_id"),
+        frequency: RecurrenceFrequency::from_str(recurrence_info.get("frequency")).unwrap_or_default(),
+        interval: recurrence_info.get("interval"),
+        by_weekday: recurrence_info
+            .get::<'_, Vec<String>, _>("by_weekday")
+            .iter()
+            .filter_map(|day| RecurrenceWeekday::from_str(day).ok())
+            .collect(),
+        by_month_day: recurrence_info.get("by_month_day"),
+        until: recurrence_info.get("until"),
+        count: recurrence_info.get("count"),
+        occurrences: recurrence_info.get("occurrences"),
+        next_occurrence_at: recurrence_info.get("next_occurrence_at"),
+        active: recurrence_info.get("active"),
+        rrule: String::new(),
+    };
+
+    recurrence.rrule = recurrence_rule(&recurrence).to_rrule();
+
+    recurrence
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::backend::testing::{test_engine, test_member, test_project, test_task};
+    use crate::resources::tasks::operations::{TaskCrudOperations, UpdateTaskInput};
+    use crate::resources::workflows::operations::{
+        CreateWorkflowInput, CreateWorkflowStatusInput, WorkflowCrudOperations,
+    };
+
+    #[sqlx::test]
+    async fn completing_an_occurrence_in_a_custom_workflow_makes_the_next_due(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+        let project_id = test_project(&engine, owner_id, "REC").await;
+
+        engine
+            .create_workflow(CreateWorkflowInput {
+                name: "Release".to_string(),
+                project_id,
+                owner_id,
+                statuses: ["Planned", "Shipped"]
+                    .iter()
+                    .zip([WorkflowStatusCategory::Unstarted, WorkflowStatusCategory::Completed])
+                    .map(|(name, category)| CreateWorkflowStatusInput {
+                        name: name.to_string(),
+                        category,
+                        ..Default::default()
+                    })
+                    .collect(),
+                transitions: None,
+            })
+            .await
+            .unwrap();
+        let shipped_id: Uuid = sqlx::query_scalar("SELECT id FROM workflow_statuses WHERE name = 'Shipped'")
+            .fetch_one(engine.db_pool.as_ref())
+            .await
+            .unwrap();
+
+        let task = test_task(&engine, owner_id, Some(project_id), "Release notes").await;
+        engine
+            .update_task(
+                task.id,
+                UpdateTaskInput {
+                    due_date: Some(Utc::now() + Duration::days(30)),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let recurrence = engine
+            .set_task_recurrence(
+                task.id,
+                SetTaskRecurrenceInput {
+                    owner_id,
+                    frequency: Some(RecurrenceFrequency::Monthly),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert!(engine.due_task_recurrences().await.unwrap().is_empty());
+
+        engine
+            .update_task(
+                task.id,
+                UpdateTaskInput {
+                    workflow_status_id: Some(shipped_id),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let due = engine.due_task_recurrences().await.unwrap();
+        assert_eq!(due.iter().map(|due| due.id).collect::<Vec<_>>(), vec![recurrence.id]);
+
+        let occurrence = engine.generate_task_occurrence(recurrence.id).await.unwrap().unwrap();
+        assert_eq!(occurrence.status, TaskStatus::ToDo);
+        assert!(engine.due_task_recurrences().await.unwrap().is_empty());
+    }
+}
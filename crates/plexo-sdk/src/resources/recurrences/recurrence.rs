@@ -0,0 +1,384 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::sdk::SDKError;
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKTaskRecurrence")]
+pub struct Recurrence {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub owner_id: Uuid,
+    /// Latest occurrence of the series, the one the next occurrence is copied from.
+    pub task_id: Uuid,
+
+    pub frequency: RecurrenceFrequency,
+    pub interval: i32,
+    pub by_weekday: Vec<RecurrenceWeekday>,
+    pub by_month_day: Vec<i32>,
+    pub until: Option<DateTime<Utc>>,
+    pub count: Option<i32>,
+
+    /// Number of occurrences in the series so far, including the first task.
+    pub occurrences: i32,
+    pub next_occurrence_at: Option<DateTime<Utc>>,
+    pub active: bool,
+
+    /// The rule in RRULE notation, e.g. `FREQ=WEEKLY;BYDAY=MO,TH`.
+    pub rrule: String,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum RecurrenceFrequency {
+    Daily,
+    #[default]
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Debug, Enum, OpenApiEnum, Copy, Clone, Display, EnumString, Deserialize, Serialize, Eq, PartialEq)]
+pub enum RecurrenceWeekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<Weekday> for RecurrenceWeekday {
+    fn from(weekday: Weekday) -> Self {
+        match weekday {
+            Weekday::Mon => RecurrenceWeekday::Monday,
+            Weekday::Tue => RecurrenceWeekday::Tuesday,
+            Weekday::Wed => RecurrenceWeekday::Wednesday,
+            Weekday::Thu => RecurrenceWeekday::Thursday,
+            Weekday::Fri => RecurrenceWeekday::Friday,
+            Weekday::Sat => RecurrenceWeekday::Saturday,
+            Weekday::Sun => RecurrenceWeekday::Sunday,
+        }
+    }
+}
+
+impl RecurrenceWeekday {
+    const RRULE_CODES: [(RecurrenceWeekday, &'static str); 7] = [
+        (RecurrenceWeekday::Monday, "MO"),
+        (RecurrenceWeekday::Tuesday, "TU"),
+        (RecurrenceWeekday::Wednesday, "WE"),
+        (RecurrenceWeekday::Thursday, "TH"),
+        (RecurrenceWeekday::Friday, "FR"),
+        (RecurrenceWeekday::Saturday, "SA"),
+        (RecurrenceWeekday::Sunday, "SU"),
+    ];
+
+    fn rrule_code(&self) -> &'static str {
+        Self::RRULE_CODES.iter().find(|(day, _)| day == self).unwrap().1
+    }
+
+    fn from_rrule_code(code: &str) -> Option<Self> {
+        Self::RRULE_CODES
+            .iter()
+            .find(|(_, day_code)| day_code.eq_ignore_ascii_case(code))
+            .map(|(day, _)| *day)
+    }
+}
+
+/// The supported subset of RFC 5545 recurrence rules: `FREQ`, `INTERVAL`, `BYDAY` (weekdays
+/// without ordinals, daily and weekly rules only), `BYMONTHDAY` (monthly rules only, negative
+/// days count from the end of the month), `COUNT` and `UNTIL`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecurrenceRule {
+    pub frequency: RecurrenceFrequency,
+    pub interval: i32,
+    pub by_weekday: Vec<RecurrenceWeekday>,
+    pub by_month_day: Vec<i32>,
+    pub until: Option<DateTime<Utc>>,
+    pub count: Option<i32>,
+}
+
+impl RecurrenceRule {
+    pub fn parse(rrule: &str) -> Result<RecurrenceRule, SDKError> {
+        let invalid = |reason: &str| SDKError::InvalidRecurrenceRule(format!("{}: {}", reason, rrule));
+
+        let body = rrule.trim();
+        let body = body
+            .strip_prefix("RRULE:")
+            .or_else(|| body.strip_prefix("rrule:"))
+            .unwrap_or(body);
+
+        let mut frequency = None;
+        let mut rule = RecurrenceRule {
+            interval: 1,
+            ..Default::default()
+        };
+
+        for part in body.split(';').filter(|part| !part.is_empty()) {
+            let (name, value) = part.split_once('=').ok_or_else(|| invalid("malformed part"))?;
+
+            match name.to_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_uppercase().as_str() {
+                        "DAILY" => RecurrenceFrequency::Daily,
+                        "WEEKLY" => RecurrenceFrequency::Weekly,
+                        "MONTHLY" => RecurrenceFrequency::Monthly,
+                        "YEARLY" => RecurrenceFrequency::Yearly,
+                        _ => return Err(invalid("unsupported FREQ")),
+                    })
+                }
+                "INTERVAL" => rule.interval = value.parse().map_err(|_| invalid("invalid INTERVAL"))?,
+                "BYDAY" => {
+                    rule.by_weekday = value
+                        .split(',')
+                        .map(|code| {
+                            RecurrenceWeekday::from_rrule_code(code.trim()).ok_or_else(|| invalid("invalid BYDAY"))
+                        })
+                        .collect::<Result<_, _>>()?
+                }
+                "BYMONTHDAY" => {
+                    rule.by_month_day = value
+                        .split(',')
+                        .map(|day| day.trim().parse().map_err(|_| invalid("invalid BYMONTHDAY")))
+                        .collect::<Result<_, _>>()?
+                }
+                "COUNT" => rule.count = Some(value.parse().map_err(|_| invalid("invalid COUNT"))?),
+                "UNTIL" => rule.until = Some(parse_rrule_date(value).ok_or_else(|| invalid("invalid UNTIL"))?),
+                _ => return Err(invalid("unsupported part")),
+            }
+        }
+
+        rule.frequency = frequency.ok_or_else(|| invalid("missing FREQ"))?;
+        rule.validate()?;
+
+        Ok(rule)
+    }
+
+    pub fn validate(&self) -> Result<(), SDKError> {
+        let invalid = |reason: &str| Err(SDKError::InvalidRecurrenceRule(reason.to_string()));
+
+        if self.interval < 1 {
+            return invalid("interval must be positive");
+        }
+
+        if self.count.is_some_and(|count| count < 1) {
+            return invalid("count must be positive");
+        }
+
+        if self.until.is_some() && self.count.is_some() {
+            return invalid("until and count can't be combined");
+        }
+
+        if !self.by_weekday.is_empty()
+            && !matches!(self.frequency, RecurrenceFrequency::Daily | RecurrenceFrequency::Weekly)
+        {
+            return invalid("weekdays are only supported by daily and weekly rules");
+        }
+
+        if !self.by_month_day.is_empty() && self.frequency != RecurrenceFrequency::Monthly {
+            return invalid("month days are only supported by monthly rules");
+        }
+
+        if self
+            .by_month_day
+            .iter()
+            .any(|day| *day == 0 || !(-31..=31).contains(day))
+        {
+            return invalid("month days must be between 1 and 31, or -31 and -1");
+        }
+
+        Ok(())
+    }
+
+    pub fn to_rrule(&self) -> String {
+        let frequency = match self.frequency {
+            RecurrenceFrequency::Daily => "DAILY",
+            RecurrenceFrequency::Weekly => "WEEKLY",
+            RecurrenceFrequency::Monthly => "MONTHLY",
+            RecurrenceFrequency::Yearly => "YEARLY",
+        };
+
+        let mut parts = vec![format!("FREQ={}", frequency)];
+
+        if self.interval != 1 {
+            parts.push(format!("INTERVAL={}", self.interval));
+        }
+
+        if !self.by_weekday.is_empty() {
+            let days = self.by_weekday.iter().map(|day| day.rrule_code()).collect::<Vec<_>>();
+            parts.push(format!("BYDAY={}", days.join(",")));
+        }
+
+        if !self.by_month_day.is_empty() {
+            let days = self.by_month_day.iter().map(|day| day.to_string()).collect::<Vec<_>>();
+            parts.push(format!("BYMONTHDAY={}", days.join(",")));
+        }
+
+        if let Some(count) = self.count {
+            parts.push(format!("COUNT={}", count));
+        }
+
+        if let Some(until) = self.until {
+            parts.push(format!("UNTIL={}", until.format("%Y%m%dT%H%M%SZ")));
+        }
+
+        parts.join(";")
+    }
+
+    /// The first occurrence strictly after `after`, keeping its time of day. Month days that
+    /// don't exist in a month are skipped, as in RFC 5545.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let interval = i64::from(self.interval.max(1));
+        let weekdays = match self.by_weekday.is_empty() {
+            true => vec![after.weekday().into()],
+            false => self.by_weekday.clone(),
+        };
+
+        match self.frequency {
+            RecurrenceFrequency::Daily => (1..=7)
+                .map(|step| after + Duration::days(step * interval))
+                .find(|date| self.by_weekday.is_empty() || weekdays.contains(&date.weekday().into())),
+            RecurrenceFrequency::Weekly => {
+                let week_start = |date: DateTime<Utc>| {
+                    date.date_naive() - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+                };
+
+                (1..=7 * interval)
+                    .map(|offset| after + Duration::days(offset))
+                    .find(|date| {
+                        let weeks = (week_start(*date) - week_start(after)).num_days() / 7;
+
+                        weeks % interval == 0 && weekdays.contains(&date.weekday().into())
+                    })
+            }
+            RecurrenceFrequency::Monthly => {
+                let month_days = match self.by_month_day.is_empty() {
+                    true => vec![after.day() as i32],
+                    false => self.by_month_day.clone(),
+                };
+
+                // Four years of candidate months is enough to find any existing month day.
+                (0..=48).find_map(|step| {
+                    let month_index = i64::from(after.year()) * 12 + i64::from(after.month0()) + step * interval;
+                    let (year, month) = (month_index.div_euclid(12) as i32, month_index.rem_euclid(12) as u32 + 1);
+                    let month_length = days_in_month(year, month)?;
+
+                    let mut days = month_days
+                        .iter()
+                        .map(|day| if *day > 0 { *day } else { month_length + 1 + day })
+                        .filter(|day| (1..=month_length).contains(day))
+                        .collect::<Vec<_>>();
+                    days.sort_unstable();
+
+                    days.into_iter()
+                        .filter_map(|day| NaiveDate::from_ymd_opt(year, month, day as u32))
+                        .map(|date| date.and_time(after.time()).and_utc())
+                        .find(|date| *date > after)
+                })
+            }
+            RecurrenceFrequency::Yearly => (1..=8).find_map(|step| {
+                NaiveDate::from_ymd_opt(after.year() + (step * interval) as i32, after.month(), after.day())
+                    .map(|date| date.and_time(after.time()).and_utc())
+            }),
+        }
+    }
+
+    /// The occurrence following `after` in a series that already has `occurrences` tasks,
+    /// skipping dates up to `not_before`. `None` once the series reached its count or end date.
+    pub fn next_occurrence(
+        &self,
+        after: DateTime<Utc>,
+        not_before: DateTime<Utc>,
+        occurrences: i32,
+    ) -> Option<DateTime<Utc>> {
+        if self.count.is_some_and(|count| occurrences >= count) {
+            return None;
+        }
+
+        let mut next = self.next_after(after)?;
+
+        while next <= not_before {
+            next = self.next_after(next)?;
+        }
+
+        self.until.is_none_or(|until| next <= until).then_some(next)
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> Option<i32> {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+
+    Some((NaiveDate::from_ymd_opt(next_year, next_month, 1)? - first).num_days() as i32)
+}
+
+fn parse_rrule_date(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim().trim_end_matches('Z');
+
+    match value.len() {
+        8 => NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()?
+            .and_hms_opt(23, 59, 59)
+            .map(|date| date.and_utc()),
+        _ => chrono::NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+            .ok()
+            .map(|date| date.and_utc()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 9, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn parses_and_prints_rrule() {
+        let rule = RecurrenceRule::parse("RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;COUNT=10").unwrap();
+
+        assert_eq!(rule.frequency, RecurrenceFrequency::Weekly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(
+            rule.by_weekday,
+            vec![RecurrenceWeekday::Monday, RecurrenceWeekday::Thursday]
+        );
+        assert_eq!(rule.count, Some(10));
+        assert_eq!(rule.to_rrule(), "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;COUNT=10");
+
+        assert!(RecurrenceRule::parse("FREQ=HOURLY").is_err());
+        assert!(RecurrenceRule::parse("FREQ=MONTHLY;BYDAY=1MO").is_err());
+        assert!(RecurrenceRule::parse("INTERVAL=2").is_err());
+    }
+
+    #[test]
+    fn weekly_rule_skips_to_matching_week() {
+        let rule = RecurrenceRule::parse("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH").unwrap();
+
+        // 2024-04-29 is a Monday.
+        assert_eq!(rule.next_after(at(2024, 4, 29)), Some(at(2024, 5, 2)));
+        assert_eq!(rule.next_after(at(2024, 5, 2)), Some(at(2024, 5, 13)));
+    }
+
+    #[test]
+    fn monthly_rule_handles_short_months() {
+        let last_day = RecurrenceRule::parse("FREQ=MONTHLY;BYMONTHDAY=-1").unwrap();
+        assert_eq!(last_day.next_after(at(2024, 1, 31)), Some(at(2024, 2, 29)));
+
+        let thirty_first = RecurrenceRule::parse("FREQ=MONTHLY").unwrap();
+        assert_eq!(thirty_first.next_after(at(2024, 1, 31)), Some(at(2024, 3, 31)));
+    }
+}
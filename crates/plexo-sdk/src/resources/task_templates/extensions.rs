@@ -0,0 +1,422 @@
+use std::collections::{HashMap, HashSet};
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use serde_json::json;
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::{
+    backend::engine::SDKEngine,
+    errors::sdk::SDKError,
+    resources::{
+        changes::{
+            change::{ChangeOperation, ChangeResourceType},
+            operations::{insert_change, CreateChangeInput},
+        },
+        tasks::{
+            operations::{insert_task, task_from_row, CreateTaskInput},
+            task::Task,
+        },
+    },
+};
+
+use super::{
+    operations::{
+        insert_task_template_items, select_task_template_items, task_template_from_row, TaskTemplateItemInput,
+    },
+    task_template::{TaskTemplate, TaskTemplateItem},
+};
+
+#[async_trait]
+pub trait TaskTemplatesExtensionOperations {
+    /// Creates the task tree of a template in a project and returns its root task. Variables fill
+    /// the `{{name}}` references of titles and descriptions, and the assignee and lead
+    /// placeholders, which take member ids.
+    async fn instantiate_template(
+        &self,
+        template_id: Uuid,
+        project_id: Uuid,
+        input: InstantiateTemplateInput,
+    ) -> Result<Task, SDKError>;
+    /// Saves a task and its subtasks as a template of the task project. Due dates become offsets
+    /// from the creation of the task.
+    async fn save_task_as_template(
+        &self,
+        task_id: Uuid,
+        input: SaveTaskAsTemplateInput,
+    ) -> Result<TaskTemplate, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct InstantiateTemplateInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub variables: Option<Vec<TemplateVariableInput>>,
+    /// The date due offsets count from, defaults to now.
+    #[builder(setter(strip_option), default)]
+    pub starts_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct TemplateVariableInput {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct SaveTaskAsTemplateInput {
+    pub name: String,
+
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+}
+
+#[async_trait]
+impl TaskTemplatesExtensionOperations for SDKEngine {
+    async fn instantiate_template(
+        &self,
+        template_id: Uuid,
+        project_id: Uuid,
+        input: InstantiateTemplateInput,
+    ) -> Result<Task, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let template = task_template_from_row(
+            &sqlx::query("SELECT * FROM task_templates WHERE id = $1")
+                .bind(template_id)
+                .fetch_one(&mut *tx)
+                .await?,
+        );
+
+        if template
+            .project_id
+            .is_some_and(|template_project_id| template_project_id != project_id)
+        {
+            return Err(SDKError::InvalidTaskTemplate(
+                "the template belongs to another project".to_string(),
+            ));
+        }
+
+        let items = select_task_template_items(&mut tx, template_id).await?;
+
+        let variables: HashMap<String, String> = input
+            .variables
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|variable| (variable.name, variable.value))
+            .collect();
+
+        let mut members = HashMap::new();
+
+        for placeholder in items
+            .iter()
+            .flat_map(|item| item.assignee_placeholders.iter().chain(&item.lead_placeholder))
+        {
+            let member_id = variables
+                .get(placeholder)
+                .and_then(|value| Uuid::parse_str(value.trim()).ok())
+                .ok_or_else(|| SDKError::InvalidTaskTemplate(format!("{placeholder} needs a member id")))?;
+
+            members.insert(placeholder.clone(), member_id);
+        }
+
+        let member_ids: Vec<Uuid> = members.values().copied().collect();
+
+        let known_members: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM members WHERE id = ANY($1)")
+            .bind(&member_ids)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        if known_members < member_ids.iter().collect::<HashSet<_>>().len() as i64 {
+            return Err(SDKError::InvalidTaskTemplate(
+                "a placeholder names an unknown member".to_string(),
+            ));
+        }
+
+        // Labels deleted since the template was saved are left out, and labels of another project
+        // or team are swapped for the label of the same name in scope, if there's one.
+        let labels: HashMap<Uuid, Uuid> = sqlx::query(
+            r#"
+            SELECT id, scoped_label(id, $2) AS scoped_id FROM labels
+            WHERE id = ANY($1) AND scoped_label(id, $2) IS NOT NULL
+            "#,
+        )
+        .bind(items.iter().flat_map(|item| item.label_ids.clone()).collect::<Vec<_>>())
+        .bind(project_id)
+        .fetch_all(&mut *tx)
+        .await?
+        .iter()
+        .map(|label_info| (label_info.get("id"), label_info.get("scoped_id")))
+        .collect();
+
+        // Same for members removed since the template was saved.
+        let known_members: HashSet<Uuid> = sqlx::query_scalar("SELECT id FROM members WHERE id = ANY($1)")
+            .bind(
+                items
+                    .iter()
+                    .flat_map(|item| item.assignee_ids.iter().chain(&item.lead_id))
+                    .copied()
+                    .collect::<Vec<_>>(),
+            )
+            .fetch_all(&mut *tx)
+            .await?
+            .into_iter()
+            .collect();
+
+        let context = InstanceContext {
+            owner_id: input.owner_id,
+            project_id,
+            starts_at: input.starts_at.unwrap_or_else(Utc::now),
+            variables,
+            members,
+            known_members,
+            labels,
+        };
+
+        let Some(root) = items.iter().find(|item| item.parent_id.is_none()) else {
+            return Err(SDKError::InvalidTaskTemplate("the template has no tasks".to_string()));
+        };
+
+        let task = insert_task(&mut tx, context.task_input(root, &items)).await?;
+
+        insert_change(
+            &mut tx,
+            CreateChangeInput {
+                owner_id: input.owner_id,
+                resource_id: task.id,
+                operation: ChangeOperation::Insert,
+                resource_type: ChangeResourceType::Tasks,
+                diff_json: serde_json::to_string(&json!({
+                    "template_id": template_id,
+                    "input": input,
+                    "result": task,
+                }))?,
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(task)
+    }
+
+    async fn save_task_as_template(
+        &self,
+        task_id: Uuid,
+        input: SaveTaskAsTemplateInput,
+    ) -> Result<TaskTemplate, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let tasks_info = sqlx::query(
+            r#"
+            WITH RECURSIVE tree AS (
+                SELECT tasks.*, ARRAY[tasks.id] AS path FROM tasks
+                WHERE tasks.id = $1
+                UNION ALL
+                SELECT tasks.*, tree.path || tasks.id FROM tasks
+                JOIN tree ON tasks.parent_id = tree.id
+                WHERE NOT tasks.id = ANY(tree.path) AND tasks.deleted_at IS NULL
+            )
+            SELECT
+                tree.*,
+                ARRAY(SELECT label_id FROM labels_by_tasks WHERE task_id = tree.id) AS label_ids,
+                ARRAY(SELECT assignee_id FROM tasks_by_assignees WHERE task_id = tree.id) AS assignee_ids
+            FROM tree
+            ORDER BY cardinality(path), rank, created_at, count, id
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let Some(root_info) = tasks_info.first() else {
+            return Err(SDKError::SQLXError(sqlx::Error::RowNotFound));
+        };
+
+        let root = task_from_row(root_info);
+
+        let mut items: HashMap<Option<Uuid>, Vec<TaskTemplateItemInput>> = HashMap::new();
+
+        for task_info in tasks_info.iter().rev() {
+            let task = task_from_row(task_info);
+
+            let item = TaskTemplateItemInput {
+                title: task.title,
+                description: task.description,
+                priority: Some(task.priority),
+                estimate: task.estimate,
+                estimate_unit: task.estimate_unit,
+                due_offset_days: task
+                    .due_date
+                    .map(|due_date| (due_date - root.created_at).num_days() as i32),
+                labels: Some(task_info.get("label_ids")),
+                assignees: Some(task_info.get("assignee_ids")),
+                assignee_placeholders: None,
+                lead_id: task.lead_id,
+                lead_placeholder: None,
+                subtasks: Some(
+                    items
+                        .remove(&Some(task.id))
+                        .unwrap_or_default()
+                        .into_iter()
+                        .rev()
+                        .collect(),
+                ),
+            };
+
+            let parent_id = if task.id == root.id { None } else { task.parent_id };
+
+            items.entry(parent_id).or_default().push(item);
+        }
+
+        let root_item = items.remove(&None).unwrap_or_default().remove(0);
+
+        let template_info = sqlx::query(
+            r#"
+            INSERT INTO task_templates (name, owner_id, project_id, description)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(input.name)
+        .bind(input.owner_id)
+        .bind(root.project_id)
+        .bind(input.description)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let template = task_template_from_row(&template_info);
+
+        insert_task_template_items(&mut tx, template.id, root_item).await?;
+
+        tx.commit().await?;
+
+        Ok(template)
+    }
+}
+
+/// What an instantiation resolves the items of a template with.
+struct InstanceContext {
+    owner_id: Uuid,
+    project_id: Uuid,
+    starts_at: DateTime<Utc>,
+    variables: HashMap<String, String>,
+    members: HashMap<String, Uuid>,
+    /// Template assignees and leads that are still members.
+    known_members: HashSet<Uuid>,
+    /// Template labels and the labels they resolve to in the project.
+    labels: HashMap<Uuid, Uuid>,
+}
+
+impl InstanceContext {
+    fn task_input(&self, item: &TaskTemplateItem, items: &[TaskTemplateItem]) -> CreateTaskInput {
+        let mut assignees = Vec::new();
+
+        for member_id in item
+            .assignee_ids
+            .iter()
+            .filter(|member_id| self.known_members.contains(member_id))
+            .chain(
+                item.assignee_placeholders
+                    .iter()
+                    .filter_map(|placeholder| self.members.get(placeholder)),
+            )
+        {
+            if !assignees.contains(member_id) {
+                assignees.push(*member_id);
+            }
+        }
+
+        let mut labels = Vec::new();
+
+        for label_id in item.label_ids.iter().filter_map(|label_id| self.labels.get(label_id)) {
+            if !labels.contains(label_id) {
+                labels.push(*label_id);
+            }
+        }
+
+        let subtasks = items
+            .iter()
+            .filter(|subitem| subitem.parent_id == Some(item.id))
+            .map(|subitem| self.task_input(subitem, items))
+            .collect();
+
+        CreateTaskInput {
+            title: fill_variables(&item.title, &self.variables),
+            owner_id: self.owner_id,
+            priority: item.priority,
+            description: item
+                .description
+                .as_deref()
+                .map(|description| fill_variables(description, &self.variables)),
+            due_date: item
+                .due_offset_days
+                .map(|days| self.starts_at + Duration::days(days.into())),
+            project_id: Some(self.project_id),
+            lead_id: item
+                .lead_placeholder
+                .as_ref()
+                .and_then(|placeholder| self.members.get(placeholder).copied())
+                .or(item.lead_id.filter(|lead_id| self.known_members.contains(lead_id))),
+            estimate: item.estimate,
+            estimate_unit: item.estimate_unit,
+            labels: Some(labels),
+            assignees: Some(assignees),
+            subtasks: Some(subtasks),
+            ..Default::default()
+        }
+    }
+}
+
+/// Replaces the `{{name}}` references of a text, leaving the unknown ones as they are.
+fn fill_variables(text: &str, variables: &HashMap<String, String>) -> String {
+    let mut filled = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}").map(|end| start + end) else {
+            break;
+        };
+
+        filled.push_str(&rest[..start]);
+
+        match variables.get(rest[start + 2..end].trim()) {
+            Some(value) => filled.push_str(value),
+            None => filled.push_str(&rest[start..end + 2]),
+        }
+
+        rest = &rest[end + 2..];
+    }
+
+    filled.push_str(rest);
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variables_fill_known_references() {
+        let variables = HashMap::from([("version".to_string(), "1.4.0".to_string())]);
+
+        assert_eq!(
+            fill_variables("Release {{ version }} ({{version}}) for {{team}} {{", &variables),
+            "Release 1.4.0 (1.4.0) for {{team}} {{"
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod extensions;
+pub mod operations;
+pub mod relations;
+pub mod task_template;
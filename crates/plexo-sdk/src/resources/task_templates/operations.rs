@@ -0,0 +1,398 @@
+use std::str::FromStr;
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, PgConnection, Row};
+use uuid::Uuid;
+
+use crate::common::filters::{SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect, StringComparison, UuidComparison};
+use crate::common::pagination::{Page, PageInput};
+use crate::resources::tasks::task::{TaskEstimateUnit, TaskPriority};
+use crate::{backend::engine::SDKEngine, common::commons::SortOrder, errors::sdk::SDKError};
+
+use super::task_template::{TaskTemplate, TaskTemplateItem};
+
+const TASK_TEMPLATES_SORTABLE_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "name"];
+
+#[async_trait]
+pub trait TaskTemplateCrudOperations {
+    async fn create_task_template(&self, input: CreateTaskTemplateInput) -> Result<TaskTemplate, SDKError>;
+    async fn get_task_template(&self, id: Uuid) -> Result<TaskTemplate, SDKError>;
+    async fn get_task_templates(&self, input: GetTaskTemplatesInput) -> Result<Vec<TaskTemplate>, SDKError>;
+    async fn get_task_templates_page(
+        &self,
+        input: PageInput<GetTaskTemplatesWhere>,
+    ) -> Result<Page<TaskTemplate>, SDKError>;
+    async fn update_task_template(&self, id: Uuid, input: UpdateTaskTemplateInput) -> Result<TaskTemplate, SDKError>;
+    async fn delete_task_template(&self, id: Uuid) -> Result<TaskTemplate, SDKError>;
+    /// The items of a template, parents before their children and siblings in order.
+    async fn get_task_template_items(&self, template_id: Uuid) -> Result<Vec<TaskTemplateItem>, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateTaskTemplateInput {
+    pub name: String,
+    pub root: TaskTemplateItemInput,
+
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+}
+
+/// A given `root` replaces the whole tree of the template.
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateTaskTemplateInput {
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub root: Option<TaskTemplateItemInput>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct TaskTemplateItemInput {
+    pub title: String,
+
+    #[builder(setter(strip_option), default)]
+    pub description: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub priority: Option<TaskPriority>,
+    #[builder(setter(strip_option), default)]
+    pub estimate: Option<f64>,
+    #[builder(setter(strip_option), default)]
+    pub estimate_unit: Option<TaskEstimateUnit>,
+    #[builder(setter(strip_option), default)]
+    pub due_offset_days: Option<i32>,
+
+    #[builder(setter(strip_option), default)]
+    pub labels: Option<Vec<Uuid>>,
+    #[builder(setter(strip_option), default)]
+    pub assignees: Option<Vec<Uuid>>,
+    #[builder(setter(strip_option), default)]
+    pub assignee_placeholders: Option<Vec<String>>,
+    #[builder(setter(strip_option), default)]
+    pub lead_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub lead_placeholder: Option<String>,
+
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub subtasks: Option<Vec<TaskTemplateItemInput>>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetTaskTemplatesInput {
+    #[builder(setter(strip_option), default)]
+    pub filter: Option<GetTaskTemplatesWhere>,
+
+    #[builder(setter(strip_option), default)]
+    pub sort_by: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub sort_order: Option<SortOrder>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetTaskTemplatesWhere {
+    #[builder(setter(strip_option), default)]
+    pub ids: Option<Vec<Uuid>>,
+    #[builder(setter(into, strip_option), default)]
+    pub name: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub owner_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub project_id: Option<UuidComparison>,
+
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _and: Option<Vec<GetTaskTemplatesWhere>>,
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _or: Option<Vec<GetTaskTemplatesWhere>>,
+}
+
+impl SQLFilter for GetTaskTemplatesWhere {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
+
+        if let Some(ids) = &self.ids {
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
+        }
+
+        if let Some(name) = &self.name {
+            conditions.push(name.compile_sql("name"));
+        }
+
+        if let Some(owner_id) = &self.owner_id {
+            conditions.push(owner_id.compile_sql("owner_id"));
+        }
+
+        if let Some(project_id) = &self.project_id {
+            conditions.push(project_id.compile_sql("project_id"));
+        }
+
+        if let Some(ands) = &self._and {
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
+        }
+
+        if let Some(ors) = &self._or {
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
+        }
+
+        SQLCondition::And(conditions)
+    }
+}
+
+#[async_trait]
+impl TaskTemplateCrudOperations for SDKEngine {
+    async fn create_task_template(&self, input: CreateTaskTemplateInput) -> Result<TaskTemplate, SDKError> {
+        validate_task_template_item(&input.root)?;
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let template_info = sqlx::query(
+            r#"
+            INSERT INTO task_templates (name, owner_id, project_id, description)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(input.name)
+        .bind(input.owner_id)
+        .bind(input.project_id)
+        .bind(input.description)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let template = task_template_from_row(&template_info);
+
+        insert_task_template_items(&mut tx, template.id, input.root).await?;
+
+        tx.commit().await?;
+
+        Ok(template)
+    }
+
+    async fn get_task_template(&self, id: Uuid) -> Result<TaskTemplate, SDKError> {
+        let template_info = sqlx::query("SELECT * FROM task_templates WHERE id = $1")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(task_template_from_row(&template_info))
+    }
+
+    async fn get_task_templates(&self, input: GetTaskTemplatesInput) -> Result<Vec<TaskTemplate>, SDKError> {
+        let mut query = SQLSelect::new("task_templates", TASK_TEMPLATES_SORTABLE_COLUMNS)
+            .filter(input.filter.as_ref())
+            .sort(input.sort_by, input.sort_order)
+            .paginate(input.limit, input.offset)
+            .build()?;
+
+        let templates_info = query.build().fetch_all(self.db_pool.as_ref()).await?;
+
+        Ok(templates_info.iter().map(task_template_from_row).collect())
+    }
+
+    async fn get_task_templates_page(
+        &self,
+        input: PageInput<GetTaskTemplatesWhere>,
+    ) -> Result<Page<TaskTemplate>, SDKError> {
+        SQLSelect::new("task_templates", TASK_TEMPLATES_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), input, task_template_from_row)
+            .await
+    }
+
+    async fn update_task_template(&self, id: Uuid, input: UpdateTaskTemplateInput) -> Result<TaskTemplate, SDKError> {
+        if let Some(root) = &input.root {
+            validate_task_template_item(root)?;
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let template_info = sqlx::query(
+            r#"
+            UPDATE task_templates
+            SET
+                name = COALESCE($1, name),
+                description = COALESCE($2, description)
+            WHERE id = $3
+            RETURNING *
+            "#,
+        )
+        .bind(input.name)
+        .bind(input.description)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let Some(root) = input.root {
+            sqlx::query("DELETE FROM task_template_items WHERE template_id = $1")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+
+            insert_task_template_items(&mut tx, id, root).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(task_template_from_row(&template_info))
+    }
+
+    async fn delete_task_template(&self, id: Uuid) -> Result<TaskTemplate, SDKError> {
+        let template_info = sqlx::query("DELETE FROM task_templates WHERE id = $1 RETURNING *")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(task_template_from_row(&template_info))
+    }
+
+    async fn get_task_template_items(&self, template_id: Uuid) -> Result<Vec<TaskTemplateItem>, SDKError> {
+        let mut conn = self.db_pool.acquire().await?;
+
+        select_task_template_items(&mut conn, template_id).await
+    }
+}
+
+fn validate_task_template_item(item: &TaskTemplateItemInput) -> Result<(), SDKError> {
+    if item.title.trim().is_empty() {
+        return Err(SDKError::InvalidTaskTemplate("task titles can't be empty".to_string()));
+    }
+
+    let placeholders = item
+        .assignee_placeholders
+        .iter()
+        .flatten()
+        .chain(item.lead_placeholder.iter());
+
+    for placeholder in placeholders {
+        if placeholder.trim().is_empty() {
+            return Err(SDKError::InvalidTaskTemplate("placeholders need a name".to_string()));
+        }
+    }
+
+    item.subtasks.iter().flatten().try_for_each(validate_task_template_item)
+}
+
+/// Inserts a template tree, numbering siblings in the order they were given.
+pub(crate) async fn insert_task_template_items(
+    conn: &mut PgConnection,
+    template_id: Uuid,
+    root: TaskTemplateItemInput,
+) -> Result<(), SDKError> {
+    let mut pending = vec![(root, None::<Uuid>, 0)];
+
+    while let Some((item, parent_id, position)) = pending.pop() {
+        let item_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO task_template_items (
+                template_id, parent_id, position, title, description, priority, estimate, estimate_unit,
+                due_offset_days, label_ids, assignee_ids, assignee_placeholders, lead_id, lead_placeholder
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            RETURNING id
+            "#,
+        )
+        .bind(template_id)
+        .bind(parent_id)
+        .bind(position)
+        .bind(item.title)
+        .bind(item.description)
+        .bind(item.priority.map(|priority| priority.to_string()))
+        .bind(item.estimate)
+        .bind(item.estimate_unit.map(|unit| unit.to_string()))
+        .bind(item.due_offset_days)
+        .bind(item.labels.unwrap_or_default())
+        .bind(item.assignees.unwrap_or_default())
+        .bind(item.assignee_placeholders.unwrap_or_default())
+        .bind(item.lead_id)
+        .bind(item.lead_placeholder)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        for (position, subtask) in item.subtasks.unwrap_or_default().into_iter().enumerate() {
+            pending.push((subtask, Some(item_id), position as i32));
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn select_task_template_items(
+    conn: &mut PgConnection,
+    template_id: Uuid,
+) -> Result<Vec<TaskTemplateItem>, SDKError> {
+    let items_info = sqlx::query(
+        r#"
+        WITH RECURSIVE tree AS (
+            SELECT task_template_items.*, ARRAY[position] AS path FROM task_template_items
+            WHERE template_id = $1 AND parent_id IS NULL
+            UNION ALL
+            SELECT task_template_items.*, tree.path || task_template_items.position FROM task_template_items
+            JOIN tree ON task_template_items.parent_id = tree.id
+        )
+        SELECT * FROM tree ORDER BY path
+        "#,
+    )
+    .bind(template_id)
+    .fetch_all(conn)
+    .await?;
+
+    Ok(items_info.iter().map(task_template_item_from_row).collect())
+}
+
+pub(crate) fn task_template_from_row(template_info: &PgRow) -> TaskTemplate {
+    TaskTemplate {
+        id: template_info.get("id"),
+        created_at: template_info.get("created_at"),
+        updated_at: template_info.get("updated_at"),
+        owner_id: template_info.get("owner_id"),
+        project_id: template_info.get("project_id"),
+        name: template_info.get("name"),
+        description: template_info.get("description"),
+    }
+}
+
+pub(crate) fn task_template_item_from_row(item_info: &PgRow) -> TaskTemplateItem {
+    TaskTemplateItem {
+        id: item_info.get("id"),
+        created_at: item_info.get("created_at"),
+        template_id: item_info.get("template_id"),
+        parent_id: item_info.get("parent_id"),
+        position: item_info.get("position"),
+        title: item_info.get("title"),
+        description: item_info.get("description"),
+        priority: item_info
+            .get::<'_, Option<String>, _>("priority")
+            .and_then(|priority| TaskPriority::from_str(&priority).ok()),
+        estimate: item_info.get("estimate"),
+        estimate_unit: item_info
+            .get::<'_, Option<String>, _>("estimate_unit")
+            .and_then(|unit| TaskEstimateUnit::from_str(&unit).ok()),
+        due_offset_days: item_info.get("due_offset_days"),
+        label_ids: item_info.get("label_ids"),
+        assignee_ids: item_info.get("assignee_ids"),
+        assignee_placeholders: item_info.get("assignee_placeholders"),
+        lead_id: item_info.get("lead_id"),
+        lead_placeholder: item_info.get("lead_placeholder"),
+    }
+}
@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+
+use crate::{
+    backend::loaders::SDKLoaders,
+    errors::sdk::SDKError,
+    resources::{members::member::Member, projects::project::Project},
+};
+
+use super::{
+    operations::TaskTemplateCrudOperations,
+    task_template::{TaskTemplate, TaskTemplateItem},
+};
+
+#[async_trait]
+pub trait TaskTemplateRelations {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Option<Project>, SDKError>;
+    async fn items(&self, loaders: &SDKLoaders) -> Result<Vec<TaskTemplateItem>, SDKError>;
+}
+
+#[async_trait]
+impl TaskTemplateRelations for TaskTemplate {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
+        let data = loaders.member_loader.load_one(self.owner_id).await.unwrap().unwrap();
+
+        Ok(data)
+    }
+
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Option<Project>, SDKError> {
+        let Some(project_id) = self.project_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.project_loader.load_one(project_id).await.unwrap();
+
+        Ok(data)
+    }
+
+    async fn items(&self, loaders: &SDKLoaders) -> Result<Vec<TaskTemplateItem>, SDKError> {
+        loaders.engine.get_task_template_items(self.id).await
+    }
+}
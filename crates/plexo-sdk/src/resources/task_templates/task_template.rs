@@ -0,0 +1,54 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use uuid::Uuid;
+
+use serde::Serialize;
+
+use crate::resources::tasks::task::{TaskEstimateUnit, TaskPriority};
+
+/// A reusable task tree, instantiated into a project as a task with its subtasks.
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKTaskTemplate")]
+pub struct TaskTemplate {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub owner_id: Uuid,
+    /// Templates without a project can be instantiated into any project.
+    pub project_id: Option<Uuid>,
+
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// A task of a template tree, the root item has no parent.
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKTaskTemplateItem")]
+pub struct TaskTemplateItem {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+
+    pub template_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    /// Order among the items sharing the same parent.
+    pub position: i32,
+
+    /// May hold `{{variable}}` references, replaced when the template is instantiated.
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: Option<TaskPriority>,
+    pub estimate: Option<f64>,
+    pub estimate_unit: Option<TaskEstimateUnit>,
+    /// Days between the instantiation and the due date of the task.
+    pub due_offset_days: Option<i32>,
+
+    pub label_ids: Vec<Uuid>,
+    pub assignee_ids: Vec<Uuid>,
+    /// Variables naming the members assigned when the template is instantiated.
+    pub assignee_placeholders: Vec<String>,
+    pub lead_id: Option<Uuid>,
+    pub lead_placeholder: Option<String>,
+}
@@ -1,17 +1,37 @@
-use std::str::FromStr;
+use std::collections::HashMap;
 
 use async_graphql::InputObject;
 use async_trait::async_trait;
 use derive_builder::Builder;
+use poem_openapi::Object;
 use serde::Serialize;
-use sqlx::Row;
+use serde_json::json;
+use sqlx::{PgConnection, Postgres, QueryBuilder, Row};
 use uuid::Uuid;
 
-use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+use crate::{
+    backend::engine::SDKEngine,
+    common::{
+        filters::SQLFilter,
+        ranks::{rank_between, spread_ranks, MAX_RANK_LENGTH},
+        trash::{hide_task_trees, restore_task_trees, TrashColumn, VisibleFilter},
+    },
+    errors::sdk::SDKError,
+    resources::{
+        changes::{
+            change::{ChangeOperation, ChangeResourceType},
+            operations::{insert_change, CreateChangeInput},
+        },
+        custom_fields::custom_field::CustomFieldKind,
+    },
+};
 
 use super::{
-    operations::{CreateTaskInput, TaskCrudOperations},
-    task::{Task, TaskPriority, TaskStatus},
+    operations::{
+        apply_task_update, insert_task, lock_visible_task, task_from_row, CreateTaskInput, GetTasksWhere,
+        TaskCrudOperations, UpdateTaskInput,
+    },
+    task::{Task, TaskStatus},
 };
 
 #[derive(Default, Builder, InputObject, Clone, Serialize)]
@@ -20,126 +40,115 @@ pub struct CreateTasksInput {
     pub tasks: Vec<CreateTaskInput>,
 }
 
-#[async_trait]
-pub trait TasksExtensionOperations {
-    async fn create_tasks(&self, input: CreateTasksInput) -> Result<Vec<Task>, SDKError>;
+/// Selects tasks by `ids`, `filter` or both (a task must match both). A selection without any
+/// condition matches nothing, so an empty filter can't rewrite every task by accident.
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct UpdateTasksInput {
+    #[graphql(skip)]
+    #[oai(skip)]
+    pub owner_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub ids: Option<Vec<Uuid>>,
+    #[builder(setter(strip_option), default)]
+    pub filter: Option<GetTasksWhere>,
+
+    pub update: UpdateTaskInput,
 }
 
-#[async_trait]
-impl TasksExtensionOperations for SDKEngine {
-    async fn create_tasks(&self, input: CreateTasksInput) -> Result<Vec<Task>, SDKError> {
-        let mut tx = self.db_pool.begin().await?;
-        // let saved_input = input.clone();
+#[derive(Default, Builder, Object, InputObject, Clone, Serialize)]
+#[builder(pattern = "owned")]
+pub struct DeleteTasksInput {
+    #[graphql(skip)]
+    #[oai(skip)]
+    pub owner_id: Uuid,
 
-        let values = input
-            .tasks
-            .iter()
-            .map(|task| {
-                format!(
-                    "('{}', '{}', {}, '{}', '{}', {}, {}, {}, {})",
-                    task.title,
-                    task.owner_id,
-                    task.description
-                        .clone()
-                        .map(|d| format!("'{}'", d))
-                        .unwrap_or("null".to_string()),
-                    task.status.unwrap_or_default(),
-                    task.priority.unwrap_or_default(),
-                    task.due_date
-                        .map(|dd| format!("'{}'", dd))
-                        .unwrap_or("null".to_string()),
-                    task.project_id
-                        .map(|p| format!("'{}'", p))
-                        .unwrap_or("null".to_string()),
-                    task.lead_id.map(|l| format!("'{}'", l)).unwrap_or("null".to_string()),
-                    task.parent_id.map(|p| format!("'{}'", p)).unwrap_or("null".to_string()),
-                )
-            })
-            .collect::<Vec<String>>();
+    pub ids: Vec<Uuid>,
+}
 
-        let query = format!(
-            "INSERT INTO tasks (title, owner_id, description, status, priority, due_date, project_id, lead_id, parent_id) VALUES {} RETURNING *",
-            values.join(", ")
-        );
+#[derive(Default, Builder, Object, InputObject, Clone, Serialize)]
+#[builder(pattern = "owned")]
+pub struct MoveTaskInput {
+    #[graphql(skip)]
+    pub owner_id: Uuid,
 
-        let tasks = sqlx::query(query.as_str()).fetch_all(&mut *tx).await?;
+    pub project_id: Uuid,
+    /// Subtasks in the same project move along unless set to false. Left behind, they take the
+    /// place of the task under its parent.
+    #[builder(setter(strip_option), default)]
+    pub include_subtasks: Option<bool>,
+}
 
-        for (i, input_task) in input.tasks.iter().enumerate() {
-            let task = &tasks[i];
+/// A task with its subtasks, ordered by creation. The counts and progress roll up the whole
+/// subtree, including levels cut off by the depth limit; canceled tasks are left out of them.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskTree {
+    pub task: Task,
+    pub depth: i32,
 
-            let task_id = task.get::<Uuid, _>("id");
-            let task_owner_id = task.get::<Uuid, _>("owner_id");
+    pub total_count: i32,
+    pub done_count: i32,
+    /// Percentage of done tasks in the subtree. Tasks without subtasks report 0 or 100.
+    pub progress: f64,
 
-            if let Some(labels) = input_task.labels.clone() {
-                for label in labels {
-                    sqlx::query!(
-                        r#"
-                    INSERT INTO labels_by_tasks (task_id, label_id)
-                    VALUES ($1, $2)
-                    "#,
-                        task_id,
-                        label,
-                    )
-                    .execute(&mut *tx)
-                    .await?;
-                }
-            }
+    pub children: Vec<TaskTree>,
+}
 
-            if let Some(assignees) = input_task.assignees.clone() {
-                for assignee in assignees {
-                    sqlx::query!(
-                        r#"
-                        INSERT INTO tasks_by_assignees (task_id, assignee_id)
-                        VALUES ($1, $2)
-                        "#,
-                        task_id,
-                        assignee,
-                    )
-                    .execute(&mut *tx)
-                    .await?;
-                }
-            }
+/// The tasks of a project with one status, in board order.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskBoardColumn {
+    pub status: TaskStatus,
+    pub tasks: Vec<Task>,
+}
 
-            if let Some(subtasks) = input_task.subtasks.clone() {
-                for mut subtask in subtasks {
-                    subtask.owner_id = task_owner_id;
+#[async_trait]
+pub trait TasksExtensionOperations {
+    async fn create_tasks(&self, input: CreateTasksInput) -> Result<Vec<Task>, SDKError>;
+    async fn add_task_dependency(&self, task_id: Uuid, blocked_by_id: Uuid) -> Result<Task, SDKError>;
+    async fn remove_task_dependency(&self, task_id: Uuid, blocked_by_id: Uuid) -> Result<Task, SDKError>;
+    async fn task_tree(&self, id: Uuid, max_depth: Option<i32>) -> Result<TaskTree, SDKError>;
+    async fn update_tasks(&self, input: UpdateTasksInput) -> Result<Vec<Task>, SDKError>;
+    async fn delete_tasks(&self, input: DeleteTasksInput) -> Result<Vec<Task>, SDKError>;
 
-                    if subtask.parent_id.is_none() {
-                        subtask.parent_id = Some(task_id);
-                    }
+    /// Moves a task right below `before_id` and/or right above `after_id`, into the `status`
+    /// column if given. When both are given they must be next to each other. Without neighbours
+    /// the task goes to the end of its column.
+    async fn reorder_task(
+        &self,
+        id: Uuid,
+        before_id: Option<Uuid>,
+        after_id: Option<Uuid>,
+        status: Option<TaskStatus>,
+    ) -> Result<Task, SDKError>;
+    /// Spreads the ranks of every board column with unranked tasks or overly long ranks, and
+    /// returns the number of tasks that were ranked again.
+    async fn rebalance_task_ranks(&self) -> Result<i64, SDKError>;
+    async fn board(&self, project_id: Uuid) -> Result<Vec<TaskBoardColumn>, SDKError>;
+    /// Archives a task together with its subtasks.
+    async fn archive_task(&self, id: Uuid) -> Result<Task, SDKError>;
+    /// Brings a task back from the trash or the archive, with the subtasks that went with it.
+    async fn restore_task(&self, id: Uuid) -> Result<Task, SDKError>;
+    /// Moves a task, and by default its subtasks, to another project in one transaction. Moved
+    /// tasks are numbered in the target project (their old keys keep resolving), mapped to its
+    /// workflow, custom fields and cycles, and recorded as a single `Move` change.
+    async fn move_task(&self, id: Uuid, input: MoveTaskInput) -> Result<Task, SDKError>;
+}
 
-                    self.create_task(subtask).await?;
-                }
-            }
+#[async_trait]
+impl TasksExtensionOperations for SDKEngine {
+    async fn create_tasks(&self, input: CreateTasksInput) -> Result<Vec<Task>, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+        // let saved_input = input.clone();
+
+        let mut tasks = Vec::with_capacity(input.tasks.len());
+
+        for input_task in input.tasks {
+            tasks.push(insert_task(&mut tx, input_task).await?);
         }
 
         tx.commit().await?;
 
-        let tasks: Vec<Task> = tasks
-            .iter()
-            .map(|task_info| Task {
-                id: task_info.get("id"),
-                created_at: task_info.get("created_at"),
-                updated_at: task_info.get("updated_at"),
-                title: task_info.get("title"),
-                description: task_info.get("description"),
-                status: task_info
-                    .get::<'_, Option<String>, _>("status")
-                    .and_then(|a| TaskStatus::from_str(&a).ok())
-                    .unwrap_or_default(),
-                priority: task_info
-                    .get::<'_, Option<String>, _>("priority")
-                    .and_then(|a| TaskPriority::from_str(&a).ok())
-                    .unwrap_or_default(),
-                due_date: task_info.get("due_date"),
-                project_id: task_info.get("project_id"),
-                lead_id: task_info.get("lead_id"),
-                owner_id: task_info.get("owner_id"),
-                count: task_info.get("count"),
-                parent_id: task_info.get("parent_id"),
-            })
-            .collect();
-
         // if self.config.with_changes_registration {
         //     let tasks = tasks.clone();
         //     let engine = self.clone();
@@ -173,4 +182,988 @@ impl TasksExtensionOperations for SDKEngine {
 
         Ok(tasks)
     }
+
+    async fn add_task_dependency(&self, task_id: Uuid, blocked_by_id: Uuid) -> Result<Task, SDKError> {
+        if task_id == blocked_by_id {
+            return Err(SDKError::TaskDependencyCycle);
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+
+        // Serializes concurrent writers so two inverse dependencies can't both pass the cycle check.
+        sqlx::query!("LOCK TABLE task_dependencies IN SHARE ROW EXCLUSIVE MODE")
+            .execute(&mut *tx)
+            .await?;
+
+        let creates_cycle = sqlx::query!(
+            r#"
+            WITH RECURSIVE blockers AS (
+                SELECT blocked_by_id FROM task_dependencies WHERE task_id = $1
+                UNION
+                SELECT task_dependencies.blocked_by_id FROM task_dependencies
+                JOIN blockers ON task_dependencies.task_id = blockers.blocked_by_id
+            )
+            SELECT EXISTS (SELECT 1 FROM blockers WHERE blocked_by_id = $2) AS "exists!"
+            "#,
+            blocked_by_id,
+            task_id,
+        )
+        .fetch_one(&mut *tx)
+        .await?
+        .exists;
+
+        if creates_cycle {
+            return Err(SDKError::TaskDependencyCycle);
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO task_dependencies (task_id, blocked_by_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            task_id,
+            blocked_by_id,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        self.get_task(task_id).await
+    }
+
+    async fn remove_task_dependency(&self, task_id: Uuid, blocked_by_id: Uuid) -> Result<Task, SDKError> {
+        sqlx::query!(
+            r#"
+            DELETE FROM task_dependencies WHERE task_id = $1 AND blocked_by_id = $2
+            "#,
+            task_id,
+            blocked_by_id,
+        )
+        .execute(self.db_pool.as_ref())
+        .await?;
+
+        self.get_task(task_id).await
+    }
+
+    async fn task_tree(&self, id: Uuid, max_depth: Option<i32>) -> Result<TaskTree, SDKError> {
+        let tasks_info = sqlx::query(
+            r#"
+            WITH RECURSIVE tree AS (
+                SELECT tasks.*, 0 AS depth, ARRAY[tasks.id] AS path FROM tasks
+                WHERE tasks.id = $1 AND tasks.archived_at IS NULL AND tasks.deleted_at IS NULL
+                UNION ALL
+                SELECT tasks.*, tree.depth + 1, tree.path || tasks.id FROM tasks
+                JOIN tree ON tasks.parent_id = tree.id
+                WHERE NOT tasks.id = ANY(tree.path) AND tasks.archived_at IS NULL AND tasks.deleted_at IS NULL
+            )
+            SELECT * FROM tree ORDER BY depth, created_at, count, id
+            "#,
+        )
+        .bind(id)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        if tasks_info.is_empty() {
+            return Err(SDKError::SQLXError(sqlx::Error::RowNotFound));
+        }
+
+        let positions: HashMap<Uuid, usize> = tasks_info
+            .iter()
+            .enumerate()
+            .map(|(position, task_info)| (task_info.get("id"), position))
+            .collect();
+
+        let mut nodes = tasks_info
+            .iter()
+            .map(|task_info| TaskTree {
+                task: task_from_row(task_info),
+                depth: task_info.get("depth"),
+                total_count: 0,
+                done_count: 0,
+                progress: 0.0,
+                children: vec![],
+            })
+            .map(Some)
+            .collect::<Vec<_>>();
+
+        let mut children: Vec<Vec<usize>> = vec![vec![]; nodes.len()];
+
+        for (position, node) in nodes.iter().enumerate().skip(1) {
+            let parent_id = node.as_ref().and_then(|node| node.task.parent_id);
+
+            if let Some(&parent) = parent_id.and_then(|parent_id| positions.get(&parent_id)) {
+                children[parent].push(position);
+            }
+        }
+
+        // Rows come sorted by depth, so walking them backwards visits every child before its parent.
+        for position in (0..nodes.len()).rev() {
+            let (mut total_count, mut done_count) = (0, 0);
+
+            for &child in &children[position] {
+                let child = nodes[child].as_ref().unwrap();
+
+                total_count += child.total_count;
+                done_count += child.done_count;
+
+                match child.task.status {
+                    TaskStatus::Canceled => {}
+                    TaskStatus::Done => {
+                        total_count += 1;
+                        done_count += 1;
+                    }
+                    _ => total_count += 1,
+                }
+            }
+
+            let node = nodes[position].as_mut().unwrap();
+
+            node.total_count = total_count;
+            node.done_count = done_count;
+            node.progress = match (total_count, node.task.status) {
+                (0, TaskStatus::Done) => 100.0,
+                (0, _) => 0.0,
+                _ => f64::from(done_count) * 100.0 / f64::from(total_count),
+            };
+        }
+
+        Ok(assemble_task_tree(0, &mut nodes, &children, max_depth))
+    }
+
+    async fn update_tasks(&self, input: UpdateTasksInput) -> Result<Vec<Task>, SDKError> {
+        let selection = GetTasksWhere {
+            ids: input.ids,
+            _and: input.filter.map(|filter| vec![filter]),
+            ..Default::default()
+        };
+
+        if selection.compile_sql().is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let mut query = QueryBuilder::<Postgres>::new("SELECT id FROM tasks WHERE ");
+        VisibleFilter(Some(selection)).compile_sql().push_to(&mut query);
+        query.push(" ORDER BY created_at FOR UPDATE");
+
+        let task_ids: Vec<Uuid> = query
+            .build()
+            .fetch_all(&mut *tx)
+            .await?
+            .iter()
+            .map(|task_info| task_info.get("id"))
+            .collect();
+
+        let mut tasks = Vec::with_capacity(task_ids.len());
+
+        for task_id in task_ids {
+            lock_visible_task(&mut tx, task_id).await?;
+            let task = apply_task_update(&mut tx, task_id, input.update.clone()).await?;
+
+            insert_change(
+                &mut tx,
+                CreateChangeInput {
+                    owner_id: input.owner_id,
+                    resource_id: task.id,
+                    operation: ChangeOperation::Update,
+                    resource_type: ChangeResourceType::Tasks,
+                    diff_json: serde_json::to_string(&json!({
+                        "input": input.update,
+                        "result": task,
+                    }))?,
+                },
+            )
+            .await?;
+
+            tasks.push(task);
+        }
+
+        tx.commit().await?;
+
+        Ok(tasks)
+    }
+
+    async fn delete_tasks(&self, input: DeleteTasksInput) -> Result<Vec<Task>, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let deleted_at = hide_task_trees(&mut tx, &input.ids, TrashColumn::Deleted).await?;
+
+        let tasks: Vec<Task> = sqlx::query(
+            r#"
+            SELECT * FROM tasks WHERE id = ANY($1) AND deleted_at = $2
+            "#,
+        )
+        .bind(&input.ids)
+        .bind(deleted_at)
+        .fetch_all(&mut *tx)
+        .await?
+        .iter()
+        .map(task_from_row)
+        .collect();
+
+        for task in tasks.iter() {
+            insert_change(
+                &mut tx,
+                CreateChangeInput {
+                    owner_id: input.owner_id,
+                    resource_id: task.id,
+                    operation: ChangeOperation::Delete,
+                    resource_type: ChangeResourceType::Tasks,
+                    diff_json: serde_json::to_string(&json!({
+                        "result": task,
+                    }))?,
+                },
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(tasks)
+    }
+
+    async fn reorder_task(
+        &self,
+        id: Uuid,
+        before_id: Option<Uuid>,
+        after_id: Option<Uuid>,
+        status: Option<TaskStatus>,
+    ) -> Result<Task, SDKError> {
+        if before_id == Some(id) || after_id == Some(id) {
+            return Err(SDKError::InvalidTaskPosition(
+                "a task can't be its own neighbour".to_string(),
+            ));
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let task = lock_visible_task(&mut tx, id).await?;
+
+        let task = match status {
+            Some(status) => {
+                apply_task_update(
+                    &mut tx,
+                    id,
+                    UpdateTaskInput {
+                        status: Some(status),
+                        ..Default::default()
+                    },
+                )
+                .await?
+            }
+            None => task,
+        };
+
+        let mut column = lock_board_column(&mut tx, task.project_id, Some(&task.status.to_string())).await?;
+        column.retain(|(task_id, _)| *task_id != id);
+
+        let (lower, upper) = board_neighbours(&column, before_id, after_id)?;
+
+        let between = |column: &BoardColumnRanks| {
+            let lower = match lower {
+                Some(lower) => Some(column[lower].1.as_deref()?),
+                None => None,
+            };
+            let upper = match upper {
+                Some(upper) => Some(column[upper].1.as_deref()?),
+                None => None,
+            };
+
+            rank_between(lower, upper)
+        };
+
+        // Unranked or equally ranked neighbours leave no room, so the column is spread out first.
+        let rank = match between(&column) {
+            Some(rank) => rank,
+            None => {
+                spread_board_column(&mut tx, &mut column).await?;
+
+                between(&column)
+                    .ok_or_else(|| SDKError::InvalidTaskPosition("no room between the neighbours".to_string()))?
+            }
+        };
+
+        let task_info = sqlx::query(
+            r#"
+            UPDATE tasks SET rank = $1
+            WHERE id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(rank)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(task_from_row(&task_info))
+    }
+
+    async fn rebalance_task_ranks(&self) -> Result<i64, SDKError> {
+        let columns_info = sqlx::query(
+            r#"
+            SELECT DISTINCT project_id, status FROM tasks
+            WHERE (rank IS NULL OR length(rank) > $1) AND archived_at IS NULL AND deleted_at IS NULL
+            "#,
+        )
+        .bind(MAX_RANK_LENGTH)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        let mut ranked = 0;
+
+        for column_info in columns_info {
+            let project_id: Option<Uuid> = column_info.get("project_id");
+            let status: Option<String> = column_info.get("status");
+
+            let mut tx = self.db_pool.begin().await?;
+
+            let mut column = lock_board_column(&mut tx, project_id, status.as_deref()).await?;
+            spread_board_column(&mut tx, &mut column).await?;
+
+            tx.commit().await?;
+
+            ranked += column.len() as i64;
+        }
+
+        Ok(ranked)
+    }
+
+    async fn board(&self, project_id: Uuid) -> Result<Vec<TaskBoardColumn>, SDKError> {
+        let tasks_info = sqlx::query(
+            r#"
+            SELECT * FROM tasks
+            WHERE project_id = $1 AND archived_at IS NULL AND deleted_at IS NULL
+            ORDER BY rank NULLS LAST, created_at, id
+            "#,
+        )
+        .bind(project_id)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        let mut columns: Vec<TaskBoardColumn> = [
+            TaskStatus::None,
+            TaskStatus::Draft,
+            TaskStatus::Backlog,
+            TaskStatus::ToDo,
+            TaskStatus::InProgress,
+            TaskStatus::Done,
+            TaskStatus::Canceled,
+        ]
+        .into_iter()
+        .map(|status| TaskBoardColumn { status, tasks: vec![] })
+        .collect();
+
+        for task in tasks_info.iter().map(task_from_row) {
+            if let Some(column) = columns.iter_mut().find(|column| column.status == task.status) {
+                column.tasks.push(task);
+            }
+        }
+
+        Ok(columns)
+    }
+
+    async fn archive_task(&self, id: Uuid) -> Result<Task, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let archived_at = hide_task_trees(&mut tx, &[id], TrashColumn::Archived).await?;
+
+        let task_info = sqlx::query("SELECT * FROM tasks WHERE id = $1 AND archived_at = $2")
+            .bind(id)
+            .bind(archived_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(task_from_row(&task_info))
+    }
+
+    async fn restore_task(&self, id: Uuid) -> Result<Task, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let task = task_from_row(
+            &sqlx::query("SELECT * FROM tasks WHERE id = $1 FOR UPDATE")
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await?,
+        );
+
+        if let Some(deleted_at) = task.deleted_at {
+            restore_task_trees(&mut tx, &[id], TrashColumn::Deleted, deleted_at).await?;
+        }
+
+        if let Some(archived_at) = task.archived_at {
+            restore_task_trees(&mut tx, &[id], TrashColumn::Archived, archived_at).await?;
+        }
+
+        let task_info = sqlx::query("SELECT * FROM tasks WHERE id = $1")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(task_from_row(&task_info))
+    }
+
+    async fn move_task(&self, id: Uuid, input: MoveTaskInput) -> Result<Task, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let task = lock_visible_task(&mut tx, id).await?;
+
+        if task.project_id == Some(input.project_id) {
+            return Err(SDKError::InvalidTaskMove(
+                "the task is already in the project".to_string(),
+            ));
+        }
+
+        sqlx::query("SELECT id FROM projects WHERE id = $1 AND archived_at IS NULL AND deleted_at IS NULL")
+            .bind(input.project_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let moved_tasks = match input.include_subtasks.unwrap_or(true) {
+            true => sqlx::query(
+                r#"
+                WITH RECURSIVE tree AS (
+                    SELECT id, key, number, created_at, count FROM tasks
+                    WHERE id = $1
+                    UNION
+                    SELECT tasks.id, tasks.key, tasks.number, tasks.created_at, tasks.count FROM tasks
+                    JOIN tree ON tasks.parent_id = tree.id
+                    WHERE tasks.project_id IS NOT DISTINCT FROM $2
+                )
+                SELECT id, key FROM tree
+                ORDER BY number NULLS LAST, created_at, count, id
+                "#,
+            )
+            .bind(id)
+            .bind(task.project_id)
+            .fetch_all(&mut *tx)
+            .await?
+            .iter()
+            .map(|task_info| {
+                (
+                    task_info.get::<Uuid, _>("id"),
+                    task_info.get::<Option<String>, _>("key"),
+                )
+            })
+            .collect::<Vec<_>>(),
+            false => {
+                sqlx::query("UPDATE tasks SET parent_id = $2 WHERE parent_id = $1")
+                    .bind(id)
+                    .bind(task.parent_id)
+                    .execute(&mut *tx)
+                    .await?;
+
+                vec![(id, task.key.clone())]
+            }
+        };
+
+        // A task whose parent stays behind becomes a top level task of the target project.
+        let leaves_parent = match task.parent_id {
+            Some(parent_id) => {
+                let parent_project_id: Option<Uuid> = sqlx::query_scalar("SELECT project_id FROM tasks WHERE id = $1")
+                    .bind(parent_id)
+                    .fetch_one(&mut *tx)
+                    .await?;
+
+                parent_project_id != Some(input.project_id)
+            }
+            None => false,
+        };
+
+        let mut moves = Vec::new();
+
+        for (task_id, from_key) in moved_tasks.iter() {
+            let update = UpdateTaskInput {
+                project_id: Some(input.project_id),
+                parent_id: (*task_id == id && leaves_parent).then_some(Uuid::nil()),
+                override_blockers: Some(true),
+                ..Default::default()
+            };
+
+            let moved_task = apply_task_update(&mut tx, *task_id, update).await?;
+
+            moves.push(json!({
+                "task_id": task_id,
+                "from_key": from_key,
+                "to_key": moved_task.key,
+            }));
+        }
+
+        let task_ids = moved_tasks.iter().map(|(task_id, _)| *task_id).collect::<Vec<_>>();
+
+        // Values follow a field of the same name and kind in the target project, as long as the
+        // selected options exist there too. The rest belong to fields the target doesn't have.
+        sqlx::query(
+            r#"
+            UPDATE custom_field_values
+            SET field_id = targets.id
+            FROM custom_fields AS sources, custom_fields AS targets
+            WHERE custom_field_values.task_id = ANY($1)
+                AND sources.id = custom_field_values.field_id
+                AND sources.project_id <> $2
+                AND targets.project_id = $2
+                AND targets.name = sources.name
+                AND targets.kind = sources.kind
+                AND (targets.kind <> $3 OR custom_field_values.text_value = ANY(targets.options))
+                AND (targets.kind <> $4 OR custom_field_values.options_value <@ targets.options)
+            "#,
+        )
+        .bind(&task_ids)
+        .bind(input.project_id)
+        .bind(CustomFieldKind::SingleSelect.to_string())
+        .bind(CustomFieldKind::MultiSelect.to_string())
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM custom_field_values
+            USING custom_fields
+            WHERE custom_field_values.task_id = ANY($1)
+                AND custom_fields.id = custom_field_values.field_id
+                AND custom_fields.project_id <> $2
+            "#,
+        )
+        .bind(&task_ids)
+        .bind(input.project_id)
+        .execute(&mut *tx)
+        .await?;
+
+        // Team cycles span the projects of the team, project cycles don't span projects.
+        sqlx::query(
+            r#"
+            UPDATE tasks
+            SET cycle_id = NULL
+            FROM cycles
+            WHERE tasks.id = ANY($1)
+                AND cycles.id = tasks.cycle_id
+                AND COALESCE(cycles.project_id <> $2, true)
+                AND NOT EXISTS (
+                    SELECT 1 FROM teams_by_projects
+                    WHERE teams_by_projects.team_id = cycles.team_id AND teams_by_projects.project_id = $2
+                )
+            "#,
+        )
+        .bind(&task_ids)
+        .bind(input.project_id)
+        .execute(&mut *tx)
+        .await?;
+
+        let task_info = sqlx::query("SELECT * FROM tasks WHERE id = $1")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+        let moved_task = task_from_row(&task_info);
+
+        insert_change(
+            &mut tx,
+            CreateChangeInput {
+                owner_id: input.owner_id,
+                resource_id: id,
+                operation: ChangeOperation::Move,
+                resource_type: ChangeResourceType::Tasks,
+                diff_json: serde_json::to_string(&json!({
+                    "from_project_id": task.project_id,
+                    "to_project_id": input.project_id,
+                    "include_subtasks": input.include_subtasks.unwrap_or(true),
+                    "tasks": moves,
+                    "result": moved_task,
+                }))?,
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(moved_task)
+    }
+}
+
+type BoardColumnRanks = Vec<(Uuid, Option<String>)>;
+
+/// Locks the tasks of a board column and returns their ranks in board order. Archived and
+/// deleted tasks aren't on the board, so they are left out.
+async fn lock_board_column(
+    conn: &mut PgConnection,
+    project_id: Option<Uuid>,
+    status: Option<&str>,
+) -> Result<BoardColumnRanks, SDKError> {
+    let column_info = sqlx::query(
+        r#"
+        SELECT id, rank FROM tasks
+        WHERE project_id IS NOT DISTINCT FROM $1 AND status IS NOT DISTINCT FROM $2
+            AND archived_at IS NULL AND deleted_at IS NULL
+        ORDER BY rank NULLS LAST, created_at, id
+        FOR UPDATE
+        "#,
+    )
+    .bind(project_id)
+    .bind(status)
+    .fetch_all(conn)
+    .await?;
+
+    Ok(column_info
+        .iter()
+        .map(|task_info| (task_info.get("id"), task_info.get("rank")))
+        .collect())
+}
+
+async fn spread_board_column(conn: &mut PgConnection, column: &mut BoardColumnRanks) -> Result<(), SDKError> {
+    let spread = spread_ranks(column.len());
+
+    for ((_, rank), spread_rank) in column.iter_mut().zip(spread) {
+        *rank = Some(spread_rank);
+    }
+
+    let (ids, ranks): (Vec<Uuid>, Vec<Option<String>>) = column.iter().cloned().unzip();
+
+    sqlx::query(
+        r#"
+        UPDATE tasks SET rank = ranks.rank
+        FROM unnest($1::uuid[], $2::varchar[]) AS ranks(id, rank)
+        WHERE tasks.id = ranks.id
+        "#,
+    )
+    .bind(ids)
+    .bind(ranks)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Positions of the tasks right above and right below the new place of the moved task, which
+/// isn't in `column`.
+fn board_neighbours(
+    column: &BoardColumnRanks,
+    before_id: Option<Uuid>,
+    after_id: Option<Uuid>,
+) -> Result<(Option<usize>, Option<usize>), SDKError> {
+    let position = |id: Uuid| {
+        column
+            .iter()
+            .position(|(task_id, _)| *task_id == id)
+            .ok_or_else(|| SDKError::InvalidTaskPosition(format!("task {id} is not in the same column")))
+    };
+
+    let before = before_id.map(position).transpose()?;
+    let after = after_id.map(position).transpose()?;
+
+    match (before, after) {
+        (Some(before), Some(after)) if after != before + 1 => Err(SDKError::InvalidTaskPosition(format!(
+            "task {} is not right above task {}",
+            before_id.unwrap(),
+            after_id.unwrap()
+        ))),
+        (Some(before), None) => Ok((Some(before), Some(before + 1).filter(|after| *after < column.len()))),
+        (None, Some(after)) => Ok((after.checked_sub(1), Some(after))),
+        (None, None) => Ok((column.len().checked_sub(1), None)),
+        neighbours => Ok(neighbours),
+    }
+}
+
+fn assemble_task_tree(
+    position: usize,
+    nodes: &mut [Option<TaskTree>],
+    children: &[Vec<usize>],
+    max_depth: Option<i32>,
+) -> TaskTree {
+    let mut node = nodes[position].take().unwrap();
+
+    if max_depth.is_none_or(|max_depth| node.depth < max_depth) {
+        node.children = children[position]
+            .iter()
+            .map(|&child| assemble_task_tree(child, nodes, children, max_depth))
+            .collect();
+    }
+
+    node
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::backend::testing::{test_engine, test_member, test_project, test_task};
+    use crate::resources::custom_fields::{
+        extensions::{CustomFieldValueInput, CustomFieldsExtensionOperations, SetCustomFieldValuesInput},
+        operations::{CreateCustomFieldInput, CustomFieldCrudOperations},
+    };
+
+    #[test]
+    fn board_neighbours_must_be_adjacent() {
+        let column: BoardColumnRanks = (0..6).map(|_| (Uuid::new_v4(), None)).collect();
+        let id = |position: usize| Some(column[position].0);
+
+        assert_eq!(board_neighbours(&column, id(2), id(3)).unwrap(), (Some(2), Some(3)));
+        assert_eq!(board_neighbours(&column, id(2), None).unwrap(), (Some(2), Some(3)));
+        assert_eq!(board_neighbours(&column, id(5), None).unwrap(), (Some(5), None));
+        assert_eq!(board_neighbours(&column, None, id(0)).unwrap(), (None, Some(0)));
+        assert_eq!(board_neighbours(&column, None, None).unwrap(), (Some(5), None));
+
+        assert!(board_neighbours(&column, id(0), id(5)).is_err());
+        assert!(board_neighbours(&column, id(3), id(2)).is_err());
+        assert!(board_neighbours(&column, id(2), id(2)).is_err());
+        assert!(board_neighbours(&column, Some(Uuid::new_v4()), None).is_err());
+    }
+
+    #[sqlx::test]
+    async fn reorder_skips_tasks_hidden_from_the_board(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+        let project_id = test_project(&engine, owner_id, "BRD").await;
+
+        let mut tasks = Vec::new();
+        for title in ["A", "B", "C", "D"] {
+            tasks.push(test_task(&engine, owner_id, Some(project_id), title).await.id);
+        }
+
+        engine.archive_task(tasks[1]).await.unwrap();
+        engine
+            .reorder_task(tasks[3], Some(tasks[0]), Some(tasks[2]), None)
+            .await
+            .unwrap();
+
+        let board = engine.board(project_id).await.unwrap();
+        let column = board.iter().find(|column| !column.tasks.is_empty()).unwrap();
+        let order = column.tasks.iter().map(|task| task.id).collect::<Vec<_>>();
+
+        assert_eq!(order, vec![tasks[0], tasks[3], tasks[2]]);
+    }
+
+    #[sqlx::test]
+    async fn dependencies_reject_cycles_and_block_progress(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+
+        let a = test_task(&engine, owner_id, None, "A").await.id;
+        let b = test_task(&engine, owner_id, None, "B").await.id;
+        let c = test_task(&engine, owner_id, None, "C").await.id;
+
+        engine.add_task_dependency(a, b).await.unwrap();
+        engine.add_task_dependency(b, c).await.unwrap();
+
+        let err = engine.add_task_dependency(c, a).await.unwrap_err();
+        assert!(matches!(err, SDKError::TaskDependencyCycle));
+        let err = engine.add_task_dependency(a, a).await.unwrap_err();
+        assert!(matches!(err, SDKError::TaskDependencyCycle));
+
+        let start = UpdateTaskInput {
+            status: Some(TaskStatus::InProgress),
+            ..Default::default()
+        };
+        let err = engine.update_task(a, start.clone()).await.unwrap_err();
+        assert!(matches!(err, SDKError::TaskBlocked(1)));
+
+        engine
+            .update_task(
+                b,
+                UpdateTaskInput {
+                    status: Some(TaskStatus::Canceled),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(engine.update_task(a, start).await.unwrap().status, TaskStatus::InProgress);
+    }
+
+    #[sqlx::test]
+    async fn rebalance_shortens_ranks_of_visible_tasks_in_order(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+        let project_id = test_project(&engine, owner_id, "RNK").await;
+
+        let mut tasks = Vec::new();
+        for title in ["A", "B", "C", "D"] {
+            tasks.push(test_task(&engine, owner_id, Some(project_id), title).await.id);
+        }
+
+        let long_rank = format!("U{}1", "0".repeat(20));
+        for (id, rank) in tasks.iter().zip(["U", long_rank.as_str(), "V", long_rank.as_str()]) {
+            sqlx::query("UPDATE tasks SET rank = $1 WHERE id = $2")
+                .bind(rank)
+                .bind(id)
+                .execute(engine.db_pool.as_ref())
+                .await
+                .unwrap();
+        }
+        engine.archive_task(tasks[3]).await.unwrap();
+
+        assert_eq!(engine.rebalance_task_ranks().await.unwrap(), 3);
+        assert_eq!(engine.rebalance_task_ranks().await.unwrap(), 0);
+
+        let board = engine.board(project_id).await.unwrap();
+        let column = board.iter().find(|column| !column.tasks.is_empty()).unwrap();
+
+        assert_eq!(
+            column.tasks.iter().map(|task| task.id).collect::<Vec<_>>(),
+            tasks[..3].to_vec()
+        );
+        assert!(column
+            .tasks
+            .iter()
+            .all(|task| task.rank.as_ref().unwrap().len() <= MAX_RANK_LENGTH as usize));
+
+        let archived_rank: Option<String> = sqlx::query_scalar("SELECT rank FROM tasks WHERE id = $1")
+            .bind(tasks[3])
+            .fetch_one(engine.db_pool.as_ref())
+            .await
+            .unwrap();
+        assert_eq!(archived_rank, Some(long_rank));
+    }
+
+    #[sqlx::test]
+    async fn restore_brings_back_the_subtasks_trashed_with_the_task(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+
+        let subtask = |title: &str, subtasks: Option<Vec<CreateTaskInput>>| CreateTaskInput {
+            title: title.to_string(),
+            subtasks,
+            ..Default::default()
+        };
+
+        let root = engine
+            .create_task(CreateTaskInput {
+                title: "Root".to_string(),
+                owner_id,
+                subtasks: Some(vec![
+                    subtask("Child", Some(vec![subtask("Grandchild", None)])),
+                    subtask("Sibling", None),
+                ]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let tree = engine.task_tree(root.id, None).await.unwrap();
+        let child = tree.children[0].task.id;
+        let grandchild = tree.children[0].children[0].task.id;
+        let sibling = tree.children[1].task.id;
+
+        engine.delete_task(sibling).await.unwrap();
+        engine.delete_task(root.id).await.unwrap();
+
+        for id in [root.id, child, grandchild, sibling] {
+            assert!(engine.get_task(id).await.is_err());
+        }
+
+        engine.restore_task(root.id).await.unwrap();
+
+        for id in [root.id, child, grandchild] {
+            assert!(engine.get_task(id).await.is_ok());
+        }
+        assert!(engine.get_task(sibling).await.is_err());
+    }
+
+    #[sqlx::test]
+    async fn move_task_remaps_keys_and_custom_field_values(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+        let source_id = test_project(&engine, owner_id, "MVA").await;
+        let target_id = test_project(&engine, owner_id, "MVB").await;
+
+        let field = |project_id, name: &str, kind| CreateCustomFieldInput {
+            name: name.to_string(),
+            project_id,
+            kind,
+            owner_id,
+            ..Default::default()
+        };
+        let source_estimate = engine
+            .create_custom_field(field(source_id, "Estimate", CustomFieldKind::Number))
+            .await
+            .unwrap();
+        let source_notes = engine
+            .create_custom_field(field(source_id, "Notes", CustomFieldKind::Text))
+            .await
+            .unwrap();
+        let target_estimate = engine
+            .create_custom_field(field(target_id, "Estimate", CustomFieldKind::Number))
+            .await
+            .unwrap();
+
+        let task = engine
+            .create_task(CreateTaskInput {
+                title: "Parent".to_string(),
+                owner_id,
+                project_id: Some(source_id),
+                subtasks: Some(vec![CreateTaskInput {
+                    title: "Subtask".to_string(),
+                    project_id: Some(source_id),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        engine
+            .set_task_custom_field_values(
+                task.id,
+                SetCustomFieldValuesInput {
+                    owner_id,
+                    values: vec![
+                        CustomFieldValueInput {
+                            field_id: source_estimate.id,
+                            number: Some(3.0),
+                            ..Default::default()
+                        },
+                        CustomFieldValueInput {
+                            field_id: source_notes.id,
+                            text: Some("Only in the source".to_string()),
+                            ..Default::default()
+                        },
+                    ],
+                },
+            )
+            .await
+            .unwrap();
+
+        let moved = engine
+            .move_task(
+                task.id,
+                MoveTaskInput {
+                    owner_id,
+                    project_id: target_id,
+                    include_subtasks: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(moved.project_id, Some(target_id));
+        assert!(moved.key.unwrap().starts_with("MVB-"));
+
+        let tree = engine.task_tree(task.id, None).await.unwrap();
+        let subtask = &tree.children[0].task;
+        assert_eq!(subtask.project_id, Some(target_id));
+        assert!(subtask.key.as_ref().unwrap().starts_with("MVB-"));
+
+        let values = engine.task_custom_field_values(task.id).await.unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].field_id, target_estimate.id);
+        assert_eq!(values[0].number, Some(3.0));
+
+        let err = engine
+            .move_task(
+                task.id,
+                MoveTaskInput {
+                    owner_id,
+                    project_id: target_id,
+                    include_subtasks: None,
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SDKError::InvalidTaskMove(..)));
+    }
 }
@@ -6,7 +6,7 @@ use uuid::Uuid;
 
 use crate::backend::engine::SDKEngine;
 
-use super::task::{Task, TaskPriority, TaskStatus};
+use super::task::{Task, TaskEstimateUnit, TaskPriority, TaskStatus};
 
 // #[derive(Clone)]
 pub struct TaskLoader(Arc<SDKEngine>);
@@ -24,7 +24,10 @@ impl Loader<Uuid> for TaskLoader {
     async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
         let tasks = sqlx::query!(
             r#"
-            SELECT * FROM tasks WHERE id  = ANY($1)
+            SELECT id, created_at, updated_at, title, description, owner_id, status, priority,
+                due_date, project_id, lead_id, labels, count, parent_id, number, key,
+                workflow_status_id, estimate, estimate_unit, rank, cycle_id, deleted_at, archived_at
+            FROM tasks WHERE id = ANY($1) AND archived_at IS NULL AND deleted_at IS NULL
             "#,
             &keys
         )
@@ -60,6 +63,18 @@ impl Loader<Uuid> for TaskLoader {
                         owner_id: task.owner_id,
                         count: task.count,
                         parent_id: task.parent_id,
+                        number: task.number,
+                        key: task.key.clone(),
+                        workflow_status_id: task.workflow_status_id,
+                        estimate: task.estimate,
+                        estimate_unit: task
+                            .estimate_unit
+                            .clone()
+                            .and_then(|unit| TaskEstimateUnit::from_str(&unit).ok()),
+                        rank: task.rank.clone(),
+                        cycle_id: task.cycle_id,
+                        archived_at: task.archived_at,
+                        deleted_at: task.deleted_at,
                     },
                 )
             })
@@ -8,22 +8,61 @@ use derive_builder::Builder;
 use poem_openapi::Object;
 use serde::Serialize;
 // use serde_json::json;
-use sqlx::Row;
+use sqlx::{postgres::PgRow, PgConnection, Row};
 // use tokio::task;
 use uuid::Uuid;
 
 use crate::backend::engine::SDKEngine;
 use crate::common::commons::{SortOrder, UpdateListInput};
+use crate::common::filters::{
+    enum_comparison, DateTimeComparison, RelationComparison, SQLCondition, SQLFieldFilter, SQLFilter, SQLRelation,
+    SQLSelect, StringComparison, UuidComparison,
+};
+use crate::common::pagination::{Page, PageInput};
+use crate::common::ranks::rank_between;
+use crate::common::trash::{hide_task_trees, TrashColumn, TrashFilter, VisibleFilter};
 use crate::errors::sdk::SDKError;
+use crate::resources::custom_fields::operations::{sort_by_custom_field, CustomFieldComparison};
+use crate::resources::cycles::operations::check_cycle_tasks;
+use crate::resources::labels::operations::{check_label_scope, rescope_task_labels};
 // use crate::resources::changes::change::{ChangeOperation, ChangeResourceType};
 // use crate::resources::changes::operations::{ChangeCrudOperations, CreateChangeInputBuilder};
-use crate::resources::tasks::task::{Task, TaskPriority, TaskStatus};
+use crate::resources::tasks::task::{Task, TaskEstimateUnit, TaskPriority, TaskStatus};
+use crate::resources::workflows::operations::{initial_workflow_state, resolve_workflow_state};
+
+const TASKS_SORTABLE_COLUMNS: &[&str] = &[
+    "id",
+    "created_at",
+    "updated_at",
+    "title",
+    "status",
+    "priority",
+    "due_date",
+    "count",
+    "number",
+    "estimate",
+    "rank",
+];
+
+const TASKS_BY_ASSIGNEES: SQLRelation = SQLRelation {
+    table: "tasks_by_assignees",
+    source_column: "task_id",
+    target_column: "assignee_id",
+};
+
+const LABELS_BY_TASKS: SQLRelation = SQLRelation {
+    table: "labels_by_tasks",
+    source_column: "task_id",
+    target_column: "label_id",
+};
 
 #[async_trait]
 pub trait TaskCrudOperations {
     async fn create_task(&self, input: CreateTaskInput) -> Result<Task, SDKError>;
     async fn get_task(&self, id: Uuid) -> Result<Task, SDKError>;
+    async fn task_by_key(&self, key: String) -> Result<Task, SDKError>;
     async fn get_tasks(&self, input: Option<GetTasksInput>) -> Result<Vec<Task>, SDKError>;
+    async fn get_tasks_page(&self, input: PageInput<GetTasksWhere>) -> Result<Page<Task>, SDKError>;
     async fn update_task(&self, id: Uuid, input: UpdateTaskInput) -> Result<Task, SDKError>;
     async fn delete_task(&self, id: Uuid) -> Result<Task, SDKError>;
 }
@@ -34,6 +73,7 @@ pub struct GetTasksInput {
     #[builder(setter(strip_option), default)]
     pub filter: Option<GetTasksWhere>,
 
+    /// A task column, or `custom_fields.<field id>` to sort by a custom field value.
     #[builder(setter(strip_option), default)]
     pub sort_by: Option<String>,
     #[builder(setter(strip_option), default)]
@@ -68,6 +108,12 @@ pub struct CreateTaskInput {
     pub lead_id: Option<Uuid>,
     #[builder(setter(strip_option), default)]
     pub parent_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub estimate: Option<f64>,
+    #[builder(setter(strip_option), default)]
+    pub estimate_unit: Option<TaskEstimateUnit>,
+    #[builder(setter(strip_option), default)]
+    pub cycle_id: Option<Uuid>,
 
     #[builder(setter(strip_option), default)]
     pub labels: Option<Vec<Uuid>>,
@@ -100,6 +146,16 @@ pub struct UpdateTaskInput {
     pub lead_id: Option<Uuid>,
     #[builder(setter(strip_option), default)]
     pub parent_id: Option<Uuid>,
+    /// Moves the task to a status of its project workflow, `status` is derived from its category.
+    #[builder(setter(strip_option), default)]
+    pub workflow_status_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub estimate: Option<f64>,
+    #[builder(setter(strip_option), default)]
+    pub estimate_unit: Option<TaskEstimateUnit>,
+    /// Plans the task in a cycle, the nil UUID takes it out of its cycle.
+    #[builder(setter(strip_option), default)]
+    pub cycle_id: Option<Uuid>,
 
     #[builder(setter(strip_option), default)]
     pub labels: Option<UpdateListInput>,
@@ -107,6 +163,10 @@ pub struct UpdateTaskInput {
     pub assignees: Option<UpdateListInput>,
     #[builder(setter(strip_option), default)]
     pub assets: Option<UpdateListInput>,
+
+    /// Allows moving the task to `InProgress` or `Done` while it still has unfinished blockers.
+    #[builder(setter(strip_option), default)]
+    pub override_blockers: Option<bool>,
 }
 
 #[derive(Default, Builder, Object, InputObject)]
@@ -114,24 +174,51 @@ pub struct UpdateTaskInput {
 pub struct GetTasksWhere {
     #[builder(setter(strip_option), default)]
     pub ids: Option<Vec<Uuid>>,
+    #[builder(setter(into, strip_option), default)]
+    pub owner_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub status: Option<TaskStatusComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub priority: Option<TaskPriorityComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub title: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub description: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub due_date: Option<DateTimeComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub project_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub lead_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub parent_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub workflow_status_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub cycle_id: Option<UuidComparison>,
+
     #[builder(setter(strip_option), default)]
-    pub owner_id: Option<Uuid>,
+    pub assignee_ids: Option<RelationComparison>,
     #[builder(setter(strip_option), default)]
-    pub status: Option<TaskStatus>,
+    pub label_ids: Option<RelationComparison>,
+    /// Every comparison must match, one per custom field.
     #[builder(setter(strip_option), default)]
-    pub priority: Option<TaskPriority>,
+    pub custom_fields: Option<Vec<CustomFieldComparison>>,
+    /// Matches every descendant of the given task, at any depth.
     #[builder(setter(strip_option), default)]
-    pub title: Option<String>,
+    pub ancestor_id: Option<Uuid>,
     #[builder(setter(strip_option), default)]
-    pub description: Option<String>,
-    #[builder(setter(strip_option), default)]
-    pub due_date: Option<DateTime<Utc>>,
+    pub has_subtasks: Option<bool>,
+    /// Matches tasks past their due date that are not done or canceled.
     #[builder(setter(strip_option), default)]
-    pub project_id: Option<Uuid>,
+    pub is_overdue: Option<bool>,
+
+    /// Archived tasks are left out unless set, only read on the top level filter.
     #[builder(setter(strip_option), default)]
-    pub lead_id: Option<Uuid>,
+    pub include_archived: Option<bool>,
+    /// Tasks in the trash are left out unless set, only read on the top level filter.
     #[builder(setter(strip_option), default)]
-    pub parent_id: Option<Uuid>,
+    pub include_deleted: Option<bool>,
 
     #[oai(skip)]
     #[builder(setter(strip_option), default)]
@@ -142,67 +229,128 @@ pub struct GetTasksWhere {
     pub _or: Option<Vec<GetTasksWhere>>,
 }
 
-impl GetTasksWhere {
-    pub fn compile_sql(&self) -> String {
+enum_comparison!(TaskStatusComparison, TaskStatus);
+
+enum_comparison!(TaskPriorityComparison, TaskPriority);
+
+impl SQLFilter for GetTasksWhere {
+    fn compile_sql(&self) -> SQLCondition {
         let mut conditions = Vec::new();
 
         if let Some(ids) = &self.ids {
-            conditions.push(format!(
-                "id = ANY(array[{}]::uuid[])",
-                ids.iter()
-                    .map(|x| format!("'{}'", x))
-                    .collect::<Vec<String>>()
-                    .join(",")
-            ));
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
         }
 
         if let Some(owner_id) = &self.owner_id {
-            conditions.push(format!("owner_id = '{}'", owner_id));
+            conditions.push(owner_id.compile_sql("owner_id"));
         }
 
         if let Some(status) = &self.status {
-            conditions.push(format!("status = '{}'", status));
+            conditions.push(status.compile_sql("status"));
         }
 
         if let Some(priority) = &self.priority {
-            conditions.push(format!("priority = '{}'", priority));
+            conditions.push(priority.compile_sql("priority"));
         }
 
         if let Some(title) = &self.title {
-            conditions.push(format!("title = '{}'", title));
+            conditions.push(title.compile_sql("title"));
         }
 
         if let Some(description) = &self.description {
-            conditions.push(format!("description = '{}'", description));
+            conditions.push(description.compile_sql("description"));
         }
 
         if let Some(due_date) = &self.due_date {
-            conditions.push(format!("due_date = '{}'", due_date));
+            conditions.push(due_date.compile_sql("due_date"));
         }
 
         if let Some(project_id) = &self.project_id {
-            conditions.push(format!("project_id = '{}'", project_id));
+            conditions.push(project_id.compile_sql("project_id"));
         }
 
         if let Some(lead_id) = &self.lead_id {
-            conditions.push(format!("lead_id = '{}'", lead_id));
+            conditions.push(lead_id.compile_sql("lead_id"));
         }
 
         if let Some(parent_id) = &self.parent_id {
-            conditions.push(format!("parent_id = '{}'", parent_id));
+            conditions.push(parent_id.compile_sql("parent_id"));
+        }
+
+        if let Some(workflow_status_id) = &self.workflow_status_id {
+            conditions.push(workflow_status_id.compile_sql("workflow_status_id"));
+        }
+
+        if let Some(cycle_id) = &self.cycle_id {
+            conditions.push(cycle_id.compile_sql("cycle_id"));
+        }
+
+        if let Some(assignee_ids) = &self.assignee_ids {
+            conditions.push(assignee_ids.compile_sql(TASKS_BY_ASSIGNEES));
+        }
+
+        if let Some(label_ids) = &self.label_ids {
+            conditions.push(label_ids.compile_sql(LABELS_BY_TASKS));
+        }
+
+        if let Some(custom_fields) = &self.custom_fields {
+            conditions.extend(custom_fields.iter().map(|custom_field| custom_field.compile_sql()));
+        }
+
+        if let Some(ancestor_id) = self.ancestor_id {
+            conditions.push(SQLCondition::Expression(
+                &[
+                    "id IN (WITH RECURSIVE descendants AS (SELECT id FROM tasks WHERE parent_id = ",
+                    " UNION SELECT tasks.id FROM tasks JOIN descendants ON tasks.parent_id = descendants.id) SELECT id FROM descendants)",
+                ],
+                vec![ancestor_id.into()],
+            ));
+        }
+
+        if let Some(has_subtasks) = self.has_subtasks {
+            conditions.push(SQLCondition::Expression(
+                match has_subtasks {
+                    true => &["id IN (SELECT parent_id FROM tasks WHERE parent_id IS NOT NULL)"],
+                    false => &["id NOT IN (SELECT parent_id FROM tasks WHERE parent_id IS NOT NULL)"],
+                },
+                vec![],
+            ));
+        }
+
+        if let Some(is_overdue) = self.is_overdue {
+            let closed_statuses = vec![TaskStatus::Done.to_string(), TaskStatus::Canceled.to_string()];
+
+            conditions.push(SQLCondition::Expression(
+                match is_overdue {
+                    true => &["(due_date < now() AND COALESCE(status, '') <> ALL(", "))"],
+                    false => &[
+                        "(due_date IS NULL OR due_date >= now() OR COALESCE(status, '') = ANY(",
+                        "))",
+                    ],
+                },
+                vec![closed_statuses.into()],
+            ));
         }
 
         if let Some(ands) = &self._and {
-            let and_conditions: Vec<String> = ands.iter().map(|and| and.compile_sql()).collect();
-            conditions.push(format!("({})", and_conditions.join(" AND ")));
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
         }
 
         if let Some(ors) = &self._or {
-            let or_conditions: Vec<String> = ors.iter().map(|or| or.compile_sql()).collect();
-            conditions.push(format!("({})", or_conditions.join(" OR ")));
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
         }
 
-        conditions.join(" AND ")
+        SQLCondition::And(conditions)
+    }
+}
+
+impl TrashFilter for GetTasksWhere {
+    fn include_archived(&self) -> bool {
+        self.include_archived.unwrap_or(false)
+    }
+
+    fn include_deleted(&self) -> bool {
+        self.include_deleted.unwrap_or(false)
     }
 }
 
@@ -212,104 +360,10 @@ impl TaskCrudOperations for SDKEngine {
         let mut tx = self.db_pool.begin().await?;
         // let saved_input = input.clone();
 
-        let task = sqlx::query!(
-            r#"
-            INSERT INTO tasks (title, description, owner_id, status, priority, due_date, project_id, lead_id, parent_id)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-            RETURNING *
-            "#,
-            input.title,
-            input.description,
-            input.owner_id,
-            input.status.unwrap_or_default().to_string(),
-            input.priority.unwrap_or_default().to_string(),
-            input.due_date,
-            input.project_id,
-            input.lead_id,
-            input.parent_id,
-        )
-        .fetch_one(&mut *tx)
-        .await?;
-
-        if let Some(labels) = input.labels {
-            for label in labels {
-                sqlx::query!(
-                    r#"
-                    INSERT INTO labels_by_tasks (task_id, label_id)
-                    VALUES ($1, $2)
-                    "#,
-                    task.id,
-                    label,
-                )
-                .execute(&mut *tx)
-                .await?;
-            }
-        }
-
-        if let Some(assignees) = input.assignees {
-            for assignee in assignees {
-                sqlx::query!(
-                    r#"
-                    INSERT INTO tasks_by_assignees (task_id, assignee_id)
-                    VALUES ($1, $2)
-                    "#,
-                    task.id,
-                    assignee,
-                )
-                .execute(&mut *tx)
-                .await?;
-            }
-        }
-
-        if let Some(subtasks) = input.subtasks {
-            for mut subtask in subtasks {
-                if subtask.parent_id.is_none() {
-                    subtask.parent_id = Some(task.id);
-                }
-
-                self.create_task(subtask).await?;
-            }
-        }
-
-        // if let Some(assets) = input.assets {
-        //     for asset in assets {
-        //         sqlx::query!(
-        //             r#"
-        //             INSERT INTO assets_by_tasks (task_id, asset_id)
-        //             VALUES ($1, $2)
-        //             "#,
-        //             task.id,
-        //             asset,
-        //         )
-        //         .execute(&mut *tx)
-        //         .await?;
-        //     }
-        // }
+        let task = insert_task(&mut tx, input).await?;
 
         tx.commit().await?;
 
-        let task = Task {
-            id: task.id,
-            created_at: task.created_at,
-            updated_at: task.updated_at,
-            title: task.title,
-            description: task.description,
-            status: task
-                .status
-                .and_then(|a| TaskStatus::from_str(&a).ok())
-                .unwrap_or_default(),
-            priority: task
-                .priority
-                .and_then(|a| TaskPriority::from_str(&a).ok())
-                .unwrap_or_default(),
-            due_date: task.due_date,
-            project_id: task.project_id,
-            lead_id: task.lead_id,
-            owner_id: task.owner_id,
-            count: task.count,
-            parent_id: task.parent_id,
-        };
-
         // if self.config.with_changes_registration {
         //     let input = saved_input.clone();
         //     let task = task.clone();
@@ -344,176 +398,38 @@ impl TaskCrudOperations for SDKEngine {
     }
 
     async fn get_task(&self, id: Uuid) -> Result<Task, SDKError> {
-        let task_info = sqlx::query!(
+        let task_info = sqlx::query("SELECT * FROM tasks WHERE id = $1 AND archived_at IS NULL AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_one(self.db_pool.as_ref())
+            .await?;
+
+        Ok(task_from_row(&task_info))
+    }
+
+    async fn task_by_key(&self, key: String) -> Result<Task, SDKError> {
+        // Keys a task had before a project move or a prefix rename stay in `task_keys`.
+        let task_info = sqlx::query(
             r#"
-            SELECT * FROM tasks WHERE id = $1
+            SELECT tasks.* FROM tasks
+            JOIN task_keys ON task_keys.task_id = tasks.id
+            WHERE task_keys.key = upper(trim($1)) AND tasks.archived_at IS NULL AND tasks.deleted_at IS NULL
             "#,
-            id,
         )
+        .bind(key)
         .fetch_one(self.db_pool.as_ref())
         .await?;
 
-        let task = Task {
-            id: task_info.id,
-            created_at: task_info.created_at,
-            updated_at: task_info.updated_at,
-            title: task_info.title,
-            description: task_info.description,
-            status: task_info
-                .status
-                .and_then(|a| TaskStatus::from_str(&a).ok())
-                .unwrap_or_default(),
-            priority: task_info
-                .priority
-                .and_then(|a| TaskPriority::from_str(&a).ok())
-                .unwrap_or_default(),
-            due_date: task_info.due_date,
-            project_id: task_info.project_id,
-            lead_id: task_info.lead_id,
-            owner_id: task_info.owner_id,
-            count: task_info.count,
-            parent_id: task_info.parent_id,
-        };
-
-        Ok(task)
+        Ok(task_from_row(&task_info))
     }
 
     async fn update_task(&self, id: Uuid, input: UpdateTaskInput) -> Result<Task, SDKError> {
         let mut tx = self.db_pool.begin().await?;
 
-        let task_final_info = sqlx::query!(
-            r#"
-            UPDATE tasks
-            SET
-                status = COALESCE($1, status),
-                priority = COALESCE($2, priority),
-                title = COALESCE($3, title),
-                description = COALESCE($4, description),
-                due_date = COALESCE($5, due_date),
-                project_id = NULLIF(COALESCE($6, project_id), '00000000-0000-0000-0000-000000000000'),
-                lead_id = NULLIF(COALESCE($7, lead_id), '00000000-0000-0000-0000-000000000000'),
-                parent_id = NULLIF(COALESCE($8, parent_id), '00000000-0000-0000-0000-000000000000')
-            WHERE id = $9
-            RETURNING *
-            "#,
-            input.status.map(|status| status.to_string()),
-            input.priority.map(|priority| priority.to_string()),
-            input.title,
-            input.description,
-            input.due_date,
-            input.project_id,
-            input.lead_id,
-            input.parent_id,
-            id,
-        )
-        .fetch_one(&mut *tx)
-        .await?;
-
-        if let Some(labels) = input.labels {
-            for label in labels.add {
-                sqlx::query!(
-                    r#"
-                    INSERT INTO labels_by_tasks (task_id, label_id)
-                    VALUES ($1, $2)
-                    "#,
-                    id,
-                    label,
-                )
-                .execute(&mut *tx)
-                .await?;
-            }
-
-            for label in labels.remove {
-                sqlx::query!(
-                    r#"
-                    DELETE FROM labels_by_tasks WHERE task_id = $1 AND label_id = $2
-                    "#,
-                    id,
-                    label,
-                )
-                .execute(&mut *tx)
-                .await?;
-            }
-        }
-
-        if let Some(assignees) = input.assignees {
-            for assignee in assignees.add {
-                sqlx::query!(
-                    r#"
-                    INSERT INTO tasks_by_assignees (task_id, assignee_id)
-                    VALUES ($1, $2)
-                    "#,
-                    id,
-                    assignee,
-                )
-                .execute(&mut *tx)
-                .await?;
-            }
-
-            for assignee in assignees.remove {
-                sqlx::query!(
-                    r#"
-                    DELETE FROM tasks_by_assignees WHERE task_id = $1 AND assignee_id = $2
-                    "#,
-                    id,
-                    assignee,
-                )
-                .execute(&mut *tx)
-                .await?;
-            }
-        }
+        lock_visible_task(&mut tx, id).await?;
+        let task = apply_task_update(&mut tx, id, input).await?;
 
         tx.commit().await?;
 
-        // if let Some(assets) = input.assets {
-        //     for asset in assets.add {
-        //         sqlx::query!(
-        //             r#"
-        //             INSERT INTO assets_by_tasks (task_id, asset_id)
-        //             VALUES ($1, $2)
-        //             "#,
-        //             id,
-        //             asset,
-        //         )
-        //         .execute(self.db_pool.as_ref())
-        //         .await?;
-        //     }
-
-        //     for asset in assets.remove {
-        //         sqlx::query!(
-        //             r#"
-        //             DELETE FROM assets_by_tasks WHERE task_id = $1 AND asset_id = $2
-        //             "#,
-        //             id,
-        //             asset,
-        //         )
-        //         .execute(self.db_pool.as_ref())
-        //         .await?;
-        //     }
-        // }
-
-        let task = Task {
-            id: task_final_info.id,
-            created_at: task_final_info.created_at,
-            updated_at: task_final_info.updated_at,
-            title: task_final_info.title,
-            description: task_final_info.description,
-            status: task_final_info
-                .status
-                .and_then(|a| TaskStatus::from_str(&a).ok())
-                .unwrap_or_default(),
-            priority: task_final_info
-                .priority
-                .and_then(|a| TaskPriority::from_str(&a).ok())
-                .unwrap_or_default(),
-            due_date: task_final_info.due_date,
-            project_id: task_final_info.project_id,
-            lead_id: task_final_info.lead_id,
-            owner_id: task_final_info.owner_id,
-            count: task_final_info.count,
-            parent_id: task_final_info.parent_id,
-        };
-
         // if self.config.with_changes_registration {
         //     let task = task.clone();
         //     let engine = self.clone();
@@ -545,37 +461,20 @@ impl TaskCrudOperations for SDKEngine {
     }
 
     async fn delete_task(&self, id: Uuid) -> Result<Task, SDKError> {
-        let task_info = sqlx::query!(
-            r#"
-            DELETE FROM tasks WHERE id = $1
-            RETURNING *
-            "#,
-            id,
-        )
-        .fetch_one(self.db_pool.as_ref())
-        .await?;
+        let mut tx = self.db_pool.begin().await?;
 
-        let task = Task {
-            id: task_info.id,
-            created_at: task_info.created_at,
-            updated_at: task_info.updated_at,
-            title: task_info.title,
-            description: task_info.description,
-            status: task_info
-                .status
-                .and_then(|a| TaskStatus::from_str(&a).ok())
-                .unwrap_or_default(),
-            priority: task_info
-                .priority
-                .and_then(|a| TaskPriority::from_str(&a).ok())
-                .unwrap_or_default(),
-            due_date: task_info.due_date,
-            project_id: task_info.project_id,
-            lead_id: task_info.lead_id,
-            owner_id: task_info.owner_id,
-            count: task_info.count,
-            parent_id: task_info.parent_id,
-        };
+        // The task goes to the trash with its subtasks, see `restore_task`.
+        let deleted_at = hide_task_trees(&mut tx, &[id], TrashColumn::Deleted).await?;
+
+        let task_info = sqlx::query("SELECT * FROM tasks WHERE id = $1 AND deleted_at = $2")
+            .bind(id)
+            .bind(deleted_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        let task = task_from_row(&task_info);
 
         // if self.config.with_changes_registration {
         //     let task = task.clone();
@@ -609,62 +508,557 @@ impl TaskCrudOperations for SDKEngine {
     }
 
     async fn get_tasks(&self, input: Option<GetTasksInput>) -> Result<Vec<Task>, SDKError> {
-        let mut query = "SELECT * FROM tasks ".to_string();
+        let select = SQLSelect::new("tasks", TASKS_SORTABLE_COLUMNS);
 
-        let query = match input {
+        let select = match input {
             Some(input) => {
-                if let Some(filter) = input.filter {
-                    query.push_str(format!("WHERE {} ", filter.compile_sql()).as_str());
-                }
+                let (select, sort_by) = sort_by_custom_field(self.db_pool.as_ref(), select, input.sort_by).await?;
 
-                if let Some(sort_by) = input.sort_by {
-                    query.push_str(format!("ORDER BY {} ", sort_by).as_str());
-                }
+                select
+                    .filter(Some(&VisibleFilter(input.filter)))
+                    .sort(sort_by, input.sort_order)
+                    .paginate(input.limit, input.offset)
+            }
+            None => select.filter(Some(&VisibleFilter::<GetTasksWhere>(None))),
+        };
 
-                if let Some(sort_order) = input.sort_order {
-                    query.push_str(format!("{} ", sort_order).as_str());
-                }
+        let tasks_info = select.build()?.build().fetch_all(self.db_pool.as_ref()).await?;
+
+        Ok(tasks_info.iter().map(task_from_row).collect())
+    }
+
+    async fn get_tasks_page(&self, mut input: PageInput<GetTasksWhere>) -> Result<Page<Task>, SDKError> {
+        let (select, sort_by) = sort_by_custom_field(
+            self.db_pool.as_ref(),
+            SQLSelect::new("tasks", TASKS_SORTABLE_COLUMNS),
+            input.sort_by.take(),
+        )
+        .await?;
+
+        input.sort_by = sort_by;
+
+        select
+            .fetch_page(self.db_pool.as_ref(), VisibleFilter::page(input), task_from_row)
+            .await
+    }
+}
+
+/// Locks a task for an edit, failing like a missing row once it's archived or deleted.
+pub(crate) async fn lock_visible_task(conn: &mut PgConnection, id: Uuid) -> Result<Task, SDKError> {
+    let task_info =
+        sqlx::query("SELECT * FROM tasks WHERE id = $1 AND archived_at IS NULL AND deleted_at IS NULL FOR UPDATE")
+            .bind(id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+    Ok(task_from_row(&task_info))
+}
+
+/// Applies `input` to a single task inside the caller's transaction, enforcing its blockers.
+pub(crate) async fn apply_task_update(
+    conn: &mut PgConnection,
+    id: Uuid,
+    input: UpdateTaskInput,
+) -> Result<Task, SDKError> {
+    let workflow_state = resolve_workflow_state(&mut *conn, id, &input).await?;
+
+    if matches!(workflow_state.status, Some(TaskStatus::InProgress | TaskStatus::Done))
+        && !input.override_blockers.unwrap_or(false)
+    {
+        let unfinished_blockers = sqlx::query!(
+            r#"
+            SELECT COUNT(*) AS "count!" FROM task_dependencies
+            JOIN tasks ON tasks.id = task_dependencies.blocked_by_id
+            WHERE task_dependencies.task_id = $1 AND COALESCE(tasks.status, '') <> ALL($2)
+                AND tasks.deleted_at IS NULL
+            "#,
+            id,
+            &[TaskStatus::Done.to_string(), TaskStatus::Canceled.to_string()],
+        )
+        .fetch_one(&mut *conn)
+        .await?
+        .count;
+
+        if unfinished_blockers > 0 {
+            return Err(SDKError::TaskBlocked(unfinished_blockers));
+        }
+    }
 
-                if let Some(limit) = input.limit {
-                    query.push_str(format!("LIMIT {} ", limit).as_str());
+    // Tasks moved to another board column go to its end, like new tasks.
+    let current = sqlx::query!("SELECT status, project_id FROM tasks WHERE id = $1", id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    let status = workflow_state
+        .status
+        .map(|status| status.to_string())
+        .or(current.status.clone());
+    let project_id = match input.project_id {
+        Some(project_id) => Some(project_id).filter(|project_id| !project_id.is_nil()),
+        None => current.project_id,
+    };
+
+    let changes_column = status != current.status || project_id != current.project_id;
+
+    let rank = match changes_column {
+        true => {
+            let last_rank = sqlx::query!(
+                r#"
+                SELECT MAX(rank) AS rank FROM tasks
+                WHERE project_id IS NOT DISTINCT FROM $1 AND status IS NOT DISTINCT FROM $2 AND id <> $3
+                "#,
+                project_id,
+                status,
+                id,
+            )
+            .fetch_one(&mut *conn)
+            .await?
+            .rank;
+
+            rank_between(last_rank.as_deref(), None)
+        }
+        false => None,
+    };
+
+    let task = task_from_row(
+        &sqlx::query(
+            r#"
+            UPDATE tasks
+            SET
+                status = COALESCE($1, status),
+                priority = COALESCE($2, priority),
+                title = COALESCE($3, title),
+                description = COALESCE($4, description),
+                due_date = COALESCE($5, due_date),
+                project_id = NULLIF(COALESCE($6, project_id), '00000000-0000-0000-0000-000000000000'),
+                lead_id = NULLIF(COALESCE($7, lead_id), '00000000-0000-0000-0000-000000000000'),
+                parent_id = NULLIF(COALESCE($8, parent_id), '00000000-0000-0000-0000-000000000000'),
+                workflow_status_id = $9,
+                estimate = COALESCE($10, estimate),
+                estimate_unit = COALESCE($11, estimate_unit),
+                cycle_id = NULLIF(COALESCE($13, cycle_id), '00000000-0000-0000-0000-000000000000'),
+                rank = CASE WHEN $15 THEN $14 ELSE rank END
+            WHERE id = $12
+            RETURNING *
+            "#,
+        )
+        .bind(workflow_state.status.map(|status| status.to_string()))
+        .bind(input.priority.map(|priority| priority.to_string()))
+        .bind(input.title)
+        .bind(input.description)
+        .bind(input.due_date)
+        .bind(input.project_id)
+        .bind(input.lead_id)
+        .bind(input.parent_id)
+        .bind(workflow_state.workflow_status_id)
+        .bind(input.estimate)
+        .bind(input.estimate_unit.map(|unit| unit.to_string()))
+        .bind(id)
+        .bind(input.cycle_id)
+        .bind(rank)
+        .bind(changes_column)
+        .fetch_one(&mut *conn)
+        .await?,
+    );
+
+    if let Some(cycle_id) = task.cycle_id.filter(|_| input.cycle_id.is_some()) {
+        check_cycle_tasks(&mut *conn, cycle_id, &[id]).await?;
+    }
+
+    if input.project_id.is_some() {
+        rescope_task_labels(&mut *conn, &[id]).await?;
+    }
+
+    if let Some(labels) = input.labels {
+        check_label_scope(&mut *conn, task.project_id, &labels.add).await?;
+
+        for label in labels.add {
+            sqlx::query!(
+                r#"
+                INSERT INTO labels_by_tasks (task_id, label_id)
+                VALUES ($1, $2)
+                ON CONFLICT DO NOTHING
+                "#,
+                id,
+                label,
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        for label in labels.remove {
+            sqlx::query!(
+                r#"
+                DELETE FROM labels_by_tasks WHERE task_id = $1 AND label_id = $2
+                "#,
+                id,
+                label,
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+    }
+
+    if let Some(assignees) = input.assignees {
+        for assignee in assignees.add {
+            sqlx::query!(
+                r#"
+                INSERT INTO tasks_by_assignees (task_id, assignee_id)
+                VALUES ($1, $2)
+                ON CONFLICT DO NOTHING
+                "#,
+                id,
+                assignee,
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        for assignee in assignees.remove {
+            sqlx::query!(
+                r#"
+                DELETE FROM tasks_by_assignees WHERE task_id = $1 AND assignee_id = $2
+                "#,
+                id,
+                assignee,
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+    }
+
+    if let Some(assets) = input.assets {
+        for asset in assets.add {
+            sqlx::query!(
+                r#"
+                INSERT INTO assets_by_tasks (task_id, asset_id)
+                VALUES ($1, $2)
+                ON CONFLICT DO NOTHING
+                "#,
+                id,
+                asset,
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        for asset in assets.remove {
+            sqlx::query!(
+                r#"
+                DELETE FROM assets_by_tasks WHERE task_id = $1 AND asset_id = $2
+                "#,
+                id,
+                asset,
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+    }
+
+    Ok(task)
+}
+
+/// Inserts a task together with its labels, assignees, assets and the whole tree of subtasks, using the
+/// caller's transaction so a failure anywhere in the tree leaves nothing behind.
+pub(crate) async fn insert_task(conn: &mut PgConnection, input: CreateTaskInput) -> Result<Task, SDKError> {
+    let mut pending = vec![input];
+    let mut root_id = None;
+
+    while let Some(input) = pending.pop() {
+        let workflow_state = initial_workflow_state(&mut *conn, input.project_id, input.status).await?;
+        let status = workflow_state.status.unwrap_or_default().to_string();
+
+        // New tasks go to the end of their board column.
+        let last_rank = sqlx::query!(
+            r#"
+            SELECT MAX(rank) AS rank FROM tasks
+            WHERE project_id IS NOT DISTINCT FROM $1 AND status = $2
+            "#,
+            input.project_id,
+            status,
+        )
+        .fetch_one(&mut *conn)
+        .await?
+        .rank;
+
+        let task_info = sqlx::query!(
+            r#"
+            INSERT INTO tasks (title, description, owner_id, status, priority, due_date, project_id, lead_id, parent_id, workflow_status_id, estimate, estimate_unit, rank, cycle_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            RETURNING id, created_at, updated_at, title, description, owner_id, status, priority,
+                due_date, project_id, lead_id, labels, count, parent_id, number, key,
+                workflow_status_id, estimate, estimate_unit, rank, cycle_id, deleted_at, archived_at
+            "#,
+            input.title,
+            input.description,
+            input.owner_id,
+            status,
+            input.priority.unwrap_or_default().to_string(),
+            input.due_date,
+            input.project_id,
+            input.lead_id,
+            input.parent_id,
+            workflow_state.workflow_status_id,
+            input.estimate,
+            input.estimate_unit.map(|unit| unit.to_string()),
+            rank_between(last_rank.as_deref(), None),
+            input.cycle_id,
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        if let Some(cycle_id) = task_info.cycle_id {
+            check_cycle_tasks(&mut *conn, cycle_id, &[task_info.id]).await?;
+        }
+
+        if let Some(labels) = input.labels {
+            check_label_scope(&mut *conn, task_info.project_id, &labels).await?;
+
+            for label in labels {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO labels_by_tasks (task_id, label_id)
+                    VALUES ($1, $2)
+                    "#,
+                    task_info.id,
+                    label,
+                )
+                .execute(&mut *conn)
+                .await?;
+            }
+        }
+
+        if let Some(assignees) = input.assignees {
+            for assignee in assignees {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO tasks_by_assignees (task_id, assignee_id)
+                    VALUES ($1, $2)
+                    "#,
+                    task_info.id,
+                    assignee,
+                )
+                .execute(&mut *conn)
+                .await?;
+            }
+        }
+
+        if let Some(assets) = input.assets {
+            for asset in assets {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO assets_by_tasks (task_id, asset_id)
+                    VALUES ($1, $2)
+                    ON CONFLICT DO NOTHING
+                    "#,
+                    task_info.id,
+                    asset,
+                )
+                .execute(&mut *conn)
+                .await?;
+            }
+        }
+
+        // Pushed in reverse so siblings are inserted (and numbered) in the order they were given.
+        // Subtasks without an owner of their own belong to the owner of their parent.
+        if let Some(subtasks) = input.subtasks {
+            for mut subtask in subtasks.into_iter().rev() {
+                if subtask.parent_id.is_none() {
+                    subtask.parent_id = Some(task_info.id);
                 }
 
-                if let Some(offset) = input.offset {
-                    query.push_str(format!("OFFSET {} ", offset).as_str());
+                if subtask.owner_id.is_nil() {
+                    subtask.owner_id = task_info.owner_id;
                 }
 
-                query
+                pending.push(subtask);
             }
-            None => query,
+        }
+
+        root_id.get_or_insert(task_info.id);
+    }
+
+    let Some(root_id) = root_id else {
+        return Err(SDKError::SQLXError(sqlx::Error::RowNotFound));
+    };
+
+    let task_info = sqlx::query("SELECT * FROM tasks WHERE id = $1")
+        .bind(root_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    Ok(task_from_row(&task_info))
+}
+
+pub(crate) fn task_from_row(task_info: &PgRow) -> Task {
+    Task {
+        id: task_info.get("id"),
+        created_at: task_info.get("created_at"),
+        updated_at: task_info.get("updated_at"),
+        title: task_info.get("title"),
+        description: task_info.get("description"),
+        status: task_info
+            .get::<'_, Option<String>, _>("status")
+            .and_then(|a| TaskStatus::from_str(&a).ok())
+            .unwrap_or_default(),
+        priority: task_info
+            .get::<'_, Option<String>, _>("priority")
+            .and_then(|a| TaskPriority::from_str(&a).ok())
+            .unwrap_or_default(),
+        // status: TaskStatus::from_optional_str(&task_info.get("status")),
+        // priority: TaskPriority::from_optional_str(&),
+        due_date: task_info.get("due_date"),
+        project_id: task_info.get("project_id"),
+        lead_id: task_info.get("lead_id"),
+        owner_id: task_info.get("owner_id"),
+        count: task_info.get("count"),
+        parent_id: task_info.get("parent_id"),
+        number: task_info.get("number"),
+        key: task_info.get("key"),
+        workflow_status_id: task_info.get("workflow_status_id"),
+        estimate: task_info.get("estimate"),
+        estimate_unit: task_info
+            .get::<'_, Option<String>, _>("estimate_unit")
+            .and_then(|unit| TaskEstimateUnit::from_str(&unit).ok()),
+        rank: task_info.get("rank"),
+        cycle_id: task_info.get("cycle_id"),
+        archived_at: task_info.get("archived_at"),
+        deleted_at: task_info.get("deleted_at"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::backend::testing::{test_engine, test_member, test_project};
+    use crate::resources::assets::operations::{AssetCrudOperations, CreateAssetInput};
+    use crate::resources::tasks::extensions::TasksExtensionOperations;
+
+    fn task_input(title: &str, subtasks: Vec<CreateTaskInput>) -> CreateTaskInput {
+        CreateTaskInput {
+            title: title.to_string(),
+            subtasks: Some(subtasks).filter(|subtasks| !subtasks.is_empty()),
+            ..Default::default()
+        }
+    }
+
+    #[sqlx::test]
+    async fn create_task_owns_the_whole_subtask_tree(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+        let project_id = test_project(&engine, owner_id, "TRE").await;
+
+        let mut input = task_input(
+            "Root",
+            vec![task_input("Child", vec![task_input("Grandchild", vec![])])],
+        );
+        input.owner_id = owner_id;
+        input.project_id = Some(project_id);
+
+        let root = engine.create_task(input).await.unwrap();
+        let tree = engine.task_tree(root.id, None).await.unwrap();
+
+        let child = &tree.children[0];
+        let grandchild = &child.children[0];
+
+        assert_eq!(child.task.owner_id, owner_id);
+        assert_eq!(grandchild.task.owner_id, owner_id);
+        assert_eq!(grandchild.task.parent_id, Some(child.task.id));
+        assert_eq!(grandchild.task.title, "Grandchild");
+    }
+
+    #[sqlx::test]
+    async fn task_tree_rolls_progress_up_from_visible_subtasks(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+
+        let with_status = |mut input: CreateTaskInput, status: TaskStatus| {
+            input.status = Some(status);
+            input
         };
 
-        let tasks_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
-
-        Ok(tasks_info
-            .iter()
-            .map(|task_info| Task {
-                id: task_info.get("id"),
-                created_at: task_info.get("created_at"),
-                updated_at: task_info.get("updated_at"),
-                title: task_info.get("title"),
-                description: task_info.get("description"),
-                status: task_info
-                    .get::<'_, Option<String>, _>("status")
-                    .and_then(|a| TaskStatus::from_str(&a).ok())
-                    .unwrap_or_default(),
-                priority: task_info
-                    .get::<'_, Option<String>, _>("priority")
-                    .and_then(|a| TaskPriority::from_str(&a).ok())
-                    .unwrap_or_default(),
-                // status: TaskStatus::from_optional_str(&task_info.get("status")),
-                // priority: TaskPriority::from_optional_str(&),
-                due_date: task_info.get("due_date"),
-                project_id: task_info.get("project_id"),
-                lead_id: task_info.get("lead_id"),
-                owner_id: task_info.get("owner_id"),
-                count: task_info.get("count"),
-                parent_id: task_info.get("parent_id"),
-            })
-            .collect())
+        let mut input = task_input(
+            "Root",
+            vec![
+                with_status(task_input("Done", vec![]), TaskStatus::Done),
+                task_input(
+                    "Open",
+                    vec![
+                        with_status(task_input("Open done", vec![]), TaskStatus::Done),
+                        task_input("Open todo", vec![]),
+                    ],
+                ),
+                with_status(task_input("Canceled", vec![]), TaskStatus::Canceled),
+            ],
+        );
+        input.owner_id = owner_id;
+
+        let root = engine.create_task(input).await.unwrap();
+        let tree = engine.task_tree(root.id, None).await.unwrap();
+
+        assert_eq!((tree.total_count, tree.done_count), (4, 2));
+        assert_eq!(tree.progress, 50.0);
+        assert_eq!(tree.children[0].progress, 100.0);
+        assert_eq!((tree.children[1].total_count, tree.children[1].done_count), (2, 1));
+
+        let open_todo = tree.children[1].children[1].task.id;
+        engine.archive_task(open_todo).await.unwrap();
+
+        let tree = engine.task_tree(root.id, Some(1)).await.unwrap();
+
+        assert_eq!((tree.total_count, tree.done_count), (3, 2));
+        assert_eq!(tree.children[1].progress, 100.0);
+        assert!(tree.children[1].children.is_empty());
+    }
+
+    async fn linked_assets(engine: &SDKEngine, task_id: Uuid) -> Vec<Uuid> {
+        sqlx::query_scalar("SELECT asset_id FROM assets_by_tasks WHERE task_id = $1")
+            .bind(task_id)
+            .fetch_all(engine.db_pool.as_ref())
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn tasks_link_and_unlink_assets(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+
+        let mut assets = Vec::new();
+        for name in ["brief.pdf", "mockup.png"] {
+            let input = CreateAssetInput {
+                name: name.to_string(),
+                owner_id,
+                ..Default::default()
+            };
+            assets.push(engine.create_asset(input).await.unwrap().id);
+        }
+
+        let mut input = task_input("With assets", vec![]);
+        input.owner_id = owner_id;
+        input.assets = Some(vec![assets[0], assets[0]]);
+
+        let task = engine.create_task(input).await.unwrap();
+        assert_eq!(linked_assets(&engine, task.id).await, vec![assets[0]]);
+
+        engine
+            .update_task(
+                task.id,
+                UpdateTaskInput {
+                    assets: Some(UpdateListInput {
+                        add: vec![assets[1]],
+                        remove: vec![assets[0]],
+                    }),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(linked_assets(&engine, task.id).await, vec![assets[1]]);
+
+        engine.delete_asset(assets[1]).await.unwrap();
+        assert!(linked_assets(&engine, task.id).await.is_empty());
     }
 }
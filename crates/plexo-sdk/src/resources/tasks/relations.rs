@@ -7,10 +7,18 @@ use crate::{
     backend::loaders::SDKLoaders,
     errors::sdk::SDKError,
     resources::{
+        assets::asset::Asset,
         changes::change::{Change, ChangeOperation, ChangeResourceType},
+        checklists::{checklist::ChecklistItem, operations::ChecklistOperations},
+        comments::{comment::Comment, operations::CommentCrudOperations},
+        custom_fields::{custom_field::CustomFieldValue, extensions::CustomFieldsExtensionOperations},
+        cycles::cycle::Cycle,
         labels::label::Label,
         members::member::Member,
         projects::project::Project,
+        recurrences::{operations::recurrence_from_row, recurrence::Recurrence},
+        work_logs::{operations::work_log_from_row, work_log::WorkLog},
+        workflows::{operations::workflow_status_from_row, workflow::WorkflowStatus},
     },
 };
 
@@ -22,11 +30,21 @@ pub trait TaskRelations {
     async fn project(&self, loaders: &SDKLoaders) -> Result<Option<Project>, SDKError>;
     async fn lead(&self, loaders: &SDKLoaders) -> Result<Option<Member>, SDKError>;
     async fn parent(&self, loaders: &SDKLoaders) -> Result<Option<Task>, SDKError>;
+    async fn workflow_status(&self, loaders: &SDKLoaders) -> Result<Option<WorkflowStatus>, SDKError>;
+    async fn recurrence(&self, loaders: &SDKLoaders) -> Result<Option<Recurrence>, SDKError>;
+    async fn cycle(&self, loaders: &SDKLoaders) -> Result<Option<Cycle>, SDKError>;
 
     async fn assignees(&self, loaders: &SDKLoaders) -> Result<Vec<Member>, SDKError>;
     async fn labels(&self, loaders: &SDKLoaders) -> Result<Vec<Label>, SDKError>;
+    async fn assets(&self, loaders: &SDKLoaders) -> Result<Vec<Asset>, SDKError>;
     async fn subtasks(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError>;
+    async fn blocked_by(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError>;
+    async fn blocking(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError>;
     async fn changes(&self, loaders: &SDKLoaders) -> Result<Vec<Change>, SDKError>;
+    async fn work_logs(&self, loaders: &SDKLoaders) -> Result<Vec<WorkLog>, SDKError>;
+    async fn custom_field_values(&self, loaders: &SDKLoaders) -> Result<Vec<CustomFieldValue>, SDKError>;
+    async fn checklist(&self, loaders: &SDKLoaders) -> Result<Vec<ChecklistItem>, SDKError>;
+    async fn comments(&self, loaders: &SDKLoaders) -> Result<Vec<Comment>, SDKError>;
 }
 
 #[async_trait]
@@ -42,9 +60,9 @@ impl TaskRelations for Task {
             return Ok(None);
         };
 
-        let data = loaders.project_loader.load_one(project_id).await.unwrap().unwrap();
+        let data = loaders.project_loader.load_one(project_id).await.unwrap();
 
-        Ok(Some(data))
+        Ok(data)
     }
 
     async fn lead(&self, loaders: &SDKLoaders) -> Result<Option<Member>, SDKError> {
@@ -62,9 +80,51 @@ impl TaskRelations for Task {
             return Ok(None);
         };
 
-        let data = loaders.task_loader.load_one(parent_id).await.unwrap().unwrap();
+        let data = loaders.task_loader.load_one(parent_id).await.unwrap();
 
-        Ok(Some(data))
+        Ok(data)
+    }
+
+    async fn workflow_status(&self, loaders: &SDKLoaders) -> Result<Option<WorkflowStatus>, SDKError> {
+        let Some(workflow_status_id) = self.workflow_status_id else {
+            return Ok(None);
+        };
+
+        let workflow_status_info = sqlx::query(
+            r#"
+            SELECT * FROM workflow_statuses
+            WHERE id = $1
+            "#,
+        )
+        .bind(workflow_status_id)
+        .fetch_optional(&*loaders.engine.db_pool)
+        .await?;
+
+        Ok(workflow_status_info.as_ref().map(workflow_status_from_row))
+    }
+
+    async fn recurrence(&self, loaders: &SDKLoaders) -> Result<Option<Recurrence>, SDKError> {
+        let recurrence_info = sqlx::query(
+            r#"
+            SELECT * FROM task_recurrences
+            WHERE task_id = $1
+            "#,
+        )
+        .bind(self.id)
+        .fetch_optional(&*loaders.engine.db_pool)
+        .await?;
+
+        Ok(recurrence_info.as_ref().map(recurrence_from_row))
+    }
+
+    async fn cycle(&self, loaders: &SDKLoaders) -> Result<Option<Cycle>, SDKError> {
+        let Some(cycle_id) = self.cycle_id else {
+            return Ok(None);
+        };
+
+        let data = loaders.cycle_loader.load_one(cycle_id).await.unwrap();
+
+        Ok(data)
     }
 
     async fn assignees(&self, loaders: &SDKLoaders) -> Result<Vec<Member>, SDKError> {
@@ -114,29 +174,84 @@ impl TaskRelations for Task {
         Ok(labels.clone())
     }
 
+    async fn assets(&self, loaders: &SDKLoaders) -> Result<Vec<Asset>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT asset_id FROM assets_by_tasks
+            WHERE task_id = $1
+            ORDER BY created_at, asset_id
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await?
+        .into_iter()
+        .map(|asset| asset.asset_id)
+        .collect();
+
+        let assets_map = loaders.asset_loader.load_many(ids.clone()).await.unwrap();
+
+        Ok(ids.into_iter().filter_map(|id| assets_map.get(&id).cloned()).collect())
+    }
+
     async fn subtasks(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError> {
         let ids: Vec<Uuid> = sqlx::query!(
             r#"
             SELECT id FROM tasks
             WHERE parent_id = $1
+            ORDER BY created_at, count, id
             "#,
             &self.id
         )
         .fetch_all(&*loaders.engine.db_pool)
-        .await
-        .unwrap()
+        .await?
         .into_iter()
         .map(|t| t.id)
         .collect();
 
-        Ok(loaders
-            .task_loader
-            .load_many(ids)
-            .await
-            .unwrap()
-            .values()
-            .map(|x| x.to_owned())
-            .collect())
+        let tasks_map = loaders.task_loader.load_many(ids.clone()).await.unwrap();
+
+        Ok(ids.into_iter().filter_map(|id| tasks_map.get(&id).cloned()).collect())
+    }
+
+    async fn blocked_by(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT blocked_by_id FROM task_dependencies
+            WHERE task_id = $1
+            ORDER BY created_at
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await?
+        .into_iter()
+        .map(|dependency| dependency.blocked_by_id)
+        .collect();
+
+        let tasks_map = loaders.task_loader.load_many(ids.clone()).await.unwrap();
+
+        Ok(ids.into_iter().filter_map(|id| tasks_map.get(&id).cloned()).collect())
+    }
+
+    async fn blocking(&self, loaders: &SDKLoaders) -> Result<Vec<Task>, SDKError> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+            SELECT task_id FROM task_dependencies
+            WHERE blocked_by_id = $1
+            ORDER BY created_at
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await?
+        .into_iter()
+        .map(|dependency| dependency.task_id)
+        .collect();
+
+        let tasks_map = loaders.task_loader.load_many(ids.clone()).await.unwrap();
+
+        Ok(ids.into_iter().filter_map(|id| tasks_map.get(&id).cloned()).collect())
     }
 
     async fn changes(&self, loaders: &SDKLoaders) -> Result<Vec<Change>, SDKError> {
@@ -164,4 +279,34 @@ impl TaskRelations for Task {
             })
             .collect())
     }
+
+    async fn work_logs(&self, loaders: &SDKLoaders) -> Result<Vec<WorkLog>, SDKError> {
+        let work_logs_info = sqlx::query(
+            r#"
+            SELECT * FROM work_logs
+            WHERE task_id = $1
+            ORDER BY started_at DESC
+            "#,
+        )
+        .bind(self.id)
+        .fetch_all(&*loaders.engine.db_pool)
+        .await?;
+
+        Ok(work_logs_info.iter().map(work_log_from_row).collect())
+    }
+
+    async fn custom_field_values(&self, loaders: &SDKLoaders) -> Result<Vec<CustomFieldValue>, SDKError> {
+        loaders.engine.task_custom_field_values(self.id).await
+    }
+
+    async fn checklist(&self, loaders: &SDKLoaders) -> Result<Vec<ChecklistItem>, SDKError> {
+        loaders.engine.get_checklist_items(self.id).await
+    }
+
+    async fn comments(&self, loaders: &SDKLoaders) -> Result<Vec<Comment>, SDKError> {
+        loaders
+            .engine
+            .get_resource_comments(ChangeResourceType::Tasks, self.id)
+            .await
+    }
 }
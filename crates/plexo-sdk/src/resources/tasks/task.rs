@@ -28,6 +28,25 @@ pub struct Task {
     pub project_id: Option<Uuid>,
     pub lead_id: Option<Uuid>,
     pub parent_id: Option<Uuid>,
+
+    /// Sequential number of the task inside its project.
+    pub number: Option<i32>,
+    /// Human readable identifier built from the project prefix and the task number, e.g. `PLX-42`.
+    pub key: Option<String>,
+    /// Status of the task in its project workflow, if the project has one.
+    pub workflow_status_id: Option<Uuid>,
+
+    pub estimate: Option<f64>,
+    pub estimate_unit: Option<TaskEstimateUnit>,
+
+    /// Position of the task in its board column, compared byte by byte.
+    pub rank: Option<String>,
+    pub cycle_id: Option<Uuid>,
+
+    /// Set while the task is archived, archived tasks are hidden from reads unless asked for.
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Set while the task is in the trash, it is purged for good after the retention period.
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 #[derive(
@@ -81,3 +100,25 @@ pub enum TaskPriority {
     High,
     Urgent,
 }
+
+#[derive(
+    Debug,
+    Enum,
+    OpenApiEnum,
+    Copy,
+    Clone,
+    Default,
+    Display,
+    EnumString,
+    Deserialize,
+    Serialize,
+    Eq,
+    PartialEq,
+    JsonSchema,
+)]
+
+pub enum TaskEstimateUnit {
+    #[default]
+    Points,
+    Minutes,
+}
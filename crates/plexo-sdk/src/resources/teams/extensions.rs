@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+
+use super::{operations::team_from_row, team::Team};
+
+#[async_trait]
+pub trait TeamsExtensionOperations {
+    async fn archive_team(&self, id: Uuid) -> Result<Team, SDKError>;
+    /// Brings a team back from the trash or the archive, with its projects and members.
+    async fn restore_team(&self, id: Uuid) -> Result<Team, SDKError>;
+}
+
+#[async_trait]
+impl TeamsExtensionOperations for SDKEngine {
+    async fn archive_team(&self, id: Uuid) -> Result<Team, SDKError> {
+        let team_info = sqlx::query(
+            r#"
+            UPDATE teams
+            SET archived_at = now()
+            WHERE id = $1 AND archived_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(team_from_row(&team_info))
+    }
+
+    async fn restore_team(&self, id: Uuid) -> Result<Team, SDKError> {
+        let team_info = sqlx::query(
+            r#"
+            UPDATE teams
+            SET archived_at = NULL, deleted_at = NULL
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(team_from_row(&team_info))
+    }
+}
@@ -23,7 +23,7 @@ impl Loader<Uuid> for TeamLoader {
     async fn load(&self, keys: &'_ [Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
         let teams = sqlx::query!(
             r#"
-            SELECT * FROM teams WHERE id  = ANY($1)
+            SELECT * FROM teams WHERE id = ANY($1) AND archived_at IS NULL AND deleted_at IS NULL
             "#,
             &keys
         )
@@ -48,6 +48,8 @@ impl Loader<Uuid> for TeamLoader {
                             .clone()
                             .and_then(|a| TeamVisibility::from_str(&a).ok())
                             .unwrap_or_default(),
+                        archived_at: team.archived_at,
+                        deleted_at: team.deleted_at,
                         prefix: team.prefix.clone(),
                     },
                 )
@@ -6,9 +6,14 @@ use async_trait::async_trait;
 use derive_builder::Builder;
 use poem_openapi::Object;
 use serde::Serialize;
-use sqlx::Row;
+use sqlx::{postgres::PgRow, Row};
 use uuid::Uuid;
 
+use crate::common::filters::{
+    enum_comparison, SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect, StringComparison, UuidComparison,
+};
+use crate::common::pagination::{Page, PageInput};
+use crate::common::trash::{TrashFilter, VisibleFilter};
 use crate::{
     backend::engine::SDKEngine,
     common::commons::{SortOrder, UpdateListInput},
@@ -17,11 +22,14 @@ use crate::{
 
 use super::team::{Team, TeamVisibility};
 
+const TEAMS_SORTABLE_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "name", "visibility", "prefix"];
+
 #[async_trait]
 pub trait TeamCrudOperations {
     async fn create_team(&self, input: CreateTeamInput) -> Result<Team, SDKError>;
     async fn get_team(&self, id: Uuid) -> Result<Team, SDKError>;
     async fn get_teams(&self, input: GetTeamsInput) -> Result<Vec<Team>, SDKError>;
+    async fn get_teams_page(&self, input: PageInput<GetTeamsWhere>) -> Result<Page<Team>, SDKError>;
     async fn update_team(&self, id: Uuid, input: UpdateTeamInput) -> Result<Team, SDKError>;
     async fn delete_team(&self, id: Uuid) -> Result<Team, SDKError>;
 }
@@ -82,14 +90,21 @@ pub struct GetTeamsInput {
 #[derive(Default, Object, Builder, InputObject)]
 #[builder(pattern = "owned")]
 pub struct GetTeamsWhere {
+    #[builder(setter(into, strip_option), default)]
+    pub name: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub owner_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub visibility: Option<TeamVisibilityComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub prefix: Option<StringComparison>,
+
+    /// Archived teams are left out unless set, only read on the top level filter.
     #[builder(setter(strip_option), default)]
-    pub name: Option<String>,
-    #[builder(setter(strip_option), default)]
-    pub owner_id: Option<Uuid>,
-    #[builder(setter(strip_option), default)]
-    pub visibility: Option<TeamVisibility>,
+    pub include_archived: Option<bool>,
+    /// Teams in the trash are left out unless set, only read on the top level filter.
     #[builder(setter(strip_option), default)]
-    pub prefix: Option<String>,
+    pub include_deleted: Option<bool>,
 
     #[oai(skip)]
     #[builder(setter(strip_option), default)]
@@ -99,55 +114,47 @@ pub struct GetTeamsWhere {
     pub _or: Option<Vec<GetTeamsWhere>>,
 }
 
-impl GetTeamsWhere {
-    pub fn compile_sql(&self) -> String {
-        let mut query = String::new();
+enum_comparison!(TeamVisibilityComparison, TeamVisibility);
+
+impl SQLFilter for GetTeamsWhere {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
 
         if let Some(name) = &self.name {
-            query.push_str(format!("name = '{}' ", name).as_str());
+            conditions.push(name.compile_sql("name"));
         }
 
         if let Some(owner_id) = &self.owner_id {
-            query.push_str(format!("owner_id = '{}' ", owner_id).as_str());
+            conditions.push(owner_id.compile_sql("owner_id"));
         }
 
         if let Some(visibility) = &self.visibility {
-            query.push_str(format!("visibility = '{}' ", visibility).as_str());
+            conditions.push(visibility.compile_sql("visibility"));
         }
 
         if let Some(prefix) = &self.prefix {
-            query.push_str(format!("prefix = '{}' ", prefix).as_str());
+            conditions.push(prefix.compile_sql("prefix"));
         }
 
-        if let Some(_and) = &self._and {
-            query.push_str("AND (");
-
-            for (index, where_clause) in _and.iter().enumerate() {
-                query.push_str(where_clause.compile_sql().as_str());
-
-                if index != _and.len() - 1 {
-                    query.push_str("AND ");
-                }
-            }
-
-            query.push_str(") ");
+        if let Some(ands) = &self._and {
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
         }
 
-        if let Some(_or) = &self._or {
-            query.push_str("OR (");
-
-            for (index, where_clause) in _or.iter().enumerate() {
-                query.push_str(where_clause.compile_sql().as_str());
+        if let Some(ors) = &self._or {
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
+        }
 
-                if index != _or.len() - 1 {
-                    query.push_str("OR ");
-                }
-            }
+        SQLCondition::And(conditions)
+    }
+}
 
-            query.push_str(") ");
-        }
+impl TrashFilter for GetTeamsWhere {
+    fn include_archived(&self) -> bool {
+        self.include_archived.unwrap_or(false)
+    }
 
-        query
+    fn include_deleted(&self) -> bool {
+        self.include_deleted.unwrap_or(false)
     }
 }
 
@@ -212,6 +219,8 @@ impl TeamCrudOperations for SDKEngine {
                 .visibility
                 .and_then(|a| TeamVisibility::from_str(&a).ok())
                 .unwrap_or_default(),
+            archived_at: team_final_info.archived_at,
+            deleted_at: team_final_info.deleted_at,
             prefix: team_final_info.prefix,
         };
 
@@ -221,9 +230,9 @@ impl TeamCrudOperations for SDKEngine {
     async fn get_team(&self, id: Uuid) -> Result<Team, SDKError> {
         let team_info = sqlx::query!(
             r#"
-            SELECT id, created_at, updated_at, name, owner_id, visibility, prefix
+            SELECT id, created_at, updated_at, name, owner_id, visibility, prefix, archived_at, deleted_at
             FROM teams
-            WHERE id = $1
+            WHERE id = $1 AND archived_at IS NULL AND deleted_at IS NULL
             "#,
             id
         )
@@ -240,6 +249,8 @@ impl TeamCrudOperations for SDKEngine {
                 .visibility
                 .and_then(|a| TeamVisibility::from_str(&a).ok())
                 .unwrap_or_default(),
+            archived_at: team_info.archived_at,
+            deleted_at: team_info.deleted_at,
             prefix: team_info.prefix,
         };
 
@@ -247,49 +258,25 @@ impl TeamCrudOperations for SDKEngine {
     }
 
     async fn get_teams(&self, input: GetTeamsInput) -> Result<Vec<Team>, SDKError> {
-        let mut query = "SELECT * FROM teams ".to_string();
+        let mut query = SQLSelect::new("teams", TEAMS_SORTABLE_COLUMNS)
+            .filter(Some(&VisibleFilter(input.filter)))
+            .sort(input.sort_by, input.sort_order)
+            .paginate(input.limit, input.offset)
+            .build()?;
 
-        if let Some(filter) = input.filter {
-            query.push_str(format!("WHERE {} ", filter.compile_sql()).as_str());
-        }
+        let teams_info = query.build().fetch_all(self.db_pool.as_ref()).await?;
 
-        if let Some(sort_by) = input.sort_by {
-            query.push_str(format!("ORDER BY {} ", sort_by).as_str());
-        }
-
-        if let Some(sort_order) = input.sort_order {
-            query.push_str(format!("{} ", sort_order).as_str());
-        }
-
-        if let Some(limit) = input.limit {
-            query.push_str(format!("LIMIT {} ", limit).as_str());
-        }
-
-        if let Some(offset) = input.offset {
-            query.push_str(format!("OFFSET {} ", offset).as_str());
-        }
-
-        let teams_info = sqlx::query(query.as_str()).fetch_all(self.db_pool.as_ref()).await?;
-
-        let teams = teams_info
-            .iter()
-            .map(|x| Team {
-                id: x.get("id"),
-                created_at: x.get("created_at"),
-                updated_at: x.get("updated_at"),
-                name: x.get("name"),
-                owner_id: x.get("owner_id"),
-                visibility: x
-                    .get::<'_, Option<String>, _>("visibility")
-                    .and_then(|a| TeamVisibility::from_str(&a).ok())
-                    .unwrap_or_default(),
-                prefix: x.get("prefix"),
-            })
-            .collect::<Vec<Team>>();
+        let teams = teams_info.iter().map(team_from_row).collect::<Vec<Team>>();
 
         Ok(teams)
     }
 
+    async fn get_teams_page(&self, input: PageInput<GetTeamsWhere>) -> Result<Page<Team>, SDKError> {
+        SQLSelect::new("teams", TEAMS_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), VisibleFilter::page(input), team_from_row)
+            .await
+    }
+
     async fn update_team(&self, id: Uuid, input: UpdateTeamInput) -> Result<Team, SDKError> {
         let mut tx = self.db_pool.begin().await?;
 
@@ -380,6 +367,8 @@ impl TeamCrudOperations for SDKEngine {
                 .visibility
                 .and_then(|a| TeamVisibility::from_str(&a).ok())
                 .unwrap_or_default(),
+            archived_at: team_final_info.archived_at,
+            deleted_at: team_final_info.deleted_at,
             prefix: team_final_info.prefix,
         };
 
@@ -389,31 +378,19 @@ impl TeamCrudOperations for SDKEngine {
     }
 
     async fn delete_team(&self, id: Uuid) -> Result<Team, SDKError> {
-        let mut tx = self.db_pool.as_ref().begin().await?;
-
-        sqlx::query!(
-            r#"
-                DELETE FROM teams_by_projects
-                WHERE team_id = $1
-                "#,
-            id,
-        )
-        .execute(&mut *tx)
-        .await?;
-
+        // The team goes to the trash with its project links, see `restore_team`.
         let team_info = sqlx::query!(
             r#"
-            DELETE FROM teams
-            WHERE id = $1
+            UPDATE teams
+            SET deleted_at = now()
+            WHERE id = $1 AND deleted_at IS NULL
             RETURNING *
             "#,
             id
         )
-        .fetch_one(&mut *tx)
+        .fetch_one(self.db_pool.as_ref())
         .await?;
 
-        tx.commit().await?;
-
         let team = Team {
             id: team_info.id,
             created_at: team_info.created_at,
@@ -424,9 +401,28 @@ impl TeamCrudOperations for SDKEngine {
                 .visibility
                 .and_then(|a| TeamVisibility::from_str(&a).ok())
                 .unwrap_or_default(),
+            archived_at: team_info.archived_at,
+            deleted_at: team_info.deleted_at,
             prefix: team_info.prefix,
         };
 
         Ok(team)
     }
 }
+
+pub(crate) fn team_from_row(team_info: &PgRow) -> Team {
+    Team {
+        id: team_info.get("id"),
+        created_at: team_info.get("created_at"),
+        updated_at: team_info.get("updated_at"),
+        name: team_info.get("name"),
+        owner_id: team_info.get("owner_id"),
+        visibility: team_info
+            .get::<'_, Option<String>, _>("visibility")
+            .and_then(|a| TeamVisibility::from_str(&a).ok())
+            .unwrap_or_default(),
+        archived_at: team_info.get("archived_at"),
+        deleted_at: team_info.get("deleted_at"),
+        prefix: team_info.get("prefix"),
+    }
+}
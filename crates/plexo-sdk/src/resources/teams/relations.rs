@@ -42,7 +42,7 @@ impl TeamRelations for Team {
 
         let projects: &Vec<Project> = &ids
             .into_iter()
-            .map(|id| projects_map.get(&id).unwrap().clone())
+            .filter_map(|id| projects_map.get(&id).cloned())
             .collect();
 
         Ok(projects.clone())
@@ -22,6 +22,9 @@ pub struct Team {
     pub visibility: TeamVisibility,
 
     pub prefix: Option<String>,
+
+    pub archived_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 #[derive(
@@ -0,0 +1,296 @@
+use async_graphql::{Enum, InputObject, SimpleObject};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use poem_openapi::{Enum as OpenApiEnum, Object};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, Row};
+use strum_macros::Display;
+use uuid::Uuid;
+
+use crate::backend::engine::SDKEngine;
+use crate::errors::sdk::SDKError;
+use crate::resources::tasks::operations::TaskCrudOperations;
+use crate::resources::tasks::task::TaskEstimateUnit;
+
+use super::{operations::work_log_from_row, work_log::WorkLog};
+
+#[async_trait]
+pub trait WorkLogsExtensionOperations {
+    /// Starts a timer on a task, stopping the member's running timer if there is one.
+    async fn start_timer(&self, input: StartTimerInput) -> Result<WorkLog, SDKError>;
+    async fn stop_timer(&self, member_id: Uuid) -> Result<WorkLog, SDKError>;
+    async fn running_timer(&self, member_id: Uuid) -> Result<Option<WorkLog>, SDKError>;
+
+    async fn work_log_totals(&self, input: GetWorkLogTotalsInput) -> Result<Vec<WorkLogTotal>, SDKError>;
+    async fn task_time_summary(
+        &self,
+        task_id: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<TaskTimeSummary, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct StartTimerInput {
+    pub task_id: Uuid,
+
+    #[graphql(skip)]
+    pub member_id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, Deserialize, Serialize, Eq, PartialEq)]
+pub enum WorkLogGroupBy {
+    #[default]
+    Task,
+    Project,
+    Member,
+}
+
+/// Totals of logged time, grouped by task, project or member. Running timers count up to now
+/// and work logs are matched by the time they started at.
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct GetWorkLogTotalsInput {
+    #[builder(setter(strip_option), default)]
+    pub group_by: Option<WorkLogGroupBy>,
+
+    #[builder(setter(strip_option), default)]
+    pub member_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+    #[builder(setter(strip_option), default)]
+    pub task_id: Option<Uuid>,
+    /// Also counts the time logged on every subtask of `task_id`.
+    #[builder(setter(strip_option), default)]
+    pub include_subtasks: Option<bool>,
+
+    #[builder(setter(strip_option), default)]
+    pub from: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+pub struct WorkLogTotal {
+    /// The task, project or member of the group, `None` for tasks outside of a project.
+    pub group_id: Option<Uuid>,
+    /// Logged time in seconds.
+    pub duration: i64,
+    pub entries: i64,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+pub struct TaskTimeSummary {
+    pub task_id: Uuid,
+    pub estimate: Option<f64>,
+    pub estimate_unit: Option<TaskEstimateUnit>,
+
+    /// Seconds logged on the task itself.
+    pub logged: i64,
+    /// Seconds logged on the task and all of its subtasks.
+    pub subtree_logged: i64,
+}
+
+#[async_trait]
+impl WorkLogsExtensionOperations for SDKEngine {
+    async fn start_timer(&self, input: StartTimerInput) -> Result<WorkLog, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        stop_running_timer(&mut tx, input.member_id).await?;
+
+        let work_log_info = sqlx::query(
+            r#"
+            INSERT INTO work_logs (member_id, task_id, note)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+        )
+        .bind(input.member_id)
+        .bind(input.task_id)
+        .bind(input.note)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(work_log_from_row(&work_log_info))
+    }
+
+    async fn stop_timer(&self, member_id: Uuid) -> Result<WorkLog, SDKError> {
+        let mut conn = self.db_pool.acquire().await?;
+
+        stop_running_timer(&mut conn, member_id)
+            .await?
+            .ok_or(SDKError::TimerNotRunning)
+    }
+
+    async fn running_timer(&self, member_id: Uuid) -> Result<Option<WorkLog>, SDKError> {
+        let work_log_info = sqlx::query(
+            r#"
+            SELECT * FROM work_logs
+            WHERE member_id = $1 AND duration IS NULL
+            "#,
+        )
+        .bind(member_id)
+        .fetch_optional(self.db_pool.as_ref())
+        .await?;
+
+        Ok(work_log_info.as_ref().map(work_log_from_row))
+    }
+
+    async fn work_log_totals(&self, input: GetWorkLogTotalsInput) -> Result<Vec<WorkLogTotal>, SDKError> {
+        let group_column = match input.group_by.unwrap_or_default() {
+            WorkLogGroupBy::Task => "work_logs.task_id",
+            WorkLogGroupBy::Project => "tasks.project_id",
+            WorkLogGroupBy::Member => "work_logs.member_id",
+        };
+
+        let totals_info = sqlx::query(&format!(
+            r#"
+            WITH RECURSIVE scope AS (
+                SELECT tasks.id, ARRAY[tasks.id] AS path FROM tasks
+                WHERE tasks.id = $3
+                UNION ALL
+                SELECT tasks.id, scope.path || tasks.id FROM tasks
+                JOIN scope ON tasks.parent_id = scope.id
+                WHERE $4 AND NOT tasks.id = ANY(scope.path)
+            )
+            SELECT
+                {group_column} AS group_id,
+                SUM(COALESCE(work_logs.duration, GREATEST(EXTRACT(EPOCH FROM now() - work_logs.started_at), 0)::integer))::bigint AS duration,
+                COUNT(*) AS entries
+            FROM work_logs
+            JOIN tasks ON tasks.id = work_logs.task_id
+            WHERE ($1::uuid IS NULL OR work_logs.member_id = $1)
+                AND ($2::uuid IS NULL OR tasks.project_id = $2)
+                AND ($3::uuid IS NULL OR work_logs.task_id IN (SELECT id FROM scope))
+                AND ($5::timestamptz IS NULL OR work_logs.started_at >= $5)
+                AND ($6::timestamptz IS NULL OR work_logs.started_at < $6)
+            GROUP BY 1
+            ORDER BY duration DESC, group_id
+            "#,
+        ))
+        .bind(input.member_id)
+        .bind(input.project_id)
+        .bind(input.task_id)
+        .bind(input.include_subtasks.unwrap_or(false))
+        .bind(input.from)
+        .bind(input.to)
+        .fetch_all(self.db_pool.as_ref())
+        .await?;
+
+        Ok(totals_info
+            .iter()
+            .map(|total_info| WorkLogTotal {
+                group_id: total_info.get("group_id"),
+                duration: total_info.get("duration"),
+                entries: total_info.get("entries"),
+            })
+            .collect())
+    }
+
+    async fn task_time_summary(
+        &self,
+        task_id: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<TaskTimeSummary, SDKError> {
+        let task = self.get_task(task_id).await?;
+
+        let totals = self
+            .work_log_totals(GetWorkLogTotalsInput {
+                group_by: Some(WorkLogGroupBy::Task),
+                task_id: Some(task_id),
+                include_subtasks: Some(true),
+                from,
+                to,
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(TaskTimeSummary {
+            task_id,
+            estimate: task.estimate,
+            estimate_unit: task.estimate_unit,
+            logged: totals
+                .iter()
+                .find(|total| total.group_id == Some(task_id))
+                .map_or(0, |total| total.duration),
+            subtree_logged: totals.iter().map(|total| total.duration).sum(),
+        })
+    }
+}
+
+async fn stop_running_timer(conn: &mut PgConnection, member_id: Uuid) -> Result<Option<WorkLog>, SDKError> {
+    let work_log_info = sqlx::query(
+        r#"
+        UPDATE work_logs
+        SET duration = GREATEST(EXTRACT(EPOCH FROM now() - started_at), 0)::integer, updated_at = now()
+        WHERE member_id = $1 AND duration IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(member_id)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(work_log_info.as_ref().map(work_log_from_row))
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::backend::testing::{test_engine, test_member, test_task};
+    use crate::resources::work_logs::operations::WorkLogCrudOperations;
+
+    fn timer_input(task_id: Uuid, member_id: Uuid) -> StartTimerInput {
+        StartTimerInput {
+            task_id,
+            member_id,
+            note: None,
+        }
+    }
+
+    #[sqlx::test]
+    async fn members_run_one_timer_at_a_time(pool: PgPool) {
+        let engine = test_engine(pool);
+        let member_id = test_member(&engine).await;
+        let first = test_task(&engine, member_id, None, "First").await.id;
+        let second = test_task(&engine, member_id, None, "Second").await.id;
+
+        let first_log = engine.start_timer(timer_input(first, member_id)).await.unwrap();
+        let second_log = engine.start_timer(timer_input(second, member_id)).await.unwrap();
+
+        let running = engine.running_timer(member_id).await.unwrap().unwrap();
+        assert_eq!(running.id, second_log.id);
+
+        let first_log = engine.get_work_log(first_log.id).await.unwrap();
+        assert!(first_log.duration.is_some());
+
+        // The index backs the check up for writers that bypass `start_timer`.
+        let err = sqlx::query("INSERT INTO work_logs (member_id, task_id) VALUES ($1, $2)")
+            .bind(member_id)
+            .bind(first)
+            .execute(engine.db_pool.as_ref())
+            .await
+            .unwrap_err();
+        assert!(err.as_database_error().unwrap().is_unique_violation());
+
+        let stopped = engine.stop_timer(member_id).await.unwrap();
+        assert_eq!(stopped.id, second_log.id);
+        assert!(stopped.duration.is_some());
+
+        assert!(engine.running_timer(member_id).await.unwrap().is_none());
+        assert!(matches!(
+            engine.stop_timer(member_id).await.unwrap_err(),
+            SDKError::TimerNotRunning
+        ));
+    }
+}
@@ -0,0 +1,273 @@
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, Row};
+use uuid::Uuid;
+
+use crate::common::filters::{
+    DateTimeComparison, SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect, StringComparison, UuidComparison,
+};
+use crate::common::pagination::{Page, PageInput};
+use crate::{backend::engine::SDKEngine, common::commons::SortOrder, errors::sdk::SDKError};
+
+use super::work_log::WorkLog;
+
+const WORK_LOGS_SORTABLE_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "started_at", "duration"];
+
+#[async_trait]
+pub trait WorkLogCrudOperations {
+    async fn create_work_log(&self, input: CreateWorkLogInput) -> Result<WorkLog, SDKError>;
+    async fn get_work_log(&self, id: Uuid) -> Result<WorkLog, SDKError>;
+    async fn get_work_logs(&self, input: GetWorkLogsInput) -> Result<Vec<WorkLog>, SDKError>;
+    async fn get_work_logs_page(&self, input: PageInput<GetWorkLogsWhere>) -> Result<Page<WorkLog>, SDKError>;
+    async fn update_work_log(&self, id: Uuid, input: UpdateWorkLogInput) -> Result<WorkLog, SDKError>;
+    async fn delete_work_log(&self, id: Uuid) -> Result<WorkLog, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateWorkLogInput {
+    pub task_id: Uuid,
+    /// Logged time in seconds.
+    pub duration: i32,
+
+    #[graphql(skip)]
+    pub member_id: Uuid,
+
+    /// Defaults to the current time.
+    #[builder(setter(strip_option), default)]
+    pub started_at: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub note: Option<String>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateWorkLogInput {
+    #[builder(setter(strip_option), default)]
+    pub started_at: Option<DateTime<Utc>>,
+    #[builder(setter(strip_option), default)]
+    pub duration: Option<i32>,
+    #[builder(setter(strip_option), default)]
+    pub note: Option<String>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetWorkLogsInput {
+    #[builder(setter(strip_option), default)]
+    pub filter: Option<GetWorkLogsWhere>,
+
+    #[builder(setter(strip_option), default)]
+    pub sort_by: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub sort_order: Option<SortOrder>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetWorkLogsWhere {
+    #[builder(setter(strip_option), default)]
+    pub ids: Option<Vec<Uuid>>,
+    #[builder(setter(into, strip_option), default)]
+    pub member_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub task_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub started_at: Option<DateTimeComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub note: Option<StringComparison>,
+
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _and: Option<Vec<GetWorkLogsWhere>>,
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _or: Option<Vec<GetWorkLogsWhere>>,
+}
+
+impl SQLFilter for GetWorkLogsWhere {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
+
+        if let Some(ids) = &self.ids {
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
+        }
+
+        if let Some(member_id) = &self.member_id {
+            conditions.push(member_id.compile_sql("member_id"));
+        }
+
+        if let Some(task_id) = &self.task_id {
+            conditions.push(task_id.compile_sql("task_id"));
+        }
+
+        if let Some(started_at) = &self.started_at {
+            conditions.push(started_at.compile_sql("started_at"));
+        }
+
+        if let Some(note) = &self.note {
+            conditions.push(note.compile_sql("note"));
+        }
+
+        if let Some(ands) = &self._and {
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
+        }
+
+        if let Some(ors) = &self._or {
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
+        }
+
+        SQLCondition::And(conditions)
+    }
+}
+
+#[async_trait]
+impl WorkLogCrudOperations for SDKEngine {
+    async fn create_work_log(&self, input: CreateWorkLogInput) -> Result<WorkLog, SDKError> {
+        let work_log_info = sqlx::query!(
+            r#"
+            INSERT INTO work_logs (member_id, task_id, started_at, duration, note)
+            VALUES ($1, $2, COALESCE($3, now()), $4, $5)
+            RETURNING *
+            "#,
+            input.member_id,
+            input.task_id,
+            input.started_at,
+            input.duration,
+            input.note,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(WorkLog {
+            id: work_log_info.id,
+            created_at: work_log_info.created_at,
+            updated_at: work_log_info.updated_at,
+            member_id: work_log_info.member_id,
+            task_id: work_log_info.task_id,
+            started_at: work_log_info.started_at,
+            duration: work_log_info.duration,
+            note: work_log_info.note,
+        })
+    }
+
+    async fn get_work_log(&self, id: Uuid) -> Result<WorkLog, SDKError> {
+        let work_log_info = sqlx::query!(
+            r#"
+            SELECT * FROM work_logs
+            WHERE id = $1
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(WorkLog {
+            id: work_log_info.id,
+            created_at: work_log_info.created_at,
+            updated_at: work_log_info.updated_at,
+            member_id: work_log_info.member_id,
+            task_id: work_log_info.task_id,
+            started_at: work_log_info.started_at,
+            duration: work_log_info.duration,
+            note: work_log_info.note,
+        })
+    }
+
+    async fn get_work_logs(&self, input: GetWorkLogsInput) -> Result<Vec<WorkLog>, SDKError> {
+        let mut query = SQLSelect::new("work_logs", WORK_LOGS_SORTABLE_COLUMNS)
+            .filter(input.filter.as_ref())
+            .sort(input.sort_by, input.sort_order)
+            .paginate(input.limit, input.offset)
+            .build()?;
+
+        let work_logs_info = query.build().fetch_all(self.db_pool.as_ref()).await?;
+
+        let work_logs = work_logs_info.iter().map(work_log_from_row).collect();
+
+        Ok(work_logs)
+    }
+
+    async fn get_work_logs_page(&self, input: PageInput<GetWorkLogsWhere>) -> Result<Page<WorkLog>, SDKError> {
+        SQLSelect::new("work_logs", WORK_LOGS_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), input, work_log_from_row)
+            .await
+    }
+
+    async fn update_work_log(&self, id: Uuid, input: UpdateWorkLogInput) -> Result<WorkLog, SDKError> {
+        let work_log_info = sqlx::query!(
+            r#"
+            UPDATE work_logs
+            SET
+                started_at = COALESCE($1, started_at),
+                duration = COALESCE($2, duration),
+                note = COALESCE($3, note),
+                updated_at = now()
+            WHERE id = $4
+            RETURNING *
+            "#,
+            input.started_at,
+            input.duration,
+            input.note,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(WorkLog {
+            id: work_log_info.id,
+            created_at: work_log_info.created_at,
+            updated_at: work_log_info.updated_at,
+            member_id: work_log_info.member_id,
+            task_id: work_log_info.task_id,
+            started_at: work_log_info.started_at,
+            duration: work_log_info.duration,
+            note: work_log_info.note,
+        })
+    }
+
+    async fn delete_work_log(&self, id: Uuid) -> Result<WorkLog, SDKError> {
+        let work_log_info = sqlx::query!(
+            r#"
+            DELETE FROM work_logs WHERE id = $1
+            RETURNING *
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(WorkLog {
+            id: work_log_info.id,
+            created_at: work_log_info.created_at,
+            updated_at: work_log_info.updated_at,
+            member_id: work_log_info.member_id,
+            task_id: work_log_info.task_id,
+            started_at: work_log_info.started_at,
+            duration: work_log_info.duration,
+            note: work_log_info.note,
+        })
+    }
+}
+
+pub(crate) fn work_log_from_row(work_log_info: &PgRow) -> WorkLog {
+    WorkLog {
+        id: work_log_info.get("id"),
+        created_at: work_log_info.get("created_at"),
+        updated_at: work_log_info.get("updated_at"),
+        member_id: work_log_info.get("member_id"),
+        task_id: work_log_info.get("task_id"),
+        started_at: work_log_info.get("started_at"),
+        duration: work_log_info.get("duration"),
+        note: work_log_info.get("note"),
+    }
+}
@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+
+use crate::{
+    backend::loaders::SDKLoaders,
+    errors::sdk::SDKError,
+    resources::{members::member::Member, tasks::task::Task},
+};
+
+use super::work_log::WorkLog;
+
+#[async_trait]
+pub trait WorkLogRelations {
+    async fn member(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
+    async fn task(&self, loaders: &SDKLoaders) -> Result<Task, SDKError>;
+}
+
+#[async_trait]
+impl WorkLogRelations for WorkLog {
+    async fn member(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
+        let data = loaders.member_loader.load_one(self.member_id).await.unwrap().unwrap();
+
+        Ok(data)
+    }
+
+    async fn task(&self, loaders: &SDKLoaders) -> Result<Task, SDKError> {
+        let data = loaders
+            .task_loader
+            .load_one(self.task_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::SQLXError(sqlx::Error::RowNotFound))?;
+
+        Ok(data)
+    }
+}
@@ -0,0 +1,23 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use uuid::Uuid;
+
+use serde::Serialize;
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKWorkLog")]
+pub struct WorkLog {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub member_id: Uuid,
+    pub task_id: Uuid,
+    pub started_at: DateTime<Utc>,
+
+    /// Logged time in seconds, `None` while the work log is a running timer.
+    pub duration: Option<i32>,
+    pub note: Option<String>,
+}
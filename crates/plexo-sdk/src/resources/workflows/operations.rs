@@ -0,0 +1,822 @@
+use std::str::FromStr;
+
+use async_graphql::InputObject;
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::Object;
+use serde::Serialize;
+use sqlx::{postgres::PgRow, PgConnection, Row};
+use uuid::Uuid;
+
+use crate::common::filters::{SQLCondition, SQLFieldFilter, SQLFilter, SQLSelect, StringComparison, UuidComparison};
+use crate::common::pagination::{Page, PageInput};
+use crate::resources::tasks::{operations::UpdateTaskInput, task::TaskStatus};
+use crate::{backend::engine::SDKEngine, common::commons::SortOrder, errors::sdk::SDKError};
+
+use super::workflow::{Workflow, WorkflowStatus, WorkflowStatusCategory};
+
+const WORKFLOWS_SORTABLE_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "name"];
+
+#[async_trait]
+pub trait WorkflowCrudOperations {
+    async fn create_workflow(&self, input: CreateWorkflowInput) -> Result<Workflow, SDKError>;
+    async fn get_workflow(&self, id: Uuid) -> Result<Workflow, SDKError>;
+    async fn get_workflows(&self, input: GetWorkflowsInput) -> Result<Vec<Workflow>, SDKError>;
+    async fn get_workflows_page(&self, input: PageInput<GetWorkflowsWhere>) -> Result<Page<Workflow>, SDKError>;
+    async fn update_workflow(&self, id: Uuid, input: UpdateWorkflowInput) -> Result<Workflow, SDKError>;
+    async fn delete_workflow(&self, id: Uuid) -> Result<Workflow, SDKError>;
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateWorkflowInput {
+    pub name: String,
+    pub project_id: Uuid,
+
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+
+    pub statuses: Vec<CreateWorkflowStatusInput>,
+    /// Allowed moves between statuses. A workflow without transitions allows every move.
+    #[builder(setter(strip_option), default)]
+    pub transitions: Option<Vec<WorkflowTransitionInput>>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct CreateWorkflowStatusInput {
+    pub name: String,
+    pub category: WorkflowStatusCategory,
+
+    /// Defaults to the order in which the statuses are given.
+    #[builder(setter(strip_option), default)]
+    pub position: Option<i32>,
+    #[builder(setter(strip_option), default)]
+    pub color: Option<String>,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateWorkflowStatusInput {
+    pub id: Uuid,
+
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub category: Option<WorkflowStatusCategory>,
+    #[builder(setter(strip_option), default)]
+    pub position: Option<i32>,
+    #[builder(setter(strip_option), default)]
+    pub color: Option<String>,
+}
+
+/// A transition between two statuses of the same workflow, referenced by name.
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct WorkflowTransitionInput {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Clone, Default, Builder, Object, InputObject, Serialize)]
+#[builder(pattern = "owned")]
+pub struct UpdateWorkflowInput {
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+
+    #[builder(setter(strip_option), default)]
+    pub add_statuses: Option<Vec<CreateWorkflowStatusInput>>,
+    #[builder(setter(strip_option), default)]
+    pub update_statuses: Option<Vec<UpdateWorkflowStatusInput>>,
+    /// Tasks in a removed status move to the first remaining status of the category of their
+    /// task status, or to the first status of the workflow.
+    #[builder(setter(strip_option), default)]
+    pub remove_statuses: Option<Vec<Uuid>>,
+
+    /// Replaces every transition of the workflow.
+    #[builder(setter(strip_option), default)]
+    pub transitions: Option<Vec<WorkflowTransitionInput>>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetWorkflowsInput {
+    #[builder(setter(strip_option), default)]
+    pub filter: Option<GetWorkflowsWhere>,
+
+    #[builder(setter(strip_option), default)]
+    pub sort_by: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub sort_order: Option<SortOrder>,
+
+    #[builder(setter(into, strip_option), default = "Some(100)")]
+    pub limit: Option<i32>,
+    #[builder(setter(into, strip_option), default = "Some(0)")]
+    pub offset: Option<i32>,
+}
+
+#[derive(Default, Builder, Object, InputObject)]
+#[builder(pattern = "owned")]
+pub struct GetWorkflowsWhere {
+    #[builder(setter(strip_option), default)]
+    pub ids: Option<Vec<Uuid>>,
+    #[builder(setter(into, strip_option), default)]
+    pub name: Option<StringComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub owner_id: Option<UuidComparison>,
+    #[builder(setter(into, strip_option), default)]
+    pub project_id: Option<UuidComparison>,
+
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _and: Option<Vec<GetWorkflowsWhere>>,
+    #[oai(skip)]
+    #[builder(setter(strip_option), default)]
+    pub _or: Option<Vec<GetWorkflowsWhere>>,
+}
+
+impl SQLFilter for GetWorkflowsWhere {
+    fn compile_sql(&self) -> SQLCondition {
+        let mut conditions = Vec::new();
+
+        if let Some(ids) = &self.ids {
+            conditions.push(SQLCondition::any_of("id", ids.clone()));
+        }
+
+        if let Some(name) = &self.name {
+            conditions.push(name.compile_sql("name"));
+        }
+
+        if let Some(owner_id) = &self.owner_id {
+            conditions.push(owner_id.compile_sql("owner_id"));
+        }
+
+        if let Some(project_id) = &self.project_id {
+            conditions.push(project_id.compile_sql("project_id"));
+        }
+
+        if let Some(ands) = &self._and {
+            conditions.push(SQLCondition::And(ands.iter().map(|and| and.compile_sql()).collect()));
+        }
+
+        if let Some(ors) = &self._or {
+            conditions.push(SQLCondition::Or(ors.iter().map(|or| or.compile_sql()).collect()));
+        }
+
+        SQLCondition::And(conditions)
+    }
+}
+
+#[async_trait]
+impl WorkflowCrudOperations for SDKEngine {
+    async fn create_workflow(&self, input: CreateWorkflowInput) -> Result<Workflow, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let workflow_info = sqlx::query!(
+            r#"
+            INSERT INTO workflows (name, owner_id, project_id)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+            input.name,
+            input.owner_id,
+            input.project_id,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        insert_workflow_statuses(&mut tx, workflow_info.id, input.statuses).await?;
+        assign_workflow_statuses(&mut tx, workflow_info.project_id).await?;
+
+        if let Some(transitions) = input.transitions {
+            replace_workflow_transitions(&mut tx, workflow_info.id, transitions).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(Workflow {
+            id: workflow_info.id,
+            created_at: workflow_info.created_at,
+            updated_at: workflow_info.updated_at,
+            name: workflow_info.name,
+            owner_id: workflow_info.owner_id,
+            project_id: workflow_info.project_id,
+        })
+    }
+
+    async fn get_workflow(&self, id: Uuid) -> Result<Workflow, SDKError> {
+        let workflow_info = sqlx::query!(
+            r#"
+            SELECT * FROM workflows
+            WHERE id = $1
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(Workflow {
+            id: workflow_info.id,
+            created_at: workflow_info.created_at,
+            updated_at: workflow_info.updated_at,
+            name: workflow_info.name,
+            owner_id: workflow_info.owner_id,
+            project_id: workflow_info.project_id,
+        })
+    }
+
+    async fn get_workflows(&self, input: GetWorkflowsInput) -> Result<Vec<Workflow>, SDKError> {
+        let mut query = SQLSelect::new("workflows", WORKFLOWS_SORTABLE_COLUMNS)
+            .filter(input.filter.as_ref())
+            .sort(input.sort_by, input.sort_order)
+            .paginate(input.limit, input.offset)
+            .build()?;
+
+        let workflows_info = query.build().fetch_all(self.db_pool.as_ref()).await?;
+
+        let workflows = workflows_info.iter().map(workflow_from_row).collect();
+
+        Ok(workflows)
+    }
+
+    async fn get_workflows_page(&self, input: PageInput<GetWorkflowsWhere>) -> Result<Page<Workflow>, SDKError> {
+        SQLSelect::new("workflows", WORKFLOWS_SORTABLE_COLUMNS)
+            .fetch_page(self.db_pool.as_ref(), input, workflow_from_row)
+            .await
+    }
+
+    async fn update_workflow(&self, id: Uuid, input: UpdateWorkflowInput) -> Result<Workflow, SDKError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let workflow_info = sqlx::query!(
+            r#"
+            UPDATE workflows
+            SET
+                name = COALESCE($1, name),
+                updated_at = now()
+            WHERE id = $2
+            RETURNING *
+            "#,
+            input.name,
+            id,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let Some(statuses) = input.remove_statuses {
+            sqlx::query!(
+                r#"
+                DELETE FROM workflow_statuses WHERE workflow_id = $1 AND id = ANY($2)
+                "#,
+                id,
+                &statuses,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        if let Some(statuses) = input.update_statuses {
+            for status in statuses {
+                let status_info = sqlx::query!(
+                    r#"
+                    UPDATE workflow_statuses
+                    SET
+                        name = COALESCE($1, workflow_statuses.name),
+                        category = COALESCE($2, workflow_statuses.category),
+                        position = COALESCE($3, workflow_statuses.position),
+                        color = COALESCE($4, workflow_statuses.color),
+                        updated_at = now()
+                    FROM (SELECT id, category FROM workflow_statuses WHERE id = $5) AS previous
+                    WHERE workflow_statuses.id = previous.id AND workflow_statuses.workflow_id = $6
+                    RETURNING workflow_statuses.category, previous.category AS previous_category
+                    "#,
+                    status.name,
+                    status.category.map(|category| category.to_string()),
+                    status.position,
+                    status.color,
+                    status.id,
+                    id,
+                )
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or(SDKError::InvalidWorkflowStatus(status.id.to_string()))?;
+
+                // Tasks keep a task status of the category their workflow status is in.
+                if status_info.category != status_info.previous_category {
+                    let category = WorkflowStatusCategory::from_str(&status_info.category).unwrap_or_default();
+
+                    sqlx::query!(
+                        r#"
+                        UPDATE tasks SET status = $1 WHERE workflow_status_id = $2
+                        "#,
+                        category.task_status().to_string(),
+                        status.id,
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+                }
+            }
+        }
+
+        if let Some(statuses) = input.add_statuses {
+            insert_workflow_statuses(&mut tx, id, statuses).await?;
+        }
+
+        assign_workflow_statuses(&mut tx, workflow_info.project_id).await?;
+
+        if let Some(transitions) = input.transitions {
+            replace_workflow_transitions(&mut tx, id, transitions).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(Workflow {
+            id: workflow_info.id,
+            created_at: workflow_info.created_at,
+            updated_at: workflow_info.updated_at,
+            name: workflow_info.name,
+            owner_id: workflow_info.owner_id,
+            project_id: workflow_info.project_id,
+        })
+    }
+
+    async fn delete_workflow(&self, id: Uuid) -> Result<Workflow, SDKError> {
+        let workflow_info = sqlx::query!(
+            r#"
+            DELETE FROM workflows WHERE id = $1
+            RETURNING *
+            "#,
+            id,
+        )
+        .fetch_one(self.db_pool.as_ref())
+        .await?;
+
+        Ok(Workflow {
+            id: workflow_info.id,
+            created_at: workflow_info.created_at,
+            updated_at: workflow_info.updated_at,
+            name: workflow_info.name,
+            owner_id: workflow_info.owner_id,
+            project_id: workflow_info.project_id,
+        })
+    }
+}
+
+/// The workflow status and task status to store for a task, `status` is `None` when it must not change.
+pub(crate) struct TaskWorkflowState {
+    pub workflow_status_id: Option<Uuid>,
+    pub status: Option<TaskStatus>,
+}
+
+/// Picks the starting workflow status of a new task: the first status in the category of the
+/// requested task status, or the first status of the workflow.
+pub(crate) async fn initial_workflow_state(
+    conn: &mut PgConnection,
+    project_id: Option<Uuid>,
+    status: Option<TaskStatus>,
+) -> Result<TaskWorkflowState, SDKError> {
+    let statuses = project_workflow_statuses(conn, project_id).await?;
+    let status = status.unwrap_or_default();
+
+    let workflow_status = first_in_category(&statuses, status.into()).or(statuses.first());
+
+    Ok(TaskWorkflowState {
+        workflow_status_id: workflow_status.map(|workflow_status| workflow_status.id),
+        status: Some(workflow_task_status(status, workflow_status)),
+    })
+}
+
+/// Resolves the workflow status a task update leads to and checks the move is an allowed
+/// transition. Plain `status` updates are mapped to the first workflow status of their category,
+/// and tasks moved into another project are mapped by category without transition checks.
+pub(crate) async fn resolve_workflow_state(
+    conn: &mut PgConnection,
+    task_id: Uuid,
+    input: &UpdateTaskInput,
+) -> Result<TaskWorkflowState, SDKError> {
+    let task_info = sqlx::query!(
+        r#"
+        SELECT project_id, status, workflow_status_id FROM tasks
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        task_id,
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let project_id = match input.project_id {
+        Some(project_id) if project_id.is_nil() => None,
+        Some(project_id) => Some(project_id),
+        None => task_info.project_id,
+    };
+
+    let statuses = project_workflow_statuses(conn, project_id).await?;
+
+    if statuses.is_empty() {
+        if let Some(workflow_status_id) = input.workflow_status_id {
+            return Err(SDKError::InvalidWorkflowStatus(workflow_status_id.to_string()));
+        }
+
+        return Ok(TaskWorkflowState {
+            workflow_status_id: None,
+            status: input.status,
+        });
+    }
+
+    let current_status = task_info
+        .status
+        .and_then(|status| TaskStatus::from_str(&status).ok())
+        .unwrap_or_default();
+    // Tasks without a workflow status of their own are checked from the status their task status
+    // maps to. Moves into another project start over in its workflow.
+    let current = match project_id == task_info.project_id {
+        true => statuses
+            .iter()
+            .find(|workflow_status| Some(workflow_status.id) == task_info.workflow_status_id)
+            .or_else(|| first_in_category(&statuses, current_status.into()).or(statuses.first())),
+        false => None,
+    };
+
+    let target = match (input.workflow_status_id, input.status, current) {
+        (Some(workflow_status_id), _, _) => statuses
+            .iter()
+            .find(|workflow_status| workflow_status.id == workflow_status_id)
+            .ok_or(SDKError::InvalidWorkflowStatus(workflow_status_id.to_string()))?,
+        (None, Some(status), Some(current)) if current.category == status.into() => current,
+        (None, Some(status), _) => {
+            first_in_category(&statuses, status.into()).ok_or(SDKError::InvalidWorkflowStatus(status.to_string()))?
+        }
+        (None, None, Some(current)) => current,
+        (None, None, None) => first_in_category(&statuses, current_status.into())
+            .or(statuses.first())
+            .unwrap(),
+    };
+
+    if let Some(current) = current {
+        if current.id != target.id && !is_transition_allowed(conn, current, target).await? {
+            return Err(SDKError::WorkflowTransitionNotAllowed(
+                current.name.clone(),
+                target.name.clone(),
+            ));
+        }
+    }
+
+    let status = input.status.unwrap_or(current_status);
+
+    Ok(TaskWorkflowState {
+        workflow_status_id: Some(target.id),
+        status: match WorkflowStatusCategory::from(status) == target.category {
+            true => input.status,
+            false => Some(target.category.task_status()),
+        },
+    })
+}
+
+/// Keeps the given task status when it belongs to the workflow status category, so finer
+/// statuses like `Backlog` survive inside an unstarted workflow status.
+fn workflow_task_status(status: TaskStatus, workflow_status: Option<&WorkflowStatus>) -> TaskStatus {
+    match workflow_status {
+        Some(workflow_status) if WorkflowStatusCategory::from(status) != workflow_status.category => {
+            workflow_status.category.task_status()
+        }
+        _ => status,
+    }
+}
+
+/// Gives the tasks of a project without a workflow status the first status in the category of
+/// their task status, or the first status of the workflow, so its transitions apply to them.
+async fn assign_workflow_statuses(conn: &mut PgConnection, project_id: Uuid) -> Result<(), SDKError> {
+    let statuses = project_workflow_statuses(conn, Some(project_id)).await?;
+
+    let task_statuses = sqlx::query!(
+        r#"
+        SELECT DISTINCT status FROM tasks
+        WHERE project_id = $1 AND workflow_status_id IS NULL
+        "#,
+        project_id,
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for task_status in task_statuses {
+        let status = task_status
+            .status
+            .as_deref()
+            .and_then(|status| TaskStatus::from_str(status).ok())
+            .unwrap_or_default();
+
+        let Some(workflow_status) = first_in_category(&statuses, status.into()).or(statuses.first()) else {
+            return Ok(());
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE tasks SET workflow_status_id = $1, status = $2
+            WHERE project_id = $3 AND workflow_status_id IS NULL AND status IS NOT DISTINCT FROM $4
+            "#,
+            workflow_status.id,
+            workflow_task_status(status, Some(workflow_status)).to_string(),
+            project_id,
+            task_status.status,
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+fn first_in_category(statuses: &[WorkflowStatus], category: WorkflowStatusCategory) -> Option<&WorkflowStatus> {
+    statuses
+        .iter()
+        .find(|workflow_status| workflow_status.category == category)
+}
+
+async fn project_workflow_statuses(
+    conn: &mut PgConnection,
+    project_id: Option<Uuid>,
+) -> Result<Vec<WorkflowStatus>, SDKError> {
+    let Some(project_id) = project_id else {
+        return Ok(vec![]);
+    };
+
+    let statuses_info = sqlx::query(
+        r#"
+        SELECT workflow_statuses.* FROM workflow_statuses
+        JOIN workflows ON workflows.id = workflow_statuses.workflow_id
+        WHERE workflows.project_id = $1
+        ORDER BY workflow_statuses.position, workflow_statuses.created_at
+        "#,
+    )
+    .bind(project_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(statuses_info.iter().map(workflow_status_from_row).collect())
+}
+
+async fn is_transition_allowed(
+    conn: &mut PgConnection,
+    from: &WorkflowStatus,
+    to: &WorkflowStatus,
+) -> Result<bool, SDKError> {
+    let allowed = sqlx::query!(
+        r#"
+        SELECT (
+            NOT EXISTS (SELECT 1 FROM workflow_transitions WHERE workflow_id = $1)
+            OR EXISTS (SELECT 1 FROM workflow_transitions WHERE from_status_id = $2 AND to_status_id = $3)
+        ) AS "allowed!"
+        "#,
+        from.workflow_id,
+        from.id,
+        to.id,
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .allowed;
+
+    Ok(allowed)
+}
+
+async fn insert_workflow_statuses(
+    conn: &mut PgConnection,
+    workflow_id: Uuid,
+    statuses: Vec<CreateWorkflowStatusInput>,
+) -> Result<(), SDKError> {
+    let next_position = sqlx::query!(
+        r#"
+        SELECT COALESCE(MAX(position) + 1, 0) AS "position!" FROM workflow_statuses
+        WHERE workflow_id = $1
+        "#,
+        workflow_id,
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .position;
+
+    for (index, status) in statuses.into_iter().enumerate() {
+        sqlx::query!(
+            r#"
+            INSERT INTO workflow_statuses (workflow_id, name, category, position, color)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            workflow_id,
+            status.name,
+            status.category.to_string(),
+            status.position.unwrap_or(next_position + index as i32),
+            status.color,
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn replace_workflow_transitions(
+    conn: &mut PgConnection,
+    workflow_id: Uuid,
+    transitions: Vec<WorkflowTransitionInput>,
+) -> Result<(), SDKError> {
+    sqlx::query!(
+        r#"
+        DELETE FROM workflow_transitions WHERE workflow_id = $1
+        "#,
+        workflow_id,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    for transition in transitions {
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO workflow_transitions (workflow_id, from_status_id, to_status_id)
+            SELECT $1, from_status.id, to_status.id
+            FROM workflow_statuses AS from_status, workflow_statuses AS to_status
+            WHERE from_status.workflow_id = $1 AND from_status.name = $2
+              AND to_status.workflow_id = $1 AND to_status.name = $3
+            ON CONFLICT DO NOTHING
+            "#,
+            workflow_id,
+            transition.from,
+            transition.to,
+        )
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+        if inserted == 0 {
+            return Err(SDKError::InvalidWorkflowTransition(transition.from, transition.to));
+        }
+    }
+
+    Ok(())
+}
+
+fn workflow_from_row(workflow_info: &PgRow) -> Workflow {
+    Workflow {
+        id: workflow_info.get("id"),
+        created_at: workflow_info.get("created_at"),
+        updated_at: workflow_info.get("updated_at"),
+        name: workflow_info.get("name"),
+        owner_id: workflow_info.get("owner_id"),
+        project_id: workflow_info.get("project_id"),
+    }
+}
+
+pub(crate) fn workflow_status_from_row(workflow_status_info: &PgRow) -> WorkflowStatus {
+    WorkflowStatus {
+        id: workflow_status_info.get("id"),
+        created_at: workflow_status_info.get("created_at"),
+        updated_at: workflow_status_info.get("updated_at"),
+        workflow_id: workflow_status_info.get("workflow_id"),
+        name: workflow_status_info.get("name"),
+        category: WorkflowStatusCategory::from_str(workflow_status_info.get("category")).unwrap_or_default(),
+        position: workflow_status_info.get("position"),
+        color: workflow_status_info.get("color"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::backend::testing::{test_engine, test_member, test_project, test_task};
+    use crate::resources::tasks::operations::TaskCrudOperations;
+
+    fn status_input(name: &str, category: WorkflowStatusCategory) -> CreateWorkflowStatusInput {
+        CreateWorkflowStatusInput {
+            name: name.to_string(),
+            category,
+            ..Default::default()
+        }
+    }
+
+    fn transition_input(from: &str, to: &str) -> WorkflowTransitionInput {
+        WorkflowTransitionInput {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    async fn review_workflow(engine: &SDKEngine, owner_id: Uuid, project_id: Uuid) -> Workflow {
+        engine
+            .create_workflow(CreateWorkflowInput {
+                name: "Review".to_string(),
+                project_id,
+                owner_id,
+                statuses: vec![
+                    status_input("Todo", WorkflowStatusCategory::Unstarted),
+                    status_input("In Progress", WorkflowStatusCategory::Started),
+                    status_input("In Review", WorkflowStatusCategory::Started),
+                    status_input("Done", WorkflowStatusCategory::Completed),
+                ],
+                transitions: Some(vec![
+                    transition_input("Todo", "In Progress"),
+                    transition_input("In Progress", "In Review"),
+                    transition_input("In Review", "Done"),
+                ]),
+            })
+            .await
+            .unwrap()
+    }
+
+    fn status_update(status: TaskStatus) -> UpdateTaskInput {
+        UpdateTaskInput {
+            status: Some(status),
+            ..Default::default()
+        }
+    }
+
+    #[sqlx::test]
+    async fn transitions_apply_to_tasks_from_before_the_workflow(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+        let project_id = test_project(&engine, owner_id, "WFL").await;
+        let task = test_task(&engine, owner_id, Some(project_id), "Older than the workflow").await;
+
+        review_workflow(&engine, owner_id, project_id).await;
+
+        let task = engine.get_task(task.id).await.unwrap();
+        assert!(task.workflow_status_id.is_some());
+
+        let err = engine
+            .update_task(task.id, status_update(TaskStatus::Done))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SDKError::WorkflowTransitionNotAllowed(..)));
+
+        let task = engine
+            .update_task(task.id, status_update(TaskStatus::InProgress))
+            .await
+            .unwrap();
+        assert_eq!(task.status, TaskStatus::InProgress);
+    }
+
+    #[sqlx::test]
+    async fn tasks_of_removed_statuses_stay_under_the_workflow(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+        let project_id = test_project(&engine, owner_id, "WFR").await;
+        let workflow = review_workflow(&engine, owner_id, project_id).await;
+        let task = test_task(&engine, owner_id, Some(project_id), "Started").await;
+        let task = engine
+            .update_task(task.id, status_update(TaskStatus::InProgress))
+            .await
+            .unwrap();
+
+        let statuses = project_workflow_statuses(&mut engine.db_pool.acquire().await.unwrap(), Some(project_id))
+            .await
+            .unwrap();
+        let status_id = |name: &str| statuses.iter().find(|status| status.name == name).unwrap().id;
+
+        engine
+            .update_workflow(
+                workflow.id,
+                UpdateWorkflowInput {
+                    remove_statuses: Some(vec![status_id("In Progress")]),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let task = engine.get_task(task.id).await.unwrap();
+        assert_eq!(task.workflow_status_id, Some(status_id("In Review")));
+
+        let err = engine
+            .update_task(task.id, status_update(TaskStatus::ToDo))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SDKError::WorkflowTransitionNotAllowed(..)));
+    }
+
+    #[sqlx::test]
+    async fn changing_a_status_category_remaps_its_tasks(pool: PgPool) {
+        let engine = test_engine(pool);
+        let owner_id = test_member(&engine).await;
+        let project_id = test_project(&engine, owner_id, "WFC").await;
+        let workflow = review_workflow(&engine, owner_id, project_id).await;
+        let task = test_task(&engine, owner_id, Some(project_id), "Waiting").await;
+
+        engine
+            .update_workflow(
+                workflow.id,
+                UpdateWorkflowInput {
+                    update_statuses: Some(vec![UpdateWorkflowStatusInput {
+                        id: task.workflow_status_id.unwrap(),
+                        category: Some(WorkflowStatusCategory::Canceled),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let task = engine.get_task(task.id).await.unwrap();
+        assert_eq!(task.status, TaskStatus::Canceled);
+    }
+}
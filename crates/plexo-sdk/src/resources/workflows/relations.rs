@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+
+use crate::{
+    backend::loaders::SDKLoaders,
+    errors::sdk::SDKError,
+    resources::{members::member::Member, projects::project::Project},
+};
+
+use super::{
+    operations::workflow_status_from_row,
+    workflow::{Workflow, WorkflowStatus, WorkflowTransition},
+};
+
+#[async_trait]
+pub trait WorkflowRelations {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError>;
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Project, SDKError>;
+    async fn statuses(&self, loaders: &SDKLoaders) -> Result<Vec<WorkflowStatus>, SDKError>;
+    async fn transitions(&self, loaders: &SDKLoaders) -> Result<Vec<WorkflowTransition>, SDKError>;
+}
+
+#[async_trait]
+impl WorkflowRelations for Workflow {
+    async fn owner(&self, loaders: &SDKLoaders) -> Result<Member, SDKError> {
+        let data = loaders.member_loader.load_one(self.owner_id).await.unwrap().unwrap();
+
+        Ok(data)
+    }
+
+    async fn project(&self, loaders: &SDKLoaders) -> Result<Project, SDKError> {
+        let data = loaders
+            .project_loader
+            .load_one(self.project_id)
+            .await
+            .unwrap()
+            .ok_or(SDKError::SQLXError(sqlx::Error::RowNotFound))?;
+
+        Ok(data)
+    }
+
+    async fn statuses(&self, loaders: &SDKLoaders) -> Result<Vec<WorkflowStatus>, SDKError> {
+        let statuses_info = sqlx::query(
+            r#"
+            SELECT * FROM workflow_statuses
+            WHERE workflow_id = $1
+            ORDER BY position, created_at
+            "#,
+        )
+        .bind(self.id)
+        .fetch_all(&*loaders.engine.db_pool)
+        .await?;
+
+        Ok(statuses_info.iter().map(workflow_status_from_row).collect())
+    }
+
+    async fn transitions(&self, loaders: &SDKLoaders) -> Result<Vec<WorkflowTransition>, SDKError> {
+        let transitions = sqlx::query!(
+            r#"
+            SELECT workflow_id, from_status_id, to_status_id FROM workflow_transitions
+            WHERE workflow_id = $1
+            ORDER BY created_at
+            "#,
+            &self.id
+        )
+        .fetch_all(&*loaders.engine.db_pool)
+        .await?
+        .into_iter()
+        .map(|transition| WorkflowTransition {
+            workflow_id: transition.workflow_id,
+            from_status_id: transition.from_status_id,
+            to_status_id: transition.to_status_id,
+        })
+        .collect();
+
+        Ok(transitions)
+    }
+}
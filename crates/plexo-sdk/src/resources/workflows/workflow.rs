@@ -0,0 +1,80 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use poem_openapi::Object;
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use poem_openapi::Enum as OpenApiEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::resources::tasks::task::TaskStatus;
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+#[graphql(name = "SDKWorkflow")]
+pub struct Workflow {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub name: String,
+    pub owner_id: Uuid,
+    pub project_id: Uuid,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+pub struct WorkflowStatus {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    pub workflow_id: Uuid,
+    pub name: String,
+    pub category: WorkflowStatusCategory,
+    pub position: i32,
+
+    pub color: Option<String>,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+pub struct WorkflowTransition {
+    pub workflow_id: Uuid,
+    pub from_status_id: Uuid,
+    pub to_status_id: Uuid,
+}
+
+#[derive(
+    Debug, Enum, OpenApiEnum, Copy, Clone, Default, Display, EnumString, Deserialize, Serialize, Eq, PartialEq,
+)]
+pub enum WorkflowStatusCategory {
+    #[default]
+    Unstarted,
+    Started,
+    Completed,
+    Canceled,
+}
+
+impl WorkflowStatusCategory {
+    /// The fixed status stored on tasks in this category, so clients unaware of workflows keep working.
+    pub fn task_status(&self) -> TaskStatus {
+        match self {
+            WorkflowStatusCategory::Unstarted => TaskStatus::ToDo,
+            WorkflowStatusCategory::Started => TaskStatus::InProgress,
+            WorkflowStatusCategory::Completed => TaskStatus::Done,
+            WorkflowStatusCategory::Canceled => TaskStatus::Canceled,
+        }
+    }
+}
+
+impl From<TaskStatus> for WorkflowStatusCategory {
+    fn from(status: TaskStatus) -> Self {
+        match status {
+            TaskStatus::None | TaskStatus::Draft | TaskStatus::Backlog | TaskStatus::ToDo => {
+                WorkflowStatusCategory::Unstarted
+            }
+            TaskStatus::InProgress => WorkflowStatusCategory::Started,
+            TaskStatus::Done => WorkflowStatusCategory::Completed,
+            TaskStatus::Canceled => WorkflowStatusCategory::Canceled,
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use std::str::FromStr;
+
+use async_graphql::{Enum, InputObject, SimpleObject};
+use async_trait::async_trait;
+use derive_builder::Builder;
+use poem_openapi::{Enum as OpenApiEnum, Object};
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, QueryBuilder, Row};
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use crate::{backend::engine::SDKEngine, errors::sdk::SDKError};
+
+#[derive(Debug, Enum, OpenApiEnum, Copy, Clone, Display, EnumString, Deserialize, Serialize, Eq, PartialEq)]
+pub enum SearchResourceType {
+    Task,
+    Project,
+    Message,
+    Asset,
+}
+
+#[derive(Debug, SimpleObject, Object, Clone, Serialize)]
+pub struct SearchHit {
+    pub resource_type: SearchResourceType,
+    pub resource_id: Uuid,
+    pub project_id: Option<Uuid>,
+
+    /// Plain text, escape it before rendering it as HTML.
+    pub title: String,
+    /// Matching fragments of the resource text as HTML: the text is escaped and the matched terms
+    /// are wrapped in `<b>` tags.
+    pub highlight: String,
+    pub rank: f32,
+}
+
+#[derive(Default, Builder, Object, InputObject, Clone)]
+#[builder(pattern = "owned")]
+pub struct SearchInput {
+    pub query: String,
+
+    #[builder(setter(strip_option), default)]
+    pub types: Option<Vec<SearchResourceType>>,
+    #[builder(setter(strip_option), default)]
+    pub project_id: Option<Uuid>,
+
+    #[builder(setter(into, strip_option), default = "Some(20)")]
+    pub limit: Option<i32>,
+}
+
+#[async_trait]
+pub trait SearchOperations {
+    async fn search(&self, input: SearchInput) -> Result<Vec<SearchHit>, SDKError>;
+}
+
+struct SearchSource {
+    resource_type: SearchResourceType,
+    table: &'static str,
+    title: &'static str,
+    document: &'static str,
+    project_id: &'static str,
+    project_filter: &'static str,
+    /// Leaves out archived and deleted rows, for the tables that have them.
+    visible: Option<&'static str>,
+}
+
+const SEARCH_SOURCES: &[SearchSource] = &[
+    SearchSource {
+        resource_type: SearchResourceType::Task,
+        table: "tasks",
+        title: "tasks.title",
+        document: "concat_ws(' ', tasks.title, tasks.description)",
+        project_id: "tasks.project_id",
+        project_filter: "tasks.project_id = ",
+        visible: Some("tasks.archived_at IS NULL AND tasks.deleted_at IS NULL"),
+    },
+    SearchSource {
+        resource_type: SearchResourceType::Project,
+        table: "projects",
+        title: "projects.name",
+        document: "concat_ws(' ', projects.name, projects.description)",
+        project_id: "projects.id",
+        project_filter: "projects.id = ",
+        visible: Some("projects.archived_at IS NULL AND projects.deleted_at IS NULL"),
+    },
+    SearchSource {
+        resource_type: SearchResourceType::Message,
+        table: "messages",
+        title: "left(messages.content, 80)",
+        document: "messages.content",
+        project_id: "CASE WHEN messages.resource_type = 'project' THEN messages.resource_id END",
+        project_filter: "messages.resource_type = 'project' AND messages.resource_id = ",
+        visible: None,
+    },
+    SearchSource {
+        resource_type: SearchResourceType::Asset,
+        table: "assets",
+        title: "assets.name",
+        document: "concat_ws(' ', assets.name, assets.extracted_text)",
+        project_id: "assets.project_id",
+        project_filter: "assets.project_id = ",
+        visible: None,
+    },
+];
+
+// Matches are marked with control characters taken out of the text beforehand, so they survive
+// escaping and can't be forged by the text itself.
+const HIGHLIGHT_START: char = '\u{2}';
+const HIGHLIGHT_STOP: char = '\u{3}';
+const HEADLINE_OPTIONS: &str =
+    "'StartSel=' || chr(2) || ', StopSel=' || chr(3) || ', MaxFragments=2, MaxWords=20, MinWords=5'";
+
+/// Escapes a headline for HTML, turning its match markers into `<b>` tags.
+fn highlight_html(headline: &str) -> String {
+    let mut html = String::with_capacity(headline.len());
+
+    for c in headline.chars() {
+        match c {
+            HIGHLIGHT_START => html.push_str("<b>"),
+            HIGHLIGHT_STOP => html.push_str("</b>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+
+    html
+}
+
+#[async_trait]
+impl SearchOperations for SDKEngine {
+    async fn search(&self, input: SearchInput) -> Result<Vec<SearchHit>, SDKError> {
+        if input.query.trim().is_empty() {
+            return Ok(vec![]);
+        }
+
+        let sources = SEARCH_SOURCES
+            .iter()
+            .filter(|source| {
+                input
+                    .types
+                    .as_ref()
+                    .is_none_or(|types| types.contains(&source.resource_type))
+            })
+            .collect::<Vec<_>>();
+
+        if sources.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM (");
+
+        for (index, source) in sources.iter().enumerate() {
+            if index > 0 {
+                query.push(" UNION ALL ");
+            }
+
+            query
+                .push(format!(
+                    "SELECT '{}' AS resource_type, {table}.id AS resource_id, {} AS project_id, {} AS title, \
+                     ts_headline('simple', translate(coalesce({}, ''), chr(2) || chr(3), ''), search_query, \
+                     {HEADLINE_OPTIONS}) AS highlight, \
+                     ts_rank({table}.search_vector, search_query) AS rank \
+                     FROM {table}, websearch_to_tsquery('simple', ",
+                    source.resource_type,
+                    source.project_id,
+                    source.title,
+                    source.document,
+                    table = source.table,
+                ))
+                .push_bind(input.query.clone())
+                .push(format!(
+                    ") AS search_query WHERE {}.search_vector @@ search_query",
+                    source.table
+                ));
+
+            if let Some(visible) = source.visible {
+                query.push(" AND ").push(visible);
+            }
+
+            if let Some(project_id) = input.project_id {
+                query.push(" AND ").push(source.project_filter).push_bind(project_id);
+            }
+        }
+
+        query.push(") AS hits ORDER BY rank DESC, resource_id");
+
+        if let Some(limit) = input.limit {
+            query.push(" LIMIT ").push_bind(i64::from(limit));
+        }
+
+        let hits_info = query.build().fetch_all(self.db_pool.as_ref()).await?;
+
+        let hits = hits_info
+            .iter()
+            .filter_map(|hit_info| {
+                Some(SearchHit {
+                    resource_type: SearchResourceType::from_str(hit_info.get("resource_type")).ok()?,
+                    resource_id: hit_info.get("resource_id"),
+                    project_id: hit_info.get("project_id"),
+                    title: hit_info.get::<Option<String>, _>("title").unwrap_or_default(),
+                    highlight: highlight_html(&hit_info.get::<Option<String>, _>("highlight").unwrap_or_default()),
+                    rank: hit_info.get("rank"),
+                })
+            })
+            .collect();
+
+        Ok(hits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlight_html_escapes_the_text_and_keeps_the_matches() {
+        assert_eq!(
+            highlight_html("<img src=x onerror=\"alert('hi')\"> fix the \u{2}bug\u{3} & more"),
+            "&lt;img src=x onerror=&quot;alert(&#39;hi&#39;)&quot;&gt; fix the <b>bug</b> &amp; more"
+        );
+    }
+}
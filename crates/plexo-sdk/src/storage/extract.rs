@@ -0,0 +1,102 @@
+use lopdf::Document;
+
+use crate::resources::assets::asset::AssetKind;
+
+/// Longest text kept for an asset, `to_tsvector` refuses documents much bigger than this.
+const MAX_EXTRACTED_TEXT_LENGTH: usize = 256 * 1024;
+
+/// Plain text of Text and PDF contents. `None` for other kinds, unreadable documents and
+/// documents without any text.
+pub fn extract_text(kind: AssetKind, content: &[u8]) -> Option<String> {
+    let text = match kind {
+        AssetKind::Text => String::from_utf8_lossy(content).into_owned(),
+        AssetKind::Pdf => {
+            let document = Document::load_mem(content).ok()?;
+
+            // Pages that can't be decoded are skipped instead of losing the whole document.
+            document
+                .get_pages()
+                .into_keys()
+                .filter_map(|page| document.extract_text(&[page]).ok())
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        _ => return None,
+    };
+
+    // Postgres text can't hold NUL characters.
+    let text = text.replace('\0', "");
+    let text = text.trim();
+
+    if text.is_empty() {
+        return None;
+    }
+
+    let mut end = text.len().min(MAX_EXTRACTED_TEXT_LENGTH);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    Some(text[..end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use lopdf::{
+        content::{Content, Operation},
+        dictionary, Object, Stream,
+    };
+
+    use super::*;
+
+    #[test]
+    fn extracts_text_and_pdf_contents() {
+        let mut document = Document::with_version("1.5");
+        let pages_id = document.new_object_id();
+        let font_id = document.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Courier",
+        });
+        let content = Content {
+            operations: vec![
+                Operation::new("BT", vec![]),
+                Operation::new("Tf", vec!["F1".into(), 12.into()]),
+                Operation::new("Tj", vec![Object::string_literal("Quarterly roadmap")]),
+                Operation::new("ET", vec![]),
+            ],
+        };
+        let content_id = document.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+        let page_id = document.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+            "Resources" => dictionary! { "Font" => dictionary! { "F1" => font_id } },
+        });
+        document.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![page_id.into()],
+                "Count" => 1,
+                "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            }),
+        );
+        let catalog_id = document.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        document.trailer.set("Root", catalog_id);
+
+        let mut pdf = Vec::new();
+        document.save_to(&mut pdf).unwrap();
+
+        assert_eq!(
+            extract_text(AssetKind::Pdf, &pdf).as_deref().map(str::trim),
+            Some("Quarterly roadmap")
+        );
+        assert_eq!(
+            extract_text(AssetKind::Text, b"  meeting\0 notes \n").as_deref(),
+            Some("meeting notes")
+        );
+        assert_eq!(extract_text(AssetKind::Text, b" \n"), None);
+        assert_eq!(extract_text(AssetKind::Image, b"\x89PNG"), None);
+    }
+}
@@ -0,0 +1,66 @@
+use std::{io::ErrorKind, path::PathBuf};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use tokio::fs;
+use tokio_stream::StreamExt;
+use tokio_util::io::ReaderStream;
+use uuid::Uuid;
+
+use crate::errors::sdk::SDKError;
+
+use super::{check_key, AssetStorage, ByteStream};
+
+/// Keeps contents as files under `root`, spread over subdirectories named after the first two
+/// characters of the key.
+pub struct LocalAssetStorage {
+    root: PathBuf,
+}
+
+impl LocalAssetStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        LocalAssetStorage { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, SDKError> {
+        check_key(key)?;
+
+        Ok(self.root.join(&key[..key.len().min(2)]).join(key))
+    }
+}
+
+#[async_trait]
+impl AssetStorage for LocalAssetStorage {
+    async fn put(&self, key: &str, content: Bytes) -> Result<(), SDKError> {
+        let path = self.path(key)?;
+        let dir = path.parent().unwrap();
+
+        fs::create_dir_all(dir).await?;
+
+        // Written aside and renamed, so a reader never sees a partial file.
+        let partial = dir.join(format!(".{}.{}", key, Uuid::new_v4()));
+        fs::write(&partial, &content).await?;
+        fs::rename(&partial, &path).await?;
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<ByteStream, SDKError> {
+        let file = match fs::File::open(self.path(key)?).await {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Err(SDKError::ResourceNotFound),
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(Box::pin(
+            ReaderStream::new(file).map(|chunk| chunk.map_err(SDKError::from)),
+        ))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), SDKError> {
+        match fs::remove_file(self.path(key)?).await {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Where asset contents live. Contents are stored under the hex SHA-256 of their bytes, so
+//! identical uploads share one stored object.
+
+pub mod extract;
+pub mod local;
+pub mod s3;
+pub mod sniff;
+
+use std::{pin::Pin, sync::Arc};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use sha2::{Digest, Sha256};
+use tokio_stream::Stream;
+
+use crate::errors::sdk::SDKError;
+
+use self::{local::LocalAssetStorage, s3::S3AssetStorage};
+
+pub type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, SDKError>> + Send>>;
+
+#[async_trait]
+pub trait AssetStorage: Send + Sync {
+    /// Stores `content` under `key`, replacing what was stored there before.
+    async fn put(&self, key: &str, content: Bytes) -> Result<(), SDKError>;
+    async fn get(&self, key: &str) -> Result<ByteStream, SDKError>;
+    async fn delete(&self, key: &str) -> Result<(), SDKError>;
+}
+
+#[derive(Clone, Debug)]
+pub enum AssetStorageConfig {
+    Local {
+        root: String,
+    },
+    /// Any S3 compatible service, addressed with path style URLs (`{endpoint}/{bucket}/{key}`).
+    S3 {
+        endpoint: String,
+        bucket: String,
+        region: String,
+        access_key_id: String,
+        secret_access_key: String,
+    },
+}
+
+impl AssetStorageConfig {
+    pub fn build(&self) -> Arc<dyn AssetStorage> {
+        match self {
+            AssetStorageConfig::Local { root } => Arc::new(LocalAssetStorage::new(root)),
+            AssetStorageConfig::S3 {
+                endpoint,
+                bucket,
+                region,
+                access_key_id,
+                secret_access_key,
+            } => Arc::new(S3AssetStorage::new(
+                endpoint,
+                bucket,
+                region,
+                access_key_id,
+                secret_access_key,
+            )),
+        }
+    }
+}
+
+pub fn content_hash(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
+/// Storage keys are content hashes, anything else could escape the storage root or bucket.
+pub(crate) fn check_key(key: &str) -> Result<(), SDKError> {
+    match !key.is_empty() && key.chars().all(|c| c.is_ascii_hexdigit()) {
+        true => Ok(()),
+        false => Err(SDKError::InvalidAssetKey(key.to_string())),
+    }
+}
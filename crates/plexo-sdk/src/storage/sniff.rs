@@ -0,0 +1,79 @@
+use crate::resources::assets::asset::AssetKind;
+
+/// Guesses the MIME type of `content` from its leading bytes. Whatever isn't recognised and isn't
+/// valid UTF-8 is `application/octet-stream`.
+pub fn sniff_mime_type(content: &[u8]) -> &'static str {
+    let starts = |magic: &[u8]| content.starts_with(magic);
+    let at = |offset: usize, magic: &[u8]| content.get(offset..offset + magic.len()) == Some(magic);
+
+    if starts(b"\x89PNG\r\n\x1a\n") {
+        "image/png"
+    } else if starts(b"\xff\xd8\xff") {
+        "image/jpeg"
+    } else if starts(b"GIF87a") || starts(b"GIF89a") {
+        "image/gif"
+    } else if starts(b"RIFF") && at(8, b"WEBP") {
+        "image/webp"
+    } else if starts(b"RIFF") && at(8, b"WAVE") {
+        "audio/wav"
+    } else if starts(b"%PDF-") {
+        "application/pdf"
+    } else if starts(b"ID3") || starts(b"\xff\xfb") || starts(b"\xff\xf3") || starts(b"\xff\xf2") {
+        "audio/mpeg"
+    } else if starts(b"OggS") {
+        "audio/ogg"
+    } else if starts(b"fLaC") {
+        "audio/flac"
+    } else if at(4, b"ftyp") {
+        "video/mp4"
+    } else if starts(b"\x1a\x45\xdf\xa3") {
+        "video/webm"
+    } else if let Ok(text) = std::str::from_utf8(content) {
+        let head = text
+            .trim_start()
+            .chars()
+            .take(14)
+            .collect::<String>()
+            .to_ascii_lowercase();
+
+        match head.starts_with("<!doctype html") || head.starts_with("<html") {
+            true => "text/html",
+            false => "text/plain",
+        }
+    } else {
+        "application/octet-stream"
+    }
+}
+
+pub fn asset_kind(mime_type: &str) -> AssetKind {
+    match mime_type.split('/').next() {
+        _ if mime_type == "application/pdf" => AssetKind::Pdf,
+        _ if mime_type == "text/html" => AssetKind::Website,
+        Some("image") => AssetKind::Image,
+        Some("audio") => AssetKind::Audio,
+        Some("video") => AssetKind::Video,
+        Some("text") => AssetKind::Text,
+        _ => AssetKind::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniffs_kind_from_content() {
+        let cases: [(&[u8], AssetKind); 6] = [
+            (b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR", AssetKind::Image),
+            (b"%PDF-1.7\n", AssetKind::Pdf),
+            (b"\0\0\0\x18ftypmp42", AssetKind::Video),
+            (b"  <!DOCTYPE html><html></html>", AssetKind::Website),
+            ("notes: café".as_bytes(), AssetKind::Text),
+            (b"\xde\xad\xbe\xef\xff", AssetKind::Unknown),
+        ];
+
+        for (content, kind) in cases {
+            assert_eq!(asset_kind(sniff_mime_type(content)), kind);
+        }
+    }
+}